@@ -4,8 +4,9 @@ use tauri::{AppHandle, State};
 
 use crate::contracts::chat_dispatch::ChatDispatchPayload;
 use crate::message_service::chat_db::{
-  self, chat_outbox_enqueue, chat_send_message_for_dispatch, ChatClearResult,
-  ChatDeleteMemberConversationsResult, ChatDbManager, ChatHomeFeedDto, ChatRepairResult,
+  self, chat_inbox_list_dead_letters, chat_inbox_replay_dead_letters, chat_outbox_enqueue,
+  chat_send_message_for_dispatch, ChatClearResult, ChatDeleteMemberConversationsResult,
+  ChatDbManager, ChatHomeFeedDto, ChatInboxDeadLetterDto, ChatRepairResult,
   ConversationSummaryDto, MessageAttachment, MessageContent, MessageDto,
 };
 
@@ -85,6 +86,7 @@ pub(crate) fn chat_send_message_and_enqueue(
   app: AppHandle,
   state: State<'_, ChatDbManager>,
   mut payload: ChatDispatchPayload,
+  attachment: Option<MessageAttachment>,
 ) -> Result<MessageDto, String> {
   let message = chat_send_message_for_dispatch(
     app,
@@ -97,7 +99,7 @@ pub(crate) fn chat_send_message_and_enqueue(
       text: payload.text.clone(),
     },
     Some(false),
-    None,
+    attachment,
   )?;
   let workspace_id = payload.workspace_id.clone();
   payload.message_id = Some(message.id.clone());
@@ -105,6 +107,21 @@ pub(crate) fn chat_send_message_and_enqueue(
   Ok(message)
 }
 
+pub(crate) fn chat_list_dead_letters(
+  state: State<'_, ChatDbManager>,
+  workspace_id: String,
+) -> Result<Vec<ChatInboxDeadLetterDto>, String> {
+  chat_inbox_list_dead_letters(state.inner(), workspace_id.as_str())
+}
+
+pub(crate) fn chat_replay_dead_letters(
+  state: State<'_, ChatDbManager>,
+  workspace_id: String,
+  task_ids: Option<Vec<String>>,
+) -> Result<u32, String> {
+  chat_inbox_replay_dead_letters(state.inner(), workspace_id.as_str(), task_ids)
+}
+
 pub(crate) fn chat_create_group(
   state: State<'_, ChatDbManager>,
   workspace_id: String,
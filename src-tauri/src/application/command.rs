@@ -8,6 +8,7 @@ use tauri::{AppHandle, State};
 use crate::application::chat as chat_app;
 use crate::message_service::chat_db::{ChatDbManager, MessageContent};
 use crate::runtime::command_center::CommandResultPayload;
+use crate::terminal_engine::session::{encode_key_sequence, terminal_send_member_keys};
 
 const WORKSPACE_ENV_KEY: &str = "GOLUTRA_WORKSPACE_ID";
 
@@ -26,6 +27,14 @@ pub(crate) enum TerminalCommand {
     text: String,
     is_ai: bool,
   },
+  // 向成员终端发送按键序列（已按 DSL 校验），如 `keys --sender <id> --to <member> "<C-c>"`。
+  // 仅供人类发送方使用：带 `--ai` 的调用在解析阶段被拒绝。
+  Keys {
+    workspace_id: String,
+    sender_id: String,
+    target_id: String,
+    keys: String,
+  },
 }
 
 pub(crate) fn parse_terminal_command(args: &[String]) -> Result<TerminalCommand, String> {
@@ -54,6 +63,9 @@ pub(crate) fn parse_terminal_command(args: &[String]) -> Result<TerminalCommand,
     .or_else(|| std::env::var(WORKSPACE_ENV_KEY).ok())
     .ok_or_else(|| "workspace_id is required".to_string())?;
 
+  if stripped.front().map(|value| value.as_str()) == Some("keys") {
+    return parse_keys_command(&workspace_id, is_ai, stripped);
+  }
   if let Some(index) = stripped.iter().position(|token| token == "->") {
     return parse_arrow_command(&workspace_id, is_ai, stripped, index);
   }
@@ -132,7 +144,70 @@ pub(crate) fn execute_terminal_command(
         })),
       })
     }
+    TerminalCommand::Keys {
+      workspace_id,
+      sender_id,
+      target_id,
+      keys,
+    } => {
+      let terminal_id =
+        terminal_send_member_keys(app, &workspace_id, &sender_id, &target_id, keys)?;
+      Ok(CommandResultPayload {
+        status: "ok".to_string(),
+        message: Some("keys sent".to_string()),
+        data: Some(json!({
+          "workspaceId": workspace_id,
+          "senderId": sender_id,
+          "targetId": target_id,
+          "terminalId": terminal_id
+        })),
+      })
+    }
+  }
+}
+
+fn parse_keys_command(
+  workspace_id: &str,
+  is_ai: bool,
+  mut tokens: VecDeque<String>,
+) -> Result<TerminalCommand, String> {
+  if is_ai {
+    return Err("keys command is not available to AI senders".to_string());
+  }
+  tokens.pop_front();
+  let mut sender_id = None;
+  let mut target_id = None;
+  let mut key_tokens = Vec::new();
+  while let Some(token) = tokens.pop_front() {
+    match token.as_str() {
+      "--sender" | "--from" => {
+        let value = tokens
+          .pop_front()
+          .ok_or_else(|| "missing value for --sender".to_string())?;
+        sender_id = Some(value);
+      }
+      "--to" | "--target" => {
+        let value = tokens
+          .pop_front()
+          .ok_or_else(|| "missing value for --to".to_string())?;
+        target_id = Some(value);
+      }
+      _ => key_tokens.push(token),
+    }
+  }
+  let sender_id = sender_id.ok_or_else(|| "sender id is required".to_string())?;
+  let target_id = target_id.ok_or_else(|| "target id is required".to_string())?;
+  let keys = key_tokens.join(" ");
+  if keys.is_empty() {
+    return Err("key sequence is required".to_string());
   }
+  encode_key_sequence(&keys)?;
+  Ok(TerminalCommand::Keys {
+    workspace_id: workspace_id.to_string(),
+    sender_id,
+    target_id,
+    keys,
+  })
 }
 
 fn parse_send_command(
@@ -250,3 +325,6 @@ fn parse_arrow_command(
     is_ai,
   })
 }
+
+#[cfg(test)]
+mod command_tests;
@@ -0,0 +1,42 @@
+use super::{parse_terminal_command, TerminalCommand};
+
+fn parse(args: &[&str]) -> Result<TerminalCommand, String> {
+  let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+  parse_terminal_command(&args)
+}
+
+#[test]
+fn keys_command_carries_sender_and_target() {
+  let command = parse(&["--workspace", "ws", "keys", "--sender", "u1", "--to", "m1", "<C-c>"])
+    .unwrap_or_else(|err| panic!("{err}"));
+  let TerminalCommand::Keys {
+    workspace_id,
+    sender_id,
+    target_id,
+    keys,
+  } = command
+  else {
+    panic!("expected keys command");
+  };
+  assert_eq!(
+    (workspace_id.as_str(), sender_id.as_str(), target_id.as_str(), keys.as_str()),
+    ("ws", "u1", "m1", "<C-c>")
+  );
+}
+
+#[test]
+fn keys_command_requires_a_human_sender() {
+  let error = |args: &[&str]| parse(args).err().expect("command is rejected");
+  assert_eq!(
+    error(&["--workspace", "ws", "keys", "--to", "m1", "y<Enter>"]),
+    "sender id is required"
+  );
+  assert_eq!(
+    error(&["--workspace", "ws", "--ai", "keys", "--sender", "m2", "--to", "m1", "y<Enter>"]),
+    "keys command is not available to AI senders"
+  );
+  assert_eq!(
+    error(&["--workspace", "ws", "keys", "--sender", "u1", "--to", "m1", "<Nope>"]),
+    "unknown key <Nope>"
+  );
+}
@@ -3,3 +3,4 @@
 pub(crate) mod chat;
 pub(crate) mod command;
 pub(crate) mod project;
+pub(crate) mod worktree;
@@ -10,8 +10,13 @@ use std::{
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use super::worktree::{
+    create_member_worktrees, remove_member_worktrees, resolve_worktree_base,
+    store_member_worktrees,
+};
 use crate::message_service::project_members::{ProjectMemberPurgeResult, ProjectMemberStore};
 use crate::runtime::{storage, StorageManager};
+use crate::terminal_engine::default_members::plugin_members;
 
 const WORKSPACE_REGISTRY_FILE: &str = "workspace-registry.json";
 const WORKSPACE_REGISTRY_LOCK_FILE: &str = "workspace-registry.lock";
@@ -27,6 +32,9 @@ pub(crate) struct ProjectInviteMembersRequest {
     pub(crate) instance_count: u32,
     pub(crate) unlimited_access: bool,
     pub(crate) sandboxed: bool,
+    // 为每个成员创建独立的 git 工作树与分支。
+    #[serde(default)]
+    pub(crate) isolated_worktree: bool,
 }
 
 #[derive(Deserialize)]
@@ -70,8 +78,8 @@ struct WorkspaceRegistryEntry {
     last_known_path: String,
 }
 
-struct WorkspaceContext {
-    workspace_path: String,
+pub(super) struct WorkspaceContext {
+    pub(super) workspace_path: String,
     workspace_name: String,
 }
 
@@ -90,8 +98,14 @@ pub(crate) fn project_invite_members(
     let context = resolve_workspace_context(storage, workspace_id)?;
     with_workspace_member_lock(workspace_id, || {
         let identity = resolve_terminal_identity(&request)?;
+        // 工作树隔离需先确认基线分支，避免成员已创建后才发现工作区不是 git 仓库。
+        let worktree_base = if request.isolated_worktree {
+            Some(resolve_worktree_base(&context.workspace_path)?)
+        } else {
+            None
+        };
         let store = ProjectMemberStore::new(storage);
-        let result = store.invite_members(
+        let mut result = store.invite_members(
             workspace_id,
             &context.workspace_path,
             &context.workspace_name,
@@ -103,6 +117,30 @@ pub(crate) fn project_invite_members(
             request.unlimited_access,
             request.sandboxed,
         )?;
+        if let Some(base_branch) = worktree_base {
+            let (updates, warnings) =
+                create_member_worktrees(&context.workspace_path, &base_branch, &result.created_members);
+            if !updates.is_empty() {
+                result.members =
+                    store_member_worktrees(&store, workspace_id, &context.workspace_path, &updates)?;
+                for member in result.created_members.iter_mut() {
+                    let update = member
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .and_then(|id| updates.get(id).cloned().flatten());
+                    if let (Some(object), Some(worktree)) = (member.as_object_mut(), update) {
+                        object.insert("worktree".to_string(), worktree);
+                    }
+                }
+            }
+            if !warnings.is_empty() {
+                let joined = warnings.join("; ");
+                result.warning = Some(match result.warning.take() {
+                    Some(existing) => format!("{existing}; {joined}"),
+                    None => joined,
+                });
+            }
+        }
 
         Ok(ProjectInviteMembersResult {
             members: result.members,
@@ -131,8 +169,11 @@ pub(crate) fn project_purge_terminal_members(
         let ProjectMemberPurgeResult {
             removed_count,
             removed_member_ids,
+            removed_worktrees,
             warning,
         } = result;
+        let mut warnings: Vec<String> = warning.into_iter().collect();
+        warnings.extend(remove_member_worktrees(&context.workspace_path, &removed_worktrees));
         let removed_workspaces = if removed_member_ids.is_empty() {
             Vec::new()
         } else {
@@ -146,7 +187,7 @@ pub(crate) fn project_purge_terminal_members(
                 scope,
                 total_removed: removed_count as u32,
                 workspace_count: 1,
-                warnings: warning.into_iter().collect(),
+                warnings,
             },
             removed_workspaces,
         });
@@ -173,12 +214,16 @@ pub(crate) fn project_purge_terminal_members(
                 let ProjectMemberPurgeResult {
                     removed_count,
                     removed_member_ids,
+                    removed_worktrees,
                     warning,
                 } = result;
                 total_removed += removed_count as u32;
                 if let Some(warning) = warning {
                     warnings.push(format!("workspace {id}: {warning}"));
                 }
+                for warning in remove_member_worktrees(workspace_path, &removed_worktrees) {
+                    warnings.push(format!("workspace {id}: {warning}"));
+                }
                 if !removed_member_ids.is_empty() {
                     removed_workspaces.push(ProjectPurgeRemovedWorkspace {
                         workspace_id: id.clone(),
@@ -206,7 +251,7 @@ struct TerminalIdentity {
     label: String,
 }
 
-fn resolve_workspace_context(
+pub(super) fn resolve_workspace_context(
     storage: &StorageManager,
     workspace_id: &str,
 ) -> Result<WorkspaceContext, String> {
@@ -256,7 +301,7 @@ fn read_workspace_registry_locked(storage: &StorageManager) -> Result<Option<Val
     Ok(registry)
 }
 
-fn with_workspace_member_lock<T>(
+pub(super) fn with_workspace_member_lock<T>(
     workspace_id: &str,
     action: impl FnOnce() -> Result<T, String>,
 ) -> Result<T, String> {
@@ -326,5 +371,12 @@ fn normalize_terminal_type(value: Option<&str>) -> Result<Option<String>, String
     ) {
         return Ok(Some(lower));
     }
+    // 插件成员类型在启动时注册，注册名已统一为小写。
+    if plugin_members()
+        .iter()
+        .any(|member| member.terminal_type == lower)
+    {
+        return Ok(Some(lower));
+    }
     Err("unsupported terminal type".to_string())
 }
@@ -0,0 +1,350 @@
+//! 成员工作树用例：为 AI 成员分配独立的 git worktree 与分支，避免多个成员在同一目录互相覆盖改动。
+//! 约定：工作树位于工作区 `.golutra/worktrees/<slug>`，分支为 `golutra/<slug>`（slug 为成员名加成员 ID 片段），
+//! 登记在项目数据 `members[].worktree`；终端启动时以工作树路径作为 cwd。
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::project::{resolve_workspace_context, with_workspace_member_lock};
+use crate::message_service::project_members::ProjectMemberStore;
+use crate::runtime::{git, StorageManager};
+
+const WORKTREES_DIR: &str = ".golutra/worktrees";
+const WORKTREES_EXCLUDE_PATTERN: &str = "/.golutra/worktrees/";
+const BRANCH_PREFIX: &str = "golutra/";
+const WORKTREE_FIELD: &str = "worktree";
+const WORKTREE_ID_SUFFIX_LEN: usize = 8; // slug 中成员 ID 片段的长度（ULID 尾部为随机位）。
+
+/// 成员工作树登记信息（项目数据 `members[].worktree`）。
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemberWorktree {
+    pub(crate) path: String,
+    pub(crate) branch: String,
+    pub(crate) base_branch: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemberWorktreeInfo {
+    pub(crate) member_id: String,
+    pub(crate) member_name: String,
+    #[serde(flatten)]
+    pub(crate) worktree: MemberWorktree,
+    pub(crate) exists: bool,
+    pub(crate) dirty: bool,
+    pub(crate) commits_ahead: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemberWorktreeFile {
+    pub(crate) status: String,
+    pub(crate) path: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemberWorktreeDiff {
+    pub(crate) member_id: String,
+    pub(crate) branch: String,
+    pub(crate) base_branch: String,
+    pub(crate) files: Vec<MemberWorktreeFile>,
+    pub(crate) diff: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MemberWorktreeMergeResult {
+    pub(crate) member_id: String,
+    pub(crate) branch: String,
+    pub(crate) base_branch: String,
+    pub(crate) committed_pending: bool,
+    pub(crate) merge_commit: String,
+}
+
+/// 邀请前校验：工作区必须是 git 仓库且检出在某个分支上，返回作为基线的分支名。
+pub(crate) fn resolve_worktree_base(workspace_path: &str) -> Result<String, String> {
+    git::current_branch(Path::new(workspace_path))
+}
+
+/// 为新成员创建工作树；单个成员失败时记录告警，该成员继续使用共享工作区。
+/// 返回写入项目数据的 `worktree` 字段与告警列表。
+pub(crate) fn create_member_worktrees(
+    workspace_path: &str,
+    base_branch: &str,
+    members: &[Value],
+) -> (HashMap<String, Option<Value>>, Vec<String>) {
+    let repo = Path::new(workspace_path);
+    let mut updates = HashMap::new();
+    let mut warnings = Vec::new();
+    if let Err(err) = git::ensure_excluded(repo, WORKTREES_EXCLUDE_PATTERN) {
+        warnings.push(err);
+    }
+    for member in members {
+        let id = member.get("id").and_then(Value::as_str).unwrap_or("");
+        let name = member.get("name").and_then(Value::as_str).unwrap_or(id);
+        if id.is_empty() {
+            continue;
+        }
+        let slug = worktree_slug(name, id);
+        let path = repo.join(WORKTREES_DIR).join(&slug);
+        let branch = format!("{BRANCH_PREFIX}{slug}");
+        match git::add_worktree(repo, &path, &branch, base_branch) {
+            Ok(()) => {
+                let worktree = MemberWorktree {
+                    path: path.to_string_lossy().to_string(),
+                    branch,
+                    base_branch: base_branch.to_string(),
+                };
+                if let Ok(value) = serde_json::to_value(&worktree) {
+                    updates.insert(id.to_string(), Some(value));
+                }
+            }
+            Err(err) => warnings.push(format!("member {name} worktree skipped: {err}")),
+        }
+    }
+    (updates, warnings)
+}
+
+/// 移除被清理成员的工作树与分支；返回告警列表。
+pub(crate) fn remove_member_worktrees(workspace_path: &str, worktrees: &[Value]) -> Vec<String> {
+    let repo = Path::new(workspace_path);
+    let mut warnings = Vec::new();
+    for value in worktrees {
+        let Ok(worktree) = serde_json::from_value::<MemberWorktree>(value.clone()) else {
+            continue;
+        };
+        if let Err(err) = git::remove_worktree(repo, Path::new(&worktree.path), &worktree.branch) {
+            warnings.push(format!("worktree {}: {err}", worktree.branch));
+        }
+    }
+    warnings
+}
+
+/// 列出工作区内所有登记了工作树的成员及其状态。
+pub(crate) fn project_member_worktrees_list(
+    storage: &StorageManager,
+    workspace_id: &str,
+) -> Result<Vec<MemberWorktreeInfo>, String> {
+    let context = resolve_workspace_context(storage, workspace_id)?;
+    let repo = Path::new(&context.workspace_path);
+    let members = ProjectMemberStore::new(storage).read_members(workspace_id, &context.workspace_path)?;
+    let mut list = Vec::new();
+    for member in &members {
+        let Some(worktree) = parse_member_worktree(member) else {
+            continue;
+        };
+        let path = PathBuf::from(&worktree.path);
+        let exists = path.is_dir();
+        let dirty = exists && git::has_changes(&path).unwrap_or(false);
+        let range = format!("{}..{}", worktree.base_branch, worktree.branch);
+        let commits_ahead = git::run_git(repo, &["rev-list", "--count", &range])
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0);
+        list.push(MemberWorktreeInfo {
+            member_id: member_string(member, "id"),
+            member_name: member_string(member, "name"),
+            worktree,
+            exists,
+            dirty,
+            commits_ahead,
+        });
+    }
+    Ok(list)
+}
+
+/// 成员工作树相对基线分支的改动：以分叉点为基准，包含未提交与未跟踪文件。
+pub(crate) fn project_member_worktree_diff(
+    storage: &StorageManager,
+    workspace_id: &str,
+    member_id: &str,
+) -> Result<MemberWorktreeDiff, String> {
+    let (_, worktree) = resolve_member_worktree(storage, workspace_id, member_id)?;
+    let path = existing_worktree_path(&worktree)?;
+    let merge_base = git::run_git(&path, &["merge-base", &worktree.base_branch, "HEAD"])?;
+    let merge_base = merge_base.trim();
+    let mut files: Vec<MemberWorktreeFile> = git::run_git(&path, &["diff", "--name-status", merge_base])?
+        .lines()
+        .filter_map(|line| {
+            let (status, file) = line.split_once('\t')?;
+            Some(MemberWorktreeFile {
+                status: status.to_string(),
+                path: file.to_string(),
+            })
+        })
+        .collect();
+    let untracked = git::run_git(&path, &["ls-files", "--others", "--exclude-standard"])?;
+    files.extend(untracked.lines().filter(|line| !line.is_empty()).map(|line| MemberWorktreeFile {
+        status: "??".to_string(),
+        path: line.to_string(),
+    }));
+    let diff = git::run_git(&path, &["diff", "--no-color", merge_base])?;
+    Ok(MemberWorktreeDiff {
+        member_id: member_id.to_string(),
+        branch: worktree.branch,
+        base_branch: worktree.base_branch,
+        files,
+        diff,
+    })
+}
+
+/// 将成员分支合并回基线分支。
+/// 约束：工作区需检出在基线分支；成员未提交的改动先自动提交；冲突时中止合并并报错，由用户手动处理。
+pub(crate) fn project_member_worktree_merge(
+    storage: &StorageManager,
+    workspace_id: &str,
+    member_id: &str,
+) -> Result<MemberWorktreeMergeResult, String> {
+    let (context_path, worktree) = resolve_member_worktree(storage, workspace_id, member_id)?;
+    merge_member_branch(Path::new(&context_path), member_id, worktree)
+}
+
+// 合并成员分支的 git 步骤，与项目数据解耦。
+fn merge_member_branch(
+    repo: &Path,
+    member_id: &str,
+    worktree: MemberWorktree,
+) -> Result<MemberWorktreeMergeResult, String> {
+    let member_path = existing_worktree_path(&worktree)?;
+    let checked_out = git::current_branch(repo)?;
+    if checked_out != worktree.base_branch {
+        return Err(format!(
+            "workspace is on branch {checked_out}; switch to {} before merging",
+            worktree.base_branch
+        ));
+    }
+    let committed_pending = git::has_changes(&member_path)?;
+    if committed_pending {
+        git::run_git(&member_path, &["add", "-A"])?;
+        let message = format!("Pending changes from {}", worktree.branch);
+        git::run_git(&member_path, &["commit", "-m", &message])?;
+    }
+    let message = format!("Merge {} into {}", worktree.branch, worktree.base_branch);
+    if let Err(err) = git::run_git(repo, &["merge", "--no-ff", "-m", &message, &worktree.branch]) {
+        let _ = git::run_git(repo, &["merge", "--abort"]);
+        return Err(format!("failed to merge {}: {err}", worktree.branch));
+    }
+    let merge_commit = git::run_git(repo, &["rev-parse", "HEAD"])?.trim().to_string();
+    Ok(MemberWorktreeMergeResult {
+        member_id: member_id.to_string(),
+        branch: worktree.branch,
+        base_branch: worktree.base_branch,
+        committed_pending,
+        merge_commit,
+    })
+}
+
+/// 丢弃成员工作树与分支，并移除登记；成员之后回到共享工作区。返回更新后的成员列表。
+pub(crate) fn project_member_worktree_discard(
+    storage: &StorageManager,
+    workspace_id: &str,
+    member_id: &str,
+) -> Result<Vec<Value>, String> {
+    let (context_path, worktree) = resolve_member_worktree(storage, workspace_id, member_id)?;
+    git::remove_worktree(
+        Path::new(&context_path),
+        Path::new(&worktree.path),
+        &worktree.branch,
+    )?;
+    let updates = HashMap::from([(member_id.to_string(), None)]);
+    with_workspace_member_lock(workspace_id, || {
+        ProjectMemberStore::new(storage).update_members_field(
+            workspace_id,
+            &context_path,
+            WORKTREE_FIELD,
+            &updates,
+        )
+    })
+}
+
+/// 写入新建成员的工作树登记，返回更新后的成员列表。
+pub(crate) fn store_member_worktrees(
+    store: &ProjectMemberStore<'_>,
+    workspace_id: &str,
+    workspace_path: &str,
+    updates: &HashMap<String, Option<Value>>,
+) -> Result<Vec<Value>, String> {
+    store.update_members_field(workspace_id, workspace_path, WORKTREE_FIELD, updates)
+}
+
+/// 成员登记的工作树路径（目录存在时）；供终端启动选择 cwd。
+pub(crate) fn member_worktree_cwd(member: &Value) -> Option<String> {
+    parse_member_worktree(member)
+        .map(|worktree| worktree.path)
+        .filter(|path| Path::new(path).is_dir())
+}
+
+fn parse_member_worktree(member: &Value) -> Option<MemberWorktree> {
+    member
+        .get(WORKTREE_FIELD)
+        .filter(|value| value.is_object())
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+fn resolve_member_worktree(
+    storage: &StorageManager,
+    workspace_id: &str,
+    member_id: &str,
+) -> Result<(String, MemberWorktree), String> {
+    let context = resolve_workspace_context(storage, workspace_id)?;
+    let members = ProjectMemberStore::new(storage).read_members(workspace_id, &context.workspace_path)?;
+    let member = members
+        .iter()
+        .find(|member| member.get("id").and_then(Value::as_str) == Some(member_id))
+        .ok_or_else(|| "member not found".to_string())?;
+    let worktree =
+        parse_member_worktree(member).ok_or_else(|| "member has no worktree".to_string())?;
+    Ok((context.workspace_path, worktree))
+}
+
+fn existing_worktree_path(worktree: &MemberWorktree) -> Result<PathBuf, String> {
+    let path = PathBuf::from(&worktree.path);
+    if !path.is_dir() {
+        return Err(format!("worktree directory is missing: {}", worktree.path));
+    }
+    Ok(path)
+}
+
+fn member_string(member: &Value, key: &str) -> String {
+    member
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string()
+}
+
+// 目录名与分支名共用：成员名加成员 ID 末尾片段，改名或同名的新成员不会落到旧成员的分支上；
+// 成员名为空或无可用字符时只用 ID 片段。
+fn worktree_slug(name: &str, id: &str) -> String {
+    let name = slug_part(name);
+    let id = slug_part(id);
+    let suffix = id[id.len().saturating_sub(WORKTREE_ID_SUFFIX_LEN)..].trim_start_matches('-');
+    if name.is_empty() {
+        suffix.to_string()
+    } else {
+        format!("{name}-{suffix}")
+    }
+}
+
+// 仅保留 ASCII 字母数字与 `_`（转小写），其余折叠为 `-`。
+fn slug_part(value: &str) -> String {
+    let mut slug = String::new();
+    for ch in value.trim().chars() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod worktree_tests;
@@ -0,0 +1,152 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use super::{
+    create_member_worktrees, merge_member_branch, remove_member_worktrees, worktree_slug,
+    MemberWorktree,
+};
+use crate::runtime::git;
+
+const ALICE_ID: &str = "01HXZK4Q8R7M2N3P0001ABCD";
+const ALICE_TWIN_ID: &str = "01HXZK4Q8R7M2N3P0002ABCD";
+
+/// 临时工作区仓库：`main` 分支上带一个初始提交，离开作用域时删除目录。
+struct TempRepo {
+    path: PathBuf,
+}
+
+impl TempRepo {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("worktree-tests-{}", ulid::Ulid::new()));
+        fs::create_dir_all(&path).expect("create repo dir");
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.name", "Golutra Tests"],
+            &["config", "user.email", "tests@golutra.invalid"],
+            &["config", "commit.gpgsign", "false"],
+        ] {
+            git::run_git(&path, args).expect("init repo");
+        }
+        fs::write(path.join("README.md"), "hello\n").expect("write file");
+        commit_all(&path, "initial");
+        Self { path }
+    }
+
+    fn workspace(&self) -> &str {
+        self.path.to_str().expect("utf-8 temp path")
+    }
+
+    // 为单个成员创建工作树并返回登记信息。
+    fn member_worktree(&self, name: &str, id: &str) -> MemberWorktree {
+        let (updates, warnings) =
+            create_member_worktrees(self.workspace(), "main", &[json!({ "id": id, "name": name })]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let value = updates[id].clone().expect("worktree registered");
+        serde_json::from_value(value).expect("worktree value")
+    }
+}
+
+impl Drop for TempRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn commit_all(dir: &Path, message: &str) {
+    git::run_git(dir, &["add", "-A"]).expect("stage");
+    git::run_git(dir, &["commit", "-q", "-m", message]).expect("commit");
+}
+
+#[test]
+fn slug_combines_member_name_with_id_suffix() {
+    assert_eq!(worktree_slug("Alice Bot!", ALICE_ID), "alice-bot-0001abcd");
+    assert_eq!(worktree_slug("  助手  ", ALICE_ID), "0001abcd");
+    assert_eq!(worktree_slug("Alice", "M_1"), "alice-m_1");
+    assert_eq!(worktree_slug("Alice", "member-xyz"), "alice-mber-xyz");
+    assert_ne!(worktree_slug("Alice", ALICE_ID), worktree_slug("Alice", ALICE_TWIN_ID));
+}
+
+#[test]
+fn same_named_members_get_separate_branches() {
+    let repo = TempRepo::new();
+    let members: Vec<Value> = [ALICE_ID, ALICE_TWIN_ID]
+        .iter()
+        .map(|id| json!({ "id": id, "name": "Alice" }))
+        .collect();
+    let (updates, warnings) = create_member_worktrees(repo.workspace(), "main", &members);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    let branches: Vec<String> = [ALICE_ID, ALICE_TWIN_ID]
+        .iter()
+        .map(|id| updates[*id].as_ref().unwrap()["branch"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(branches, ["golutra/alice-0001abcd", "golutra/alice-0002abcd"]);
+}
+
+#[test]
+fn stale_branch_is_reported_instead_of_reused() {
+    let repo = TempRepo::new();
+    git::run_git(&repo.path, &["branch", "golutra/alice-0001abcd"]).expect("create stale branch");
+    let members = [json!({ "id": ALICE_ID, "name": "Alice" })];
+    let (updates, warnings) = create_member_worktrees(repo.workspace(), "main", &members);
+    assert!(updates.is_empty());
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("already exists"), "{warnings:?}");
+}
+
+#[test]
+fn merge_commits_pending_changes_and_merges_into_base() {
+    let repo = TempRepo::new();
+    let worktree = repo.member_worktree("Alice", ALICE_ID);
+    let member_path = PathBuf::from(&worktree.path);
+    fs::write(member_path.join("feature.txt"), "done\n").expect("write file");
+    commit_all(&member_path, "feature");
+    fs::write(member_path.join("notes.txt"), "pending\n").expect("write file");
+
+    let result = merge_member_branch(&repo.path, ALICE_ID, worktree).expect("merge");
+    assert!(result.committed_pending);
+    assert_eq!(result.base_branch, "main");
+    assert!(repo.path.join("feature.txt").is_file());
+    assert!(repo.path.join("notes.txt").is_file());
+    let head = git::run_git(&repo.path, &["rev-parse", "HEAD"]).unwrap();
+    assert_eq!(head.trim(), result.merge_commit);
+}
+
+#[test]
+fn merge_requires_the_base_branch_checked_out() {
+    let repo = TempRepo::new();
+    let worktree = repo.member_worktree("Alice", ALICE_ID);
+    git::run_git(&repo.path, &["checkout", "-q", "-b", "other"]).expect("switch branch");
+    let err = merge_member_branch(&repo.path, ALICE_ID, worktree).err().expect("merge fails");
+    assert!(err.contains("switch to main"), "{err}");
+}
+
+#[test]
+fn merge_conflict_is_aborted() {
+    let repo = TempRepo::new();
+    let worktree = repo.member_worktree("Alice", ALICE_ID);
+    let member_path = PathBuf::from(&worktree.path);
+    fs::write(member_path.join("README.md"), "member\n").expect("write file");
+    commit_all(&member_path, "member edit");
+    fs::write(repo.path.join("README.md"), "base\n").expect("write file");
+    commit_all(&repo.path, "base edit");
+
+    let err = merge_member_branch(&repo.path, ALICE_ID, worktree).err().expect("merge fails");
+    assert!(err.contains("failed to merge golutra/alice-0001abcd"), "{err}");
+    assert!(!git::has_changes(&repo.path).unwrap());
+    assert_eq!(fs::read_to_string(repo.path.join("README.md")).unwrap(), "base\n");
+}
+
+#[test]
+fn removed_members_lose_worktree_and_branch() {
+    let repo = TempRepo::new();
+    let worktree = repo.member_worktree("Alice", ALICE_ID);
+    let value = serde_json::to_value(&worktree).unwrap();
+    let warnings = remove_member_worktrees(repo.workspace(), &[value]);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert!(!Path::new(&worktree.path).exists());
+    assert!(!git::branch_exists(&repo.path, &worktree.branch));
+}
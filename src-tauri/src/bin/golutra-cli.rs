@@ -131,6 +131,6 @@ fn main() {
 
 fn print_help() {
   println!(
-    "golutra command usage:\n  golutra send [--async] [--workspace <id>] <command>\n  golutra wait <request_id>\n\nExamples:\n  golutra send --workspace <id> send --sender <id> --conversation <id> --text \"hello\"\n  golutra send --workspace <id> a -> b #conversation-id hello\n  golutra wait <request_id>"
+    "golutra command usage:\n  golutra send [--async] [--workspace <id>] <command>\n  golutra keys [--workspace <id>] --sender <id> --to <member_id> <keys>\n  golutra wait <request_id>\n\nKeys: <Enter> <Esc> <Tab> <Up> <Down> <Left> <Right> <C-c> <M-x> <lt> paste{{text}}\n\nExamples:\n  golutra send --workspace <id> send --sender <id> --conversation <id> --text \"hello\"\n  golutra send --workspace <id> a -> b #conversation-id hello\n  golutra keys --workspace <id> --sender <id> --to <member_id> \"<C-c>\"\n  golutra wait <request_id>"
   );
 }
@@ -10,7 +10,7 @@ pub(crate) struct TerminalCursorPayload {
 }
 
 #[derive(Serialize, Clone)]
-/// 消息元数据：用于聊天侧渲染补充信息（命令、行数、光标等）。
+/// 消息元数据：用于聊天侧渲染补充信息（命令、行数、光标、退出码等）。
 /// 约束：字段可选以避免在低成本路径中强制计算。
 pub(crate) struct TerminalMessageMeta {
   #[serde(rename = "command")]
@@ -18,10 +18,14 @@ pub(crate) struct TerminalMessageMeta {
   #[serde(rename = "lineCount")]
   pub(crate) line_count: Option<u32>,
   pub(crate) cursor: Option<TerminalCursorPayload>,
+  /// 内容在会话快照中的绝对行区间 `[startRow, endRow)`：行号自会话启动起递增，可用于取回原文。
   #[serde(rename = "startRow")]
-  pub(crate) start_row: Option<u16>,
+  pub(crate) start_row: Option<u64>,
   #[serde(rename = "endRow")]
-  pub(crate) end_row: Option<u16>,
+  pub(crate) end_row: Option<u64>,
+  /// 命令退出码：仅在 shell 集成标记报告命令结束（OSC 133/633 D）时存在。
+  #[serde(rename = "exitCode")]
+  pub(crate) exit_code: Option<i32>,
 }
 
 #[derive(Serialize, Clone)]
@@ -21,10 +21,13 @@ pub(crate) use ui_gateway::app::now_millis;
 
 use platform::{resolve_log_dir, ActivationState, UpdaterState};
 use runtime::spawn_command_ipc_server;
+use runtime::shell_integration::init_shell_integration_dir;
 use runtime::state::AppState;
 use runtime::{CommandCenter, StorageManager};
 use orchestration::chat_dispatch_batcher::ChatDispatchBatcher;
+use orchestration::chat_inbox::spawn_chat_inbox_worker;
 use orchestration::chat_outbox::spawn_chat_outbox_worker;
+use terminal_engine::default_members::{load_plugin_members, MEMBER_TYPES_DIR};
 use terminal_engine::TerminalManager;
 use ui_gateway::message_pipeline::{
     UiMessageRepository, UiMessageTransport, UiTerminalMessagePipeline,
@@ -34,7 +37,7 @@ use ui_gateway::terminal_session_repository::UiTerminalSessionRepository;
 use ui_gateway::{
     apply_main_window_size, apply_windows_rounding, cleanup_ephemeral_sessions_for_window, export_commands,
     has_active_sessions, schedule_main_window_frame_refresh, setup_tray, show_main_window, shutdown_sessions,
-    spawn_snapshot_dumper, spawn_status_poller, ChatDbManager, DiagnosticsState, NotificationBadgeState,
+    spawn_hibernation_sweeper, spawn_snapshot_dumper, spawn_status_poller, ChatDbManager, DiagnosticsState, NotificationBadgeState,
     MAIN_WINDOW_LABEL,
 };
 
@@ -59,10 +62,11 @@ pub fn run() {
             app.manage(NotificationBadgeState::new(&app_handle));
             let transport = Arc::new(UiMessageTransport::new(app_handle.clone()));
             let repository = Arc::new(UiMessageRepository::new(app_handle.clone()));
-            let pipeline = Arc::new(UiTerminalMessagePipeline::new(transport, repository));
+            let pipeline = Arc::new(UiTerminalMessagePipeline::new(transport, repository.clone()));
             app.state::<TerminalManager>()
                 .set_message_pipeline(pipeline);
             let app_data_dir = app.path().app_data_dir()?;
+            init_shell_integration_dir(&app_data_dir);
             let app_cache_dir = app.path().app_cache_dir()?;
             let storage_manager = StorageManager::new(app_data_dir.clone(), app_cache_dir);
             app.manage(storage_manager.clone());
@@ -101,10 +105,15 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            // 插件成员类型需在首个终端创建前加载；放在日志初始化之后以便输出校验告警。
+            let member_types_dir = app.path().app_config_dir()?.join(MEMBER_TYPES_DIR);
+            load_plugin_members(&member_types_dir);
             let manager = &app.state::<TerminalManager>();
             spawn_status_poller(app.handle().clone(), manager);
+            spawn_hibernation_sweeper(app.handle().clone(), manager);
             spawn_snapshot_dumper(manager, resolve_log_dir());
             spawn_chat_outbox_worker(app.handle().clone());
+            spawn_chat_inbox_worker(app.handle().clone(), repository);
             let command_center = app.state::<Arc<CommandCenter>>();
             if let Err(err) =
                 spawn_command_ipc_server(app.handle().clone(), Arc::clone(command_center.inner()))
@@ -0,0 +1,368 @@
+//! 聊天 Inbox：持久化终端 final 回写，落库失败后重试并进入死信。
+//! 任务存放在独立的 `chat_inbox.redb`，消息库不可用时入队仍能成功。
+
+use redb::ReadableTable;
+use ulid::Ulid;
+
+use super::store::{
+  decode, encode, format_ulid, now_millis, open_inbox_db, parse_ulid, CHAT_INBOX_SCHEDULE,
+  CHAT_INBOX_TASKS,
+};
+use super::types::{ChatInboxDeadLetterDto, ChatInboxPayload, ChatInboxStatus, ChatInboxTask};
+use super::ChatDbManager;
+
+fn remove_schedule_entry(
+  schedule: &mut redb::Table<(u64, u128), ()>,
+  scheduled_at: u64,
+  task_id: u128,
+) {
+  let _ = schedule.remove((scheduled_at, task_id));
+}
+
+fn insert_schedule_entry(
+  schedule: &mut redb::Table<(u64, u128), ()>,
+  scheduled_at: u64,
+  task_id: u128,
+) -> Result<(), String> {
+  schedule
+    .insert((scheduled_at, task_id), ())
+    .map_err(|err| format!("failed to insert chat_inbox_schedule: {err}"))?;
+  Ok(())
+}
+
+/// 写入入站任务并直接视为第一次尝试已被认领（Sending）。
+/// 调用方随后同步落库；进程中断时租约到期由后台 worker 接手。
+pub(crate) fn chat_inbox_enqueue(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  payload: ChatInboxPayload,
+  lease_ms: u64,
+) -> Result<ChatInboxTask, String> {
+  let task_id_u128 = Ulid::new().0;
+  let now = now_millis()?;
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write: {err}"))?;
+
+  let task = {
+    let mut schedule = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+    let mut tasks = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+
+    let task = ChatInboxTask {
+      task_id: format_ulid(task_id_u128),
+      payload,
+      status: ChatInboxStatus::Sending,
+      attempts: 1,
+      created_at: now,
+      updated_at: now,
+      next_attempt_at: now.saturating_add(lease_ms),
+      sending_since: Some(now),
+      last_error: None,
+    };
+
+    let encoded = encode(&task)?;
+    tasks
+      .insert(task_id_u128, encoded.as_slice())
+      .map_err(|err| format!("failed to insert chat_inbox_tasks: {err}"))?;
+    insert_schedule_entry(&mut schedule, task.next_attempt_at, task_id_u128)?;
+    task
+  };
+
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox enqueue: {err}"))?;
+
+  Ok(task)
+}
+
+pub(crate) fn chat_inbox_claim_due(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  now: u64,
+  limit: usize,
+  lease_ms: u64,
+) -> Result<Vec<ChatInboxTask>, String> {
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write: {err}"))?;
+
+  let claimed = {
+    let mut schedule = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+    let mut tasks = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+
+    let mut claimed = Vec::new();
+    let mut candidates = Vec::new();
+    let start = (0u64, 0u128);
+    let end = (now, u128::MAX);
+    for entry in schedule
+      .range(start..=end)
+      .map_err(|err| format!("failed to scan chat_inbox_schedule: {err}"))?
+    {
+      let (key, _) = entry.map_err(|err| format!("failed to decode chat_inbox_schedule: {err}"))?;
+      candidates.push(key.value());
+      if candidates.len() >= limit {
+        break;
+      }
+    }
+
+    for (scheduled_at, task_id) in candidates {
+      remove_schedule_entry(&mut schedule, scheduled_at, task_id);
+      let task = match tasks
+        .get(task_id)
+        .map_err(|err| format!("failed to read chat_inbox_tasks: {err}"))?
+      {
+        Some(value) => decode::<ChatInboxTask>(value.value())?,
+        None => continue,
+      };
+
+      if task.status == ChatInboxStatus::Dead {
+        continue;
+      }
+
+      if let Some(sending_since) = task.sending_since {
+        if task.status == ChatInboxStatus::Sending && sending_since.saturating_add(lease_ms) > now {
+          let mut updated = task;
+          updated.next_attempt_at = sending_since.saturating_add(lease_ms);
+          let encoded = encode(&updated)?;
+          tasks
+            .insert(task_id, encoded.as_slice())
+            .map_err(|err| format!("failed to update chat_inbox_tasks: {err}"))?;
+          insert_schedule_entry(&mut schedule, updated.next_attempt_at, task_id)?;
+          continue;
+        }
+      }
+
+      let mut updated = task;
+      updated.status = ChatInboxStatus::Sending;
+      updated.attempts = updated.attempts.saturating_add(1);
+      updated.updated_at = now;
+      updated.sending_since = Some(now);
+      updated.next_attempt_at = now.saturating_add(lease_ms);
+
+      let encoded = encode(&updated)?;
+      tasks
+        .insert(task_id, encoded.as_slice())
+        .map_err(|err| format!("failed to update chat_inbox_tasks: {err}"))?;
+      insert_schedule_entry(&mut schedule, updated.next_attempt_at, task_id)?;
+      claimed.push(updated);
+    }
+    claimed
+  };
+
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox claim: {err}"))?;
+
+  Ok(claimed)
+}
+
+/// 落库成功：消息本身即为记录，任务直接删除，避免入站表无限增长。
+pub(crate) fn chat_inbox_mark_delivered(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  task_id: &str,
+) -> Result<(), String> {
+  let task_id_u128 = parse_ulid(task_id)?;
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write: {err}"))?;
+  {
+    let mut schedule = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+    let mut tasks = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+
+    let task: ChatInboxTask = {
+      let value = tasks
+        .get(task_id_u128)
+        .map_err(|err| format!("failed to read chat_inbox_tasks: {err}"))?;
+      let Some(value) = value else {
+        return Ok(());
+      };
+      decode(value.value())?
+    };
+    remove_schedule_entry(&mut schedule, task.next_attempt_at, task_id_u128);
+    tasks
+      .remove(task_id_u128)
+      .map_err(|err| format!("failed to remove chat_inbox_tasks: {err}"))?;
+  }
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox delivered: {err}"))?;
+  Ok(())
+}
+
+pub(crate) fn chat_inbox_mark_failed(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  task_id: &str,
+  next_attempt_at: u64,
+  error: &str,
+  mark_dead: bool,
+) -> Result<(), String> {
+  let task_id_u128 = parse_ulid(task_id)?;
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write: {err}"))?;
+  {
+    let mut schedule = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+    let mut tasks = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+
+    let mut task: ChatInboxTask = {
+      let value = tasks
+        .get(task_id_u128)
+        .map_err(|err| format!("failed to read chat_inbox_tasks: {err}"))?;
+      let Some(value) = value else {
+        return Ok(());
+      };
+      decode(value.value())?
+    };
+    remove_schedule_entry(&mut schedule, task.next_attempt_at, task_id_u128);
+    task.updated_at = now_millis()?;
+    task.sending_since = None;
+    task.last_error = Some(error.to_string());
+    if mark_dead {
+      task.status = ChatInboxStatus::Dead;
+    } else {
+      task.status = ChatInboxStatus::Failed;
+      task.next_attempt_at = next_attempt_at;
+      insert_schedule_entry(&mut schedule, task.next_attempt_at, task_id_u128)?;
+    }
+    let encoded = encode(&task)?;
+    tasks
+      .insert(task_id_u128, encoded.as_slice())
+      .map_err(|err| format!("failed to update chat_inbox_tasks: {err}"))?;
+  }
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox failed: {err}"))?;
+  Ok(())
+}
+
+/// 列出工作区内全部死信，按创建时间升序。
+pub(crate) fn chat_inbox_list_dead_letters(
+  state: &ChatDbManager,
+  workspace_id: &str,
+) -> Result<Vec<ChatInboxDeadLetterDto>, String> {
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_read()
+    .map_err(|err| format!("failed to open chat inbox read: {err}"))?;
+  let tasks = txn
+    .open_table(CHAT_INBOX_TASKS)
+    .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+  let mut dead_letters = Vec::new();
+  for entry in tasks
+    .iter()
+    .map_err(|err| format!("failed to scan chat_inbox_tasks: {err}"))?
+  {
+    let (_, value) = entry.map_err(|err| format!("failed to decode chat_inbox_tasks: {err}"))?;
+    let task: ChatInboxTask = decode(value.value())?;
+    if task.status != ChatInboxStatus::Dead {
+      continue;
+    }
+    dead_letters.push(ChatInboxDeadLetterDto {
+      task_id: task.task_id,
+      conversation_id: task.payload.conversation_id,
+      member_id: task.payload.member_id,
+      content: task.payload.content,
+      attempts: task.attempts,
+      created_at: task.created_at,
+      updated_at: task.updated_at,
+      last_error: task.last_error,
+    });
+  }
+  dead_letters.sort_by_key(|item| item.created_at);
+  Ok(dead_letters)
+}
+
+/// 将死信重新放回队列，重置尝试次数并立即调度。
+/// 输入：`task_ids` 为空时重放全部死信。
+/// 返回：实际重放的任务数。
+pub(crate) fn chat_inbox_replay_dead_letters(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  task_ids: Option<Vec<String>>,
+) -> Result<u32, String> {
+  let selected: Option<Vec<u128>> = match task_ids {
+    Some(ids) => Some(
+      ids
+        .iter()
+        .map(|id| parse_ulid(id))
+        .collect::<Result<Vec<_>, _>>()?,
+    ),
+    None => None,
+  };
+  let now = now_millis()?;
+  let db = open_inbox_db(state, workspace_id)?;
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write: {err}"))?;
+  let replayed = {
+    let mut schedule = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+    let mut tasks = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+
+    let mut dead = Vec::new();
+    for entry in tasks
+      .iter()
+      .map_err(|err| format!("failed to scan chat_inbox_tasks: {err}"))?
+    {
+      let (key, value) = entry.map_err(|err| format!("failed to decode chat_inbox_tasks: {err}"))?;
+      let task_id = key.value();
+      if let Some(selected) = selected.as_ref() {
+        if !selected.contains(&task_id) {
+          continue;
+        }
+      }
+      let task: ChatInboxTask = decode(value.value())?;
+      if task.status == ChatInboxStatus::Dead {
+        dead.push((task_id, task));
+      }
+    }
+
+    let mut replayed = 0u32;
+    for (task_id, mut task) in dead {
+      task.status = ChatInboxStatus::Pending;
+      task.attempts = 0;
+      task.updated_at = now;
+      task.next_attempt_at = now;
+      task.sending_since = None;
+      let encoded = encode(&task)?;
+      tasks
+        .insert(task_id, encoded.as_slice())
+        .map_err(|err| format!("failed to update chat_inbox_tasks: {err}"))?;
+      insert_schedule_entry(&mut schedule, now, task_id)?;
+      replayed += 1;
+    }
+    replayed
+  };
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox replay: {err}"))?;
+  Ok(replayed)
+}
+
+#[cfg(test)]
+mod queue_tests;
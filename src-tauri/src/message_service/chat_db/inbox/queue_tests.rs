@@ -0,0 +1,97 @@
+use std::fs;
+
+use ulid::Ulid;
+
+use crate::message_service::pipeline::{plan_terminal_inbox_retry, TERMINAL_INBOX_MAX_ATTEMPTS};
+
+use super::super::store::now_millis;
+use super::super::types::{ChatInboxPayload, ChatInboxStatus, ChatInboxTask};
+use super::super::ChatDbManager;
+use super::{
+  chat_inbox_claim_due, chat_inbox_enqueue, chat_inbox_list_dead_letters,
+  chat_inbox_mark_delivered, chat_inbox_mark_failed, chat_inbox_replay_dead_letters,
+};
+
+const WORKSPACE: &str = "ws-inbox";
+const LEASE_MS: u64 = 1_000;
+
+struct TempInbox {
+  state: ChatDbManager,
+  dir: std::path::PathBuf,
+}
+
+impl TempInbox {
+  fn new() -> Self {
+    let dir = std::env::temp_dir().join(format!("golutra-inbox-{}", Ulid::new()));
+    let state = ChatDbManager::default();
+    state.set_base_dir(dir.clone());
+    Self { state, dir }
+  }
+}
+
+impl Drop for TempInbox {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.dir);
+  }
+}
+
+fn claim(inbox: &TempInbox, now: u64) -> Vec<ChatInboxTask> {
+  chat_inbox_claim_due(&inbox.state, WORKSPACE, now, 8, LEASE_MS).expect("claim")
+}
+
+fn payload() -> ChatInboxPayload {
+  ChatInboxPayload {
+    conversation_id: Ulid::new().to_string(),
+    member_id: Ulid::new().to_string(),
+    viewer_id: Ulid::new().to_string(),
+    content: "done".to_string(),
+    span_id: None,
+  }
+}
+
+#[test]
+fn backoff_doubles_until_cap_and_marks_dead_at_limit() {
+  assert_eq!(plan_terminal_inbox_retry(1, 0), (800, false));
+  assert_eq!(plan_terminal_inbox_retry(2, 0), (1_600, false));
+  assert_eq!(plan_terminal_inbox_retry(5, 0), (12_800, false));
+  assert_eq!(plan_terminal_inbox_retry(TERMINAL_INBOX_MAX_ATTEMPTS, 0), (25_600, true));
+  assert_eq!(plan_terminal_inbox_retry(40, 0), (30_000, true));
+}
+
+#[test]
+fn enqueued_task_is_leased_until_expiry() {
+  let inbox = TempInbox::new();
+  let task = chat_inbox_enqueue(&inbox.state, WORKSPACE, payload(), LEASE_MS).expect("enqueue");
+  assert_eq!((task.status, task.attempts), (ChatInboxStatus::Sending, 1));
+  // 同步落库期间租约未到期，后台不会重复认领。
+  let now = now_millis().unwrap();
+  assert!(claim(&inbox, now).is_empty());
+  let claimed = claim(&inbox, task.next_attempt_at);
+  assert_eq!(claimed.len(), 1);
+  assert_eq!((claimed[0].task_id.as_str(), claimed[0].attempts), (task.task_id.as_str(), 2));
+  chat_inbox_mark_delivered(&inbox.state, WORKSPACE, &task.task_id).unwrap();
+  assert!(claim(&inbox, u64::MAX).is_empty());
+}
+
+#[test]
+fn failed_task_waits_for_backoff_then_dead_letters_and_replays() {
+  let inbox = TempInbox::new();
+  let task = chat_inbox_enqueue(&inbox.state, WORKSPACE, payload(), LEASE_MS).expect("enqueue");
+  let retry_at = task.next_attempt_at + 5_000;
+  chat_inbox_mark_failed(&inbox.state, WORKSPACE, &task.task_id, retry_at, "db busy", false)
+    .expect("mark failed");
+  assert!(claim(&inbox, retry_at - 1).is_empty());
+  let claimed = claim(&inbox, retry_at);
+  assert_eq!(claimed[0].attempts, 2);
+
+  chat_inbox_mark_failed(&inbox.state, WORKSPACE, &task.task_id, 0, "still busy", true).unwrap();
+  assert!(claim(&inbox, u64::MAX).is_empty());
+  let dead = chat_inbox_list_dead_letters(&inbox.state, WORKSPACE).unwrap();
+  assert_eq!(dead.len(), 1);
+  assert_eq!(dead[0].last_error.as_deref(), Some("still busy"));
+
+  assert_eq!(chat_inbox_replay_dead_letters(&inbox.state, WORKSPACE, None).unwrap(), 1);
+  assert!(chat_inbox_list_dead_letters(&inbox.state, WORKSPACE).unwrap().is_empty());
+  let replayed = claim(&inbox, u64::MAX);
+  assert_eq!((replayed[0].attempts, replayed[0].status), (1, ChatInboxStatus::Sending));
+}
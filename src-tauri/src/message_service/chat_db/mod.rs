@@ -4,6 +4,7 @@ mod read;
 mod store;
 mod terminal_session_map;
 mod types;
+mod inbox;
 mod outbox;
 mod write;
 
@@ -17,15 +18,25 @@ pub use types::{
     ChatClearResult, ChatDeleteMemberConversationsResult, ChatHomeFeedDto, ChatRepairResult,
     ConversationSummaryDto, MessageAttachment, MessageContent, MessageDto,
 };
-pub(crate) use types::{ChatOutboxTask, MessageStatus};
+pub(crate) use types::{
+    ChatConversationBrief, ChatInboxDeadLetterDto, ChatInboxPayload, ChatInboxTask,
+    ChatOutboxTask, ChatTerminalAppend, MessageStatus,
+};
 
-pub(crate) use read::compute_workspace_unread_summary;
+pub(crate) use read::{
+    chat_list_conversation_briefs, chat_list_member_conversation_ids,
+    compute_workspace_unread_summary,
+};
 pub use read::{chat_get_conversation_member_ids, chat_get_messages, chat_list_conversations};
+pub(crate) use inbox::{
+    chat_inbox_claim_due, chat_inbox_enqueue, chat_inbox_list_dead_letters,
+    chat_inbox_mark_delivered, chat_inbox_mark_failed, chat_inbox_replay_dead_letters,
+};
 pub(crate) use outbox::{
     chat_outbox_claim_due, chat_outbox_enqueue, chat_outbox_mark_failed, chat_outbox_mark_sent,
 };
 
-pub(crate) use write::chat_append_terminal_message;
+pub(crate) use write::{chat_append_system_message, chat_append_terminal_message};
 pub use write::{
     chat_clear_all_messages, chat_clear_conversation, chat_create_group, chat_delete_conversation,
     chat_delete_member_conversations, chat_ensure_direct, chat_mark_conversation_read_latest,
@@ -2,6 +2,7 @@
 
 use std::collections::{HashMap, HashSet};
 
+use redb::ReadableTable;
 use tauri::State;
 
 use super::store::{
@@ -9,7 +10,10 @@ use super::store::{
   load_member_ids_from_table, open_db, parse_ulid, CONVERSATIONS, MEMBERS, MESSAGES,
   TIMELINE_INDEX, USER_CONVS,
 };
-use super::types::{ChatHomeFeedDto, ConversationMeta, ConvId, UserConversationSettings, WorkspaceUnreadSummary};
+use super::types::{
+  ChatConversationBrief, ChatHomeFeedDto, ConversationMeta, ConvId, UserConversationSettings,
+  WorkspaceUnreadSummary,
+};
 use super::{ChatDbManager};
 
 /// 获取会话列表与时间线汇总。
@@ -317,6 +321,60 @@ pub fn chat_get_conversation_member_ids(
   Ok(member_ids.into_iter().map(format_ulid).collect())
 }
 
+/// 列出工作区全部会话的类型与自定义名称。
+pub(crate) fn chat_list_conversation_briefs(
+  state: &ChatDbManager,
+  workspace_id: &str,
+) -> Result<Vec<ChatConversationBrief>, String> {
+  let db = open_db(state, workspace_id)?;
+  let read_txn = db
+    .begin_read()
+    .map_err(|err| format!("failed to open chat read transaction: {err}"))?;
+  let table = read_txn
+    .open_table(CONVERSATIONS)
+    .map_err(|err| format!("failed to open conversations table: {err}"))?;
+  let mut briefs = Vec::new();
+  for entry in table
+    .iter()
+    .map_err(|err| format!("failed to scan conversations: {err}"))?
+  {
+    let (key, value) = entry.map_err(|err| format!("failed to read conversation: {err}"))?;
+    let meta: ConversationMeta = decode(value.value())?;
+    briefs.push(ChatConversationBrief {
+      id: format_ulid(key.value()),
+      kind: meta.kind.as_str().to_string(),
+      name: meta.custom_name,
+    });
+  }
+  Ok(briefs)
+}
+
+/// 列出成员参与的全部会话 ID（私聊与群聊），供后端系统提示按成员广播。
+pub(crate) fn chat_list_member_conversation_ids(
+  state: &ChatDbManager,
+  workspace_id: &str,
+  member_id: &str,
+) -> Result<Vec<String>, String> {
+  let user_id = parse_ulid(member_id)?;
+  let db = open_db(state, workspace_id)?;
+  let read_txn = db
+    .begin_read()
+    .map_err(|err| format!("failed to open chat read transaction: {err}"))?;
+  let table = read_txn
+    .open_table(USER_CONVS)
+    .map_err(|err| format!("failed to open user_convs table: {err}"))?;
+  let mut conversation_ids = Vec::new();
+  for entry in table
+    .range((user_id, 0)..=(user_id, u128::MAX))
+    .map_err(|err| format!("failed to scan user_convs: {err}"))?
+  {
+    let (key, _) = entry.map_err(|err| format!("failed to decode user_convs entry: {err}"))?;
+    let (_, conv_id) = key.value();
+    conversation_ids.push(format_ulid(conv_id));
+  }
+  Ok(conversation_ids)
+}
+
 /// 读取指定工作区未读概览，给窗口列表与托盘使用。
 pub(crate) fn compute_workspace_unread_summary(
   state: &ChatDbManager,
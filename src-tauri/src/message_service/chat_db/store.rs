@@ -36,11 +36,18 @@ pub(super) const CHAT_OUTBOX_TASKS: TableDefinition<MsgId, &[u8]> =
   TableDefinition::new("chat_outbox_tasks");
 pub(super) const CHAT_OUTBOX_SCHEDULE: TableDefinition<(u64, MsgId), ()> =
   TableDefinition::new("chat_outbox_schedule");
+pub(super) const CHAT_INBOX_TASKS: TableDefinition<MsgId, &[u8]> =
+  TableDefinition::new("chat_inbox_tasks");
+pub(super) const CHAT_INBOX_SCHEDULE: TableDefinition<(u64, MsgId), ()> =
+  TableDefinition::new("chat_inbox_schedule");
 const CHAT_DB_FILE: &str = "chat.redb";
+// 入站队列独立成库：消息库损坏或写入失败时仍能先持久化终端回写，由后台重试。
+const CHAT_INBOX_DB_FILE: &str = "chat_inbox.redb";
 
 /// 聊天数据库管理器：按 workspace 缓存 DB，并确保修复仅执行一次。
 pub struct ChatDbManager {
   dbs: Mutex<HashMap<String, Arc<Database>>>,
+  inbox_dbs: Mutex<HashMap<String, Arc<Database>>>,
   repaired: Mutex<HashSet<String>>,
   base_dir: Mutex<Option<PathBuf>>,
 }
@@ -49,6 +56,7 @@ impl Default for ChatDbManager {
   fn default() -> Self {
     Self {
       dbs: Mutex::new(HashMap::new()),
+      inbox_dbs: Mutex::new(HashMap::new()),
       repaired: Mutex::new(HashSet::new()),
       base_dir: Mutex::new(None),
     }
@@ -125,10 +133,10 @@ fn sanitize_workspace_id(workspace_id: &str) -> Result<&str, String> {
   Ok(trimmed)
 }
 
-fn db_path(state: &ChatDbManager, workspace_id: &str) -> Result<PathBuf, String> {
+fn db_path(state: &ChatDbManager, workspace_id: &str, file_name: &str) -> Result<PathBuf, String> {
   let workspace_id = sanitize_workspace_id(workspace_id)?;
   let base_dir = state.base_dir()?;
-  Ok(base_dir.join(workspace_id).join(CHAT_DB_FILE))
+  Ok(base_dir.join(workspace_id).join(file_name))
 }
 
 fn ensure_tables(db: &Database) -> Result<(), String> {
@@ -177,6 +185,24 @@ fn ensure_tables(db: &Database) -> Result<(), String> {
   Ok(())
 }
 
+fn ensure_inbox_tables(db: &Database) -> Result<(), String> {
+  let txn = db
+    .begin_write()
+    .map_err(|err| format!("failed to open chat inbox write transaction: {err}"))?;
+  {
+    let _ = txn
+      .open_table(CHAT_INBOX_TASKS)
+      .map_err(|err| format!("failed to open chat_inbox_tasks: {err}"))?;
+    let _ = txn
+      .open_table(CHAT_INBOX_SCHEDULE)
+      .map_err(|err| format!("failed to open chat_inbox_schedule: {err}"))?;
+  }
+  txn
+    .commit()
+    .map_err(|err| format!("failed to commit chat inbox init: {err}"))?;
+  Ok(())
+}
+
 pub(super) fn open_db(
   state: &ChatDbManager,
   workspace_id: &str,
@@ -192,7 +218,7 @@ pub(super) fn open_db(
     maybe_repair_messages(state, workspace_id, &db)?;
     return Ok(db);
   }
-  let path = db_path(state, workspace_id)?;
+  let path = db_path(state, workspace_id, CHAT_DB_FILE)?;
   if let Some(parent) = path.parent() {
     fs::create_dir_all(parent).map_err(|err| format!("failed to create chat data dir: {err}"))?;
   }
@@ -204,7 +230,31 @@ pub(super) fn open_db(
   Ok(db)
 }
 
-/// 枚举已创建聊天库的工作区，用于 Outbox 扫描。
+/// 打开工作区的入站队列库（与消息库分离，互不影响可用性）。
+pub(super) fn open_inbox_db(
+  state: &ChatDbManager,
+  workspace_id: &str,
+) -> Result<Arc<Database>, String> {
+  let mut guard = state
+    .inbox_dbs
+    .lock()
+    .map_err(|_| "chat inbox db registry lock poisoned".to_string())?;
+  if let Some(db) = guard.get(workspace_id) {
+    return Ok(db.clone());
+  }
+  let path = db_path(state, workspace_id, CHAT_INBOX_DB_FILE)?;
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|err| format!("failed to create chat data dir: {err}"))?;
+  }
+  let db =
+    Database::create(path).map_err(|err| format!("failed to open chat inbox database: {err}"))?;
+  ensure_inbox_tables(&db)?;
+  let db = Arc::new(db);
+  guard.insert(workspace_id.to_string(), db.clone());
+  Ok(db)
+}
+
+/// 枚举已创建聊天库的工作区，用于 Outbox/Inbox 扫描。
 pub(crate) fn list_workspace_ids(state: &ChatDbManager) -> Result<Vec<String>, String> {
   let base_dir = state.base_dir()?;
   let entries = fs::read_dir(&base_dir)
@@ -563,7 +613,7 @@ pub(super) fn attachment_index_entry(
         thumbnail_path: thumbnail_path.clone(),
       },
     )),
-    MessageAttachmentDb::Roadmap { .. } => None,
+    MessageAttachmentDb::Roadmap { .. } | MessageAttachmentDb::Routed { .. } => None,
   }
 }
 
@@ -698,6 +748,16 @@ pub(super) fn sync_conversation_members(
   Ok(())
 }
 
+/// 待落库的消息内容（不含 id）。
+pub(super) struct MessageDraft {
+  pub(super) conv_id: ConvId,
+  pub(super) sender_id: Option<UserId>,
+  pub(super) content: MessageContent,
+  pub(super) is_ai: bool,
+  pub(super) status: MessageStatus,
+  pub(super) attachment: Option<MessageAttachment>,
+}
+
 pub(super) fn save_message_in_db(
   db: &Database,
   conv_id: ConvId,
@@ -707,7 +767,34 @@ pub(super) fn save_message_in_db(
   status: MessageStatus,
   attachment: Option<MessageAttachment>,
 ) -> Result<MessageDto, String> {
-  let msg_id = Ulid::new().0;
+  save_message_with_id_in_db(
+    db,
+    Ulid::new().0,
+    MessageDraft {
+      conv_id,
+      sender_id,
+      content,
+      is_ai,
+      status,
+      attachment,
+    },
+  )
+}
+
+/// 以调用方指定的消息 id 落库；同 id 重复写入会覆盖原记录，用于入站重试的幂等。
+pub(super) fn save_message_with_id_in_db(
+  db: &Database,
+  msg_id: MsgId,
+  draft: MessageDraft,
+) -> Result<MessageDto, String> {
+  let MessageDraft {
+    conv_id,
+    sender_id,
+    content,
+    is_ai,
+    status,
+    attachment,
+  } = draft;
   let created_at = now_millis()?;
   let content_db = MessageContentDb::from(content.clone());
   let attachment_db = attachment.clone().map(MessageAttachmentDb::from);
@@ -794,7 +881,7 @@ pub(super) fn compute_workspace_unread_summary(
   user_id: &str,
 ) -> Result<WorkspaceUnreadSummary, String> {
   let user_id = parse_ulid(user_id)?;
-  let path = db_path(state, workspace_id)?;
+  let path = db_path(state, workspace_id, CHAT_DB_FILE)?;
   if !path.exists() {
     return Ok(WorkspaceUnreadSummary {
       workspace_id: workspace_id.to_string(),
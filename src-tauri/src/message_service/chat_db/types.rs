@@ -124,6 +124,80 @@ pub(crate) struct ChatOutboxTask {
   pub(crate) last_error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+/// 入站队列状态：终端 final 回写的重试与死信标记。
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ChatInboxStatus {
+  Pending,
+  Sending,
+  Failed,
+  Dead,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+/// 入站载荷：终端 final 回写所需的最小信息。
+pub(crate) struct ChatInboxPayload {
+  pub(crate) conversation_id: String,
+  pub(crate) member_id: String,
+  pub(crate) viewer_id: String,
+  pub(crate) content: String,
+  pub(crate) span_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+/// Inbox 任务：先持久化终端回写，再落库为消息；task_id 同时作为消息 id 保证重试幂等。
+pub(crate) struct ChatInboxTask {
+  pub(crate) task_id: String,
+  pub(crate) payload: ChatInboxPayload,
+  pub(crate) status: ChatInboxStatus,
+  pub(crate) attempts: u32,
+  pub(crate) created_at: u64,
+  pub(crate) updated_at: u64,
+  pub(crate) next_attempt_at: u64,
+  pub(crate) sending_since: Option<u64>,
+  pub(crate) last_error: Option<String>,
+}
+
+/// 终端语义回写的落库请求。
+pub(crate) struct ChatTerminalAppend<'a> {
+  pub(crate) workspace_id: &'a str,
+  pub(crate) conversation_id: &'a str,
+  pub(crate) member_id: &'a str,
+  pub(crate) content: String,
+  pub(crate) viewer_id: &'a str,
+  pub(crate) span_id: Option<&'a str>,
+  /// 入站队列预分配的消息 id；为空时新建。
+  pub(crate) message_id: Option<&'a str>,
+}
+
+/// 终端回写落库结果：`created=false` 表示该 id 此前已落库（入站重试），未重复写入与广播。
+pub(crate) struct ChatTerminalAppended {
+  pub(crate) message_id: String,
+  pub(crate) created: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+/// 死信条目（对外 API）：供 UI 查看与重放。
+#[serde(rename_all = "camelCase")]
+pub struct ChatInboxDeadLetterDto {
+  pub(crate) task_id: String,
+  pub(crate) conversation_id: String,
+  pub(crate) member_id: String,
+  pub(crate) content: String,
+  pub(crate) attempts: u32,
+  pub(crate) created_at: u64,
+  pub(crate) updated_at: u64,
+  pub(crate) last_error: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+/// 会话简要信息：供路由规则按 id 或名称匹配会话。
+pub(crate) struct ChatConversationBrief {
+  pub(crate) id: String,
+  pub(crate) kind: String,
+  pub(crate) name: Option<String>,
+}
+
 #[derive(Serialize, Clone)]
 /// 消息状态更新事件载荷（对外 API）。
 #[serde(rename_all = "camelCase")]
@@ -170,6 +244,13 @@ pub enum MessageAttachment {
     thumbnail_path: Option<String>,
   },
   Roadmap { title: String },
+  /// 路由规则转发的消息：发送者仍为来源成员，附带规则与原消息以便区分人工发送。
+  #[serde(rename_all = "camelCase")]
+  Routed {
+    rule_id: String,
+    source_member_id: String,
+    source_message_id: String,
+  },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -185,6 +266,11 @@ pub(super) enum MessageAttachmentDb {
     thumbnail_path: Option<String>,
   },
   Roadmap { title: String },
+  Routed {
+    rule_id: String,
+    source_member_id: String,
+    source_message_id: String,
+  },
 }
 
 impl From<MessageContent> for MessageContentDb {
@@ -226,6 +312,15 @@ impl From<MessageAttachment> for MessageAttachmentDb {
         thumbnail_path,
       },
       MessageAttachment::Roadmap { title } => MessageAttachmentDb::Roadmap { title },
+      MessageAttachment::Routed {
+        rule_id,
+        source_member_id,
+        source_message_id,
+      } => MessageAttachmentDb::Routed {
+        rule_id,
+        source_member_id,
+        source_message_id,
+      },
     }
   }
 }
@@ -251,6 +346,15 @@ impl From<MessageAttachmentDb> for MessageAttachment {
         thumbnail_path,
       },
       MessageAttachmentDb::Roadmap { title } => MessageAttachment::Roadmap { title },
+      MessageAttachmentDb::Routed {
+        rule_id,
+        source_member_id,
+        source_message_id,
+      } => MessageAttachment::Routed {
+        rule_id,
+        source_member_id,
+        source_message_id,
+      },
     }
   }
 }
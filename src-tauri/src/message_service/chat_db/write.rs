@@ -1,6 +1,6 @@
 //! 聊天写入子域：负责会话与消息的变更与事件广播。
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use redb::ReadableTable;
 use serde_json::json;
@@ -13,12 +13,13 @@ use super::store::{
   build_conversation_summary, clear_chat_storage, compute_conversation_unread_count,
   compute_total_unread_count, count_unread_messages, decode, encode, format_ulid,
   load_member_ids_from_table, now_millis, open_db, parse_ulid, repair_invalid_messages,
-  save_message_in_db, sync_conversation_members, ts_rev, ATTACHMENTS_INDEX, CONVERSATIONS, MEMBERS,
-  MESSAGES, TIMELINE_INDEX, USER_CONVS,
+  save_message_in_db, save_message_with_id_in_db, sync_conversation_members, ts_rev, MessageDraft,
+  ATTACHMENTS_INDEX, CONVERSATIONS, MEMBERS, MESSAGES, TIMELINE_INDEX, USER_CONVS,
 };
 use super::types::{
   ChatClearResult, ChatDeleteMemberConversationsResult, ChatMessage, ChatMessageCreatedPayload,
-  ChatMessageStatusPayload, ChatRepairResult, ChatUnreadSyncPayload, ConversationKind,
+  ChatMessageStatusPayload, ChatRepairResult, ChatTerminalAppend, ChatTerminalAppended,
+  ChatUnreadSyncPayload, ConversationKind,
   ConversationMeta, ConversationSummaryDto, MessageAttachment, MessageContent, MessageDto,
   MessageStatus, UserConversationSettings,
 };
@@ -432,31 +433,66 @@ pub(crate) fn chat_update_message_status(
   Ok(())
 }
 
+fn message_exists(
+  db: &redb::Database,
+  conv_id: super::types::ConvId,
+  msg_id: super::types::MsgId,
+) -> Result<bool, String> {
+  let txn = db
+    .begin_read()
+    .map_err(|err| format!("failed to open chat read transaction: {err}"))?;
+  let table = txn
+    .open_table(MESSAGES)
+    .map_err(|err| format!("failed to open messages table: {err}"))?;
+  let existing = table
+    .get((conv_id, msg_id))
+    .map_err(|err| format!("failed to read message: {err}"))?;
+  Ok(existing.is_some())
+}
+
 /// 终端语义回写的消息追加逻辑。
-/// 返回：持久化后的消息 id。
+/// 输入：`message_id` 由入站队列预分配时按该 id 落库；该 id 已存在时直接返回，重试不会重复写入或广播。
+/// 返回：持久化后的消息 id 与是否为本次新建。
 pub(crate) fn chat_append_terminal_message(
   app: &AppHandle,
   state: &ChatDbManager,
-  workspace_id: &str,
-  conversation_id: &str,
-  member_id: &str,
-  content: String,
-  viewer_id: &str,
-  span_id: Option<&str>,
-) -> Result<String, String> {
+  request: ChatTerminalAppend<'_>,
+) -> Result<ChatTerminalAppended, String> {
+  let ChatTerminalAppend {
+    workspace_id,
+    conversation_id,
+    member_id,
+    content,
+    viewer_id,
+    span_id,
+    message_id,
+  } = request;
   let content_for_log = content.clone();
   let conv_id = parse_ulid(conversation_id)?;
   let sender_id = parse_ulid(member_id)?;
   let viewer_id = parse_ulid(viewer_id)?;
+  let msg_id = match message_id {
+    Some(value) => parse_ulid(value)?,
+    None => Ulid::new().0,
+  };
   let db = open_db(state, workspace_id)?;
-  let message = save_message_in_db(
+  if message_id.is_some() && message_exists(&db, conv_id, msg_id)? {
+    return Ok(ChatTerminalAppended {
+      message_id: format_ulid(msg_id),
+      created: false,
+    });
+  }
+  let message = save_message_with_id_in_db(
     &db,
-    conv_id,
-    Some(sender_id),
-    MessageContent::Text { text: content },
-    false,
-    MessageStatus::Sent,
-    None,
+    msg_id,
+    MessageDraft {
+      conv_id,
+      sender_id: Some(sender_id),
+      content: MessageContent::Text { text: content },
+      is_ai: false,
+      status: MessageStatus::Sent,
+      attachment: None,
+    },
   )?;
   let message_id = message.id.clone();
   diagnostics_log_backend_event(
@@ -502,6 +538,65 @@ pub(crate) fn chat_append_terminal_message(
       "totalUnreadCount": total_unread_count
     }),
   );
+  Ok(ChatTerminalAppended {
+    message_id,
+    created: true,
+  })
+}
+
+/// 后端系统提示的消息追加逻辑（限流、熔断等），无发送者。
+/// 返回：持久化后的消息 id。
+pub(crate) fn chat_append_system_message(
+  app: &AppHandle,
+  state: &ChatDbManager,
+  workspace_id: &str,
+  conversation_id: &str,
+  key: &str,
+  args: Option<HashMap<String, String>>,
+  viewer_id: &str,
+) -> Result<String, String> {
+  let conv_id = parse_ulid(conversation_id)?;
+  let viewer_id = parse_ulid(viewer_id)?;
+  let db = open_db(state, workspace_id)?;
+  let message = save_message_in_db(
+    &db,
+    conv_id,
+    None,
+    MessageContent::System {
+      key: key.to_string(),
+      args: args.clone(),
+    },
+    false,
+    MessageStatus::Sent,
+    None,
+  )?;
+  let message_id = message.id.clone();
+  diagnostics_log_backend_event(
+    &app.state::<DiagnosticsState>(),
+    None,
+    None,
+    Some(conversation_id.to_string()),
+    None,
+    Some(workspace_id.to_string()),
+    "chat_append_system_message",
+    json!({
+      "workspaceId": workspace_id,
+      "conversationId": conversation_id,
+      "messageId": message.id.clone(),
+      "key": key,
+      "args": args
+    }),
+  );
+  let total_unread_count = compute_total_unread_count(&db, viewer_id)?;
+  let payload = ChatMessageCreatedPayload {
+    workspace_id: workspace_id.to_string(),
+    conversation_id: conversation_id.to_string(),
+    message,
+    total_unread_count,
+    span_id: None,
+  };
+  let _ = app.emit("chat-message-created", payload);
+  emit_unread_sync(app, workspace_id, viewer_id, &db, Some(conv_id), false)?;
   Ok(message_id)
 }
 
@@ -1,9 +1,34 @@
 //! 分发解析阶段：确定投递目标与渠道。
 
+use crate::now_millis;
+
+use super::normalize::is_recently_delivered;
 use super::types::{DispatchPlan, MessageEnvelope};
 
-pub(crate) fn plan_terminal(_envelope: &MessageEnvelope) -> Result<DispatchPlan, String> {
-  // [TODO/message-service, 2026-01-26] 生成投递计划（目标、渠道、去向）。
+pub(crate) fn plan_terminal(envelope: &MessageEnvelope) -> Result<DispatchPlan, String> {
+  let payload = &envelope.payload;
+  // 流式帧脱敏后与上一帧一致（增量为空）时无需投递。
+  if payload.mode != "final" {
+    return Ok(DispatchPlan {
+      should_deliver: !payload.content.is_empty(),
+    });
+  }
+  // 归一化后为空或同一 final 块已落库时不再投递。
+  if payload.content.trim().is_empty() {
+    return Ok(DispatchPlan {
+      should_deliver: false,
+    });
+  }
+  if is_recently_delivered(&envelope.dedupe_key, now_millis()?) {
+    log::info!(
+      "terminal final deduped terminal_id={} dedupe_key={}",
+      payload.terminal_id,
+      envelope.dedupe_key
+    );
+    return Ok(DispatchPlan {
+      should_deliver: false,
+    });
+  }
   Ok(DispatchPlan {
     should_deliver: true,
   })
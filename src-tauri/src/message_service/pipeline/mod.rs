@@ -4,6 +4,7 @@ mod dispatch;
 mod normalize;
 mod policy;
 mod reliability;
+mod secrets;
 mod throttle;
 mod types;
 
@@ -18,6 +19,13 @@ use policy::evaluate_terminal;
 use reliability::{deliver_terminal_final, deliver_terminal_stream};
 use throttle::apply_terminal;
 
+pub(crate) use reliability::{
+  plan_terminal_inbox_retry, redeliver_terminal_inbound, TERMINAL_INBOX_LEASE_MS,
+  TERMINAL_INBOX_MAX_ATTEMPTS,
+};
+pub(crate) use throttle::apply_chat_dispatch;
+pub(crate) use types::ThrottleNotice;
+
 pub(crate) fn process_terminal_stream(
   transport: &dyn TerminalMessageTransport,
   repository: &dyn TerminalMessageRepository,
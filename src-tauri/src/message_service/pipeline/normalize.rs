@@ -1,10 +1,181 @@
 //! 归一化阶段：统一消息格式与提取元信息。
+//! 顺序：剥离控制序列 → 去框线与多余空行（仅 final）→ 敏感信息脱敏 → 流式增量计算 → 计算去重键。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use sha2::{Digest, Sha256};
 
 use crate::contracts::terminal_message::TerminalMessagePayload;
+use crate::terminal_engine::filters::rules::sanitize::{clean_lines, strip_control_sequences};
 
+use super::secrets::mask_secrets;
 use super::types::MessageEnvelope;
 
-pub(crate) fn normalize_terminal(payload: TerminalMessagePayload) -> Result<MessageEnvelope, String> {
-  // [TODO/message-service, 2026-01-26] 补齐去重键、元信息抽取与格式标准化。
-  Ok(MessageEnvelope { payload })
+/// 去重窗口：同一成员在同一会话、同一派发 span 内重复的 final 块在该时长内只落库一次。
+const DEDUPE_WINDOW_MS: u64 = 2 * 60 * 1000;
+/// 去重缓存上限，超出后按写入顺序淘汰。
+const DEDUPE_MAX_ENTRIES: usize = 512;
+/// 流式帧缓存上限（按 span 计），超出后按写入顺序淘汰。
+const STREAM_MAX_SPANS: usize = 64;
+
+pub(crate) fn normalize_terminal(mut payload: TerminalMessagePayload) -> Result<MessageEnvelope, String> {
+  let stripped = strip_control_sequences(&payload.content);
+  // 流式增量需与前一帧前缀拼接，不能改动空行结构；final 才做完整清洗。
+  let content = if payload.mode == "final" {
+    let lines: Vec<String> = stripped.split('\n').map(|line| line.to_string()).collect();
+    clean_lines(&lines).join("\n")
+  } else {
+    stripped
+  };
+  payload.content = mask_secrets(&content);
+  if let Some(meta) = payload.meta.as_mut() {
+    meta.command = meta.command.as_deref().map(mask_secrets);
+  }
+  if payload.mode == "final" {
+    forget_stream_frame(&payload);
+  } else {
+    diff_stream_frame(&mut payload);
+  }
+  let dedupe_key = build_dedupe_key(&payload);
+  Ok(MessageEnvelope {
+    payload,
+    dedupe_key,
+  })
+}
+
+/// 去重键：工作区 + 会话 + 成员 + span + 归一化后正文的 SHA-256。
+/// span 区分不同派发轮次，避免相同短回复（如 "Done."）被误判为重复。
+fn build_dedupe_key(payload: &TerminalMessagePayload) -> String {
+  dedupe_key(
+    payload.workspace_id.as_deref().unwrap_or(""),
+    payload.conversation_id.as_deref().unwrap_or(""),
+    payload.member_id.as_deref().unwrap_or(payload.terminal_id.as_str()),
+    payload.span_id.as_deref().unwrap_or(""),
+    &payload.content,
+  )
+}
+
+/// 去重键计算，入站重试按同样的字段重建以与同步落库共享去重窗口。
+pub(super) fn dedupe_key(
+  workspace_id: &str,
+  conversation_id: &str,
+  member_id: &str,
+  span_id: &str,
+  content: &str,
+) -> String {
+  let mut hasher = Sha256::new();
+  for part in [workspace_id, conversation_id, member_id, span_id] {
+    hasher.update(part.as_bytes());
+    hasher.update([0u8]);
+  }
+  hasher.update(content.as_bytes());
+  let digest = hasher.finalize();
+  let mut out = String::with_capacity(digest.len() * 2);
+  for byte in digest {
+    out.push_str(&format!("{:02x}", byte));
+  }
+  out
+}
+
+#[derive(Default)]
+struct StreamFrameCache {
+  masked: HashMap<String, String>,
+  order: VecDeque<String>,
+}
+
+static STREAM_FRAME_CACHE: OnceLock<Mutex<StreamFrameCache>> = OnceLock::new();
+
+fn lock_stream_cache() -> MutexGuard<'static, StreamFrameCache> {
+  let cache = STREAM_FRAME_CACHE.get_or_init(|| Mutex::new(StreamFrameCache::default()));
+  match cache.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+fn stream_key(payload: &TerminalMessagePayload) -> String {
+  format!("{}:{}", payload.terminal_id, payload.span_id.as_deref().unwrap_or(""))
 }
+
+/// 流式帧每次携带整段全文，在全文上脱敏后再与上一帧比较：能接续时只投递增量（`delta`），
+/// 否则整段替换（`stream`）。逐段脱敏会让跨帧拆开的密钥逃过遮蔽。
+fn diff_stream_frame(payload: &mut TerminalMessagePayload) {
+  let key = stream_key(payload);
+  let mut cache = lock_stream_cache();
+  let previous = cache.masked.insert(key.clone(), payload.content.clone());
+  if previous.is_none() {
+    cache.order.push_back(key);
+  }
+  while cache.order.len() > STREAM_MAX_SPANS {
+    if let Some(oldest) = cache.order.pop_front() {
+      cache.masked.remove(&oldest);
+    }
+  }
+  let delta = previous
+    .as_deref()
+    .and_then(|previous| payload.content.strip_prefix(previous))
+    .map(|delta| delta.to_string());
+  match delta {
+    Some(delta) => {
+      payload.content = delta;
+      payload.mode = "delta".to_string();
+    }
+    None => payload.mode = "stream".to_string(),
+  }
+}
+
+/// final 落库后该 span 的流式预览结束。
+fn forget_stream_frame(payload: &TerminalMessagePayload) {
+  let key = stream_key(payload);
+  let mut cache = lock_stream_cache();
+  if cache.masked.remove(&key).is_some() {
+    cache.order.retain(|item| item != &key);
+  }
+}
+
+#[derive(Default)]
+struct DedupeCache {
+  delivered_at: HashMap<String, u64>,
+  order: VecDeque<String>,
+}
+
+static DEDUPE_CACHE: OnceLock<Mutex<DedupeCache>> = OnceLock::new();
+
+fn lock_dedupe_cache() -> MutexGuard<'static, DedupeCache> {
+  let cache = DEDUPE_CACHE.get_or_init(|| Mutex::new(DedupeCache::default()));
+  match cache.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+/// 判断去重键是否在窗口期内已落库。
+pub(super) fn is_recently_delivered(dedupe_key: &str, now: u64) -> bool {
+  let cache = lock_dedupe_cache();
+  cache
+    .delivered_at
+    .get(dedupe_key)
+    .map(|delivered_at| now.saturating_sub(*delivered_at) < DEDUPE_WINDOW_MS)
+    .unwrap_or(false)
+}
+
+/// 落库成功后记录去重键。
+pub(super) fn remember_delivered(dedupe_key: &str, now: u64) {
+  let mut cache = lock_dedupe_cache();
+  if cache
+    .delivered_at
+    .insert(dedupe_key.to_string(), now)
+    .is_none()
+  {
+    cache.order.push_back(dedupe_key.to_string());
+  }
+  while cache.order.len() > DEDUPE_MAX_ENTRIES {
+    if let Some(oldest) = cache.order.pop_front() {
+      cache.delivered_at.remove(&oldest);
+    }
+  }
+}
+
+#[cfg(test)]
+mod stream_tests;
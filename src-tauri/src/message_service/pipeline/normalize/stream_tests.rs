@@ -0,0 +1,54 @@
+use crate::contracts::terminal_message::TerminalMessagePayload;
+
+use super::normalize_terminal;
+
+fn frame(span_id: &str, mode: &str, content: &str) -> (String, String) {
+  let payload = TerminalMessagePayload {
+    terminal_id: "term-stream".to_string(),
+    member_id: Some("m-1".to_string()),
+    workspace_id: Some("ws-1".to_string()),
+    conversation_id: Some("conv-1".to_string()),
+    conversation_type: None,
+    sender_id: Some("m-1".to_string()),
+    sender_name: None,
+    seq: 0,
+    timestamp: 0,
+    content: content.to_string(),
+    message_type: "stream".to_string(),
+    source: "semantic".to_string(),
+    mode: mode.to_string(),
+    span_id: Some(span_id.to_string()),
+    meta: None,
+  };
+  let envelope = normalize_terminal(payload).expect("normalized");
+  (envelope.payload.mode, envelope.payload.content)
+}
+
+#[test]
+fn secret_split_across_frames_is_masked_on_full_text() {
+  let frame = |mode: &str, content: &str| frame("span-split", mode, content);
+  assert_eq!(
+    frame("stream", "key: sk-ant-abcd"),
+    ("stream".to_string(), "key: sk-ant-abcd".to_string())
+  );
+  // 后续帧补全密钥后整段替换，而不是把密钥后半段作为增量发出。
+  assert_eq!(
+    frame("stream", "key: sk-ant-abcdefghijklmnopqr"),
+    ("stream".to_string(), "key: sk-ant-****".to_string())
+  );
+  assert_eq!(
+    frame("stream", "key: sk-ant-abcdefghijklmnopqr done"),
+    ("delta".to_string(), " done".to_string())
+  );
+  assert_eq!(
+    frame("stream", "key: sk-ant-abcdefghijklmnopqr done"),
+    ("delta".to_string(), String::new())
+  );
+}
+
+#[test]
+fn final_frame_resets_stream_state() {
+  assert_eq!(frame("span-final", "stream", "hello").0, "stream");
+  frame("span-final", "final", "hello");
+  assert_eq!(frame("span-final", "stream", "hello world").0, "stream");
+}
@@ -1,11 +1,21 @@
 //! 投递可靠性阶段：队列化、重试与失败补偿。
 
+use crate::now_millis;
 use crate::ports::message_service::{
-  TerminalMessageAppendResult, TerminalMessageRepository, TerminalMessageTransport,
+  TerminalInboundMessage, TerminalMessageAppendResult, TerminalMessageRepository,
+  TerminalMessageTransport,
 };
 
+use super::normalize::{dedupe_key, remember_delivered};
 use super::types::{DispatchPlan, MessageEnvelope, PolicyDecision, ThrottleDecision};
 
+/// 入站任务租约：同步落库期间后台 worker 不会重复认领。
+pub(crate) const TERMINAL_INBOX_LEASE_MS: u64 = 8000;
+/// 入站任务最大尝试次数（含首次同步落库），超过后进入死信。
+pub(crate) const TERMINAL_INBOX_MAX_ATTEMPTS: u32 = 6;
+const TERMINAL_INBOX_BACKOFF_BASE_MS: u64 = 800;
+const TERMINAL_INBOX_BACKOFF_MAX_MS: u64 = 30_000;
+
 pub(crate) fn deliver_terminal_stream(
   transport: &dyn TerminalMessageTransport,
   _repository: &dyn TerminalMessageRepository,
@@ -30,10 +40,14 @@ pub(crate) fn deliver_terminal_final(
   policy: &PolicyDecision,
   throttle: &ThrottleDecision,
 ) -> Result<TerminalMessageAppendResult, String> {
-  if !plan.should_deliver || !policy.allowed || !throttle.allowed {
+  if !plan.should_deliver || !policy.allowed {
     return Ok(TerminalMessageAppendResult::skipped());
   }
   let payload = &envelope.payload;
+  if !throttle.allowed {
+    post_throttle_notice(repository, envelope, throttle);
+    return Ok(TerminalMessageAppendResult::skipped());
+  }
   let missing: Vec<&str> = [
     ("workspaceId", payload.workspace_id.as_ref()),
     ("conversationId", payload.conversation_id.as_ref()),
@@ -52,19 +66,141 @@ pub(crate) fn deliver_terminal_final(
     );
     return Ok(TerminalMessageAppendResult::skipped());
   }
-  let (workspace_id, conversation_id, member_id, viewer_id) = (
-    payload.workspace_id.as_ref().unwrap(),
-    payload.conversation_id.as_ref().unwrap(),
-    payload.member_id.as_ref().unwrap(),
-    payload.sender_id.as_ref().unwrap(),
-  );
-  // [TODO/message-service, 2026-01-26] 接入重试/死信队列，避免持久化失败后丢消息。
-  repository.append_terminal_message(
+  let message = TerminalInboundMessage {
+    workspace_id: payload.workspace_id.clone().unwrap_or_default(),
+    conversation_id: payload.conversation_id.clone().unwrap_or_default(),
+    member_id: payload.member_id.clone().unwrap_or_default(),
+    viewer_id: payload.sender_id.clone().unwrap_or_default(),
+    content: payload.content.clone(),
+    span_id: payload.span_id.clone(),
+  };
+  // 先写入入站队列（独立库）再落库。入站库本身不可用时只能直接落库，此次失败将无法自动重试，按错误上报。
+  let task_id = match repository.enqueue_terminal_inbound(&message, TERMINAL_INBOX_LEASE_MS) {
+    Ok(task_id) => Some(task_id),
+    Err(err) => {
+      log::error!(
+        "terminal inbox enqueue failed, appending without retry terminal_id={} err={}",
+        payload.terminal_id,
+        err
+      );
+      None
+    }
+  };
+  let result = append_inbound(repository, &message, task_id.as_deref(), 1)?;
+  if result.persisted {
+    remember_delivered(&envelope.dedupe_key, now_millis()?);
+  }
+  Ok(result)
+}
+
+/// 后台重试入站任务，与同步落库走同一路径：成功后删除任务并记录去重键，失败按次数退避或进入死信。
+/// 输入：`attempts` 为含本次在内的已尝试次数。
+pub(crate) fn redeliver_terminal_inbound(
+  repository: &dyn TerminalMessageRepository,
+  task_id: &str,
+  attempts: u32,
+  message: &TerminalInboundMessage,
+) -> Result<TerminalMessageAppendResult, String> {
+  let result = append_inbound(repository, message, Some(task_id), attempts)?;
+  if result.persisted {
+    let dedupe_key = dedupe_key(
+      &message.workspace_id,
+      &message.conversation_id,
+      &message.member_id,
+      message.span_id.as_deref().unwrap_or(""),
+      &message.content,
+    );
+    remember_delivered(&dedupe_key, now_millis()?);
+  }
+  Ok(result)
+}
+
+/// 按入站任务 id 落库：成功后删除任务；失败时安排下次重试，达到上限进入死信。
+fn append_inbound(
+  repository: &dyn TerminalMessageRepository,
+  message: &TerminalInboundMessage,
+  task_id: Option<&str>,
+  attempts: u32,
+) -> Result<TerminalMessageAppendResult, String> {
+  let workspace_id = message.workspace_id.as_str();
+  let result = match repository.append_terminal_message(message, task_id) {
+    Ok(result) => result,
+    Err(err) => {
+      let Some(task_id) = task_id else {
+        return Err(err);
+      };
+      let (next_attempt_at, mark_dead) = plan_terminal_inbox_retry(attempts, now_millis()?);
+      if let Err(mark_err) = repository.mark_terminal_inbound_failed(
+        workspace_id,
+        task_id,
+        next_attempt_at,
+        &err,
+        mark_dead,
+      ) {
+        log::warn!(
+          "terminal inbox mark failed workspace_id={} task_id={} err={}",
+          workspace_id,
+          task_id,
+          mark_err
+        );
+      }
+      return Err(format!(
+        "failed to append terminal message, queued for retry task_id={task_id}: {err}"
+      ));
+    }
+  };
+  if let Some(task_id) = task_id {
+    if let Err(err) = repository.mark_terminal_inbound_delivered(workspace_id, task_id) {
+      log::warn!(
+        "terminal inbox mark delivered failed workspace_id={} task_id={} err={}",
+        workspace_id,
+        task_id,
+        err
+      );
+    }
+  }
+  Ok(result)
+}
+
+/// 入站重试计划：按已尝试次数指数退避，达到上限返回死信标记。
+/// 返回：(下次尝试时间, 是否进入死信)。
+pub(crate) fn plan_terminal_inbox_retry(attempts: u32, now: u64) -> (u64, bool) {
+  let mark_dead = attempts >= TERMINAL_INBOX_MAX_ATTEMPTS;
+  let factor = attempts.max(1).saturating_sub(1).min(6);
+  let delay = TERMINAL_INBOX_BACKOFF_BASE_MS
+    .saturating_mul(2u64.saturating_pow(factor))
+    .min(TERMINAL_INBOX_BACKOFF_MAX_MS);
+  (now.saturating_add(delay), mark_dead)
+}
+
+/// 限流拒绝时写入系统提示；提示本身失败不影响主流程。
+fn post_throttle_notice(
+  repository: &dyn TerminalMessageRepository,
+  envelope: &MessageEnvelope,
+  throttle: &ThrottleDecision,
+) {
+  let Some(notice) = throttle.notice.as_ref() else {
+    return;
+  };
+  let payload = &envelope.payload;
+  let (Some(workspace_id), Some(conversation_id), Some(viewer_id)) = (
+    payload.workspace_id.as_deref(),
+    payload.conversation_id.as_deref(),
+    payload.sender_id.as_deref(),
+  ) else {
+    return;
+  };
+  if let Err(err) = repository.append_system_message(
     workspace_id,
     conversation_id,
-    member_id,
-    payload.content.clone(),
+    notice.key.as_str(),
+    Some(notice.args.clone()),
     viewer_id,
-    payload.span_id.as_deref(),
-  )
+  ) {
+    log::warn!(
+      "terminal throttle notice failed terminal_id={} err={}",
+      payload.terminal_id,
+      err
+    );
+  }
 }
@@ -0,0 +1,260 @@
+//! 敏感信息脱敏：在消息落库与广播前遮蔽疑似密钥、令牌与私钥。
+//! 约束：宁可多遮蔽也不漏遮；仅依赖字符扫描，不引入正则。
+
+const MASK: &str = "****";
+const PRIVATE_KEY_MASK: &str = "[REDACTED PRIVATE KEY]";
+
+/// 已知厂商令牌前缀与前缀后最短长度（不含前缀）。
+const TOKEN_PREFIXES: &[(&str, usize)] = &[
+  ("sk-ant-", 16),
+  ("sk-proj-", 16),
+  ("sk-", 20),
+  ("ghp_", 20),
+  ("gho_", 20),
+  ("ghu_", 20),
+  ("ghs_", 20),
+  ("ghr_", 20),
+  ("github_pat_", 20),
+  ("glpat-", 16),
+  ("xoxb-", 16),
+  ("xoxp-", 16),
+  ("xoxa-", 16),
+  ("xapp-", 16),
+  ("AKIA", 16),
+  ("ASIA", 16),
+  ("AIza", 30),
+  ("hf_", 20),
+  ("npm_", 20),
+];
+
+/// `KEY=value` / `"key": "value"` 中被视为敏感的键名片段（小写比较）。
+const SENSITIVE_KEY_PARTS: &[&str] = &[
+  "api_key",
+  "apikey",
+  "api-key",
+  "secret",
+  "token",
+  "password",
+  "passwd",
+  "access_key",
+  "private_key",
+  "credential",
+];
+
+/// 常见于用量统计的键名片段（如 `max_tokens`、`total_tokens`），值为纯数字时不视为凭据。
+const NUMERIC_EXEMPT_KEY_PARTS: &[&str] = &["token"];
+
+/// 赋值类脱敏的最短值长度，避免把 `token=1` 这类示例误判。
+const MIN_ASSIGNED_SECRET_LEN: usize = 8;
+/// Bearer/JWT 的最短长度。
+const MIN_BEARER_LEN: usize = 16;
+
+/// 对文本执行全部脱敏规则，返回遮蔽后的文本。
+pub(super) fn mask_secrets(text: &str) -> String {
+  let text = mask_private_key_blocks(text);
+  let text = mask_prefixed_tokens(&text);
+  let text = mask_bearer_tokens(&text);
+  let text = mask_jwt_tokens(&text);
+  mask_assignments(&text)
+}
+
+fn is_token_char(ch: char) -> bool {
+  ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'
+}
+
+fn mask_private_key_blocks(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find("-----BEGIN ") {
+    let header_end = match rest[start..].find("-----\n").or_else(|| rest[start..].find("-----\r")) {
+      Some(offset) => start + offset,
+      None => break,
+    };
+    let header = &rest[start..header_end];
+    if !header.contains("PRIVATE KEY") {
+      output.push_str(&rest[..header_end]);
+      rest = &rest[header_end..];
+      continue;
+    }
+    output.push_str(&rest[..start]);
+    output.push_str(PRIVATE_KEY_MASK);
+    // 找不到结尾时遮蔽到文本末尾，避免截断输出泄漏半个私钥。
+    match rest[header_end..].find("-----END ") {
+      Some(offset) => {
+        let end_start = header_end + offset;
+        let end = rest[end_start + 9..]
+          .find("-----")
+          .map(|value| end_start + 9 + value + 5)
+          .unwrap_or(rest.len());
+        rest = &rest[end..];
+      }
+      None => {
+        rest = "";
+      }
+    }
+  }
+  output.push_str(rest);
+  output
+}
+
+fn mask_prefixed_tokens(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut index = 0usize;
+  let mut previous: Option<char> = None;
+  while index < text.len() {
+    let rest = &text[index..];
+    let at_boundary = previous.map(|ch| !is_token_char(ch)).unwrap_or(true);
+    if at_boundary {
+      if let Some((prefix, token_len)) = match_prefixed_token(rest) {
+        output.push_str(prefix);
+        output.push_str(MASK);
+        index += token_len;
+        previous = text[..index].chars().next_back();
+        continue;
+      }
+    }
+    let ch = rest.chars().next().unwrap_or_default();
+    output.push(ch);
+    index += ch.len_utf8();
+    previous = Some(ch);
+  }
+  output
+}
+
+fn match_prefixed_token(rest: &str) -> Option<(&'static str, usize)> {
+  for (prefix, min_len) in TOKEN_PREFIXES {
+    if !rest.starts_with(prefix) {
+      continue;
+    }
+    let body_len = rest[prefix.len()..]
+      .chars()
+      .take_while(|ch| is_token_char(*ch))
+      .count();
+    if body_len >= *min_len {
+      return Some((prefix, prefix.len() + body_len));
+    }
+  }
+  None
+}
+
+fn mask_bearer_tokens(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut rest = text;
+  loop {
+    let lower = rest.to_ascii_lowercase();
+    let Some(start) = lower.find("bearer ") else {
+      break;
+    };
+    let value_start = start + "bearer ".len();
+    let value_len = rest[value_start..]
+      .chars()
+      .take_while(|ch| is_token_char(*ch) || *ch == '.' || *ch == '=' || *ch == '+' || *ch == '/')
+      .count();
+    output.push_str(&rest[..value_start]);
+    if value_len >= MIN_BEARER_LEN {
+      output.push_str(MASK);
+    } else {
+      output.push_str(&rest[value_start..value_start + value_len]);
+    }
+    rest = &rest[value_start + value_len..];
+  }
+  output.push_str(rest);
+  output
+}
+
+fn mask_jwt_tokens(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find("eyJ") {
+    let boundary = rest[..start]
+      .chars()
+      .next_back()
+      .map(|ch| !is_token_char(ch))
+      .unwrap_or(true);
+    let candidate_len = rest[start..]
+      .chars()
+      .take_while(|ch| is_token_char(*ch) || *ch == '.')
+      .count();
+    let candidate = &rest[start..start + candidate_len];
+    output.push_str(&rest[..start]);
+    if boundary && candidate.split('.').filter(|part| !part.is_empty()).count() == 3
+      && candidate_len >= MIN_BEARER_LEN
+    {
+      output.push_str(MASK);
+    } else {
+      output.push_str(candidate);
+    }
+    rest = &rest[start + candidate_len..];
+  }
+  output.push_str(rest);
+  output
+}
+
+/// 按行处理 `KEY=value`、`key: value` 与 JSON 形式的赋值，仅遮蔽值部分。
+fn mask_assignments(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  for (index, line) in text.split('\n').enumerate() {
+    if index > 0 {
+      output.push('\n');
+    }
+    output.push_str(&mask_assignment_line(line));
+  }
+  output
+}
+
+fn mask_assignment_line(line: &str) -> String {
+  let mut output = String::with_capacity(line.len());
+  let mut rest = line;
+  while let Some(separator) = rest.find(['=', ':']) {
+    let key = rest[..separator]
+      .trim_end()
+      .trim_end_matches(['"', '\''])
+      .rsplit(|ch: char| !(is_token_char(ch) || ch == '.'))
+      .next()
+      .unwrap_or("")
+      .to_ascii_lowercase();
+    let after = &rest[separator + 1..];
+    let value_offset = after.len() - after.trim_start().len();
+    let value_area = &after[value_offset..];
+    let quote = value_area.chars().next().filter(|ch| *ch == '"' || *ch == '\'');
+    let value_body = match quote {
+      Some(_) => &value_area[1..],
+      None => value_area,
+    };
+    let value_len = match quote {
+      Some(quote) => value_body.find(quote).unwrap_or(value_body.len()),
+      None => value_body
+        .find(|ch: char| ch.is_whitespace() || ch == ',' || ch == ';')
+        .unwrap_or(value_body.len()),
+    };
+    let value = &value_body[..value_len];
+    let sensitive = is_sensitive_assignment(&key, value)
+      && value.chars().count() >= MIN_ASSIGNED_SECRET_LEN
+      && value != MASK
+      && !value.ends_with(MASK);
+    let value_start = separator + 1 + value_offset + quote.map(|_| 1).unwrap_or(0);
+    if sensitive {
+      output.push_str(&rest[..value_start]);
+      output.push_str(MASK);
+      rest = &rest[value_start + value_len..];
+    } else {
+      output.push_str(&rest[..separator + 1]);
+      rest = &rest[separator + 1..];
+    }
+  }
+  output.push_str(rest);
+  output
+}
+
+fn is_sensitive_assignment(key: &str, value: &str) -> bool {
+  let numeric = value
+    .chars()
+    .all(|ch| ch.is_ascii_digit() || ch == '.' || ch == ',' || ch == '_');
+  SENSITIVE_KEY_PARTS
+    .iter()
+    .filter(|part| key.contains(*part))
+    .any(|part| !numeric || !NUMERIC_EXEMPT_KEY_PARTS.contains(part))
+}
+
+#[cfg(test)]
+mod mask_tests;
@@ -1,14 +1,370 @@
 //! 节流与配额阶段：统一限速与削峰逻辑。
+//! 覆盖两个方向：终端 final 回写聊天、聊天派发到终端；均按成员/会话/工作区三级令牌桶限流。
+//! 另外追踪 agent 之间的 @ 链路，无人类介入时链路过长视为循环并熔断。
 
-use super::types::{DispatchPlan, MessageEnvelope, PolicyDecision, ThrottleDecision};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::now_millis;
+
+use super::types::{DispatchPlan, MessageEnvelope, PolicyDecision, ThrottleDecision, ThrottleNotice};
+
+/// 令牌桶规格：`capacity` 为突发上限，`refill_interval_ms` 为每补充 1 个令牌的间隔。
+#[derive(Clone, Copy)]
+struct BucketSpec {
+  capacity: u32,
+  refill_interval_ms: u64,
+}
+
+// 终端 final 回写：单成员 6 条突发、约 10 秒 1 条；会话与工作区逐级放宽。
+const FINAL_MEMBER_BUCKET: BucketSpec = BucketSpec {
+  capacity: 6,
+  refill_interval_ms: 10_000,
+};
+const FINAL_CONVERSATION_BUCKET: BucketSpec = BucketSpec {
+  capacity: 20,
+  refill_interval_ms: 3_000,
+};
+const FINAL_WORKSPACE_BUCKET: BucketSpec = BucketSpec {
+  capacity: 60,
+  refill_interval_ms: 1_000,
+};
+// 聊天派发到终端：成员维度按目标成员计数，避免同一终端被指令淹没。
+const DISPATCH_MEMBER_BUCKET: BucketSpec = BucketSpec {
+  capacity: 6,
+  refill_interval_ms: 10_000,
+};
+const DISPATCH_CONVERSATION_BUCKET: BucketSpec = BucketSpec {
+  capacity: 20,
+  refill_interval_ms: 3_000,
+};
+const DISPATCH_WORKSPACE_BUCKET: BucketSpec = BucketSpec {
+  capacity: 60,
+  refill_interval_ms: 1_000,
+};
+/// agent 间连续 @ 的最大跳数；超过后在人类发言前不再派发。
+const MENTION_CHAIN_MAX_HOPS: u32 = 12;
+/// 链路空闲超过该时长视为新的一轮协作。
+const MENTION_CHAIN_IDLE_RESET_MS: u64 = 10 * 60 * 1000;
+/// 同一限流键的系统提示冷却，避免提示本身刷屏。
+const NOTICE_COOLDOWN_MS: u64 = 60_000;
+
+const NOTICE_KEY_FINAL_LIMITED: &str = "chat.messages.system.throttle.final";
+const NOTICE_KEY_DISPATCH_LIMITED: &str = "chat.messages.system.throttle.dispatch";
+const NOTICE_KEY_MENTION_LOOP: &str = "chat.messages.system.throttle.mentionLoop";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ThrottleDirection {
+  Final,
+  Dispatch,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ThrottleScope {
+  Member,
+  Conversation,
+  Workspace,
+}
+
+impl ThrottleScope {
+  fn as_str(self) -> &'static str {
+    match self {
+      ThrottleScope::Member => "member",
+      ThrottleScope::Conversation => "conversation",
+      ThrottleScope::Workspace => "workspace",
+    }
+  }
+}
+
+type BucketKey = (ThrottleDirection, ThrottleScope, String);
+
+struct TokenBucket {
+  spec: BucketSpec,
+  tokens: u32,
+  last_refill_at: u64,
+}
+
+impl TokenBucket {
+  fn new(spec: BucketSpec, now: u64) -> Self {
+    Self {
+      spec,
+      tokens: spec.capacity,
+      last_refill_at: now,
+    }
+  }
+
+  fn refill(&mut self, now: u64) {
+    if self.tokens >= self.spec.capacity {
+      self.last_refill_at = now;
+      return;
+    }
+    let elapsed = now.saturating_sub(self.last_refill_at);
+    let interval = self.spec.refill_interval_ms.max(1);
+    let gained = elapsed / interval;
+    if gained == 0 {
+      return;
+    }
+    let gained = u32::try_from(gained).unwrap_or(u32::MAX);
+    self.tokens = self.tokens.saturating_add(gained).min(self.spec.capacity);
+    self.last_refill_at = self.last_refill_at.saturating_add(interval.saturating_mul(u64::from(gained)));
+  }
+
+  /// 距离下一个令牌可用的等待时长（毫秒）。
+  fn retry_after_ms(&self, now: u64) -> u64 {
+    if self.tokens > 0 {
+      return 0;
+    }
+    let next_at = self.last_refill_at.saturating_add(self.spec.refill_interval_ms);
+    next_at.saturating_sub(now)
+  }
+}
+
+#[derive(Default)]
+struct MentionChain {
+  hops: u32,
+  last_at: u64,
+}
+
+#[derive(Default)]
+struct ThrottleRegistry {
+  buckets: HashMap<BucketKey, TokenBucket>,
+  mention_chains: HashMap<(String, String), MentionChain>,
+  notice_sent_at: HashMap<String, u64>,
+}
+
+/// 待检查的限流维度：方向 + 维度 + 标识 + 规格。
+struct BucketRequest<'a> {
+  direction: ThrottleDirection,
+  scope: ThrottleScope,
+  id: &'a str,
+  spec: BucketSpec,
+}
+
+struct BucketBreach {
+  scope: ThrottleScope,
+  id: String,
+  retry_after_ms: u64,
+}
+
+impl ThrottleRegistry {
+  /// 全部维度都有令牌时才整体扣减，避免部分扣减导致配额泄漏。
+  fn try_acquire(&mut self, requests: &[BucketRequest<'_>], now: u64) -> Result<(), BucketBreach> {
+    for request in requests {
+      let key = (request.direction, request.scope, request.id.to_string());
+      let bucket = self
+        .buckets
+        .entry(key)
+        .or_insert_with(|| TokenBucket::new(request.spec, now));
+      bucket.refill(now);
+      if bucket.tokens == 0 {
+        return Err(BucketBreach {
+          scope: request.scope,
+          id: request.id.to_string(),
+          retry_after_ms: bucket.retry_after_ms(now),
+        });
+      }
+    }
+    for request in requests {
+      let key = (request.direction, request.scope, request.id.to_string());
+      if let Some(bucket) = self.buckets.get_mut(&key) {
+        bucket.tokens = bucket.tokens.saturating_sub(1);
+      }
+    }
+    Ok(())
+  }
+
+  /// 返回本次派发放行后的链路跳数（不记录）；人类发言会重置链路。
+  fn next_mention_hops(&mut self, key: &(String, String), sender_is_agent: bool, now: u64) -> u32 {
+    if !sender_is_agent {
+      self.mention_chains.remove(key);
+      return 0;
+    }
+    let hops = match self.mention_chains.get(key) {
+      Some(chain) if now.saturating_sub(chain.last_at) <= MENTION_CHAIN_IDLE_RESET_MS => chain.hops,
+      _ => 0,
+    };
+    hops.saturating_add(1)
+  }
+
+  /// 聊天派发的限流与循环检测；只有放行的派发才计入 @ 链路，被限流的派发不累积跳数。
+  fn check_chat_dispatch(
+    &mut self,
+    workspace_id: &str,
+    conversation_id: &str,
+    sender_id: &str,
+    sender_is_agent: bool,
+    target_ids: &[String],
+    now: u64,
+  ) -> ThrottleDecision {
+    let chain_key = (workspace_id.to_string(), conversation_id.to_string());
+    let hops = self.next_mention_hops(&chain_key, sender_is_agent, now);
+    if hops > MENTION_CHAIN_MAX_HOPS {
+      log::warn!(
+        "chat dispatch mention loop workspace_id={} conversation_id={} sender_id={} hops={}",
+        workspace_id,
+        conversation_id,
+        sender_id,
+        hops
+      );
+      let notice = self
+        .should_notify(format!("loop:{}:{}", workspace_id, conversation_id), now)
+        .then(|| ThrottleNotice {
+          key: NOTICE_KEY_MENTION_LOOP.to_string(),
+          args: HashMap::from([("hops".to_string(), MENTION_CHAIN_MAX_HOPS.to_string())]),
+        });
+      return ThrottleDecision::deny(notice);
+    }
+    let mut requests = vec![
+      BucketRequest {
+        direction: ThrottleDirection::Dispatch,
+        scope: ThrottleScope::Conversation,
+        id: conversation_id,
+        spec: DISPATCH_CONVERSATION_BUCKET,
+      },
+      BucketRequest {
+        direction: ThrottleDirection::Dispatch,
+        scope: ThrottleScope::Workspace,
+        id: workspace_id,
+        spec: DISPATCH_WORKSPACE_BUCKET,
+      },
+    ];
+    requests.extend(target_ids.iter().map(|target_id| BucketRequest {
+      direction: ThrottleDirection::Dispatch,
+      scope: ThrottleScope::Member,
+      id: target_id.as_str(),
+      spec: DISPATCH_MEMBER_BUCKET,
+    }));
+    let Err(breach) = self.try_acquire(&requests, now) else {
+      if sender_is_agent {
+        self.mention_chains.insert(chain_key, MentionChain { hops, last_at: now });
+      }
+      return ThrottleDecision::allow();
+    };
+    log::warn!(
+      "chat dispatch throttled workspace_id={} conversation_id={} scope={} id={} retry_after_ms={}",
+      workspace_id,
+      conversation_id,
+      breach.scope.as_str(),
+      breach.id,
+      breach.retry_after_ms
+    );
+    let notice = self
+      .should_notify(
+        format!("dispatch:{}:{}:{}", conversation_id, breach.scope.as_str(), breach.id),
+        now,
+      )
+      .then(|| build_limit_notice(NOTICE_KEY_DISPATCH_LIMITED, &breach));
+    ThrottleDecision::deny(notice)
+  }
+
+  /// 同一提示键在冷却期内只放行一次。
+  fn should_notify(&mut self, notice_key: String, now: u64) -> bool {
+    match self.notice_sent_at.get(&notice_key) {
+      Some(sent_at) if now.saturating_sub(*sent_at) < NOTICE_COOLDOWN_MS => false,
+      _ => {
+        self.notice_sent_at.insert(notice_key, now);
+        true
+      }
+    }
+  }
+}
+
+static THROTTLE_REGISTRY: OnceLock<Mutex<ThrottleRegistry>> = OnceLock::new();
+
+fn lock_registry() -> MutexGuard<'static, ThrottleRegistry> {
+  let registry = THROTTLE_REGISTRY.get_or_init(|| Mutex::new(ThrottleRegistry::default()));
+  match registry.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
 
 pub(crate) fn apply_terminal(
-  _envelope: &MessageEnvelope,
-  _plan: &DispatchPlan,
-  _policy: &PolicyDecision,
+  envelope: &MessageEnvelope,
+  plan: &DispatchPlan,
+  policy: &PolicyDecision,
+) -> Result<ThrottleDecision, String> {
+  let payload = &envelope.payload;
+  // 流式增量只影响侧边预览，不计入配额；仅 final 落库才扣令牌。
+  if !plan.should_deliver || !policy.allowed || payload.mode != "final" {
+    return Ok(ThrottleDecision::allow());
+  }
+  let (Some(workspace_id), Some(conversation_id), Some(member_id)) = (
+    payload.workspace_id.as_deref(),
+    payload.conversation_id.as_deref(),
+    payload.member_id.as_deref(),
+  ) else {
+    return Ok(ThrottleDecision::allow());
+  };
+  let now = now_millis()?;
+  let mut registry = lock_registry();
+  let requests = [
+    BucketRequest {
+      direction: ThrottleDirection::Final,
+      scope: ThrottleScope::Member,
+      id: member_id,
+      spec: FINAL_MEMBER_BUCKET,
+    },
+    BucketRequest {
+      direction: ThrottleDirection::Final,
+      scope: ThrottleScope::Conversation,
+      id: conversation_id,
+      spec: FINAL_CONVERSATION_BUCKET,
+    },
+    BucketRequest {
+      direction: ThrottleDirection::Final,
+      scope: ThrottleScope::Workspace,
+      id: workspace_id,
+      spec: FINAL_WORKSPACE_BUCKET,
+    },
+  ];
+  let Err(breach) = registry.try_acquire(&requests, now) else {
+    return Ok(ThrottleDecision::allow());
+  };
+  log::warn!(
+    "terminal final throttled terminal_id={} scope={} id={} retry_after_ms={}",
+    payload.terminal_id,
+    breach.scope.as_str(),
+    breach.id,
+    breach.retry_after_ms
+  );
+  let notice = registry
+    .should_notify(
+      format!("final:{}:{}:{}", conversation_id, breach.scope.as_str(), breach.id),
+      now,
+    )
+    .then(|| build_limit_notice(NOTICE_KEY_FINAL_LIMITED, &breach));
+  Ok(ThrottleDecision::deny(notice))
+}
+
+/// 聊天派发到终端前的限流与循环检测。
+/// 输入：`sender_is_agent` 表示发送方是否为终端成员；`target_ids` 为本次派发目标。
+/// 返回：允许时 `allowed=true`；拒绝时可能携带需要写入会话的系统提示。
+pub(crate) fn apply_chat_dispatch(
+  workspace_id: &str,
+  conversation_id: &str,
+  sender_id: &str,
+  sender_is_agent: bool,
+  target_ids: &[String],
 ) -> Result<ThrottleDecision, String> {
-  // [TODO/message-service, 2026-01-26] 会话/成员/频道维度限流与配额。
-  Ok(ThrottleDecision {
-    allowed: true,
-  })
+  let now = now_millis()?;
+  Ok(lock_registry().check_chat_dispatch(
+    workspace_id,
+    conversation_id,
+    sender_id,
+    sender_is_agent,
+    target_ids,
+    now,
+  ))
 }
+
+fn build_limit_notice(key_prefix: &str, breach: &BucketBreach) -> ThrottleNotice {
+  let retry_after_secs = breach.retry_after_ms.div_ceil(1000).max(1);
+  ThrottleNotice {
+    key: format!("{}.{}", key_prefix, breach.scope.as_str()),
+    args: HashMap::from([("seconds".to_string(), retry_after_secs.to_string())]),
+  }
+}
+
+#[cfg(test)]
+mod bucket_tests;
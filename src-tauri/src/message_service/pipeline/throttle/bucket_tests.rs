@@ -0,0 +1,101 @@
+use super::{
+  BucketRequest, BucketSpec, ThrottleDirection, ThrottleRegistry, ThrottleScope, TokenBucket,
+  MENTION_CHAIN_IDLE_RESET_MS, MENTION_CHAIN_MAX_HOPS,
+};
+
+const SPEC: BucketSpec = BucketSpec {
+  capacity: 2,
+  refill_interval_ms: 1_000,
+};
+
+fn dispatch(
+  registry: &mut ThrottleRegistry,
+  sender_is_agent: bool,
+  target: &str,
+  now: u64,
+) -> (bool, Option<String>) {
+  let targets = [target.to_string()];
+  let decision = registry.check_chat_dispatch("ws", "conv", "sender", sender_is_agent, &targets, now);
+  (decision.allowed, decision.notice.map(|notice| notice.key))
+}
+
+#[test]
+fn bucket_refills_whole_intervals_up_to_capacity() {
+  let mut bucket = TokenBucket::new(SPEC, 0);
+  bucket.tokens = 0;
+  assert_eq!(bucket.retry_after_ms(400), 600);
+  bucket.refill(1_500);
+  assert_eq!((bucket.tokens, bucket.last_refill_at), (1, 1_000));
+  // 不足一个间隔的剩余时间保留到下次补充。
+  bucket.refill(1_999);
+  assert_eq!(bucket.tokens, 1);
+  assert_eq!(bucket.retry_after_ms(1_999), 0);
+  bucket.refill(60_000);
+  assert_eq!(bucket.tokens, SPEC.capacity);
+}
+
+#[test]
+fn acquire_is_all_or_nothing_across_scopes() {
+  let mut registry = ThrottleRegistry::default();
+  let request = |scope, id| BucketRequest {
+    direction: ThrottleDirection::Dispatch,
+    scope,
+    id,
+    spec: SPEC,
+  };
+  let member = [request(ThrottleScope::Member, "m1")];
+  assert!(registry.try_acquire(&member, 0).is_ok());
+  assert!(registry.try_acquire(&member, 0).is_ok());
+  let both = [request(ThrottleScope::Conversation, "c1"), request(ThrottleScope::Member, "m1")];
+  let breach = registry.try_acquire(&both, 250).unwrap_err();
+  assert_eq!((breach.scope.as_str(), breach.retry_after_ms), ("member", 750));
+  // 成员维度拒绝时会话维度不扣减。
+  let key = (ThrottleDirection::Dispatch, ThrottleScope::Conversation, "c1".to_string());
+  assert_eq!(registry.buckets[&key].tokens, SPEC.capacity);
+}
+
+#[test]
+fn member_limit_denies_with_notice_once_per_cooldown() {
+  let mut registry = ThrottleRegistry::default();
+  for _ in 0..6 {
+    assert_eq!(dispatch(&mut registry, false, "m1", 0), (true, None));
+  }
+  assert_eq!(
+    dispatch(&mut registry, false, "m1", 0),
+    (false, Some("chat.messages.system.throttle.dispatch.member".to_string()))
+  );
+  assert_eq!(dispatch(&mut registry, false, "m1", 1), (false, None));
+  assert_eq!(dispatch(&mut registry, false, "m1", 10_000), (true, None));
+}
+
+#[test]
+fn throttled_dispatches_do_not_extend_mention_chain() {
+  let mut registry = ThrottleRegistry::default();
+  let max = MENTION_CHAIN_MAX_HOPS as usize;
+  for _ in 0..6 {
+    assert!(dispatch(&mut registry, true, "m1", 0).0);
+  }
+  for _ in 0..10 {
+    assert!(!dispatch(&mut registry, true, "m1", 0).0);
+  }
+  for index in 6..max {
+    assert!(dispatch(&mut registry, true, &format!("m{index}"), 0).0, "hop {index}");
+  }
+  assert_eq!(
+    dispatch(&mut registry, true, "fresh", 0),
+    (false, Some("chat.messages.system.throttle.mentionLoop".to_string()))
+  );
+  // 人类发言重置链路。
+  assert!(dispatch(&mut registry, false, "human-target", 0).0);
+  assert!(dispatch(&mut registry, true, "fresh", 0).0);
+}
+
+#[test]
+fn idle_mention_chain_starts_over() {
+  let mut registry = ThrottleRegistry::default();
+  for index in 0..MENTION_CHAIN_MAX_HOPS {
+    assert!(dispatch(&mut registry, true, &format!("m{index}"), 0).0);
+  }
+  assert!(!dispatch(&mut registry, true, "late", 1).0);
+  assert!(dispatch(&mut registry, true, "late", MENTION_CHAIN_IDLE_RESET_MS + 1).0);
+}
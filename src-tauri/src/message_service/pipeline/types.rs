@@ -1,10 +1,14 @@
 //! 消息流水线类型：承载规范化与投递决策数据。
 
+use std::collections::HashMap;
+
 use crate::contracts::terminal_message::TerminalMessagePayload;
 
 #[derive(Clone)]
 pub(crate) struct MessageEnvelope {
   pub(crate) payload: TerminalMessagePayload,
+  /// 归一化后正文的去重键，用于避免同一 final 块重复落库。
+  pub(crate) dedupe_key: String,
 }
 
 #[derive(Clone)]
@@ -20,4 +24,29 @@ pub(crate) struct PolicyDecision {
 #[derive(Clone)]
 pub(crate) struct ThrottleDecision {
   pub(crate) allowed: bool,
+  /// 限流触发时需要写入会话的系统提示；冷却期内为空。
+  pub(crate) notice: Option<ThrottleNotice>,
+}
+
+impl ThrottleDecision {
+  pub(crate) fn allow() -> Self {
+    Self {
+      allowed: true,
+      notice: None,
+    }
+  }
+
+  pub(crate) fn deny(notice: Option<ThrottleNotice>) -> Self {
+    Self {
+      allowed: false,
+      notice,
+    }
+  }
+}
+
+#[derive(Clone)]
+/// 限流系统提示：对应 `MessageContent::System` 的 i18n key 与参数。
+pub(crate) struct ThrottleNotice {
+  pub(crate) key: String,
+  pub(crate) args: HashMap<String, String>,
 }
@@ -1,5 +1,7 @@
 //! 项目数据服务：负责工作区项目数据的读取与持久化规则。
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use serde::Serialize;
 use serde_json::Value;
 
@@ -7,6 +9,14 @@ use crate::runtime::{storage, StorageManager};
 
 const PROJECT_DATA_PATH: &str = ".golutra/workspace.json";
 
+// 项目数据写入版本：每次写入递增，供派生缓存（如路由规则）判断是否失效。
+static PROJECT_DATA_REVISION: AtomicU64 = AtomicU64::new(0);
+
+/// 当前项目数据写入版本；与缓存时记录的版本不同即需重新读取。
+pub(crate) fn project_data_revision() -> u64 {
+  PROJECT_DATA_REVISION.load(Ordering::Acquire)
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 /// 项目数据读取结果：包含来源与可选告警信息。
@@ -104,6 +114,8 @@ pub(crate) fn write_project_data(
     return Err("workspace path is empty".to_string());
   }
 
+  // 先递增版本：写入中途失败时也让缓存重读，避免继续使用旧数据。
+  PROJECT_DATA_REVISION.fetch_add(1, Ordering::AcqRel);
   let app_path = project_data_app_path(workspace_id);
   if read_only {
     storage::write_app_json(storage, &app_path, payload)?;
@@ -1,12 +1,14 @@
 //! 项目成员存储：封装成员列表的读写与清理操作。
 
+use std::collections::HashMap;
+
 use serde_json::{json, Map, Value};
 use ulid::Ulid;
 
 use crate::message_service::project_data;
 use crate::runtime::StorageManager;
 
-const DEFAULT_OWNER_ID: &str = "01J00000000000000000000000";
+pub(crate) const DEFAULT_OWNER_ID: &str = "01J00000000000000000000000";
 const DEFAULT_OWNER_NAME: &str = "Owner";
 const DEFAULT_OWNER_ROLE_KEY: &str = "members.roles.owner";
 const DEFAULT_OWNER_ROLE_TYPE: &str = "owner";
@@ -30,9 +32,36 @@ pub(crate) struct ProjectMemberStore<'a> {
 pub(crate) struct ProjectMemberPurgeResult {
     pub(crate) removed_count: usize,
     pub(crate) removed_member_ids: Vec<String>,
+    // 被清理成员登记的 `worktree` 字段，由调用方负责移除对应的 git 工作树。
+    pub(crate) removed_worktrees: Vec<Value>,
     pub(crate) warning: Option<String>,
 }
 
+/// 成员记录：`member` 为该成员对象，`members` 为完整成员列表（引导词需要队友信息），
+/// `onboarding` 为项目级引导配置。
+pub(crate) struct ProjectMemberRecord {
+    pub(crate) members: Vec<Value>,
+    pub(crate) member: Option<Value>,
+    pub(crate) onboarding: Option<Value>,
+}
+
+impl ProjectMemberRecord {
+    /// 成员对象字段（`terminalTuning`、`restartPolicy`、`approvalPolicy`）；缺失或不是对象时返回 None。
+    pub(crate) fn object_field(&self, key: &str) -> Option<&Value> {
+        self.field(key).filter(|value| value.is_object())
+    }
+
+    /// 成员自定义启动后脚本（`postReadyScript`，步骤数组或对象）；缺失时返回 None。
+    pub(crate) fn post_ready_script(&self) -> Option<&Value> {
+        self.field("postReadyScript")
+            .filter(|value| value.is_object() || value.is_array())
+    }
+
+    fn field(&self, key: &str) -> Option<&Value> {
+        self.member.as_ref()?.get(key)
+    }
+}
+
 pub(crate) struct ProjectMemberInviteResult {
     pub(crate) members: Vec<Value>,
     pub(crate) created_members: Vec<Value>,
@@ -168,6 +197,102 @@ impl<'a> ProjectMemberStore<'a> {
         })
     }
 
+    /// 读取成员记录与项目级引导配置；项目数据缺失或成员不存在时对应字段为空。
+    /// 创建终端会话时只读一次，调优、策略、引导词与启动后脚本均从该记录解析。
+    pub(crate) fn member_record(
+        &self,
+        workspace_id: &str,
+        workspace_path: &str,
+        member_id: &str,
+    ) -> Result<ProjectMemberRecord, String> {
+        let read_result = project_data::read_project_data(self.storage, workspace_path, workspace_id)?;
+        let payload = read_result.data.unwrap_or(Value::Null);
+        let members = payload
+            .get("members")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let member = members
+            .iter()
+            .find(|member| member.get("id").and_then(Value::as_str) == Some(member_id))
+            .cloned();
+        let onboarding = payload
+            .get("onboarding")
+            .filter(|value| value.is_object())
+            .cloned();
+        Ok(ProjectMemberRecord {
+            members,
+            member,
+            onboarding,
+        })
+    }
+
+    /// 读取成员列表；项目数据缺失时返回空列表。
+    pub(crate) fn read_members(
+        &self,
+        workspace_id: &str,
+        workspace_path: &str,
+    ) -> Result<Vec<Value>, String> {
+        let read_result = project_data::read_project_data(self.storage, workspace_path, workspace_id)?;
+        Ok(read_result
+            .data
+            .as_ref()
+            .and_then(|payload| payload.get("members"))
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// 批量设置成员字段：`None` 表示移除该字段；返回更新后的成员列表。
+    pub(crate) fn update_members_field(
+        &self,
+        workspace_id: &str,
+        workspace_path: &str,
+        key: &str,
+        updates: &HashMap<String, Option<Value>>,
+    ) -> Result<Vec<Value>, String> {
+        let read_result = project_data::read_project_data(self.storage, workspace_path, workspace_id)?;
+        let mut payload = read_result
+            .data
+            .ok_or_else(|| "project data not found".to_string())?;
+        let members = payload
+            .get_mut("members")
+            .and_then(Value::as_array_mut)
+            .ok_or_else(|| "project members is not a JSON array".to_string())?;
+        for member in members.iter_mut() {
+            let Some(object) = member.as_object_mut() else {
+                continue;
+            };
+            let Some(update) = object
+                .get("id")
+                .and_then(Value::as_str)
+                .and_then(|id| updates.get(id))
+            else {
+                continue;
+            };
+            match update {
+                Some(value) => {
+                    object.insert(key.to_string(), value.clone());
+                }
+                None => {
+                    object.remove(key);
+                }
+            }
+        }
+        let members = members.clone();
+        let write_result = project_data::write_project_data(
+            self.storage,
+            workspace_path,
+            workspace_id,
+            false,
+            payload,
+        )?;
+        if let Some(warning) = write_result.warning {
+            log::warn!("project members update warning workspace_id={} warning={}", workspace_id, warning);
+        }
+        Ok(members)
+    }
+
     pub(crate) fn purge_terminal_members(
         &self,
         workspace_id: &str,
@@ -181,6 +306,7 @@ impl<'a> ProjectMemberStore<'a> {
                 return Ok(ProjectMemberPurgeResult {
                     removed_count: 0,
                     removed_member_ids: Vec::new(),
+                    removed_worktrees: Vec::new(),
                     warning,
                 });
             }
@@ -202,6 +328,7 @@ impl<'a> ProjectMemberStore<'a> {
 
         let mut removed_count = 0usize;
         let mut removed_member_ids = Vec::new();
+        let mut removed_worktrees = Vec::new();
         members_array.retain(|value| {
             if !is_terminal_member(value) || is_owner_member(value) {
                 return true;
@@ -209,6 +336,9 @@ impl<'a> ProjectMemberStore<'a> {
             if let Some(id) = value.get("id").and_then(|id| id.as_str()) {
                 removed_member_ids.push(id.to_string());
             }
+            if let Some(worktree) = value.get("worktree").filter(|worktree| worktree.is_object()) {
+                removed_worktrees.push(worktree.clone());
+            }
             removed_count += 1;
             false
         });
@@ -231,6 +361,7 @@ impl<'a> ProjectMemberStore<'a> {
         Ok(ProjectMemberPurgeResult {
             removed_count,
             removed_member_ids,
+            removed_worktrees,
             warning,
         })
     }
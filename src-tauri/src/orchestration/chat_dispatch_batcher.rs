@@ -112,6 +112,18 @@ impl ChatDispatchBatcher {
     Ok(())
   }
 
+  /// 终端在批次器中的排队深度（含在途批次），用于成员池的最少排队策略。
+  pub(crate) fn queue_depth(&self, terminal_id: &str) -> usize {
+    let guard = match self.queues.lock() {
+      Ok(guard) => guard,
+      Err(err) => err.into_inner(),
+    };
+    guard
+      .get(terminal_id)
+      .map(|queue| queue.pending.len() + usize::from(queue.inflight.is_some()))
+      .unwrap_or(0)
+  }
+
   fn handle_semantic_flush_complete(&self, app: &AppHandle, terminal_id: &str) {
     let mut dispatch_now: Option<DispatchBatch> = None;
     {
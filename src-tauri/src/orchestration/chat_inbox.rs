@@ -0,0 +1,119 @@
+//! 聊天入站 Inbox Worker：重试终端 final 回写，超过上限进入死信。
+//! 重试经由消息流水线的可靠性阶段落库，与同步落库共享去重、路由与死信逻辑。
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::message_service::chat_db::{
+  chat_inbox_claim_due, list_workspace_ids, ChatDbManager, ChatInboxTask,
+};
+use crate::message_service::pipeline::{
+  plan_terminal_inbox_retry, redeliver_terminal_inbound, TERMINAL_INBOX_LEASE_MS,
+  TERMINAL_INBOX_MAX_ATTEMPTS,
+};
+use crate::now_millis;
+use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
+use crate::ports::message_service::{TerminalInboundMessage, TerminalMessageRepository};
+
+const INBOX_POLL_INTERVAL_MS: u64 = 500;
+const INBOX_CLAIM_LIMIT: usize = 8;
+
+pub(crate) fn spawn_chat_inbox_worker(app: AppHandle, repository: Arc<dyn TerminalMessageRepository>) {
+  thread::spawn(move || loop {
+    let now = match now_millis() {
+      Ok(value) => value,
+      Err(err) => {
+        log::warn!("chat inbox time read failed err={}", err);
+        thread::sleep(Duration::from_millis(INBOX_POLL_INTERVAL_MS));
+        continue;
+      }
+    };
+    let workspace_ids: Vec<String> = match list_workspace_ids(app.state::<ChatDbManager>().inner()) {
+      Ok(value) => value,
+      Err(err) => {
+        log::warn!("chat inbox list workspace failed err={}", err);
+        thread::sleep(Duration::from_millis(INBOX_POLL_INTERVAL_MS));
+        continue;
+      }
+    };
+    for workspace_id in workspace_ids {
+      let tasks = match chat_inbox_claim_due(
+        app.state::<ChatDbManager>().inner(),
+        workspace_id.as_str(),
+        now,
+        INBOX_CLAIM_LIMIT,
+        TERMINAL_INBOX_LEASE_MS,
+      ) {
+        Ok(value) => value,
+        Err(err) => {
+          log::warn!(
+            "chat inbox claim failed workspace_id={} err={}",
+            workspace_id,
+            err
+          );
+          continue;
+        }
+      };
+      for task in tasks {
+        deliver_inbox_task(&app, repository.as_ref(), &workspace_id, task);
+      }
+    }
+    thread::sleep(Duration::from_millis(INBOX_POLL_INTERVAL_MS));
+  });
+}
+
+fn deliver_inbox_task(
+  app: &AppHandle,
+  repository: &dyn TerminalMessageRepository,
+  workspace_id: &str,
+  task: ChatInboxTask,
+) {
+  let payload = &task.payload;
+  let message = TerminalInboundMessage {
+    workspace_id: workspace_id.to_string(),
+    conversation_id: payload.conversation_id.clone(),
+    member_id: payload.member_id.clone(),
+    viewer_id: payload.viewer_id.clone(),
+    content: payload.content.clone(),
+    span_id: payload.span_id.clone(),
+  };
+  if let Err(err) = redeliver_terminal_inbound(repository, &task.task_id, task.attempts, &message) {
+    log_inbox_error(app, workspace_id, &task, err.as_str());
+  }
+}
+
+/// 重试失败的诊断记录；任务状态已由流水线更新。
+fn log_inbox_error(app: &AppHandle, workspace_id: &str, task: &ChatInboxTask, error: &str) {
+  let now = now_millis().unwrap_or(0);
+  let (next_attempt_at, mark_dead) = plan_terminal_inbox_retry(task.attempts, now);
+  log::warn!(
+    "chat inbox redeliver failed workspace_id={} task_id={} attempts={} dead={} err={}",
+    workspace_id,
+    task.task_id,
+    task.attempts,
+    mark_dead,
+    error
+  );
+  diagnostics_log_backend_event(
+    &app.state::<DiagnosticsState>(),
+    Some(task.payload.member_id.clone()),
+    None,
+    Some(task.payload.conversation_id.clone()),
+    None,
+    Some(workspace_id.to_string()),
+    "chat_inbox_deliver_error",
+    json!({
+      "workspaceId": workspace_id,
+      "conversationId": task.payload.conversation_id,
+      "taskId": task.task_id,
+      "attempts": task.attempts,
+      "maxAttempts": TERMINAL_INBOX_MAX_ATTEMPTS,
+      "error": error,
+      "nextAttemptAt": if mark_dead { None } else { Some(next_attempt_at) }
+    }),
+  );
+}
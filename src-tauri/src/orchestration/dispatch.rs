@@ -5,15 +5,24 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use tauri::{AppHandle, Manager, State, WebviewWindow};
 
+use crate::application::worktree::member_worktree_cwd;
 use crate::contracts::chat_dispatch::{ChatDispatchMentions, ChatDispatchPayload};
-use crate::message_service::chat_db::{chat_get_conversation_member_ids, ChatDbManager};
+use crate::message_service::chat_db::{
+    chat_append_system_message, chat_get_conversation_member_ids, ChatDbManager,
+};
+use crate::message_service::pipeline::{self, ThrottleNotice};
 use crate::message_service::project_data;
 use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
 use crate::runtime::StorageManager;
 use crate::terminal_engine::session::{
-    terminal_create, terminal_dispatch_chat, TerminalDispatchContext, TerminalManager,
+    terminal_create, terminal_dispatch_chat, wake_hibernating_session, TerminalCreateOptions,
+    TerminalDispatchContext, TerminalManager,
 };
 use super::chat_dispatch_batcher::ChatDispatchBatcher;
+use super::member_pool::{
+    advance_round_robin, parse_pool_mentions, pick_pool_member, resolve_pool_config,
+    PoolCandidate, PoolPick,
+};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -42,6 +51,8 @@ struct MemberTerminalConfig {
     terminal_type: Option<String>,
     terminal_command: Option<String>,
     terminal_path: Option<String>,
+    // 成员独立工作树路径；存在时作为终端 cwd。
+    worktree_path: Option<String>,
 }
 
 /// 编排并执行终端消息派发。
@@ -104,8 +115,8 @@ pub fn orchestrate_chat_dispatch(
         &payload.workspace_path,
         &payload.workspace_id,
     )?;
-    let member_configs = match project_data.data {
-        Some(value) => collect_member_configs(&value),
+    let member_configs = match project_data.data.as_ref() {
+        Some(value) => collect_member_configs(value),
         None => HashMap::new(),
     };
     let member_config_count = member_configs.len();
@@ -122,6 +133,26 @@ pub fn orchestrate_chat_dispatch(
         payload.sender_id.as_str(),
         mentions,
     );
+    let mut round_robin_keys = Vec::new();
+    if payload.conversation_type != "dm" {
+        let batcher = app.state::<Arc<ChatDispatchBatcher>>();
+        for pick in resolve_pool_targets(
+            app,
+            &payload,
+            &terminal_state,
+            batcher.inner().as_ref(),
+            project_data.data.as_ref(),
+            &member_ids,
+            &member_configs,
+        ) {
+            if let Some(key) = pick.round_robin_key {
+                round_robin_keys.push(key);
+            }
+            if !targets.contains(&pick.member_id) {
+                targets.push(pick.member_id);
+            }
+        }
+    }
     if targets.is_empty() {
         log_chat_dispatch_skip(
             app,
@@ -148,6 +179,38 @@ pub fn orchestrate_chat_dispatch(
         return Ok(());
     }
 
+    // 限流与 @ 循环检测：发送方为终端成员时计入 agent 链路，人类发言会重置链路。
+    let sender_is_agent = member_configs
+        .get(&payload.sender_id)
+        .map(has_terminal_config)
+        .unwrap_or(false);
+    let throttle = pipeline::apply_chat_dispatch(
+        &payload.workspace_id,
+        &payload.conversation_id,
+        &payload.sender_id,
+        sender_is_agent,
+        &targets,
+    )?;
+    if !throttle.allowed {
+        log_chat_dispatch_skip(
+            app,
+            &payload,
+            "throttled",
+            json!({
+                "senderIsAgent": sender_is_agent,
+                "targetCount": targets.len(),
+                "noticeKey": throttle.notice.as_ref().map(|notice| notice.key.clone())
+            }),
+        );
+        if let Some(notice) = throttle.notice {
+            post_dispatch_notice(app, &payload, notice);
+        }
+        return Ok(());
+    }
+    for key in &round_robin_keys {
+        advance_round_robin(key);
+    }
+
     let context = TerminalDispatchContext {
         conversation_id: payload.conversation_id.clone(),
         conversation_type: payload.conversation_type.clone(),
@@ -226,6 +289,69 @@ fn resolve_targets(
         .collect()
 }
 
+/// 解析 `@name*` 池提及：候选为会话内属于该池且具备终端配置的成员（排除发送者），按池策略选出一个实例。
+fn resolve_pool_targets(
+    app: &AppHandle,
+    payload: &ChatDispatchPayload,
+    terminal_state: &State<'_, TerminalManager>,
+    batcher: &ChatDispatchBatcher,
+    project_data: Option<&Value>,
+    member_ids: &[String],
+    member_configs: &HashMap<String, MemberTerminalConfig>,
+) -> Vec<PoolPick> {
+    let mut picked: Vec<PoolPick> = Vec::new();
+    for prefix in parse_pool_mentions(&payload.text) {
+        let pool = resolve_pool_config(project_data, &prefix);
+        let candidates: Vec<PoolCandidate> = member_ids
+            .iter()
+            .filter(|id| id.as_str() != payload.sender_id)
+            .filter_map(|id| member_configs.get(id))
+            .filter(|config| has_terminal_config(config))
+            .filter(|config| pool.contains(&prefix, &config.id, &config.name))
+            .map(|config| {
+                let load = terminal_state
+                    .member_dispatch_load(&config.id, Some(payload.workspace_id.as_str()));
+                let batched = terminal_state
+                    .find_session_id_by_member(&config.id, Some(payload.workspace_id.as_str()))
+                    .map(|terminal_id| batcher.queue_depth(&terminal_id))
+                    .unwrap_or(0);
+                PoolCandidate {
+                    member_id: config.id.clone(),
+                    idle: load.as_ref().map(|load| load.idle && batched == 0).unwrap_or(false),
+                    queued: load.map(|load| load.queued).unwrap_or(0) + batched,
+                }
+            })
+            .collect();
+        let strategy = pool.strategy;
+        let pool_key = format!("{}:{}", payload.workspace_id, prefix.to_lowercase());
+        let selected = pick_pool_member(&pool_key, strategy, &candidates);
+        diagnostics_log_backend_event(
+            &app.state::<DiagnosticsState>(),
+            Some(payload.sender_id.clone()),
+            None,
+            Some(payload.conversation_id.clone()),
+            None,
+            Some(payload.workspace_id.clone()),
+            "chat_dispatch_pool_pick",
+            json!({
+                "workspaceId": payload.workspace_id,
+                "conversationId": payload.conversation_id,
+                "messageId": payload.message_id,
+                "pool": prefix,
+                "strategy": strategy.as_str(),
+                "candidateCount": candidates.len(),
+                "selected": selected.as_ref().map(|pick| pick.member_id.clone())
+            }),
+        );
+        if let Some(pick) = selected {
+            if !picked.iter().any(|item| item.member_id == pick.member_id) {
+                picked.push(pick);
+            }
+        }
+    }
+    picked
+}
+
 fn collect_member_configs(payload: &Value) -> HashMap<String, MemberTerminalConfig> {
     let mut map = HashMap::new();
     let Some(members) = payload.get("members").and_then(|value| value.as_array()) else {
@@ -259,6 +385,7 @@ fn collect_member_configs(payload: &Value) -> HashMap<String, MemberTerminalConf
                 terminal_type,
                 terminal_command,
                 terminal_path,
+                worktree_path: member_worktree_cwd(member),
             },
         );
     }
@@ -302,6 +429,7 @@ fn ensure_backend_session(
     if let Some(session_id) =
         state.find_session_id_by_member(config.id.as_str(), workspace_id)
     {
+        wake_hibernating_session(app, &session_id)?;
         return Ok(session_id);
     }
 
@@ -309,23 +437,18 @@ fn ensure_backend_session(
         app.clone(),
         window.clone(),
         state.clone(),
-        None,
-        None,
-        cwd.map(|value| value.to_string()),
-        Some(config.id.clone()),
-        workspace_id.map(|value| value.to_string()),
-        Some(true),
-        None,
-        Some(config.terminal_type.clone()),
-        config.terminal_command.clone(),
-        config.terminal_path.clone(),
-        None,
-        Some("none".to_string()),
-        Some(config.name.clone()),
-        None,
-        None,
-        None,
-        None,
+        TerminalCreateOptions {
+            cwd: cwd.map(|value| value.to_string()),
+            member_id: Some(config.id.clone()),
+            workspace_id: workspace_id.map(|value| value.to_string()),
+            keep_alive: Some(true),
+            terminal_type: Some(config.terminal_type.clone()),
+            terminal_command: config.terminal_command.clone(),
+            terminal_path: config.terminal_path.clone(),
+            post_ready_mode: Some("none".to_string()),
+            member_name: Some(config.name.clone()),
+            ..TerminalCreateOptions::default()
+        },
     )
 }
 
@@ -340,33 +463,50 @@ fn ensure_backend_member_session(
     if let Some(session_id) =
         state.find_session_id_by_member(config.id.as_str(), Some(workspace_id))
     {
+        // 休眠成员原地唤醒，派发消息排队至唤醒流程结束后投递。
+        wake_hibernating_session(app, &session_id)?;
         return Ok(session_id);
     }
 
+    let cwd = config.worktree_path.as_deref().unwrap_or(workspace_path);
     terminal_create(
         app.clone(),
         window.clone(),
         state.clone(),
-        None,
-        None,
-        Some(workspace_path.to_string()),
-        Some(config.id.clone()),
-        Some(workspace_id.to_string()),
-        Some(true),
-        None,
-        config.terminal_type.clone(),
-        config.terminal_command.clone(),
-        config.terminal_path.clone(),
-        None,
-        Some("none".to_string()),
-        Some(config.name.clone()),
-        None,
-        None,
-        None,
-        None,
+        TerminalCreateOptions {
+            cwd: Some(cwd.to_string()),
+            workspace_path: Some(workspace_path.to_string()),
+            member_id: Some(config.id.clone()),
+            workspace_id: Some(workspace_id.to_string()),
+            keep_alive: Some(true),
+            terminal_type: config.terminal_type.clone(),
+            terminal_command: config.terminal_command.clone(),
+            terminal_path: config.terminal_path.clone(),
+            post_ready_mode: Some("none".to_string()),
+            member_name: Some(config.name.clone()),
+            ..TerminalCreateOptions::default()
+        },
     )
 }
 
+fn post_dispatch_notice(app: &AppHandle, payload: &ChatDispatchPayload, notice: ThrottleNotice) {
+    if let Err(err) = chat_append_system_message(
+        app,
+        app.state::<ChatDbManager>().inner(),
+        &payload.workspace_id,
+        &payload.conversation_id,
+        notice.key.as_str(),
+        Some(notice.args),
+        &payload.sender_id,
+    ) {
+        log::warn!(
+            "chat dispatch throttle notice failed conversation_id={} err={}",
+            payload.conversation_id,
+            err
+        );
+    }
+}
+
 fn log_chat_dispatch_skip(
     app: &AppHandle,
     payload: &ChatDispatchPayload,
@@ -0,0 +1,210 @@
+//! 成员池派发：将 `@coder*` 这类角色级提及解析为池内某个具体实例。
+//! 策略：轮询（round-robin）、最少排队（least-queued）、首个空闲（first-idle）。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use serde_json::Value;
+
+const MEMBER_POOLS_KEY: &str = "memberPools";
+const POOL_MENTION_SUFFIX: char = '*';
+const MENTION_TRAILING_PUNCTUATION: [char; 9] = [',', '.', ':', ';', '!', '?', '，', '。', '：'];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// 成员池选择策略；未配置时默认轮询。
+pub(crate) enum PoolStrategy {
+  RoundRobin,
+  LeastQueued,
+  FirstIdle,
+}
+
+impl PoolStrategy {
+  fn from_str(value: &str) -> Option<Self> {
+    match value.trim().to_lowercase().as_str() {
+      "round-robin" | "roundrobin" => Some(PoolStrategy::RoundRobin),
+      "least-queued" | "leastqueued" => Some(PoolStrategy::LeastQueued),
+      "first-idle" | "firstidle" => Some(PoolStrategy::FirstIdle),
+      _ => None,
+    }
+  }
+
+  pub(crate) fn as_str(&self) -> &'static str {
+    match self {
+      PoolStrategy::RoundRobin => "round-robin",
+      PoolStrategy::LeastQueued => "least-queued",
+      PoolStrategy::FirstIdle => "first-idle",
+    }
+  }
+}
+
+/// 池配置：策略与可选的显式成员列表（id 或名称）；未列出成员时按实例命名规则匹配。
+pub(crate) struct PoolConfig {
+  pub(crate) strategy: PoolStrategy,
+  members: Option<Vec<String>>,
+}
+
+impl PoolConfig {
+  /// 判断成员是否属于池：显式列表优先，否则要求名称为 `{pool}-{n}` 实例名。
+  pub(crate) fn contains(&self, prefix: &str, member_id: &str, name: &str) -> bool {
+    match self.members.as_ref() {
+      Some(members) => members.iter().any(|entry| {
+        let entry = entry.trim().trim_start_matches('@');
+        entry == member_id || entry.eq_ignore_ascii_case(name.trim())
+      }),
+      None => is_pool_member_name(prefix, name),
+    }
+  }
+}
+
+/// 池内选中结果：轮询策略附带游标键，需在派发放行后再推进游标。
+pub(crate) struct PoolPick {
+  pub(crate) member_id: String,
+  pub(crate) round_robin_key: Option<String>,
+}
+
+/// 池内候选实例：顺序即成员创建顺序，用于稳定的平局处理。
+pub(crate) struct PoolCandidate {
+  pub(crate) member_id: String,
+  pub(crate) idle: bool,
+  pub(crate) queued: usize,
+}
+
+static ROUND_ROBIN_CURSORS: OnceLock<Mutex<HashMap<String, usize>>> = OnceLock::new();
+
+fn lock_round_robin_cursors() -> MutexGuard<'static, HashMap<String, usize>> {
+  let cursors = ROUND_ROBIN_CURSORS.get_or_init(|| Mutex::new(HashMap::new()));
+  match cursors.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+/// 从消息文本中提取 `@name*` 形式的池提及，返回去重后的名称前缀。
+pub(crate) fn parse_pool_mentions(text: &str) -> Vec<String> {
+  let mut prefixes: Vec<String> = Vec::new();
+  for token in text.split_whitespace() {
+    let Some(rest) = token.strip_prefix('@') else {
+      continue;
+    };
+    let rest = rest.trim_end_matches(MENTION_TRAILING_PUNCTUATION);
+    let Some(prefix) = rest.strip_suffix(POOL_MENTION_SUFFIX) else {
+      continue;
+    };
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+      continue;
+    }
+    if !prefixes.iter().any(|item| item.eq_ignore_ascii_case(prefix)) {
+      prefixes.push(prefix.to_string());
+    }
+  }
+  prefixes
+}
+
+/// 判断成员名称是否为池实例：与批量邀请的命名一致，形如 `{pool}-{n}`（池名大小写不敏感）。
+pub(crate) fn is_pool_member_name(prefix: &str, name: &str) -> bool {
+  let prefix = prefix.trim();
+  let name = name.trim();
+  if prefix.is_empty() {
+    return false;
+  }
+  let Some(head) = name.get(..prefix.len()) else {
+    return false;
+  };
+  let Some(sequence) = name[prefix.len()..].strip_prefix('-') else {
+    return false;
+  };
+  head.eq_ignore_ascii_case(prefix)
+    && !sequence.is_empty()
+    && sequence.chars().all(|ch| ch.is_ascii_digit())
+}
+
+/// 读取项目数据 `memberPools` 中的池配置：
+/// `[{ "name": "coder", "strategy": "least-queued", "members": ["coder-1", "reviewer"] }]`。
+pub(crate) fn resolve_pool_config(project_data: Option<&Value>, prefix: &str) -> PoolConfig {
+  let pool = project_data
+    .and_then(|value| value.get(MEMBER_POOLS_KEY))
+    .and_then(|value| value.as_array())
+    .and_then(|pools| {
+      pools.iter().find(|pool| {
+        pool
+          .get("name")
+          .and_then(|value| value.as_str())
+          .map(|name| name.trim().eq_ignore_ascii_case(prefix.trim()))
+          .unwrap_or(false)
+      })
+    });
+  let strategy = pool
+    .and_then(|pool| pool.get("strategy"))
+    .and_then(|value| value.as_str())
+    .and_then(PoolStrategy::from_str)
+    .unwrap_or(PoolStrategy::RoundRobin);
+  let members = pool
+    .and_then(|pool| pool.get("members"))
+    .and_then(|value| value.as_array())
+    .map(|entries| {
+      entries
+        .iter()
+        .filter_map(|entry| entry.as_str())
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+    });
+  PoolConfig { strategy, members }
+}
+
+/// 按策略在候选中挑选实例；轮询只读取游标，放行后由 `advance_round_robin` 推进。
+/// first-idle 无空闲实例时退化为 least-queued；least-queued 平局取创建顺序靠前者。
+pub(crate) fn pick_pool_member(
+  pool_key: &str,
+  strategy: PoolStrategy,
+  candidates: &[PoolCandidate],
+) -> Option<PoolPick> {
+  if candidates.is_empty() {
+    return None;
+  }
+  let pick = match strategy {
+    PoolStrategy::RoundRobin => {
+      let cursor = lock_round_robin_cursors().get(pool_key).copied().unwrap_or(0);
+      PoolPick {
+        member_id: candidates[cursor % candidates.len()].member_id.clone(),
+        round_robin_key: Some(pool_key.to_string()),
+      }
+    }
+    PoolStrategy::FirstIdle => {
+      let picked = match candidates.iter().find(|candidate| candidate.idle) {
+        Some(candidate) => candidate,
+        None => least_queued(candidates),
+      };
+      PoolPick {
+        member_id: picked.member_id.clone(),
+        round_robin_key: None,
+      }
+    }
+    PoolStrategy::LeastQueued => PoolPick {
+      member_id: least_queued(candidates).member_id.clone(),
+      round_robin_key: None,
+    },
+  };
+  Some(pick)
+}
+
+/// 推进轮询游标：仅在派发通过限流后调用，被拦截的消息不占用轮次。
+pub(crate) fn advance_round_robin(pool_key: &str) {
+  let mut cursors = lock_round_robin_cursors();
+  let cursor = cursors.entry(pool_key.to_string()).or_insert(0);
+  *cursor = cursor.wrapping_add(1);
+}
+
+fn least_queued(candidates: &[PoolCandidate]) -> &PoolCandidate {
+  let mut best = &candidates[0];
+  for candidate in &candidates[1..] {
+    if candidate.queued < best.queued {
+      best = candidate;
+    }
+  }
+  best
+}
+
+#[cfg(test)]
+mod pool_tests;
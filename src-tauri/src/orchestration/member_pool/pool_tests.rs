@@ -0,0 +1,93 @@
+use serde_json::json;
+
+use super::{
+  advance_round_robin, is_pool_member_name, parse_pool_mentions, pick_pool_member,
+  resolve_pool_config, PoolCandidate, PoolStrategy,
+};
+
+fn candidate(member_id: &str, idle: bool, queued: usize) -> PoolCandidate {
+  PoolCandidate {
+    member_id: member_id.to_string(),
+    idle,
+    queued,
+  }
+}
+
+fn picked(pool_key: &str, strategy: PoolStrategy, candidates: &[PoolCandidate]) -> String {
+  pick_pool_member(pool_key, strategy, candidates)
+    .map(|pick| pick.member_id)
+    .unwrap_or_default()
+}
+
+#[test]
+fn parse_pool_mentions_dedupes_and_strips_punctuation() {
+  assert_eq!(
+    parse_pool_mentions("@coder* please, then @Coder*. and @reviewer*： ok"),
+    vec!["coder".to_string(), "reviewer".to_string()]
+  );
+  // 普通提及、裸 `@*` 与中间带星号的词都不是池提及。
+  assert!(parse_pool_mentions("@coder hi @* a@b* @co*der").is_empty());
+}
+
+#[test]
+fn pool_member_name_requires_instance_suffix() {
+  assert!(is_pool_member_name("coder", "coder-1"));
+  assert!(is_pool_member_name("coder", " Coder-12 "));
+  assert!(!is_pool_member_name("coder", "coder"));
+  assert!(!is_pool_member_name("coder", "coder-"));
+  assert!(!is_pool_member_name("coder", "coder-x"));
+  assert!(!is_pool_member_name("coder", "codereview-1"));
+  assert!(!is_pool_member_name("code", "coder-1"));
+  assert!(!is_pool_member_name("", "-1"));
+}
+
+#[test]
+fn pool_config_reads_strategy_and_explicit_members() {
+  let data = json!({
+    "memberPools": [
+      { "name": "Coder", "strategy": "least-queued", "members": ["@alice", "m-2", ""] }
+    ]
+  });
+  let pool = resolve_pool_config(Some(&data), "coder");
+  assert_eq!(pool.strategy, PoolStrategy::LeastQueued);
+  assert!(pool.contains("coder", "m-1", "Alice"));
+  assert!(pool.contains("coder", "m-2", "bob"));
+  // 配置了显式列表时不再按实例命名匹配。
+  assert!(!pool.contains("coder", "m-3", "coder-1"));
+
+  let fallback = resolve_pool_config(Some(&data), "reviewer");
+  assert_eq!(fallback.strategy, PoolStrategy::RoundRobin);
+  assert!(fallback.contains("reviewer", "m-4", "reviewer-2"));
+  assert!(!fallback.contains("reviewer", "m-5", "reviewer"));
+}
+
+#[test]
+fn round_robin_advances_only_when_committed() {
+  let key = "pool-tests:round-robin";
+  let candidates = [candidate("a", true, 0), candidate("b", true, 0), candidate("c", true, 0)];
+  let first = pick_pool_member(key, PoolStrategy::RoundRobin, &candidates).expect("pick");
+  assert_eq!(first.member_id, "a");
+  assert_eq!(first.round_robin_key.as_deref(), Some(key));
+  // 未推进游标（例如被限流）时重复选择同一实例。
+  assert_eq!(picked(key, PoolStrategy::RoundRobin, &candidates), "a");
+  advance_round_robin(key);
+  assert_eq!(picked(key, PoolStrategy::RoundRobin, &candidates), "b");
+  advance_round_robin(key);
+  advance_round_robin(key);
+  assert_eq!(picked(key, PoolStrategy::RoundRobin, &candidates), "a");
+}
+
+#[test]
+fn least_queued_and_first_idle_pick_stable_candidates() {
+  let key = "pool-tests:other";
+  let candidates = [candidate("a", false, 2), candidate("b", false, 1), candidate("c", true, 1)];
+  assert_eq!(picked(key, PoolStrategy::LeastQueued, &candidates), "b");
+  assert_eq!(picked(key, PoolStrategy::FirstIdle, &candidates), "c");
+  let busy = [candidate("a", false, 3), candidate("b", false, 1)];
+  assert_eq!(picked(key, PoolStrategy::FirstIdle, &busy), "b");
+  assert!(pick_pool_member(key, PoolStrategy::LeastQueued, &busy)
+    .expect("pick")
+    .round_robin_key
+    .is_none());
+  assert!(pick_pool_member(key, PoolStrategy::RoundRobin, &[]).is_none());
+}
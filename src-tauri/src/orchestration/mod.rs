@@ -3,4 +3,7 @@
 pub(crate) mod terminal_friend_invite;
 pub(crate) mod dispatch;
 pub(crate) mod chat_dispatch_batcher;
+pub(crate) mod chat_inbox;
 pub(crate) mod chat_outbox;
+pub(crate) mod member_pool;
+pub(crate) mod routing_rules;
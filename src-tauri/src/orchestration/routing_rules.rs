@@ -0,0 +1,433 @@
+//! 路由规则：终端 final 落库后按项目数据中的声明式规则自动转发给其他成员。
+//! 规则存于项目数据 `routingRules`；转发写入普通聊天消息并走 outbox，具备重试、限流与诊断。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::application::chat as chat_app;
+use crate::contracts::chat_dispatch::{ChatDispatchMentions, ChatDispatchPayload};
+use crate::message_service::chat_db::{
+  chat_list_conversation_briefs, ChatConversationBrief, ChatDbManager, MessageAttachment,
+};
+use crate::message_service::project_data;
+use crate::now_millis;
+use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
+use crate::runtime::StorageManager;
+use crate::terminal_engine::TerminalManager;
+
+const ROUTING_RULES_KEY: &str = "routingRules";
+// 默认转发文本：@ 目标成员后附原始输出，保持与人工 @ 一致的展示。
+const DEFAULT_TEMPLATE: &str = "{mentions} {content}";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+/// 单条路由规则：所有条件均满足才触发；未设置的条件视为通配。
+struct RoutingRule {
+  #[serde(default)]
+  id: String,
+  #[serde(default = "default_enabled")]
+  enabled: bool,
+  /// 来源成员（id 或名称，可带 `@`）。
+  from: Option<String>,
+  /// 来源会话（id 或名称，可带 `#`）。
+  conversation: Option<String>,
+  /// 正文包含的子串。
+  contains: Option<String>,
+  /// 任一行（去掉行首空白）以该前缀开头，例如 `/deploy`。
+  prefix: Option<String>,
+  #[serde(default)]
+  ignore_case: bool,
+  /// 转发目标成员（id 或名称，可带 `@`）。
+  #[serde(default)]
+  to: Vec<String>,
+  /// 转发到的会话，缺省为来源会话。
+  to_conversation: Option<String>,
+  /// 转发文本模板，支持 `{mentions}`、`{from}`、`{content}`、`{rule}`。
+  template: Option<String>,
+}
+
+fn default_enabled() -> bool {
+  true
+}
+
+#[derive(Clone, Debug)]
+struct RoutingMember {
+  id: String,
+  name: String,
+}
+
+#[derive(Clone)]
+/// 路由配置：从项目数据解析出的规则与成员。
+struct RoutingConfig {
+  rules: Arc<Vec<RoutingRule>>,
+  members: Arc<Vec<RoutingMember>>,
+}
+
+/// 工作区路由配置快照：按项目数据写入版本失效，避免每条 final 都重读项目数据。
+struct RoutingSnapshot {
+  revision: u64,
+  workspace_path: String,
+  config: RoutingConfig,
+}
+
+static ROUTING_CACHE: OnceLock<Mutex<HashMap<String, RoutingSnapshot>>> = OnceLock::new();
+
+fn lock_routing_cache() -> MutexGuard<'static, HashMap<String, RoutingSnapshot>> {
+  let cache = ROUTING_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+  match cache.lock() {
+    Ok(guard) => guard,
+    Err(poisoned) => poisoned.into_inner(),
+  }
+}
+
+/// 已落库的终端 final：路由规则的输入。
+pub(crate) struct TerminalFinalRoute<'a> {
+  pub(crate) workspace_id: &'a str,
+  pub(crate) conversation_id: &'a str,
+  pub(crate) member_id: &'a str,
+  pub(crate) message_id: &'a str,
+  pub(crate) content: &'a str,
+}
+
+/// 评估路由规则并转发命中的 final；失败只记录日志，不影响已完成的落库。
+pub(crate) fn route_terminal_final(app: &AppHandle, source: TerminalFinalRoute<'_>) {
+  if let Err(err) = route_terminal_final_inner(app, &source) {
+    log::warn!(
+      "routing rules failed workspace_id={} message_id={} err={}",
+      source.workspace_id,
+      source.message_id,
+      err
+    );
+  }
+}
+
+fn route_terminal_final_inner(app: &AppHandle, source: &TerminalFinalRoute<'_>) -> Result<(), String> {
+  // 工作区路径记录在成员会话上；找不到会话（如重启后的入站重试）时无法定位项目数据，直接跳过。
+  let Some(workspace_path) = app
+    .state::<TerminalManager>()
+    .find_member_workspace_path(source.member_id, Some(source.workspace_id))
+  else {
+    return Ok(());
+  };
+  let RoutingConfig { rules, members } =
+    load_routing_config(app, source.workspace_id, &workspace_path)?;
+  if rules.is_empty() {
+    return Ok(());
+  }
+  let conversations =
+    chat_list_conversation_briefs(app.state::<ChatDbManager>().inner(), source.workspace_id)?;
+  let sender_name = members
+    .iter()
+    .find(|member| member.id == source.member_id)
+    .map(|member| member.name.clone())
+    .unwrap_or_default();
+
+  for rule in rules.iter().filter(|rule| rule.enabled) {
+    if !rule_matches_source(rule, source, &members, &conversations) {
+      continue;
+    }
+    let targets = resolve_rule_targets(rule, source.member_id, &members);
+    if targets.is_empty() {
+      log_routing_event(
+        app,
+        source,
+        "chat_routing_skip",
+        json!({ "ruleId": rule.id, "reason": "no_targets" }),
+      );
+      continue;
+    }
+    let destination = match rule.to_conversation.as_deref() {
+      Some(selector) => match find_conversation(&conversations, selector) {
+        Some(brief) => brief.clone(),
+        None => {
+          log_routing_event(
+            app,
+            source,
+            "chat_routing_skip",
+            json!({ "ruleId": rule.id, "reason": "conversation_not_found", "toConversation": selector }),
+          );
+          continue;
+        }
+      },
+      None => ChatConversationBrief {
+        id: source.conversation_id.to_string(),
+        kind: conversations
+          .iter()
+          .find(|brief| brief.id == source.conversation_id)
+          .map(|brief| brief.kind.clone())
+          .unwrap_or_else(|| "channel".to_string()),
+        name: None,
+      },
+    };
+    let text = render_template(rule, &sender_name, source.content, &targets);
+    let payload = ChatDispatchPayload {
+      workspace_id: source.workspace_id.to_string(),
+      workspace_path: workspace_path.clone(),
+      conversation_id: destination.id.clone(),
+      conversation_type: destination.kind.clone(),
+      text,
+      sender_id: source.member_id.to_string(),
+      sender_name: sender_name.clone(),
+      mentions: Some(ChatDispatchMentions {
+        mention_ids: targets.iter().map(|member| member.id.clone()).collect(),
+        mention_all: false,
+      }),
+      message_id: None,
+      client_trace_id: Some(format!("routing:{}:{}", rule.id, source.message_id)),
+      timestamp: Some(now_millis()?),
+    };
+    // 转发仍以来源成员名义发送，附带 routed 标记区分人工消息。
+    let routed = MessageAttachment::Routed {
+      rule_id: rule.id.clone(),
+      source_member_id: source.member_id.to_string(),
+      source_message_id: source.message_id.to_string(),
+    };
+    let forwarded = chat_app::chat_send_message_and_enqueue(
+      app.clone(),
+      app.state::<ChatDbManager>(),
+      payload,
+      Some(routed),
+    )?;
+    log_routing_event(
+      app,
+      source,
+      "chat_routing_forward",
+      json!({
+        "ruleId": rule.id,
+        "toConversationId": destination.id,
+        "targetIds": targets.iter().map(|member| member.id.clone()).collect::<Vec<_>>(),
+        "forwardedMessageId": forwarded.id
+      }),
+    );
+  }
+  Ok(())
+}
+
+/// 读取路由规则与成员；项目数据未写入过且工作区路径未变时复用缓存。
+fn load_routing_config(
+  app: &AppHandle,
+  workspace_id: &str,
+  workspace_path: &str,
+) -> Result<RoutingConfig, String> {
+  let revision = project_data::project_data_revision();
+  if let Some(snapshot) = lock_routing_cache().get(workspace_id) {
+    if snapshot.revision == revision && snapshot.workspace_path == workspace_path {
+      return Ok(snapshot.config.clone());
+    }
+  }
+  let project_data = project_data::read_project_data(
+    app.state::<StorageManager>().inner(),
+    workspace_path,
+    workspace_id,
+  )?;
+  let (rules, members) = match project_data.data {
+    Some(data) => (collect_routing_rules(&data), collect_routing_members(&data)),
+    None => (Vec::new(), Vec::new()),
+  };
+  let config = RoutingConfig {
+    rules: Arc::new(rules),
+    members: Arc::new(members),
+  };
+  lock_routing_cache().insert(
+    workspace_id.to_string(),
+    RoutingSnapshot {
+      revision,
+      workspace_path: workspace_path.to_string(),
+      config: config.clone(),
+    },
+  );
+  Ok(config)
+}
+
+fn collect_routing_rules(data: &Value) -> Vec<RoutingRule> {
+  let Some(entries) = data.get(ROUTING_RULES_KEY).and_then(|value| value.as_array()) else {
+    return Vec::new();
+  };
+  let mut rules = Vec::new();
+  for (index, entry) in entries.iter().enumerate() {
+    match serde_json::from_value::<RoutingRule>(entry.clone()) {
+      Ok(mut rule) => {
+        if rule.id.trim().is_empty() {
+          rule.id = format!("rule-{index}");
+        }
+        rules.push(rule);
+      }
+      Err(err) => {
+        log::warn!("routing rule ignored index={} err={}", index, err);
+      }
+    }
+  }
+  rules
+}
+
+fn collect_routing_members(data: &Value) -> Vec<RoutingMember> {
+  let Some(members) = data.get("members").and_then(|value| value.as_array()) else {
+    return Vec::new();
+  };
+  members
+    .iter()
+    .filter_map(|member| {
+      let id = member.get("id").and_then(|value| value.as_str())?.trim();
+      if id.is_empty() {
+        return None;
+      }
+      let name = member
+        .get("name")
+        .and_then(|value| value.as_str())
+        .unwrap_or("")
+        .trim();
+      Some(RoutingMember {
+        id: id.to_string(),
+        name: name.to_string(),
+      })
+    })
+    .collect()
+}
+
+fn rule_matches_source(
+  rule: &RoutingRule,
+  source: &TerminalFinalRoute<'_>,
+  members: &[RoutingMember],
+  conversations: &[ChatConversationBrief],
+) -> bool {
+  if let Some(selector) = rule.from.as_deref() {
+    match find_member(members, selector) {
+      Some(member) if member.id == source.member_id => {}
+      _ => return false,
+    }
+  }
+  if let Some(selector) = rule.conversation.as_deref() {
+    match find_conversation(conversations, selector) {
+      Some(brief) if brief.id == source.conversation_id => {}
+      _ => return false,
+    }
+  }
+  content_matches(rule, source.content)
+}
+
+fn content_matches(rule: &RoutingRule, content: &str) -> bool {
+  let fold = |value: &str| {
+    if rule.ignore_case {
+      value.to_lowercase()
+    } else {
+      value.to_string()
+    }
+  };
+  let content = fold(content);
+  if let Some(needle) = rule.contains.as_deref().filter(|value| !value.is_empty()) {
+    if !content.contains(&fold(needle)) {
+      return false;
+    }
+  }
+  if let Some(prefix) = rule.prefix.as_deref().filter(|value| !value.is_empty()) {
+    let prefix = fold(prefix);
+    if !content
+      .lines()
+      .any(|line| line.trim_start().starts_with(prefix.as_str()))
+    {
+      return false;
+    }
+  }
+  true
+}
+
+fn resolve_rule_targets(
+  rule: &RoutingRule,
+  source_member_id: &str,
+  members: &[RoutingMember],
+) -> Vec<RoutingMember> {
+  let mut seen = HashSet::new();
+  rule
+    .to
+    .iter()
+    .filter_map(|selector| find_member(members, selector))
+    .filter(|member| member.id != source_member_id)
+    .filter(|member| seen.insert(member.id.clone()))
+    .cloned()
+    .collect()
+}
+
+fn find_member<'a>(members: &'a [RoutingMember], selector: &str) -> Option<&'a RoutingMember> {
+  let selector = selector.trim().trim_start_matches('@');
+  if selector.is_empty() {
+    return None;
+  }
+  members
+    .iter()
+    .find(|member| member.id == selector)
+    .or_else(|| {
+      members
+        .iter()
+        .find(|member| member.name.eq_ignore_ascii_case(selector))
+    })
+}
+
+fn find_conversation<'a>(
+  conversations: &'a [ChatConversationBrief],
+  selector: &str,
+) -> Option<&'a ChatConversationBrief> {
+  let selector = selector.trim().trim_start_matches('#');
+  if selector.is_empty() {
+    return None;
+  }
+  conversations
+    .iter()
+    .find(|brief| brief.id == selector)
+    .or_else(|| {
+      conversations.iter().find(|brief| {
+        brief
+          .name
+          .as_deref()
+          .map(|name| name.trim().trim_start_matches('#').eq_ignore_ascii_case(selector))
+          .unwrap_or(false)
+      })
+    })
+}
+
+fn render_template(
+  rule: &RoutingRule,
+  sender_name: &str,
+  content: &str,
+  targets: &[RoutingMember],
+) -> String {
+  let mentions = targets
+    .iter()
+    .map(|member| format!("@{}", member.name))
+    .collect::<Vec<_>>()
+    .join(" ");
+  rule
+    .template
+    .as_deref()
+    .unwrap_or(DEFAULT_TEMPLATE)
+    .replace("{mentions}", &mentions)
+    .replace("{from}", sender_name)
+    .replace("{rule}", &rule.id)
+    .replace("{content}", content)
+    .trim()
+    .to_string()
+}
+
+fn log_routing_event(app: &AppHandle, source: &TerminalFinalRoute<'_>, event: &str, extra: Value) {
+  diagnostics_log_backend_event(
+    &app.state::<DiagnosticsState>(),
+    Some(source.member_id.to_string()),
+    None,
+    Some(source.conversation_id.to_string()),
+    None,
+    Some(source.workspace_id.to_string()),
+    event,
+    json!({
+      "workspaceId": source.workspace_id,
+      "conversationId": source.conversation_id,
+      "sourceMessageId": source.message_id,
+      "detail": extra
+    }),
+  );
+}
+
+#[cfg(test)]
+mod rule_tests;
@@ -0,0 +1,154 @@
+use serde_json::json;
+
+use super::{
+  collect_routing_members, collect_routing_rules, content_matches, find_conversation,
+  render_template, resolve_rule_targets, rule_matches_source, ChatConversationBrief,
+  RoutingMember, TerminalFinalRoute,
+};
+
+fn members() -> Vec<RoutingMember> {
+  vec![
+    RoutingMember {
+      id: "m-build".to_string(),
+      name: "Builder".to_string(),
+    },
+    RoutingMember {
+      id: "m-review".to_string(),
+      name: "Reviewer".to_string(),
+    },
+  ]
+}
+
+fn conversations() -> Vec<ChatConversationBrief> {
+  vec![
+    ChatConversationBrief {
+      id: "c-general".to_string(),
+      kind: "channel".to_string(),
+      name: Some("#general".to_string()),
+    },
+    ChatConversationBrief {
+      id: "c-ops".to_string(),
+      kind: "channel".to_string(),
+      name: Some("ops".to_string()),
+    },
+  ]
+}
+
+fn source<'a>(
+  member_id: &'a str,
+  conversation_id: &'a str,
+  content: &'a str,
+) -> TerminalFinalRoute<'a> {
+  TerminalFinalRoute {
+    workspace_id: "ws",
+    conversation_id,
+    member_id,
+    message_id: "msg-1",
+    content,
+  }
+}
+
+fn rules(value: serde_json::Value) -> Vec<super::RoutingRule> {
+  collect_routing_rules(&json!({ "routingRules": value }))
+}
+
+#[test]
+fn collect_routing_rules_defaults_ids_and_skips_invalid_entries() {
+  let collected = rules(json!([
+    { "to": ["@Reviewer"] },
+    { "id": "deploy", "enabled": false, "prefix": "/deploy", "to": ["m-build"] },
+    { "id": "broken", "to": "not-a-list" }
+  ]));
+  assert_eq!(collected.len(), 2);
+  assert_eq!(collected[0].id, "rule-0");
+  assert!(collected[0].enabled);
+  assert_eq!(collected[1].id, "deploy");
+  assert!(!collected[1].enabled);
+  assert!(collect_routing_rules(&json!({})).is_empty());
+}
+
+#[test]
+fn collect_routing_members_skips_blank_ids() {
+  let collected = collect_routing_members(&json!({
+    "members": [
+      { "id": " m-1 ", "name": " One " },
+      { "id": "", "name": "Ghost" },
+      { "name": "NoId" }
+    ]
+  }));
+  assert_eq!(collected.len(), 1);
+  assert_eq!((collected[0].id.as_str(), collected[0].name.as_str()), ("m-1", "One"));
+}
+
+#[test]
+fn content_matches_contains_and_line_prefix() {
+  let rule = &rules(json!([{ "contains": "DONE", "prefix": "/deploy" }]))[0];
+  assert!(content_matches(rule, "build ok\n  /deploy staging\nDONE"));
+  assert!(!content_matches(rule, "build ok DONE"));
+  // 前缀必须出现在行首，行中出现不算。
+  assert!(!content_matches(rule, "please /deploy DONE"));
+  assert!(!content_matches(rule, "/deploy done"));
+
+  let folded = &rules(json!([{ "contains": "DONE", "ignoreCase": true }]))[0];
+  assert!(content_matches(folded, "all done"));
+
+  let empty = &rules(json!([{ "contains": "", "prefix": "" }]))[0];
+  assert!(content_matches(empty, "anything"));
+}
+
+#[test]
+fn rule_matches_source_by_member_and_conversation_selectors() {
+  let members = members();
+  let conversations = conversations();
+  let matches = |rule: &super::RoutingRule, member_id: &str, conversation_id: &str| {
+    rule_matches_source(rule, &source(member_id, conversation_id, "x"), &members, &conversations)
+  };
+  let rule = &rules(json!([{ "from": "@builder", "conversation": "#general" }]))[0];
+  assert!(matches(rule, "m-build", "c-general"));
+  assert!(!matches(rule, "m-review", "c-general"));
+  assert!(!matches(rule, "m-build", "c-ops"));
+
+  // 选择器无法解析时不应退化为通配。
+  let unknown = &rules(json!([{ "from": "@nobody" }]))[0];
+  assert!(!matches(unknown, "m-build", "c-general"));
+
+  let by_id = &rules(json!([{ "conversation": "c-ops" }]))[0];
+  assert!(matches(by_id, "m-review", "c-ops"));
+}
+
+#[test]
+fn find_conversation_matches_id_then_name_without_hash() {
+  let conversations = conversations();
+  let find = |selector: &str| {
+    find_conversation(&conversations, selector).map(|brief| brief.id.clone())
+  };
+  assert_eq!(find("c-ops").as_deref(), Some("c-ops"));
+  assert_eq!(find("#GENERAL").as_deref(), Some("c-general"));
+  assert_eq!(find("#ops").as_deref(), Some("c-ops"));
+  assert!(find("#").is_none());
+}
+
+#[test]
+fn resolve_rule_targets_dedupes_and_excludes_source() {
+  let members = members();
+  let rule = &rules(json!([{ "to": ["@Reviewer", "m-review", "Builder", "@missing"] }]))[0];
+  let targets = resolve_rule_targets(rule, "m-build", &members);
+  assert_eq!(targets.len(), 1);
+  assert_eq!(targets[0].id, "m-review");
+}
+
+#[test]
+fn render_template_fills_placeholders() {
+  let members = members();
+  let default_rule = &rules(json!([{ "id": "fwd" }]))[0];
+  assert_eq!(
+    render_template(default_rule, "Builder", "tests passed", &members[1..]),
+    "@Reviewer tests passed"
+  );
+  let custom =
+    &rules(json!([{ "id": "fwd", "template": "[{rule}] {from}: {content} {mentions}" }]))[0];
+  assert_eq!(
+    render_template(custom, "Builder", "tests passed", &members),
+    "[fwd] Builder: tests passed @Builder @Reviewer"
+  );
+}
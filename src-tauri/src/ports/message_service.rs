@@ -1,5 +1,7 @@
 //! 消息服务端口：隔离业务流水线对传输与存储的直接依赖。
 
+use std::collections::HashMap;
+
 use crate::contracts::terminal_message::TerminalMessagePayload;
 
 #[derive(Clone, Debug)]
@@ -25,18 +27,53 @@ impl TerminalMessageAppendResult {
   }
 }
 
+#[derive(Clone, Debug)]
+/// 终端 final 入站记录：落库前先写入入站队列，失败后可重试与重放。
+pub(crate) struct TerminalInboundMessage {
+  pub(crate) workspace_id: String,
+  pub(crate) conversation_id: String,
+  pub(crate) member_id: String,
+  pub(crate) viewer_id: String,
+  pub(crate) content: String,
+  pub(crate) span_id: Option<String>,
+}
+
 pub(crate) trait TerminalMessageTransport: Send + Sync {
   fn emit_terminal_stream(&self, payload: TerminalMessagePayload) -> Result<(), String>;
 }
 
 pub(crate) trait TerminalMessageRepository: Send + Sync {
+  /// 落库终端回写；`message_id` 为入站任务 id 时按该 id 幂等写入，已存在则不再广播与路由。
   fn append_terminal_message(
+    &self,
+    message: &TerminalInboundMessage,
+    message_id: Option<&str>,
+  ) -> Result<TerminalMessageAppendResult, String>;
+
+  /// 持久化入站记录并返回任务 id；该 id 同时作为最终消息 id。
+  fn enqueue_terminal_inbound(
+    &self,
+    message: &TerminalInboundMessage,
+    lease_ms: u64,
+  ) -> Result<String, String>;
+
+  fn mark_terminal_inbound_delivered(&self, workspace_id: &str, task_id: &str) -> Result<(), String>;
+
+  fn mark_terminal_inbound_failed(
+    &self,
+    workspace_id: &str,
+    task_id: &str,
+    next_attempt_at: u64,
+    error: &str,
+    mark_dead: bool,
+  ) -> Result<(), String>;
+
+  fn append_system_message(
     &self,
     workspace_id: &str,
     conversation_id: &str,
-    member_id: &str,
-    content: String,
+    key: &str,
+    args: Option<HashMap<String, String>>,
     viewer_id: &str,
-    span_id: Option<&str>,
-  ) -> Result<TerminalMessageAppendResult, String>;
+  ) -> Result<String, String>;
 }
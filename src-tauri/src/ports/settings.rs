@@ -6,4 +6,13 @@ pub trait TerminalSettingsPort: Send + Sync {
 
     /// 获取聊天流式输出开关（true=开启）
     fn get_chat_stream_enabled(&self) -> Option<bool>;
+
+    /// 获取指定终端类型的时序调优覆盖（原始 JSON 对象，由终端引擎解析）
+    fn get_terminal_tuning(&self, terminal_type: &str) -> Option<serde_json::Value>;
+
+    /// 获取指定终端类型的声明式过滤规则（原始 JSON 对象，由过滤层编译）
+    fn get_terminal_filter_rules(&self, terminal_type: &str) -> Option<serde_json::Value>;
+
+    /// 获取成员终端空闲休眠阈值（分钟）；None 表示不休眠
+    fn get_member_hibernate_after_minutes(&self) -> Option<u64>;
 }
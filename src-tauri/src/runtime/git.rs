@@ -0,0 +1,106 @@
+//! Git 命令封装：为成员工作树隔离提供最小的 git 能力（worktree 增删、分支、diff 与合并）。
+//! 边界：只负责调用系统 git 并归一化错误，不感知成员与项目数据。
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// 在 `dir` 下执行 git 并返回 stdout；非零退出码时以 stderr 作为错误信息。
+pub(crate) fn run_git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("failed to run git: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let subcommand = args.first().copied().unwrap_or("");
+        return Err(format!("git {subcommand} failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// git 公共目录（对象库与引用所在）；成员工作树下指向主仓库的 `.git`，不是 git 仓库时返回 None。
+pub(crate) fn common_dir(dir: &Path) -> Option<PathBuf> {
+    let value = run_git(dir, &["rev-parse", "--git-common-dir"]).ok()?;
+    let path = PathBuf::from(value.trim());
+    Some(if path.is_absolute() { path } else { dir.join(path) })
+}
+
+/// 当前检出的分支名；HEAD 游离时报错，避免成员分支基于不可追踪的提交。
+pub(crate) fn current_branch(dir: &Path) -> Result<String, String> {
+    run_git(dir, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .map(|value| value.trim().to_string())
+        .map_err(|_| "workspace is not a git repository or HEAD is detached".to_string())
+}
+
+pub(crate) fn branch_exists(dir: &Path, branch: &str) -> bool {
+    let reference = format!("refs/heads/{branch}");
+    run_git(dir, &["rev-parse", "--verify", "--quiet", &reference]).is_ok()
+}
+
+/// 以 `base` 为起点新建 `branch` 并检出到 `path`。
+/// 分支已存在时报错而不是复用，避免新成员继承遗留分支上其他成员的提交。
+pub(crate) fn add_worktree(repo: &Path, path: &Path, branch: &str, base: &str) -> Result<(), String> {
+    if branch_exists(repo, branch) {
+        return Err(format!("branch {branch} already exists"));
+    }
+    run_git(repo, &["worktree", "add", "-b", branch, &path.to_string_lossy(), base])?;
+    Ok(())
+}
+
+/// 移除工作树并删除分支；目录已被手动删除时仅清理 git 登记。
+pub(crate) fn remove_worktree(repo: &Path, path: &Path, branch: &str) -> Result<(), String> {
+    if path.exists() {
+        run_git(repo, &["worktree", "remove", "--force", &path.to_string_lossy()])?;
+    } else {
+        run_git(repo, &["worktree", "prune"])?;
+    }
+    if branch_exists(repo, branch) {
+        run_git(repo, &["branch", "-D", branch])?;
+    }
+    Ok(())
+}
+
+/// 工作区是否有未提交改动（含未跟踪文件）。
+pub(crate) fn has_changes(dir: &Path) -> Result<bool, String> {
+    run_git(dir, &["status", "--porcelain"]).map(|value| !value.trim().is_empty())
+}
+
+/// 将模式追加到仓库本地排除列表（`info/exclude`），已存在时跳过。
+pub(crate) fn ensure_excluded(repo: &Path, pattern: &str) -> Result<(), String> {
+    let exclude = run_git(repo, &["rev-parse", "--git-path", "info/exclude"])?;
+    let exclude = PathBuf::from(exclude.trim());
+    let exclude = if exclude.is_absolute() {
+        exclude
+    } else {
+        repo.join(exclude)
+    };
+    let existing = fs::read_to_string(&exclude).unwrap_or_default();
+    if existing.lines().any(|line| line.trim() == pattern) {
+        return Ok(());
+    }
+    if let Some(parent) = exclude.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create git exclude dir: {err}"))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&exclude)
+        .map_err(|err| format!("failed to open git exclude file: {err}"))?;
+    let separator = if existing.is_empty() || existing.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    writeln!(file, "{separator}{pattern}")
+        .map_err(|err| format!("failed to write git exclude file: {err}"))
+}
+
+#[cfg(test)]
+mod git_tests;
@@ -0,0 +1,115 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    add_worktree, branch_exists, common_dir, ensure_excluded, has_changes, remove_worktree, run_git,
+};
+
+/// 临时仓库：`main` 分支上带一个初始提交，离开作用域时删除目录。
+struct TempRepo {
+    path: PathBuf,
+}
+
+impl TempRepo {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("git-tests-{}", ulid::Ulid::new()));
+        fs::create_dir_all(&path).expect("create repo dir");
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.name", "Golutra Tests"],
+            &["config", "user.email", "tests@golutra.invalid"],
+            &["config", "commit.gpgsign", "false"],
+        ] {
+            run_git(&path, args).expect("init repo");
+        }
+        fs::write(path.join("README.md"), "hello\n").expect("write file");
+        run_git(&path, &["add", "-A"]).expect("stage");
+        run_git(&path, &["commit", "-q", "-m", "initial"]).expect("commit");
+        Self { path }
+    }
+
+    fn worktree(&self, name: &str) -> PathBuf {
+        self.path.join(".golutra/worktrees").join(name)
+    }
+}
+
+impl Drop for TempRepo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn head(dir: &Path) -> String {
+    run_git(dir, &["rev-parse", "HEAD"]).expect("rev-parse").trim().to_string()
+}
+
+#[test]
+fn add_worktree_branches_from_base() {
+    let repo = TempRepo::new();
+    let path = repo.worktree("alice");
+    add_worktree(&repo.path, &path, "golutra/alice", "main").expect("add worktree");
+    assert!(path.join("README.md").is_file());
+    assert!(branch_exists(&repo.path, "golutra/alice"));
+    assert_eq!(head(&path), head(&repo.path));
+    assert_eq!(
+        run_git(&path, &["symbolic-ref", "--short", "HEAD"]).unwrap().trim(),
+        "golutra/alice"
+    );
+}
+
+#[test]
+fn add_worktree_refuses_an_existing_branch() {
+    let repo = TempRepo::new();
+    run_git(&repo.path, &["branch", "golutra/alice"]).expect("create stale branch");
+    let path = repo.worktree("alice");
+    let err = add_worktree(&repo.path, &path, "golutra/alice", "main").unwrap_err();
+    assert!(err.contains("golutra/alice already exists"), "{err}");
+    assert!(!path.exists());
+}
+
+#[test]
+fn remove_worktree_deletes_directory_and_branch() {
+    let repo = TempRepo::new();
+    let path = repo.worktree("alice");
+    add_worktree(&repo.path, &path, "golutra/alice", "main").expect("add worktree");
+    fs::write(path.join("scratch.txt"), "dirty\n").expect("write file");
+    remove_worktree(&repo.path, &path, "golutra/alice").expect("remove worktree");
+    assert!(!path.exists());
+    assert!(!branch_exists(&repo.path, "golutra/alice"));
+}
+
+#[test]
+fn remove_worktree_prunes_a_manually_deleted_directory() {
+    let repo = TempRepo::new();
+    let path = repo.worktree("alice");
+    add_worktree(&repo.path, &path, "golutra/alice", "main").expect("add worktree");
+    fs::remove_dir_all(&path).expect("delete worktree dir");
+    remove_worktree(&repo.path, &path, "golutra/alice").expect("remove worktree");
+    assert!(!branch_exists(&repo.path, "golutra/alice"));
+    let listed = run_git(&repo.path, &["worktree", "list", "--porcelain"]).unwrap();
+    assert!(!listed.contains("alice"), "{listed}");
+}
+
+#[test]
+fn worktree_common_dir_is_the_main_repository() {
+    let repo = TempRepo::new();
+    let path = repo.worktree("alice");
+    add_worktree(&repo.path, &path, "golutra/alice", "main").expect("add worktree");
+    let expected = repo.path.join(".git").canonicalize().unwrap();
+    assert_eq!(common_dir(&path).unwrap().canonicalize().unwrap(), expected);
+    assert_eq!(common_dir(&std::env::temp_dir().join("missing-repo")), None);
+}
+
+#[test]
+fn excluded_pattern_is_appended_once_and_hides_worktrees() {
+    let repo = TempRepo::new();
+    ensure_excluded(&repo.path, "/.golutra/worktrees/").expect("exclude");
+    ensure_excluded(&repo.path, "/.golutra/worktrees/").expect("exclude again");
+    let exclude = fs::read_to_string(repo.path.join(".git/info/exclude")).unwrap();
+    assert_eq!(exclude.matches("/.golutra/worktrees/").count(), 1);
+    let path = repo.worktree("alice");
+    add_worktree(&repo.path, &path, "golutra/alice", "main").expect("add worktree");
+    assert!(!has_changes(&repo.path).unwrap());
+}
@@ -2,8 +2,11 @@
 
 pub(crate) mod command_center;
 pub(crate) mod command_ipc;
+pub(crate) mod git;
 pub(crate) mod pty;
+pub(crate) mod sandbox;
 pub(crate) mod settings;
+pub(crate) mod shell_integration;
 pub(crate) mod state;
 pub(crate) mod storage;
 
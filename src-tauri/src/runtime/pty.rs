@@ -11,6 +11,9 @@ use std::{
 
 use portable_pty::{native_pty_system, Child, ChildKiller, CommandBuilder, MasterPty, PtySize};
 use serde::Serialize;
+
+use super::sandbox::{wrap_command, SandboxPolicy};
+use super::shell_integration::prepare_shell_integration;
 #[cfg(windows)]
 use std::ffi::OsStr;
 #[cfg(windows)]
@@ -210,6 +213,7 @@ fn spawn_with_shim(
   cwd: Option<String>,
   program: &str,
   args: &[String],
+  envs: &[(String, String)],
 ) -> Result<SpawnedPty, String> {
   let shim = resolve_shim_path()?;
   // 通过 shim 统一发送 OSC 就绪与退出信号，避免平台差异。
@@ -224,6 +228,9 @@ fn spawn_with_shim(
   if !args.is_empty() {
     cmd.args(args);
   }
+  for (key, value) in envs {
+    cmd.env(key, value);
+  }
   spawn_with_command(cmd, cols, rows, cwd)
 }
 
@@ -401,8 +408,27 @@ fn build_command_spec(program: &str, args: &[String], cwd: Option<String>) -> Co
   }
 }
 
+// 沙箱包装位于 shim 之内：shim 仍在宿主侧发送就绪/退出信号，bwrap 再启动目标程序。
+fn spawn_spec(
+  cols: u16,
+  rows: u16,
+  spec: CommandSpec,
+  envs: &[(String, String)],
+  sandbox: Option<&SandboxPolicy>,
+) -> Result<SpawnedPty, String> {
+  match sandbox {
+    Some(policy) => {
+      let (program, args) = wrap_command(policy, &spec.program, &spec.args);
+      spawn_with_shim(cols, rows, spec.cwd, &program, &args, envs)
+    }
+    None => spawn_with_shim(cols, rows, spec.cwd, &spec.program, &spec.args, envs),
+  }
+}
+
 /// 启动默认 shell 的 PTY 会话。
-/// 输入：`cols/rows` 为终端尺寸，`cwd` 为可选工作目录；`shell_path` 可选用于覆盖默认 shell。
+/// 输入：`cols/rows` 为终端尺寸，`cwd` 为可选工作目录；`shell_path` 可选用于覆盖默认 shell；
+/// `sandbox` 存在时在沙箱内启动。
+/// 可识别的 shell（bash/zsh/fish/pwsh）会注入集成脚本，输出 OSC 133 命令边界标记。
 /// 返回：包含子进程与读写端的 `SpawnedPty`。
 /// 错误：PTy 创建失败、shell 不可用或 shim 不可用。
 pub(crate) fn spawn_shell(
@@ -410,6 +436,7 @@ pub(crate) fn spawn_shell(
   rows: u16,
   cwd: Option<String>,
   shell_path: Option<&str>,
+  sandbox: Option<&SandboxPolicy>,
 ) -> Result<SpawnedPty, String> {
   // shell_path 指定时优先解析该可执行，避免默认探测覆盖用户选择。
   let (shell, args) = if let Some(value) = shell_path.map(str::trim).filter(|value| !value.is_empty()) {
@@ -424,12 +451,16 @@ pub(crate) fn spawn_shell(
     // 选择系统默认 shell，shim 负责就绪与退出信号。
     default_shell_spec()
   };
+  let (args, envs) = match prepare_shell_integration(&shell, &args) {
+    Some(integration) => (integration.args, integration.env),
+    None => (args, Vec::new()),
+  };
   let spec = build_command_spec(&shell, &args, cwd);
-  spawn_with_shim(cols, rows, spec.cwd, &spec.program, &spec.args)
+  spawn_spec(cols, rows, spec, &envs, sandbox)
 }
 
 /// 启动指定命令的 PTY 会话。
-/// 输入：`program/args` 为目标命令，`cwd` 为可选工作目录。
+/// 输入：`program/args` 为目标命令，`cwd` 为可选工作目录；`sandbox` 存在时在沙箱内启动。
 /// 返回：包含子进程与读写端的 `SpawnedPty`。
 /// 错误：PTy 创建失败、命令不可用或 shim 不可用。
 pub(crate) fn spawn_command(
@@ -438,9 +469,10 @@ pub(crate) fn spawn_command(
   cwd: Option<String>,
   program: &str,
   args: &[String],
+  sandbox: Option<&SandboxPolicy>,
 ) -> Result<SpawnedPty, String> {
   let spec = build_command_spec(program, args, cwd);
-  spawn_with_shim(cols, rows, spec.cwd, &spec.program, &spec.args)
+  spawn_spec(cols, rows, spec, &[], sandbox)
 }
 
 /// 调整 PTY 尺寸。
@@ -0,0 +1,135 @@
+//! 成员沙箱：Linux 下通过 bubblewrap 隔离沙箱成员的进程。
+//! 策略：工作区、成员 CLI 状态目录与工作树的 git 公共目录读写绑定，其余文件系统只读，/tmp 私有，可选禁用网络。
+//! 约束：沙箱不可用时直接报错，调用方不得退化为无沙箱启动。
+
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+#[cfg(target_os = "linux")]
+use super::pty::lookup_binary;
+use super::shell_integration::integration_dir;
+
+#[cfg(target_os = "linux")]
+const SANDBOX_BINARY: &str = "bwrap";
+
+/// 沙箱策略：`workspace` 为规范化后的工作区绝对路径，`binary` 为已验证可用的 bwrap。
+/// `writable_paths` 为工作区外需读写的路径：成员 CLI 的状态目录/文件（如 `~/.claude`），保留登录态与会话恢复；
+/// 以及独立工作树所属仓库的 git 公共目录，保证成员能在工作树内提交。
+#[derive(Clone, Debug)]
+pub(crate) struct SandboxPolicy {
+  binary: String,
+  workspace: PathBuf,
+  writable_paths: Vec<PathBuf>,
+  deny_network: bool,
+}
+
+impl SandboxPolicy {
+  /// 构建沙箱策略；工作区缺失、不是目录或沙箱不可用时报错。
+  /// 不存在的路径跳过：bwrap 无法绑定缺失的源路径，首次登录需在沙箱外完成；位于工作区内的路径已随工作区绑定。
+  pub(crate) fn new(
+    workspace: Option<&str>,
+    writable_paths: Vec<PathBuf>,
+    deny_network: bool,
+  ) -> Result<Self, String> {
+    let workspace = workspace
+      .map(str::trim)
+      .filter(|value| !value.is_empty())
+      .ok_or_else(|| "sandbox requires a workspace directory".to_string())?;
+    let workspace = Path::new(workspace)
+      .canonicalize()
+      .map_err(|err| format!("failed to resolve sandbox workspace {workspace}: {err}"))?;
+    if !workspace.is_dir() {
+      return Err(format!(
+        "sandbox workspace is not a directory: {}",
+        workspace.to_string_lossy()
+      ));
+    }
+    let binary = ensure_sandbox_available()?;
+    let writable_paths = writable_paths
+      .into_iter()
+      .filter_map(|path| path.canonicalize().ok())
+      .filter(|path| !path.starts_with(&workspace))
+      .collect();
+    Ok(Self {
+      binary,
+      workspace,
+      writable_paths,
+      deny_network,
+    })
+  }
+}
+
+// 检查沙箱可用性并返回 bwrap 路径。
+// 除了查找二进制，还会空跑一次以确认内核允许非特权用户命名空间。
+#[cfg(target_os = "linux")]
+fn ensure_sandbox_available() -> Result<String, String> {
+  let binary = lookup_binary(SANDBOX_BINARY, None).map_err(|_| {
+    "sandbox unavailable: bubblewrap (bwrap) is not installed".to_string()
+  })?;
+  let output = Command::new(&binary)
+    .args(["--ro-bind", "/", "/", "--dev", "/dev", "--unshare-all", "--", "true"])
+    .output()
+    .map_err(|err| format!("sandbox unavailable: failed to run bwrap: {err}"))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(format!(
+      "sandbox unavailable: bwrap cannot create namespaces: {}",
+      stderr.trim()
+    ));
+  }
+  Ok(binary)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn ensure_sandbox_available() -> Result<String, String> {
+  Err("sandbox unavailable: sandboxed members are only supported on Linux".to_string())
+}
+
+/// 将目标命令包装为 bwrap 调用，返回新的程序与参数。
+pub(crate) fn wrap_command(
+  policy: &SandboxPolicy,
+  program: &str,
+  args: &[String],
+) -> (String, Vec<String>) {
+  let mut wrapped = build_sandbox_args(policy, integration_dir());
+  wrapped.push("--".to_string());
+  wrapped.push(program.to_string());
+  wrapped.extend(args.iter().cloned());
+  (policy.binary.clone(), wrapped)
+}
+
+// bwrap 按参数顺序挂载：先只读根，再覆盖私有 /tmp，然后绑定可写路径与工作区，保证它们位于 /tmp 下时仍可写。
+fn build_sandbox_args(policy: &SandboxPolicy, shell_integration_dir: Option<&Path>) -> Vec<String> {
+  let workspace = policy.workspace.to_string_lossy().to_string();
+  let mut args: Vec<String> = [
+    "--ro-bind", "/", "/",
+    "--dev", "/dev",
+    "--proc", "/proc",
+    "--tmpfs", "/tmp",
+    "--unshare-pid",
+    "--unshare-ipc",
+    "--die-with-parent",
+  ]
+  .iter()
+  .map(|value| value.to_string())
+  .collect();
+  // Shell 集成脚本位于应用数据目录；显式只读绑回，保证数据目录落在 /tmp 等被覆盖的挂载点下时仍可见。
+  if let Some(dir) = shell_integration_dir.filter(|dir| dir.is_dir()) {
+    let dir = dir.to_string_lossy().to_string();
+    args.extend(["--ro-bind".to_string(), dir.clone(), dir]);
+  }
+  for path in policy.writable_paths.iter() {
+    let path = path.to_string_lossy().to_string();
+    args.extend(["--bind".to_string(), path.clone(), path]);
+  }
+  args.extend(["--bind".to_string(), workspace.clone(), workspace.clone()]);
+  if policy.deny_network {
+    args.push("--unshare-net".to_string());
+  }
+  args.extend(["--chdir".to_string(), workspace]);
+  args
+}
+
+#[cfg(test)]
+mod sandbox_tests;
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+
+use super::{build_sandbox_args, SandboxPolicy};
+
+fn policy(deny_network: bool, writable_paths: Vec<PathBuf>) -> SandboxPolicy {
+  SandboxPolicy {
+    binary: "bwrap".to_string(),
+    workspace: PathBuf::from("/tmp/project"),
+    writable_paths,
+    deny_network,
+  }
+}
+
+fn position(args: &[String], window: &[&str]) -> usize {
+  args
+    .windows(window.len())
+    .position(|candidate| candidate.iter().zip(window).all(|(arg, expected)| arg == expected))
+    .unwrap_or_else(|| panic!("missing {window:?} in {args:?}"))
+}
+
+#[test]
+fn workspace_is_bound_after_readonly_root_and_private_tmp() {
+  let args = build_sandbox_args(&policy(false, Vec::new()), None);
+  let root = position(&args, &["--ro-bind", "/", "/"]);
+  let tmp = position(&args, &["--tmpfs", "/tmp"]);
+  let workspace = position(&args, &["--bind", "/tmp/project", "/tmp/project"]);
+  assert!(root < tmp && tmp < workspace, "{args:?}");
+  assert_eq!(args[args.len() - 2..], ["--chdir", "/tmp/project"]);
+}
+
+#[test]
+fn network_is_unshared_only_when_denied() {
+  let allowed = build_sandbox_args(&policy(false, Vec::new()), None);
+  assert!(!allowed.iter().any(|arg| arg == "--unshare-net"));
+  let denied = build_sandbox_args(&policy(true, Vec::new()), None);
+  assert_eq!(denied.iter().filter(|arg| *arg == "--unshare-net").count(), 1);
+}
+
+#[test]
+fn writable_paths_are_bound_writable_after_private_tmp() {
+  let state = vec![PathBuf::from("/home/dev/.claude"), PathBuf::from("/home/dev/.claude.json")];
+  let args = build_sandbox_args(&policy(false, state), None);
+  let tmp = position(&args, &["--tmpfs", "/tmp"]);
+  let claude = position(&args, &["--bind", "/home/dev/.claude", "/home/dev/.claude"]);
+  let config = position(&args, &["--bind", "/home/dev/.claude.json", "/home/dev/.claude.json"]);
+  let workspace = position(&args, &["--bind", "/tmp/project", "/tmp/project"]);
+  assert!(tmp < claude && claude < config && config < workspace, "{args:?}");
+}
+
+#[test]
+fn shell_integration_dir_is_rebound_readonly_when_present() {
+  let dir = std::env::temp_dir().join(format!("sandbox-tests-{}", ulid::Ulid::new()));
+  let missing = build_sandbox_args(&policy(false, Vec::new()), Some(&dir));
+  assert_eq!(missing.iter().filter(|arg| *arg == "--ro-bind").count(), 1);
+
+  fs::create_dir_all(&dir).expect("create integration dir");
+  let args = build_sandbox_args(&policy(false, Vec::new()), Some(&dir));
+  let _ = fs::remove_dir_all(&dir);
+  let dir = dir.to_string_lossy().to_string();
+  let tmp = position(&args, &["--tmpfs", "/tmp"]);
+  let rebind = position(&args, &["--ro-bind", dir.as_str(), dir.as_str()]);
+  assert!(tmp < rebind, "{args:?}");
+}
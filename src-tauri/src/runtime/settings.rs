@@ -2,6 +2,7 @@
 
 use super::storage::{read_app_json, StorageManager};
 use serde::Deserialize;
+use serde_json::{Map, Value};
 
 const GLOBAL_SETTINGS_PATH: &str = "global-settings.json";
 
@@ -11,6 +12,9 @@ struct StoredGlobalSettings {
     locale: Option<String>,
     chat: Option<StoredChatSettings>,
     general: Option<StoredGeneralSettings>,
+    terminal_tuning: Option<Map<String, Value>>,
+    terminal_filter_rules: Option<Map<String, Value>>,
+    members: Option<StoredMemberSettings>,
 }
 
 #[derive(Deserialize)]
@@ -25,6 +29,12 @@ struct StoredGeneralSettings {
     language: Option<String>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredMemberSettings {
+    hibernate_after_minutes: Option<u64>,
+}
+
 pub(crate) struct SettingsService {
     storage: StorageManager,
 }
@@ -50,6 +60,35 @@ impl SettingsService {
         let parsed = self.read_settings()?;
         parsed.chat.and_then(|chat| chat.stream_output)
     }
+
+    /// 读取 `terminalTuning.<terminal_type>` 覆盖项；未配置时返回 None 使用引擎默认值。
+    pub(crate) fn get_terminal_tuning(&self, terminal_type: &str) -> Option<Value> {
+        let mut parsed = self.read_settings()?;
+        parsed
+            .terminal_tuning
+            .as_mut()?
+            .remove(terminal_type)
+            .filter(Value::is_object)
+    }
+
+    /// 读取 `terminalFilterRules.<terminal_type>` 声明式过滤规则；未配置时使用内置 profile。
+    pub(crate) fn get_terminal_filter_rules(&self, terminal_type: &str) -> Option<Value> {
+        let mut parsed = self.read_settings()?;
+        parsed
+            .terminal_filter_rules
+            .as_mut()?
+            .remove(terminal_type)
+            .filter(Value::is_object)
+    }
+
+    /// 读取 `members.hibernateAfterMinutes`；0 或未配置视为关闭休眠。
+    pub(crate) fn get_member_hibernate_after_minutes(&self) -> Option<u64> {
+        let parsed = self.read_settings()?;
+        parsed
+            .members
+            .and_then(|members| members.hibernate_after_minutes)
+            .filter(|minutes| *minutes > 0)
+    }
 }
 
 impl crate::ports::settings::TerminalSettingsPort for SettingsService {
@@ -60,4 +99,16 @@ impl crate::ports::settings::TerminalSettingsPort for SettingsService {
     fn get_chat_stream_enabled(&self) -> Option<bool> {
         self.get_chat_stream_enabled()
     }
+
+    fn get_terminal_tuning(&self, terminal_type: &str) -> Option<Value> {
+        self.get_terminal_tuning(terminal_type)
+    }
+
+    fn get_terminal_filter_rules(&self, terminal_type: &str) -> Option<Value> {
+        self.get_terminal_filter_rules(terminal_type)
+    }
+
+    fn get_member_hibernate_after_minutes(&self) -> Option<u64> {
+        self.get_member_hibernate_after_minutes()
+    }
 }
@@ -0,0 +1,177 @@
+//! Shell 集成脚本：为 bash/zsh/fish/pwsh 注入 OSC 133 命令边界标记（提示符、命令开始/结束与退出码）。
+//! 边界：只负责落盘脚本与改写启动参数/环境变量，标记解析在会话层完成；识别不了的 shell 原样启动。
+
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+  sync::OnceLock,
+};
+
+const BASH_SCRIPT: &str = include_str!("shell_integration/bash.sh");
+const ZSH_ENV_SCRIPT: &str = include_str!("shell_integration/zshenv.zsh");
+const ZSH_RC_SCRIPT: &str = include_str!("shell_integration/zshrc.zsh");
+const FISH_SCRIPT: &str = include_str!("shell_integration/fish.fish");
+const PWSH_SCRIPT: &str = include_str!("shell_integration/pwsh.ps1");
+const SHELL_INTEGRATION_ENV: &str = "GOLUTRA_SHELL_INTEGRATION"; // 设为 0/false/off 时关闭注入，便于排查用户 rc 冲突。
+const USER_ZDOTDIR_ENV: &str = "GOLUTRA_USER_ZDOTDIR"; // zsh 集成目录内转发到用户原始 ZDOTDIR。
+const INTEGRATION_DIR_NAME: &str = "shell-integration"; // 应用数据目录下的脚本子目录。
+
+static INTEGRATION_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IntegratedShell {
+  Bash,
+  Zsh,
+  Fish,
+  PowerShell,
+}
+
+/// 注入后的启动参数与附加环境变量。
+pub(crate) struct ShellIntegration {
+  pub(crate) args: Vec<String>,
+  pub(crate) env: Vec<(String, String)>,
+}
+
+fn shell_integration_enabled() -> bool {
+  match env::var(SHELL_INTEGRATION_ENV) {
+    Ok(value) => !matches!(value.trim().to_lowercase().as_str(), "0" | "false" | "off" | "no"),
+    Err(_) => true,
+  }
+}
+
+fn detect_shell(program: &str) -> Option<IntegratedShell> {
+  let stem = Path::new(program)
+    .file_stem()
+    .and_then(|value| value.to_str())?
+    .to_lowercase();
+  match stem.as_str() {
+    "bash" => Some(IntegratedShell::Bash),
+    "zsh" => Some(IntegratedShell::Zsh),
+    "fish" => Some(IntegratedShell::Fish),
+    "pwsh" | "powershell" => Some(IntegratedShell::PowerShell),
+    _ => None,
+  }
+}
+
+/// 设置集成脚本目录（位于当前用户的应用数据目录下）；需在首个终端创建前调用。
+pub(crate) fn init_shell_integration_dir(app_data_dir: &Path) {
+  let _ = INTEGRATION_DIR.set(app_data_dir.join(INTEGRATION_DIR_NAME));
+}
+
+/// 集成脚本目录；未初始化时为 `None`，此时不注入。
+pub(crate) fn integration_dir() -> Option<&'static Path> {
+  INTEGRATION_DIR.get().map(PathBuf::as_path)
+}
+
+// 脚本会被 shell 直接 source，目录只对当前用户开放，避免他人替换注入内容。
+fn ensure_private_dir(dir: &Path) -> Result<(), String> {
+  fs::create_dir_all(dir).map_err(|err| format!("failed to create shell integration dir: {err}"))?;
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+      .map_err(|err| format!("failed to restrict shell integration dir: {err}"))?;
+  }
+  Ok(())
+}
+
+// 内容未变化时跳过写入，避免多个终端并发创建时反复覆盖同一文件。
+fn write_script(path: &Path, content: &str) -> Result<(), String> {
+  if fs::read_to_string(path).ok().as_deref() == Some(content) {
+    return Ok(());
+  }
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)
+      .map_err(|err| format!("failed to create shell integration dir: {err}"))?;
+  }
+  fs::write(path, content).map_err(|err| format!("failed to write shell integration script: {err}"))
+}
+
+fn prepare_bash(dir: &Path) -> Result<ShellIntegration, String> {
+  let script = dir.join("bash").join("golutra.bashrc");
+  write_script(&script, BASH_SCRIPT)?;
+  Ok(ShellIntegration {
+    args: vec!["--rcfile".to_string(), script.to_string_lossy().to_string()],
+    env: Vec::new(),
+  })
+}
+
+fn prepare_zsh(dir: &Path) -> Result<ShellIntegration, String> {
+  let zdotdir = dir.join("zsh");
+  write_script(&zdotdir.join(".zshenv"), ZSH_ENV_SCRIPT)?;
+  write_script(&zdotdir.join(".zshrc"), ZSH_RC_SCRIPT)?;
+  // 记录用户原始 ZDOTDIR（未设置时为 HOME），集成脚本加载完用户配置后恢复。
+  let user_zdotdir = env::var("ZDOTDIR")
+    .ok()
+    .filter(|value| !value.trim().is_empty())
+    .or_else(|| env::var("HOME").ok())
+    .unwrap_or_default();
+  Ok(ShellIntegration {
+    args: Vec::new(),
+    env: vec![
+      ("ZDOTDIR".to_string(), zdotdir.to_string_lossy().to_string()),
+      (USER_ZDOTDIR_ENV.to_string(), user_zdotdir),
+    ],
+  })
+}
+
+fn prepare_fish(dir: &Path) -> Result<ShellIntegration, String> {
+  let script = dir.join("fish").join("golutra.fish");
+  write_script(&script, FISH_SCRIPT)?;
+  Ok(ShellIntegration {
+    args: vec![
+      "--init-command".to_string(),
+      format!("source '{}'", script.to_string_lossy()),
+    ],
+    env: Vec::new(),
+  })
+}
+
+fn prepare_powershell(dir: &Path) -> Result<ShellIntegration, String> {
+  let script = dir.join("pwsh").join("golutra.ps1");
+  write_script(&script, PWSH_SCRIPT)?;
+  let escaped = script.to_string_lossy().replace('\'', "''");
+  Ok(ShellIntegration {
+    args: vec![
+      "-NoLogo".to_string(),
+      "-NoExit".to_string(),
+      "-ExecutionPolicy".to_string(),
+      "Bypass".to_string(),
+      "-Command".to_string(),
+      format!(". '{escaped}'"),
+    ],
+    env: Vec::new(),
+  })
+}
+
+/// 为交互式 shell 准备集成脚本并返回改写后的参数。
+/// 输入：`program` 为已解析的 shell 路径，`args` 为原始参数（非空时视为调用方自定义启动，跳过注入）。
+/// 返回：`None` 表示不注入（未识别的 shell、已关闭、目录未初始化或写入失败），按原参数启动。
+pub(crate) fn prepare_shell_integration(program: &str, args: &[String]) -> Option<ShellIntegration> {
+  if !shell_integration_enabled() {
+    return None;
+  }
+  let shell = detect_shell(program)?;
+  // PowerShell 默认只带 -NoLogo，集成参数已包含该项。
+  let custom_args = match shell {
+    IntegratedShell::PowerShell => args.iter().any(|arg| arg != "-NoLogo"),
+    _ => !args.is_empty(),
+  };
+  if custom_args {
+    return None;
+  }
+  let dir = integration_dir()?;
+  let prepared = ensure_private_dir(dir).and_then(|_| match shell {
+    IntegratedShell::Bash => prepare_bash(dir),
+    IntegratedShell::Zsh => prepare_zsh(dir),
+    IntegratedShell::Fish => prepare_fish(dir),
+    IntegratedShell::PowerShell => prepare_powershell(dir),
+  });
+  match prepared {
+    Ok(integration) => Some(integration),
+    Err(err) => {
+      log::warn!("terminal shell integration skipped program={} err={}", program, err);
+      None
+    }
+  }
+}
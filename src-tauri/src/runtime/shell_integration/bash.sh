@@ -0,0 +1,42 @@
+# golutra shell integration (bash)：通过 --rcfile 注入，先加载用户 rc，再挂载 OSC 133 命令边界标记。
+# A=提示符开始，B=命令输入开始，C=命令输出开始，D;<code>=命令结束。
+
+if [ -z "$GOLUTRA_SHELL_INTEGRATION_LOADED" ]; then
+  GOLUTRA_SHELL_INTEGRATION_LOADED=1
+
+  if [ -f "$HOME/.bashrc" ]; then
+    . "$HOME/.bashrc"
+  fi
+
+  __golutra_in_command=0
+  __golutra_at_prompt=0
+
+  __golutra_precmd() {
+    local __golutra_status=$?
+    if [ "$__golutra_in_command" = "1" ]; then
+      printf '\033]133;D;%s\007' "$__golutra_status"
+    fi
+    __golutra_in_command=0
+    printf '\033]133;A\007'
+  }
+
+  __golutra_prompt_ready() {
+    __golutra_at_prompt=1
+  }
+
+  __golutra_preexec() {
+    [ "$__golutra_at_prompt" = "1" ] || return
+    [ -n "$COMP_LINE" ] && return
+    case "$BASH_COMMAND" in
+      __golutra_precmd*|__golutra_prompt_ready*) return ;;
+    esac
+    __golutra_at_prompt=0
+    __golutra_in_command=1
+    printf '\033]133;C\007'
+  }
+
+  # precmd 必须最先执行以拿到上一条命令的退出码；prompt_ready 最后执行，避免用户 PROMPT_COMMAND 被误判为命令。
+  PROMPT_COMMAND="__golutra_precmd;${PROMPT_COMMAND:+$PROMPT_COMMAND;}__golutra_prompt_ready"
+  PS1="${PS1}\[\033]133;B\007\]"
+  trap '__golutra_preexec' DEBUG
+fi
@@ -0,0 +1,18 @@
+# golutra shell integration (fish)：通过 --init-command 加载，挂载 OSC 133 命令边界标记。
+# A=提示符开始，C=命令输出开始，D;<code>=命令结束。
+
+if not set -q GOLUTRA_SHELL_INTEGRATION_LOADED
+  set -g GOLUTRA_SHELL_INTEGRATION_LOADED 1
+
+  function __golutra_prompt --on-event fish_prompt
+    printf '\e]133;A\a'
+  end
+
+  function __golutra_preexec --on-event fish_preexec
+    printf '\e]133;C\a'
+  end
+
+  function __golutra_postexec --on-event fish_postexec
+    printf '\e]133;D;%s\a' $status
+  end
+end
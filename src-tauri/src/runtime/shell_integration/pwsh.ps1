@@ -0,0 +1,31 @@
+# golutra shell integration (PowerShell)：通过 -NoExit -Command 点源加载，挂载 OSC 133 命令边界标记。
+# A=提示符开始，B=命令输入开始，C=命令输出开始，D;<code>=命令结束。兼容 Windows PowerShell 5.1（不使用 `e 转义）。
+
+if (-not $Global:__GolutraShellIntegrationLoaded) {
+  $Global:__GolutraShellIntegrationLoaded = $true
+  $Global:__GolutraInCommand = $false
+  $Global:__GolutraOriginalPrompt = $function:prompt
+
+  function Global:prompt {
+    $succeeded = $?
+    $exitCode = if ($succeeded) { 0 } elseif ($LASTEXITCODE) { $LASTEXITCODE } else { 1 }
+    $esc = [char]27
+    $bel = [char]7
+    $marks = ""
+    if ($Global:__GolutraInCommand) {
+      $marks += "$esc]133;D;$exitCode$bel"
+    }
+    $Global:__GolutraInCommand = $false
+    $marks += "$esc]133;A$bel"
+    $original = & $Global:__GolutraOriginalPrompt
+    return "$marks$original$esc]133;B$bel"
+  }
+
+  if (Get-Module -Name PSReadLine) {
+    Set-PSReadLineKeyHandler -Chord Enter -ScriptBlock {
+      [Microsoft.PowerShell.PSConsoleReadLine]::AcceptLine()
+      $Global:__GolutraInCommand = $true
+      [Console]::Write([string][char]27 + "]133;C" + [string][char]7)
+    }
+  }
+}
@@ -1,13 +1,38 @@
 //! Claude 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan};
+use super::registry::{
+    SessionStoreProjectKey, TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan,
+    TerminalSessionStore,
+};
 
 pub(crate) const CLAUDE_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDefaultMemberConfig {
     id: "claude-code",
     terminal_type: "claude",
     default_command: "claude",
     unlimited_access_flag: Some("--dangerously-skip-permissions"),
-    resume_command_template: None,
+    resume_command_template: Some("--resume {session_id}"),
+    // 会话记录：~/.claude/projects/<编码后的工作目录>/<session_id>.jsonl
+    session_store: Some(TerminalSessionStore {
+        root: ".claude/projects",
+        project_key: SessionStoreProjectKey::EncodedPath,
+        subdir: None,
+        extension: "jsonl",
+        id_field: None,
+        directory_field: None,
+    }),
+    state_dirs: &[".claude", ".claude.json"],
+    filter_profile: None,
+    // 权限确认为选项列表，默认高亮「Yes」，Esc 对应「No, and tell Claude what to do differently」。
+    approval_prompt: Some(TerminalApprovalPrompt {
+        patterns: &[
+            "do you want to proceed?",
+            "do you want to make this edit",
+            "do you want to create",
+            "do you want to allow",
+        ],
+        approve_input: "\r",
+        deny_input: "\x1b",
+    }),
     post_ready_plan: TerminalPostReadyPlan {
         post_ready_steps: &[
             super::ai_shared::AI_ONBOARDING_STEP,
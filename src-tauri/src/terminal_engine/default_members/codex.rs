@@ -1,6 +1,8 @@
 //! Codex 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan, TerminalPostReadyStep};
+use super::registry::{
+    TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan, TerminalPostReadyStep,
+};
 
 pub(crate) const CODEX_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDefaultMemberConfig {
     id: "codex",
@@ -8,6 +10,19 @@ pub(crate) const CODEX_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDef
     default_command: "codex",
     unlimited_access_flag: Some("--dangerously-bypass-approvals-and-sandbox"),
     resume_command_template: Some("resume {session_id}"),
+    session_store: None,
+    state_dirs: &[".codex"],
+    filter_profile: None,
+    // 审批弹窗：`y` 批准本次，Esc 拒绝并回到输入框。
+    approval_prompt: Some(TerminalApprovalPrompt {
+        patterns: &[
+            "allow command?",
+            "would you like to run the following command",
+            "would you like to make the following edits",
+        ],
+        approve_input: "y",
+        deny_input: "\x1b",
+    }),
     post_ready_plan: TerminalPostReadyPlan {
         post_ready_steps: &[
             TerminalPostReadyStep::Input {
@@ -1,13 +1,35 @@
 //! Gemini 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan};
+use super::registry::{
+    SessionStoreProjectKey, TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan,
+    TerminalSessionStore,
+};
+
+/// 工具确认为选项列表，首项「Yes, allow once」默认高亮，Esc 取消；Qwen Code 沿用同一界面。
+pub(crate) const GEMINI_APPROVAL_PROMPT: TerminalApprovalPrompt = TerminalApprovalPrompt {
+    patterns: &["allow execution", "apply this change?", "do you want to proceed?"],
+    approve_input: "\r",
+    deny_input: "\x1b",
+};
 
 pub(crate) const GEMINI_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDefaultMemberConfig {
     id: "gemini-cli",
     terminal_type: "gemini",
     default_command: "gemini",
     unlimited_access_flag: Some("--yolo"),
-    resume_command_template: None,
+    resume_command_template: Some("--resume {session_id}"),
+    // 会话记录：~/.gemini/tmp/<工作目录 SHA-256>/chats/session-*.json，ID 在 sessionId 字段。
+    session_store: Some(TerminalSessionStore {
+        root: ".gemini/tmp",
+        project_key: SessionStoreProjectKey::HashedPath,
+        subdir: Some("chats"),
+        extension: "json",
+        id_field: Some("sessionId"),
+        directory_field: None,
+    }),
+    state_dirs: &[".gemini"],
+    filter_profile: None,
+    approval_prompt: Some(GEMINI_APPROVAL_PROMPT),
     post_ready_plan: TerminalPostReadyPlan {
         post_ready_steps: &[
             super::ai_shared::AI_ONBOARDING_STEP,
@@ -6,10 +6,13 @@ pub(crate) mod codex;
 pub(crate) mod gemini;
 pub(crate) mod onboarding;
 pub(crate) mod opencode;
+pub(crate) mod plugins;
 pub(crate) mod qwen;
 pub(crate) mod registry;
+pub(crate) mod session_store;
 pub(crate) mod shell;
 
+pub(crate) use plugins::{load_plugin_members, plugin_members, MEMBER_TYPES_DIR};
 pub(crate) use registry::{
     apply_resume_command, apply_unlimited_access_command, resolve_default_command_for_invite,
     resolve_default_member,
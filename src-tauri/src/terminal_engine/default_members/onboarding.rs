@@ -1,30 +1,294 @@
 //! 终端引导逻辑：生成基于语言与场景的初始提示词。
+//! 引导词按成员角色选模板，模板可在项目数据 `onboarding.templates` 中按 roleType 覆盖（`default` 兜底），
+//! 支持变量 `{name}`、`{memberId}`、`{role}`、`{workspaceId}`、`{workspacePath}`、`{teammates}`、
+//! `{sendCommand}`、`{conventions}`；项目约定取自 `onboarding.conventions`。
+//! 模板与约定可写成字符串（不分语言）或 `{ "zh": ..., "en": ... }`。
+
+use std::collections::HashMap;
+
+use serde_json::Value;
 
 pub(crate) const PROMPT_TYPE_ONBOARDING: &str = "onboarding";
 
+const TEMPLATE_FALLBACK_KEY: &str = "default"; // 项目模板中未按角色配置时使用的兜底键。
+const ROLE_TYPE_ASSISTANT: &str = "assistant";
+
+const ONBOARDING_ASSISTANT_ZH: &str = "{name}，这是你的名字（成员 ID：{memberId}），你在团队中的角色是{role}。\n工作目录：{workspacePath}\n团队成员：\n{teammates}\n给队友发消息请执行：\n{sendCommand}\n现在正在和团队一起解决问题，收到任务后请完成并把结果汇报给发起人。";
+const ONBOARDING_ASSISTANT_EN: &str = "{name}, this is your name (member id: {memberId}). Your role on the team is {role}.\nWorkspace: {workspacePath}\nTeammates:\n{teammates}\nTo message a teammate, run:\n{sendCommand}\nYou are working with the team to solve problems; when you receive a task, finish it and report the result back to whoever asked.";
+const ONBOARDING_MEMBER_ZH: &str = "{name}，这是你的名字（成员 ID：{memberId}），你在团队中的角色是{role}。\n工作目录：{workspacePath}\n团队成员：\n{teammates}\n给队友发消息请执行：\n{sendCommand}\n现在正在和团队一起解决问题。";
+const ONBOARDING_MEMBER_EN: &str = "{name}, this is your name (member id: {memberId}). Your role on the team is {role}.\nWorkspace: {workspacePath}\nTeammates:\n{teammates}\nTo message a teammate, run:\n{sendCommand}\nYou are working with the team to solve problems.";
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) enum PromptType {
     Onboarding,
 }
 
+/// 按语言区分的文本；只配置一种语言时另一种语言沿用它。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LocalizedText {
+    zh: Option<String>,
+    en: Option<String>,
+}
+
+impl LocalizedText {
+    fn from_value(value: &Value) -> Option<Self> {
+        let text = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| value.to_string())
+        };
+        let parsed = match value {
+            Value::String(_) => {
+                let text = text(Some(value));
+                Self {
+                    zh: text.clone(),
+                    en: text,
+                }
+            }
+            Value::Object(map) => Self {
+                zh: text(map.get("zh")),
+                en: text(map.get("en")),
+            },
+            _ => return None,
+        };
+        (parsed.zh.is_some() || parsed.en.is_some()).then_some(parsed)
+    }
+
+    fn get(&self, is_english: bool) -> Option<&str> {
+        let (primary, secondary) = if is_english {
+            (&self.en, &self.zh)
+        } else {
+            (&self.zh, &self.en)
+        };
+        primary.as_deref().or(secondary.as_deref())
+    }
+}
+
+/// 引导词中列出的队友。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OnboardingTeammate {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) role_type: String,
+    pub(crate) role: String,
+}
+
+/// 生成引导词所需的成员与项目信息，会话注册时从项目数据解析。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct OnboardingContext {
+    pub(crate) member_id: String,
+    pub(crate) member_name: String,
+    pub(crate) role_type: String,
+    pub(crate) role: String,
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_path: Option<String>,
+    pub(crate) teammates: Vec<OnboardingTeammate>,
+    conventions: Option<LocalizedText>,
+    templates: HashMap<String, LocalizedText>,
+}
+
+impl OnboardingContext {
+    /// 仅有成员标识时的最小上下文：名称缺省时沿用成员 ID，不含队友与项目约定。
+    pub(crate) fn for_member(member_id: &str, member_name: Option<&str>, workspace_id: Option<&str>) -> Self {
+        Self {
+            member_id: member_id.to_string(),
+            member_name: member_name.unwrap_or(member_id).to_string(),
+            workspace_id: workspace_id.unwrap_or_default().to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// 从项目数据的成员列表与 `onboarding` 配置解析上下文；成员不在列表中时返回 None。
+    /// 无法识别的模板或约定直接忽略，回退为内置模板。
+    pub(crate) fn from_project(
+        workspace_id: &str,
+        workspace_path: Option<&str>,
+        member_id: &str,
+        members: &[Value],
+        onboarding: Option<&Value>,
+    ) -> Option<Self> {
+        let teammate = |member: &Value| -> Option<OnboardingTeammate> {
+            let field = |key: &str| {
+                member
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let id = field("id");
+            if id.is_empty() {
+                return None;
+            }
+            let name = field("name");
+            Some(OnboardingTeammate {
+                name: if name.is_empty() { id.clone() } else { name },
+                id,
+                role_type: field("roleType"),
+                role: field("role"),
+            })
+        };
+        let mut roster: Vec<OnboardingTeammate> = members.iter().filter_map(teammate).collect();
+        let index = roster.iter().position(|member| member.id == member_id)?;
+        let me = roster.remove(index);
+        let templates = onboarding
+            .and_then(|value| value.get("templates"))
+            .and_then(Value::as_object)
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(role, value)| {
+                        LocalizedText::from_value(value).map(|text| (role.trim().to_lowercase(), text))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            member_id: me.id,
+            member_name: me.name,
+            role_type: me.role_type,
+            role: me.role,
+            workspace_id: workspace_id.to_string(),
+            workspace_path: workspace_path.map(|path| path.to_string()),
+            teammates: roster,
+            conventions: onboarding
+                .and_then(|value| value.get("conventions"))
+                .and_then(LocalizedText::from_value),
+            templates,
+        })
+    }
+
+    fn template(&self, is_english: bool) -> &str {
+        let role_type = self.role_type.to_lowercase();
+        let custom = [role_type.as_str(), TEMPLATE_FALLBACK_KEY]
+            .iter()
+            .find_map(|key| self.templates.get(*key).and_then(|text| text.get(is_english)));
+        if let Some(template) = custom {
+            return template;
+        }
+        match (role_type == ROLE_TYPE_ASSISTANT, is_english) {
+            (true, true) => ONBOARDING_ASSISTANT_EN,
+            (true, false) => ONBOARDING_ASSISTANT_ZH,
+            (false, true) => ONBOARDING_MEMBER_EN,
+            (false, false) => ONBOARDING_MEMBER_ZH,
+        }
+    }
+}
+
 pub(crate) fn generate_prompt(
     prompt_type: PromptType,
-    terminal_id: &str,
+    context: &OnboardingContext,
     language: Option<&str>,
 ) -> String {
     let language = language.unwrap_or("zh");
     let is_english = language.to_lowercase().starts_with("en");
 
     match prompt_type {
-        PromptType::Onboarding => {
-            if is_english {
+        PromptType::Onboarding => render_onboarding(context, is_english),
+    }
+}
+
+fn render_onboarding(context: &OnboardingContext, is_english: bool) -> String {
+    let template = context.template(is_english);
+    let conventions = context
+        .conventions
+        .as_ref()
+        .and_then(|text| text.get(is_english))
+        .unwrap_or_default();
+    let teammates = if context.teammates.is_empty() {
+        if is_english {
+            "- (no other members yet)".to_string()
+        } else {
+            "- （暂无其他成员）".to_string()
+        }
+    } else {
+        context
+            .teammates
+            .iter()
+            .map(|member| {
                 format!(
-                    "{}, this is your name. You are working with the team to solve problems.",
-                    terminal_id
+                    "- {} ({}), id: {}",
+                    member.name,
+                    role_label(&member.role_type, &member.role, is_english),
+                    member.id
                 )
-            } else {
-                format!("{}，这是你的名字，现在正在和团队解决问题", terminal_id)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let send_command = if is_english {
+        format!(
+            "golutra send --workspace {} --ai --sender {} --to <member id> --text \"<message>\"",
+            context.workspace_id, context.member_id
+        )
+    } else {
+        format!(
+            "golutra send --workspace {} --ai --sender {} --to <成员ID> --text \"<消息内容>\"",
+            context.workspace_id, context.member_id
+        )
+    };
+    let workspace_path = context.workspace_path.as_deref().unwrap_or("-");
+    let role = role_label(&context.role_type, &context.role, is_english);
+    let variables = [
+        ("{name}", context.member_name.as_str()),
+        ("{memberId}", context.member_id.as_str()),
+        ("{role}", role.as_str()),
+        ("{workspaceId}", context.workspace_id.as_str()),
+        ("{workspacePath}", workspace_path),
+        ("{teammates}", teammates.as_str()),
+        ("{sendCommand}", send_command.as_str()),
+        ("{conventions}", conventions),
+    ];
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    // 单次扫描替换，避免变量值中恰好含有占位符时被二次展开。
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match variables.iter().find(|(key, _)| tail.starts_with(key)) {
+            Some((key, value)) => {
+                rendered.push_str(value);
+                rest = &tail[key.len()..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &tail[1..];
             }
         }
     }
+    rendered.push_str(rest);
+    // 模板未引用约定时追加在末尾，保证项目约定总能送达成员。
+    if !conventions.is_empty() && !template.contains("{conventions}") {
+        rendered.push_str(if is_english {
+            "\nProject conventions:\n"
+        } else {
+            "\n项目约定：\n"
+        });
+        rendered.push_str(conventions);
+    }
+    // 回车会被 CLI 当作提交，模板内换行统一为 `\n`，由后续回车步骤提交整段引导词。
+    rendered.replace("\r\n", "\n").replace('\r', "\n")
 }
+
+/// 角色显示名：成员自定义的 `role` 优先，否则按 roleType 给出本地化名称。
+fn role_label(role_type: &str, role: &str, is_english: bool) -> String {
+    if !role.is_empty() {
+        return role.to_string();
+    }
+    let label = match (role_type, is_english) {
+        ("owner", false) => "群主",
+        ("owner", true) => "Owner",
+        ("admin", false) => "管理员",
+        ("admin", true) => "Admin",
+        ("assistant", false) => "AI 助手",
+        ("assistant", true) => "AI Assistant",
+        ("member", false) | ("", false) => "成员",
+        ("member", true) | ("", true) => "Member",
+        (other, _) => other,
+    };
+    label.to_string()
+}
+
+#[cfg(test)]
+mod template_tests;
@@ -0,0 +1,88 @@
+use serde_json::{json, Value};
+
+use super::{generate_prompt, OnboardingContext, PromptType};
+
+fn roster() -> Vec<Value> {
+    vec![
+        json!({ "id": "owner-1", "name": "Owner", "role": "", "roleType": "owner" }),
+        json!({ "id": "m-claude", "name": "demo-claude-1", "role": "", "roleType": "assistant" }),
+        json!({ "id": "m-codex", "name": "demo-codex-1", "role": "Reviewer", "roleType": "assistant" }),
+    ]
+}
+
+fn context(onboarding: Option<Value>) -> OnboardingContext {
+    OnboardingContext::from_project(
+        "ws-1",
+        Some("/work/demo"),
+        "m-claude",
+        &roster(),
+        onboarding.as_ref(),
+    )
+    .expect("member in roster")
+}
+
+#[test]
+fn builtin_prompt_lists_name_roster_and_send_command() {
+    let prompt = generate_prompt(PromptType::Onboarding, &context(None), Some("en-US"));
+    assert!(prompt.starts_with("demo-claude-1, this is your name (member id: m-claude)."));
+    assert!(prompt.contains("Your role on the team is AI Assistant."));
+    assert!(prompt.contains("Workspace: /work/demo"));
+    assert!(prompt.contains("- Owner (Owner), id: owner-1\n- demo-codex-1 (Reviewer), id: m-codex"));
+    assert!(prompt.contains("golutra send --workspace ws-1 --ai --sender m-claude --to <member id>"));
+    assert!(!prompt.contains("demo-claude-1 (AI Assistant)"));
+    assert!(!prompt.contains('\r'));
+
+    let zh = generate_prompt(PromptType::Onboarding, &context(None), Some("zh-CN"));
+    assert!(zh.starts_with("demo-claude-1，这是你的名字"));
+    assert!(zh.contains("- Owner (群主), id: owner-1"));
+}
+
+#[test]
+fn project_template_overrides_by_role_and_language() {
+    let onboarding = json!({
+        "templates": {
+            "assistant": { "zh": "{name} 负责 {role}", "en": "{name} handles {role} in {workspacePath}" },
+            "default": "fallback {name}"
+        },
+        "conventions": { "en": "Run cargo fmt before committing." }
+    });
+    let context = context(Some(onboarding));
+    assert_eq!(
+        generate_prompt(PromptType::Onboarding, &context, Some("en")),
+        "demo-claude-1 handles AI Assistant in /work/demo\nProject conventions:\nRun cargo fmt before committing."
+    );
+    // 中文缺失约定时沿用英文版本。
+    assert_eq!(
+        generate_prompt(PromptType::Onboarding, &context, None),
+        "demo-claude-1 负责 AI 助手\n项目约定：\nRun cargo fmt before committing."
+    );
+}
+
+#[test]
+fn placeholders_expand_once_and_unknown_ones_stay() {
+    let onboarding = json!({
+        "templates": { "default": "{name}: {conventions} {unknown}\r{teammates}" },
+        "conventions": "Use {name} literally"
+    });
+    let context = OnboardingContext::from_project(
+        "ws-1",
+        None,
+        "owner-1",
+        &roster()[..1],
+        Some(&onboarding),
+    )
+    .expect("member in roster");
+    assert_eq!(
+        generate_prompt(PromptType::Onboarding, &context, Some("en")),
+        "Owner: Use {name} literally {unknown}\n- (no other members yet)"
+    );
+}
+
+#[test]
+fn unknown_member_falls_back_to_minimal_context() {
+    assert!(OnboardingContext::from_project("ws-1", None, "missing", &roster(), None).is_none());
+    let context = OnboardingContext::for_member("m-1", None, Some("ws-1"));
+    let prompt = generate_prompt(PromptType::Onboarding, &context, Some("en"));
+    assert!(prompt.starts_with("m-1, this is your name (member id: m-1). Your role on the team is Member."));
+    assert!(prompt.contains("Workspace: -"));
+}
@@ -1,6 +1,9 @@
 //! OpenCode 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan};
+use super::registry::{
+    SessionStoreProjectKey, TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan,
+    TerminalSessionStore,
+};
 
 pub(crate) const OPENCODE_DEFAULT_MEMBER: TerminalDefaultMemberConfig =
     TerminalDefaultMemberConfig {
@@ -8,7 +11,25 @@ pub(crate) const OPENCODE_DEFAULT_MEMBER: TerminalDefaultMemberConfig =
         terminal_type: "opencode",
         default_command: "opencode",
         unlimited_access_flag: None,
-        resume_command_template: None,
+        resume_command_template: Some("--session {session_id}"),
+        // 会话记录：~/.local/share/opencode/storage/session/<项目>/<session_id>.json，
+        // 项目目录名与工作目录无关，按 directory 字段匹配。
+        session_store: Some(TerminalSessionStore {
+            root: ".local/share/opencode/storage/session",
+            project_key: SessionStoreProjectKey::AnyProject,
+            subdir: None,
+            extension: "json",
+            id_field: Some("id"),
+            directory_field: Some("directory"),
+        }),
+        state_dirs: &[".local/share/opencode", ".config/opencode"],
+        filter_profile: None,
+        // 权限请求面板：Enter 允许本次，Esc 拒绝。
+        approval_prompt: Some(TerminalApprovalPrompt {
+            patterns: &["permission required", "allow once"],
+            approve_input: "\r",
+            deny_input: "\x1b",
+        }),
         post_ready_plan: TerminalPostReadyPlan {
             post_ready_steps: &[
                 super::ai_shared::AI_ONBOARDING_STEP,
@@ -0,0 +1,272 @@
+//! 插件成员类型：从用户配置目录加载 JSON 定义，补充内置成员之外的 CLI（如 Aider、Cursor agent）。
+//! 约束：仅在启动时加载并校验一次；校验失败的定义记录告警后跳过，不影响其他定义与内置成员。
+//! 定义在进程内常驻，字符串以 `&'static` 形式保存，与内置成员共用同一套解析与命令拼装逻辑。
+
+use std::{
+    fs,
+    path::{Component, Path},
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::onboarding::PROMPT_TYPE_ONBOARDING;
+use super::registry::{
+    TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan,
+    TerminalPostReadyStep, DEFAULT_TERMINAL_MEMBERS,
+};
+use crate::terminal_engine::filters::registry::parse_profile;
+use crate::terminal_engine::session::{
+    parse_post_ready_steps, PostReadyScriptStep, StoredPostReadyStep,
+};
+
+/// 插件定义所在的配置子目录（位于应用配置目录下），每个 `*.json` 文件一个成员类型。
+pub(crate) const MEMBER_TYPES_DIR: &str = "member-types";
+
+static PLUGIN_MEMBERS: OnceLock<Vec<TerminalDefaultMemberConfig>> = OnceLock::new();
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StoredMemberType {
+    id: String,
+    terminal_type: String,
+    default_command: String,
+    unlimited_access_flag: Option<String>,
+    resume_command_template: Option<String>,
+    #[serde(default)]
+    state_dirs: Vec<String>,
+    filter_profile: Option<String>,
+    approval_prompt: Option<StoredApprovalPrompt>,
+    #[serde(default)]
+    post_ready_steps: Vec<StoredPostReadyStep>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StoredApprovalPrompt {
+    patterns: Vec<String>,
+    approve_input: String,
+    deny_input: String,
+}
+
+/// 启动时加载插件成员类型；重复调用只保留首次结果。
+/// 目录不存在视为未配置；返回成功注册的数量。
+pub(crate) fn load_plugin_members(dir: &Path) -> usize {
+    PLUGIN_MEMBERS
+        .get_or_init(|| {
+            let (members, errors) = read_plugin_members(dir);
+            for err in errors {
+                log::warn!("terminal member type skipped dir={} err={}", dir.display(), err);
+            }
+            for member in members.iter() {
+                log::info!(
+                    "terminal member type registered id={} terminal_type={}",
+                    member.id,
+                    member.terminal_type
+                );
+            }
+            members
+        })
+        .len()
+}
+
+/// 已注册的插件成员类型；未加载时为空。
+pub(crate) fn plugin_members() -> &'static [TerminalDefaultMemberConfig] {
+    PLUGIN_MEMBERS.get().map(Vec::as_slice).unwrap_or(&[])
+}
+
+// 按文件名排序读取，保证冲突时先到先得的结果稳定。
+fn read_plugin_members(dir: &Path) -> (Vec<TerminalDefaultMemberConfig>, Vec<String>) {
+    let mut members = Vec::new();
+    let mut errors = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return (members, errors),
+        Err(err) => {
+            errors.push(format!("failed to read member types directory: {err}"));
+            return (members, errors);
+        }
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read file: {err}"))
+            .and_then(|contents| {
+                serde_json::from_str::<Value>(&contents)
+                    .map_err(|err| format!("failed to parse JSON: {err}"))
+            })
+            .and_then(|value| parse_member_type(&value, &members));
+        match parsed {
+            Ok(member) => members.push(member),
+            Err(err) => errors.push(format!("{}: {err}", path.display())),
+        }
+    }
+    (members, errors)
+}
+
+/// 解析并校验单个成员类型定义；`registered` 为已接受的插件定义，用于冲突检测。
+pub(crate) fn parse_member_type(
+    value: &Value,
+    registered: &[TerminalDefaultMemberConfig],
+) -> Result<TerminalDefaultMemberConfig, String> {
+    let stored: StoredMemberType = serde_json::from_value(value.clone())
+        .map_err(|err| format!("invalid member type definition: {err}"))?;
+    let id = stored.id.trim();
+    if id.is_empty() {
+        return Err("id is required".to_string());
+    }
+    let terminal_type = stored.terminal_type.trim().to_lowercase();
+    if terminal_type.is_empty()
+        || !terminal_type
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(format!(
+            "terminalType {terminal_type:?} must use letters, digits, '-' or '_'"
+        ));
+    }
+    let existing = DEFAULT_TERMINAL_MEMBERS.iter().chain(registered.iter());
+    for member in existing {
+        if member.id == id {
+            return Err(format!("id {id:?} is already registered"));
+        }
+        if member.terminal_type == terminal_type {
+            return Err(format!("terminalType {terminal_type:?} is already registered"));
+        }
+    }
+    let default_command = stored.default_command.trim();
+    if default_command.is_empty() {
+        return Err("defaultCommand is required".to_string());
+    }
+    let unlimited_access_flag = match stored.unlimited_access_flag.as_deref().map(str::trim) {
+        Some(flag) if flag.is_empty() || flag.contains(char::is_whitespace) => {
+            return Err("unlimitedAccessFlag must be a single argument".to_string());
+        }
+        flag => flag,
+    };
+    let resume_command_template = match stored.resume_command_template.as_deref().map(str::trim) {
+        Some(template) if !template.contains("{session_id}") => {
+            return Err("resumeCommandTemplate must contain {session_id}".to_string());
+        }
+        template => template,
+    };
+    let mut state_dirs = Vec::with_capacity(stored.state_dirs.len());
+    for dir in stored.state_dirs.iter().map(|dir| dir.trim()) {
+        let relative = Path::new(dir);
+        if dir.is_empty()
+            || relative.is_absolute()
+            || relative
+                .components()
+                .any(|component| matches!(component, Component::ParentDir))
+        {
+            return Err(format!("stateDirs entry {dir:?} must be a relative path under HOME"));
+        }
+        state_dirs.push(leak(dir));
+    }
+    let filter_profile = match stored.filter_profile.as_deref().map(str::trim) {
+        Some(name) if parse_profile(name).is_none() => {
+            return Err(format!("filterProfile {name:?} is not a known profile"));
+        }
+        name => name,
+    };
+    let approval_prompt = stored
+        .approval_prompt
+        .map(parse_approval_prompt)
+        .transpose()?;
+    let steps = parse_post_ready_steps(stored.post_ready_steps, "postReadySteps")?
+        .into_iter()
+        .enumerate()
+        .map(|(index, step)| {
+            member_post_ready_step(step).map_err(|err| format!("postReadySteps[{index}]: {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(TerminalDefaultMemberConfig {
+        id: leak(id),
+        terminal_type: leak(&terminal_type),
+        default_command: leak(default_command),
+        unlimited_access_flag: unlimited_access_flag.map(leak),
+        resume_command_template: resume_command_template.map(leak),
+        session_store: None,
+        state_dirs: Box::leak(state_dirs.into_boxed_slice()),
+        filter_profile: filter_profile.map(|name| leak(&name.to_lowercase())),
+        approval_prompt,
+        post_ready_plan: TerminalPostReadyPlan {
+            post_ready_steps: Box::leak(steps.into_boxed_slice()),
+        },
+    })
+}
+
+// 特征统一转小写，与屏幕文本的大小写无关匹配保持一致。
+fn parse_approval_prompt(stored: StoredApprovalPrompt) -> Result<TerminalApprovalPrompt, String> {
+    let patterns: Vec<&'static str> = stored
+        .patterns
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| leak(&pattern.to_lowercase()))
+        .collect();
+    if patterns.is_empty() {
+        return Err("approvalPrompt requires at least one pattern".to_string());
+    }
+    if stored.approve_input.is_empty() || stored.deny_input.is_empty() {
+        return Err("approvalPrompt requires approveInput and denyInput".to_string());
+    }
+    Ok(TerminalApprovalPrompt {
+        patterns: Box::leak(patterns.into_boxed_slice()),
+        approve_input: leak(&stored.approve_input),
+        deny_input: leak(&stored.deny_input),
+    })
+}
+
+// 成员类型的内置流程只支持顺序步骤：延迟、分支与等待超时仅用于项目数据脚本。
+fn member_post_ready_step(step: PostReadyScriptStep) -> Result<TerminalPostReadyStep, String> {
+    let step = match step {
+        PostReadyScriptStep::Input {
+            input,
+            require_stable,
+        } => TerminalPostReadyStep::Input {
+            input: leak(&input),
+            require_stable,
+        },
+        PostReadyScriptStep::ExtractSessionId {
+            keyword,
+            require_stable,
+        } => TerminalPostReadyStep::ExtractSessionId {
+            keyword: leak(&keyword),
+            require_stable,
+        },
+        PostReadyScriptStep::WaitForPattern {
+            pattern,
+            timeout_ms: None,
+            require_stable,
+        } => TerminalPostReadyStep::WaitForPattern {
+            pattern: leak(&pattern),
+            require_stable,
+        },
+        PostReadyScriptStep::WaitForPattern { .. } => {
+            return Err("waitForPattern timeoutMs is not supported for member types".to_string());
+        }
+        PostReadyScriptStep::Introduction { require_stable, .. } => {
+            TerminalPostReadyStep::Introduction {
+                prompt_type: PROMPT_TYPE_ONBOARDING,
+                require_stable,
+            }
+        }
+        PostReadyScriptStep::Delay { .. } | PostReadyScriptStep::IfPattern { .. } => {
+            return Err("delay and ifPattern steps are not supported for member types".to_string());
+        }
+    };
+    Ok(step)
+}
+
+fn leak(value: &str) -> &'static str {
+    Box::leak(value.to_string().into_boxed_str())
+}
+
+#[cfg(test)]
+mod validation_tests;
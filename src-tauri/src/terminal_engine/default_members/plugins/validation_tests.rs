@@ -0,0 +1,118 @@
+use serde_json::json;
+
+use super::parse_member_type;
+use crate::terminal_engine::default_members::registry::TerminalPostReadyStep;
+
+fn aider_definition() -> serde_json::Value {
+    json!({
+        "id": "aider",
+        "terminalType": "Aider",
+        "defaultCommand": "aider",
+        "unlimitedAccessFlag": "--yes-always",
+        "resumeCommandTemplate": "aider --restore-chat-history {session_id}",
+        "stateDirs": [" .aider ", ".config/aider"],
+        "filterProfile": "generic",
+        "approvalPrompt": {
+            "patterns": ["Allow edits to", "  "],
+            "approveInput": "y\r",
+            "denyInput": "n\r"
+        },
+        "postReadySteps": [
+            { "type": "waitForPattern", "pattern": ">" },
+            { "type": "keys", "keys": "<Esc>" },
+            { "type": "introduction", "promptType": "onboarding", "requireStable": false }
+        ]
+    })
+}
+
+#[test]
+fn valid_definition_is_registered() {
+    let member = parse_member_type(&aider_definition(), &[]).expect("valid definition");
+    assert_eq!(member.id, "aider");
+    assert_eq!(member.terminal_type, "aider");
+    assert_eq!(member.default_command, "aider");
+    assert_eq!(member.unlimited_access_flag, Some("--yes-always"));
+    assert_eq!(member.state_dirs, [".aider", ".config/aider"]);
+    assert_eq!(member.filter_profile, Some("generic"));
+    let approval = member.approval_prompt.expect("approval prompt");
+    assert_eq!(approval.patterns, ["allow edits to"]);
+    assert_eq!(approval.approve_input, "y\r");
+    let steps = member.post_ready_plan.post_ready_steps;
+    assert_eq!(steps.len(), 3);
+    assert!(matches!(
+        steps[0],
+        TerminalPostReadyStep::WaitForPattern {
+            pattern: ">",
+            require_stable: true
+        }
+    ));
+    assert!(matches!(steps[1], TerminalPostReadyStep::Input { input: "\x1b", .. }));
+    assert!(matches!(
+        steps[2],
+        TerminalPostReadyStep::Introduction {
+            require_stable: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn conflicting_types_are_rejected() {
+    let mut builtin = aider_definition();
+    builtin["terminalType"] = json!("codex");
+    let err = parse_member_type(&builtin, &[]).unwrap_err();
+    assert!(err.contains("already registered"), "{err}");
+
+    let first = parse_member_type(&aider_definition(), &[]).expect("valid definition");
+    let mut duplicate = aider_definition();
+    duplicate["id"] = json!("aider-2");
+    let err = parse_member_type(&duplicate, &[first]).unwrap_err();
+    assert!(err.contains("terminalType"), "{err}");
+}
+
+#[test]
+fn invalid_fields_are_rejected() {
+    let cases = [
+        ("resumeCommandTemplate", json!("aider --resume"), "{session_id}"),
+        ("filterProfile", json!("vim"), "filterProfile"),
+        ("unlimitedAccessFlag", json!("--yes always"), "single argument"),
+        ("terminalType", json!("my cli"), "terminalType"),
+        ("defaultCommand", json!("  "), "defaultCommand"),
+        ("stateDirs", json!(["/etc"]), "stateDirs"),
+        ("stateDirs", json!(["../.ssh"]), "stateDirs"),
+        (
+            "approvalPrompt",
+            json!({ "patterns": [], "approveInput": "y", "denyInput": "n" }),
+            "pattern",
+        ),
+    ];
+    for (field, value, expected) in cases {
+        let mut definition = aider_definition();
+        definition[field] = value;
+        let err = parse_member_type(&definition, &[]).unwrap_err();
+        assert!(err.contains(expected), "{field}: {err}");
+    }
+}
+
+#[test]
+fn unknown_steps_and_fields_are_rejected() {
+    let mut definition = aider_definition();
+    definition["postReadySteps"] = json!([{ "type": "introduction", "promptType": "custom" }]);
+    assert!(parse_member_type(&definition, &[]).is_err());
+
+    let mut definition = aider_definition();
+    definition["postReadySteps"] = json!([{ "type": "shell", "input": "ls" }]);
+    assert!(parse_member_type(&definition, &[]).is_err());
+
+    let mut definition = aider_definition();
+    definition["postReadySteps"] = json!([
+        { "type": "input", "input": "/help" },
+        { "type": "delay", "ms": 500 }
+    ]);
+    let err = parse_member_type(&definition, &[]).unwrap_err();
+    assert!(err.contains("postReadySteps[1]"), "{err}");
+
+    let mut definition = aider_definition();
+    definition["icon"] = json!("aider.svg");
+    assert!(parse_member_type(&definition, &[]).is_err());
+}
@@ -1,13 +1,28 @@
 //! Qwen Code 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan};
+use super::gemini::GEMINI_APPROVAL_PROMPT;
+use super::registry::{
+    SessionStoreProjectKey, TerminalDefaultMemberConfig, TerminalPostReadyPlan, TerminalSessionStore,
+};
 
 pub(crate) const QWEN_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDefaultMemberConfig {
     id: "qwen-code",
     terminal_type: "qwen",
     default_command: "qwen",
     unlimited_access_flag: Some("--yolo"),
-    resume_command_template: None,
+    resume_command_template: Some("--resume {session_id}"),
+    // 会话记录：~/.qwen/projects/<编码后的工作目录>/chats/<session_id>.jsonl
+    session_store: Some(TerminalSessionStore {
+        root: ".qwen/projects",
+        project_key: SessionStoreProjectKey::EncodedPath,
+        subdir: Some("chats"),
+        extension: "jsonl",
+        id_field: None,
+        directory_field: None,
+    }),
+    state_dirs: &[".qwen"],
+    filter_profile: None,
+    approval_prompt: Some(GEMINI_APPROVAL_PROMPT),
     post_ready_plan: TerminalPostReadyPlan {
         post_ready_steps: &[
             super::ai_shared::AI_ONBOARDING_STEP,
@@ -33,6 +33,41 @@ impl TerminalPostReadyPlan {
     };
 }
 
+/// 会话存储中项目目录的定位方式。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SessionStoreProjectKey {
+    /// 工作目录中非字母数字字符替换为 `-`（如 `/a/b.c` -> `-a-b-c`）。
+    EncodedPath,
+    /// 工作目录绝对路径的 SHA-256 十六进制。
+    HashedPath,
+    /// 不按目录名定位，遍历全部项目目录并按 `directory_field` 过滤。
+    AnyProject,
+}
+
+/// CLI 本地会话存储：进程启动后据此发现会话 ID，用于重启时续接对话。
+/// 路径形如 `~/<root>/<项目目录>/<subdir>/<会话文件>.<extension>`。
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TerminalSessionStore {
+    /// 相对用户主目录的存储根目录。
+    pub(crate) root: &'static str,
+    pub(crate) project_key: SessionStoreProjectKey,
+    pub(crate) subdir: Option<&'static str>,
+    pub(crate) extension: &'static str,
+    /// 会话 ID 所在的 JSON 字段；为空时取文件名（去扩展名）。
+    pub(crate) id_field: Option<&'static str>,
+    /// 记录工作目录的 JSON 字段，`AnyProject` 时用于匹配当前工作目录。
+    pub(crate) directory_field: Option<&'static str>,
+}
+
+/// 审批提示：CLI 停下等待确认时屏幕尾部出现的提示文本，以及批准/拒绝对应的按键。
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TerminalApprovalPrompt {
+    /// 提示特征（小写子串），屏幕尾部任一行命中即视为等待审批。
+    pub(crate) patterns: &'static [&'static str],
+    pub(crate) approve_input: &'static str,
+    pub(crate) deny_input: &'static str,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct TerminalDefaultMemberConfig {
     pub(crate) id: &'static str,
@@ -41,9 +76,18 @@ pub(crate) struct TerminalDefaultMemberConfig {
     pub(crate) unlimited_access_flag: Option<&'static str>,
     /// 会话恢复命令模板，{session_id} 将被替换为实际 ID。
     pub(crate) resume_command_template: Option<&'static str>,
+    /// 本地会话存储；配置后启动时从磁盘发现会话 ID（无 /status 可解析的 CLI）。
+    pub(crate) session_store: Option<TerminalSessionStore>,
+    /// CLI 状态目录/文件（相对 HOME）；沙箱内读写绑定，保留登录态与会话恢复。
+    pub(crate) state_dirs: &'static [&'static str],
+    /// 过滤 profile 名称；为空时按 terminal_type 选择。
+    pub(crate) filter_profile: Option<&'static str>,
+    /// 审批提示识别；为空时不检测等待确认状态。
+    pub(crate) approval_prompt: Option<TerminalApprovalPrompt>,
     pub(crate) post_ready_plan: TerminalPostReadyPlan,
 }
 
+use super::plugins::plugin_members;
 use super::{
     claude::CLAUDE_DEFAULT_MEMBER, codex::CODEX_DEFAULT_MEMBER, gemini::GEMINI_DEFAULT_MEMBER,
     opencode::OPENCODE_DEFAULT_MEMBER, qwen::QWEN_DEFAULT_MEMBER, shell::SHELL_DEFAULT_MEMBER,
@@ -58,12 +102,14 @@ pub(crate) const DEFAULT_TERMINAL_MEMBERS: [TerminalDefaultMemberConfig; 6] = [
     SHELL_DEFAULT_MEMBER,
 ];
 
+/// 按终端类型查找成员配置：先内置成员，再启动时加载的插件成员类型。
 pub(crate) fn resolve_default_member(
     terminal_type: &str,
 ) -> Option<&'static TerminalDefaultMemberConfig> {
     let normalized = terminal_type.trim().to_lowercase();
     DEFAULT_TERMINAL_MEMBERS
         .iter()
+        .chain(plugin_members().iter())
         .find(|member| member.terminal_type == normalized)
 }
 
@@ -157,16 +203,21 @@ pub(crate) fn apply_resume_command(
     };
     // 替换 {session_id} 占位符
     let resume_args = template.replace("{session_id}", session_id);
+    // 占位符前的参数即恢复旗标（如 `--resume`），命令中已带该旗标时视为用户自行指定。
+    let resume_flag = template
+        .split_whitespace()
+        .take_while(|part| !part.contains("{session_id}"))
+        .last();
     let mut base = command
         .as_deref()
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(|value| value.to_string())
         .unwrap_or_else(|| member.default_command.to_string());
-    if base
-        .split_whitespace()
-        .any(|part| part.eq_ignore_ascii_case("resume"))
-        || base.contains(session_id)
+    if base.split_whitespace().any(|part| {
+        part.eq_ignore_ascii_case("resume")
+            || resume_flag.is_some_and(|flag| part.eq_ignore_ascii_case(flag))
+    }) || base.contains(session_id)
     {
         return Some(base);
     }
@@ -0,0 +1,116 @@
+//! CLI 本地会话存储发现：在成员工作目录对应的存储目录中找出进程启动后写入的会话文件。
+//! 边界：只读磁盘，不解析对话内容；多个同类型成员共用工作目录时通过排除已占用的 ID 区分。
+
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::registry::{SessionStoreProjectKey, TerminalSessionStore};
+
+/// 当前用户主目录；Windows 下回退到 `USERPROFILE`。
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 查找 `since` 之后写入的最新会话并返回其 ID；`exclude` 中的 ID 已被其他会话占用。
+pub(crate) fn discover_session_id(
+    store: &TerminalSessionStore,
+    home: &Path,
+    cwd: &Path,
+    since: SystemTime,
+    exclude: &HashSet<String>,
+) -> Option<String> {
+    let mut candidates: Vec<(SystemTime, PathBuf)> = project_dirs(store, home, cwd)
+        .into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(store.extension))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+            (modified >= since).then_some((modified, path))
+        })
+        .collect();
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.0));
+    candidates
+        .into_iter()
+        .filter_map(|(_, path)| session_id_from_file(store, &path, cwd))
+        .find(|id| !exclude.contains(id))
+}
+
+fn project_dirs(store: &TerminalSessionStore, home: &Path, cwd: &Path) -> Vec<PathBuf> {
+    let root = home.join(store.root);
+    let with_subdir = |dir: PathBuf| match store.subdir {
+        Some(subdir) => dir.join(subdir),
+        None => dir,
+    };
+    match store.project_key {
+        SessionStoreProjectKey::EncodedPath => vec![with_subdir(root.join(encode_project_path(cwd)))],
+        SessionStoreProjectKey::HashedPath => vec![with_subdir(root.join(hash_project_path(cwd)))],
+        SessionStoreProjectKey::AnyProject => fs::read_dir(&root)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .map(with_subdir)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn session_id_from_file(store: &TerminalSessionStore, path: &Path, cwd: &Path) -> Option<String> {
+    if store.id_field.is_none() && store.directory_field.is_none() {
+        return path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.to_string());
+    }
+    let contents = fs::read_to_string(path).ok()?;
+    let value: Value = serde_json::from_str(&contents).ok()?;
+    if let Some(field) = store.directory_field {
+        let directory = value.get(field).and_then(Value::as_str)?;
+        if Path::new(directory) != cwd {
+            return None;
+        }
+    }
+    match store.id_field {
+        Some(field) => value
+            .get(field)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| id.to_string()),
+        None => path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| stem.to_string()),
+    }
+}
+
+/// 工作目录编码为单层目录名：非 ASCII 字母数字字符一律替换为 `-`。
+pub(crate) fn encode_project_path(cwd: &Path) -> String {
+    cwd.to_string_lossy()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect()
+}
+
+pub(crate) fn hash_project_path(cwd: &Path) -> String {
+    let digest = Sha256::digest(cwd.to_string_lossy().as_bytes());
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod discovery_tests;
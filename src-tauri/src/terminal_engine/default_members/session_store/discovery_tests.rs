@@ -0,0 +1,119 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde_json::json;
+
+use super::{discover_session_id, encode_project_path, hash_project_path};
+use crate::terminal_engine::default_members::{apply_resume_command, resolve_default_member};
+
+struct TempHome(PathBuf);
+
+impl TempHome {
+    fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!(
+            "golutra-session-store-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("create temp home");
+        Self(path)
+    }
+
+    fn write(&self, relative: &str, contents: &str) {
+        let path = self.0.join(relative);
+        fs::create_dir_all(path.parent().expect("parent")).expect("create store dir");
+        fs::write(path, contents).expect("write session file");
+    }
+}
+
+impl Drop for TempHome {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn store_for(terminal_type: &str) -> super::TerminalSessionStore {
+    resolve_default_member(terminal_type)
+        .and_then(|member| member.session_store)
+        .expect("session store configured")
+}
+
+fn since() -> SystemTime {
+    SystemTime::now() - Duration::from_secs(60)
+}
+
+#[test]
+fn project_path_keys() {
+    assert_eq!(encode_project_path(Path::new("/work/my.app")), "-work-my-app");
+    assert_eq!(hash_project_path(Path::new("/work")).len(), 64);
+}
+
+#[test]
+fn claude_session_is_named_after_the_file() {
+    let home = TempHome::new("claude");
+    let cwd = Path::new("/work/app");
+    home.write(".claude/projects/-work-app/4f1c.jsonl", "{}\n");
+    home.write(".claude/projects/-work-other/9999.jsonl", "{}\n");
+    let store = store_for("claude");
+    assert_eq!(
+        discover_session_id(&store, &home.0, cwd, since(), &HashSet::new()).as_deref(),
+        Some("4f1c")
+    );
+    let claimed = HashSet::from(["4f1c".to_string()]);
+    assert_eq!(discover_session_id(&store, &home.0, cwd, since(), &claimed), None);
+    let future = SystemTime::now() + Duration::from_secs(60);
+    assert_eq!(discover_session_id(&store, &home.0, cwd, future, &HashSet::new()), None);
+}
+
+#[test]
+fn gemini_session_id_is_read_from_the_chat_file() {
+    let home = TempHome::new("gemini");
+    let cwd = Path::new("/work/app");
+    let chat = format!(".gemini/tmp/{}/chats/session-1.json", hash_project_path(cwd));
+    home.write(&chat, &json!({ "sessionId": "gem-1", "messages": [] }).to_string());
+    assert_eq!(
+        discover_session_id(&store_for("gemini"), &home.0, cwd, since(), &HashSet::new()).as_deref(),
+        Some("gem-1")
+    );
+}
+
+#[test]
+fn opencode_session_is_matched_by_directory() {
+    let home = TempHome::new("opencode");
+    let cwd = Path::new("/work/app");
+    let root = ".local/share/opencode/storage/session";
+    home.write(
+        &format!("{root}/proj-a/ses_a.json"),
+        &json!({ "id": "ses_a", "directory": "/work/other" }).to_string(),
+    );
+    home.write(
+        &format!("{root}/proj-b/ses_b.json"),
+        &json!({ "id": "ses_b", "directory": "/work/app" }).to_string(),
+    );
+    assert_eq!(
+        discover_session_id(&store_for("opencode"), &home.0, cwd, since(), &HashSet::new()).as_deref(),
+        Some("ses_b")
+    );
+}
+
+#[test]
+fn discovered_ids_resume_through_the_registry() {
+    assert_eq!(
+        apply_resume_command("claude", None, Some("4f1c")).as_deref(),
+        Some("claude --resume 4f1c")
+    );
+    assert_eq!(
+        apply_resume_command("opencode", Some("opencode".to_string()), Some("ses_b")).as_deref(),
+        Some("opencode --session ses_b")
+    );
+    // 用户已自行指定恢复旗标时保持原命令。
+    assert_eq!(
+        apply_resume_command("gemini", Some("gemini --resume latest".to_string()), Some("gem-1"))
+            .as_deref(),
+        Some("gemini --resume latest")
+    );
+}
@@ -1,6 +1,6 @@
 //! Shell 默认成员配置。
 
-use super::registry::{TerminalDefaultMemberConfig, TerminalPostReadyPlan};
+use super::registry::{TerminalApprovalPrompt, TerminalDefaultMemberConfig, TerminalPostReadyPlan};
 
 pub(crate) const SHELL_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDefaultMemberConfig {
     id: "terminal",
@@ -8,5 +8,14 @@ pub(crate) const SHELL_DEFAULT_MEMBER: TerminalDefaultMemberConfig = TerminalDef
     default_command: "",
     unlimited_access_flag: None,
     resume_command_template: None,
+    session_store: None,
+    state_dirs: &[],
+    filter_profile: None,
+    // 交互式命令的 y/n 确认（apt、rm -i、脚本 read 等）。
+    approval_prompt: Some(TerminalApprovalPrompt {
+        patterns: &["[y/n]", "(y/n)", "[yes/no]", "(yes/no)"],
+        approve_input: "y\r",
+        deny_input: "n\r",
+    }),
     post_ready_plan: TerminalPostReadyPlan::EMPTY,
 };
@@ -1,6 +1,11 @@
 //! 终端仿真与快照序列化：基于 wezterm_term 生成可回放的 ANSI 视图。
+//! 另提供轻量网格后端（`grid`），按使用场景在 `EmulatorConfig` 中选择。
 //! 边界：只负责渲染语义与快照，不触碰 PTY 读写与会话状态。
 
+mod grid;
+#[cfg(test)]
+mod conformance_tests;
+
 use std::io;
 use std::sync::Arc;
 
@@ -9,10 +14,19 @@ use wezterm_term::{
   Blink, CellAttributes, Intensity, Terminal, TerminalConfiguration, TerminalSize, Underline,
 };
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// 仿真后端选择。
+/// `Wezterm`：完整样式与 ANSI 快照，供会话 attach/回放；`Grid`：仅字符网格，供语义分析等只读文本的场景。
+pub(crate) enum EmulatorBackend {
+  Wezterm,
+  Grid,
+}
+
 #[derive(Clone, Copy, Debug)]
 /// 终端仿真配置。
 /// 约束：`scrollback_limit` 直接影响内存占用与快照大小。
 pub(crate) struct EmulatorConfig {
+  pub(crate) backend: EmulatorBackend,
   pub(crate) rows: u16,
   pub(crate) cols: u16,
   pub(crate) scrollback_limit: usize,
@@ -44,14 +58,30 @@ impl SnapshotMetrics {
   }
 }
 
+/// 绝对行区间内容：`start_row..end_row` 为实际返回的区间（已滚出回滚缓冲的部分被裁掉）。
+#[derive(Clone, Debug)]
+pub(crate) struct RowRangeSnapshot {
+  pub(crate) start_row: u64,
+  pub(crate) end_row: u64,
+  pub(crate) lines: Vec<String>,
+  pub(crate) ansi: Vec<u8>,
+}
+
 /// 终端仿真接口，供会话与语义分析共享。
 /// 约束：`cursor_position` 为 0 基坐标；`snapshot_ansi` 返回可直接回放的 ANSI。
+/// 绝对行号自仿真器创建起单调递增，行滚出回滚缓冲后编号不复用。
 pub(crate) trait TerminalEmulator: Send {
   fn apply_output(&mut self, bytes: &[u8]);
   fn set_size(&mut self, rows: u16, cols: u16);
   fn cursor_position(&self) -> (u16, u16);
+  /// 光标所在行的绝对行号。
+  fn absolute_cursor_row(&self) -> u64;
+  /// 读取绝对行区间 `[start_row, end_row)` 的纯文本与 ANSI；区间已完全不可用时返回 None。
+  fn rows_in_range(&self, start_row: u64, end_row: u64) -> Option<RowRangeSnapshot>;
   fn snapshot_lines(&self) -> Vec<String>;
   fn snapshot_ansi(&self) -> Vec<u8>;
+  /// 程序是否开启了 bracketed paste（DECSET 2004），决定多行输入能否整段粘贴。
+  fn bracketed_paste_enabled(&self) -> bool;
   fn snapshot_ansi_segments(&self) -> SnapshotSegments {
     SnapshotSegments {
       history: None,
@@ -130,19 +160,22 @@ impl AttrState {
   }
 }
 
-/// 创建默认模拟器实例。
+/// 按配置中的后端创建模拟器实例。
 /// 返回：实现 `TerminalEmulator` 的对象。
 pub(crate) fn create_emulator(config: EmulatorConfig) -> Box<dyn TerminalEmulator> {
   create_emulator_with_writer(config, None)
 }
 
 /// 创建可选带响应写入器的模拟器。
-/// 用途：在需要捕获终端查询响应时接收回写字节。
+/// 用途：在需要捕获终端查询响应时接收回写字节；`Grid` 后端不响应查询，忽略 writer。
 pub(crate) fn create_emulator_with_writer(
   config: EmulatorConfig,
   writer: Option<Box<dyn io::Write + Send>>,
 ) -> Box<dyn TerminalEmulator> {
-  Box::new(WeztermEmulator::new(config, writer))
+  match config.backend {
+    EmulatorBackend::Wezterm => Box::new(WeztermEmulator::new(config, writer)),
+    EmulatorBackend::Grid => Box::new(grid::GridEmulator::new(config)),
+  }
 }
 
 impl WeztermEmulator {
@@ -193,6 +226,40 @@ impl TerminalEmulator for WeztermEmulator {
     (row, col)
   }
 
+  fn absolute_cursor_row(&self) -> u64 {
+    let pos = self.terminal.cursor_pos();
+    self.terminal.screen().visible_row_to_stable_row(pos.y).max(0) as u64
+  }
+
+  fn rows_in_range(&self, start_row: u64, end_row: u64) -> Option<RowRangeSnapshot> {
+    let screen = self.terminal.screen();
+    let total_rows = screen.scrollback_rows();
+    if total_rows == 0 {
+      return None;
+    }
+    // 稳定行号即绝对行号：物理行 0 对应当前仍保留的最早一行。
+    let first_row = screen.phys_to_stable_row_index(0).max(0) as u64;
+    let start = start_row.max(first_row);
+    let end = end_row.min(first_row + total_rows as u64);
+    if start >= end {
+      return None;
+    }
+    let phys_start = (start - first_row) as usize;
+    let phys_end = (end - first_row) as usize;
+    let lines = screen.lines_in_phys_range(phys_start..phys_end);
+    let mut state = AttrState::default();
+    let ansi = serialize_lines_to_ansi(&lines, &mut state, &CellAttributes::blank(), false);
+    Some(RowRangeSnapshot {
+      start_row: start,
+      end_row: end,
+      lines: lines
+        .iter()
+        .map(|line| line.as_str().trim_end().to_string())
+        .collect(),
+      ansi: ansi.into_bytes(),
+    })
+  }
+
   fn snapshot_lines(&self) -> Vec<String> {
     let screen = self.terminal.screen();
     let visible_rows = screen.physical_rows.max(1);
@@ -209,6 +276,10 @@ impl TerminalEmulator for WeztermEmulator {
     lines
   }
 
+  fn bracketed_paste_enabled(&self) -> bool {
+    self.terminal.bracketed_paste_enabled()
+  }
+
   fn snapshot_ansi(&self) -> Vec<u8> {
     let pos = self.terminal.cursor_pos();
     let cursor_row = if pos.y <= 0 {
@@ -0,0 +1,311 @@
+//! 后端一致性测试：同一字节流分别喂给 wezterm 与 grid 后端，比较文本快照与光标位置。
+
+use super::{create_emulator, EmulatorBackend, EmulatorConfig, TerminalEmulator};
+
+const ROWS: u16 = 6;
+const COLS: u16 = 20;
+
+fn build(backend: EmulatorBackend, rows: u16, cols: u16) -> Box<dyn TerminalEmulator> {
+  create_emulator(EmulatorConfig {
+    backend,
+    rows,
+    cols,
+    scrollback_limit: 100,
+  })
+}
+
+fn assert_conformance_sized(name: &str, rows: u16, cols: u16, chunks: &[&[u8]]) {
+  let mut wezterm = build(EmulatorBackend::Wezterm, rows, cols);
+  let mut grid = build(EmulatorBackend::Grid, rows, cols);
+  for chunk in chunks {
+    wezterm.apply_output(chunk);
+    grid.apply_output(chunk);
+  }
+  assert_eq!(
+    grid.snapshot_lines(),
+    wezterm.snapshot_lines(),
+    "snapshot_lines mismatch: {name}"
+  );
+  assert_eq!(
+    grid.cursor_position(),
+    wezterm.cursor_position(),
+    "cursor_position mismatch: {name}"
+  );
+}
+
+fn assert_conformance(name: &str, chunks: &[&[u8]]) {
+  assert_conformance_sized(name, ROWS, COLS, chunks);
+}
+
+/// 调整尺寸场景的一步：输出字节或 `set_size(rows, cols)`。
+enum Step<'a> {
+  Output(&'a [u8]),
+  Resize(u16, u16),
+}
+
+fn assert_resize_conformance(name: &str, steps: &[Step<'_>]) {
+  let mut wezterm = build(EmulatorBackend::Wezterm, ROWS, COLS);
+  let mut grid = build(EmulatorBackend::Grid, ROWS, COLS);
+  for (index, step) in steps.iter().enumerate() {
+    match step {
+      Step::Output(bytes) => {
+        wezterm.apply_output(bytes);
+        grid.apply_output(bytes);
+      }
+      Step::Resize(rows, cols) => {
+        wezterm.set_size(*rows, *cols);
+        grid.set_size(*rows, *cols);
+      }
+    }
+    assert_eq!(
+      grid.snapshot_lines(),
+      wezterm.snapshot_lines(),
+      "snapshot_lines mismatch: {name} step {index}"
+    );
+    assert_eq!(
+      grid.cursor_position(),
+      wezterm.cursor_position(),
+      "cursor_position mismatch: {name} step {index}"
+    );
+  }
+}
+
+#[test]
+fn plain_text_and_newlines() {
+  assert_conformance("plain", &[b"hello world\r\nsecond line\r\n> "]);
+}
+
+#[test]
+fn bare_linefeed_keeps_column() {
+  assert_conformance("lf", &[b"abc\ndef\n"]);
+}
+
+#[test]
+fn carriage_return_overwrites() {
+  assert_conformance("cr", &[b"progress 10%\rprogress 99%\r\n"]);
+}
+
+#[test]
+fn backspace_and_tab() {
+  assert_conformance("bs-tab", &[b"abcd\x08\x08XY\r\n\tx\tyz\r\n"]);
+}
+
+#[test]
+fn autowrap_and_pending_wrap() {
+  assert_conformance("wrap-exact", &[b"01234567890123456789"]);
+  assert_conformance("wrap-next", &[b"01234567890123456789abc"]);
+  assert_conformance("wrap-cr", &[b"01234567890123456789\r\nx"]);
+}
+
+#[test]
+fn scrolls_past_bottom() {
+  assert_conformance(
+    "scroll",
+    &[b"l1\r\nl2\r\nl3\r\nl4\r\nl5\r\nl6\r\nl7\r\nl8\r\n> prompt"],
+  );
+}
+
+#[test]
+fn cursor_movement() {
+  assert_conformance(
+    "cup",
+    &[b"\x1b[3;5Hmid\x1b[1;1Htop\x1b[2Bdown\x1b[3Dleft\x1b[A\x1b[4Cright"],
+  );
+  assert_conformance("cha-vpa", &[b"\x1b[10Gx\x1b[4dy\x1b[Ez\x1b[Fw"]);
+  assert_conformance("clamp", &[b"\x1b[99;1Hend\x1b[99Aup\x1b[99Dleft"]);
+}
+
+#[test]
+fn erase_line_and_display() {
+  assert_conformance(
+    "el",
+    &[b"abcdefghij\x1b[5G\x1b[K\r\n0123456789\x1b[5G\x1b[1K\r\nqwerty\x1b[2K"],
+  );
+  assert_conformance(
+    "ed",
+    &[b"aaaa\r\nbbbb\r\ncccc\r\ndddd\x1b[2;3H\x1b[J"],
+  );
+  assert_conformance(
+    "ed1",
+    &[b"aaaa\r\nbbbb\r\ncccc\r\ndddd\x1b[2;3H\x1b[1J"],
+  );
+  assert_conformance("ed2", &[b"aaaa\r\nbbbb\x1b[2J\x1b[Hfresh"]);
+}
+
+#[test]
+fn insert_delete_chars() {
+  assert_conformance("dch", &[b"abcdefgh\x1b[3G\x1b[2P"]);
+  assert_conformance("ich", &[b"abcdefgh\x1b[3G\x1b[2@XY"]);
+  assert_conformance("ech", &[b"abcdefgh\x1b[3G\x1b[3X"]);
+}
+
+#[test]
+fn insert_delete_lines() {
+  assert_conformance("il", &[b"l1\r\nl2\r\nl3\r\nl4\x1b[2;1H\x1b[L"]);
+  assert_conformance("dl", &[b"l1\r\nl2\r\nl3\r\nl4\x1b[2;1H\x1b[2M"]);
+}
+
+#[test]
+fn scroll_region() {
+  assert_conformance(
+    "decstbm",
+    &[b"head\x1b[2;4r\x1b[2;1Ha\r\nb\r\nc\r\nd\r\ne\x1b[r\x1b[6;1Hfoot"],
+  );
+  assert_conformance(
+    "reverse-index",
+    &[b"l1\r\nl2\r\nl3\x1b[1;1H\x1bMnew"],
+  );
+  assert_conformance("su-sd", &[b"l1\r\nl2\r\nl3\x1b[2S\x1b[1T"]);
+}
+
+#[test]
+fn save_restore_cursor() {
+  assert_conformance("decsc", &[b"ab\x1b7\x1b[5;5Hxy\x1b8cd"]);
+  assert_conformance("scosc", &[b"ab\x1b[s\x1b[5;5Hxy\x1b[ucd"]);
+}
+
+#[test]
+fn alternate_screen() {
+  assert_conformance(
+    "alt-active",
+    &[b"shell$ run\r\n\x1b[?1049h\x1b[Hfull screen ui\x1b[3;3Hbox"],
+  );
+  assert_conformance(
+    "alt-restored",
+    &[b"shell$ run\r\n\x1b[?1049h\x1b[Hfull screen ui\x1b[?1049lafter"],
+  );
+}
+
+#[test]
+fn ignores_styles_and_strings() {
+  assert_conformance(
+    "sgr",
+    &[b"\x1b[1;31mred\x1b[0m \x1b[38;5;200mpink\x1b[m \x1b[38;2;1;2;3mrgb\x1b[0m"],
+  );
+  assert_conformance(
+    "osc",
+    &[b"\x1b]0;title\x07\x1b]633;A\x1b\\prompt\x1b]8;;http://x\x1b\\link\x1b]8;;\x1b\\"],
+  );
+  assert_conformance("private-modes", &[b"\x1b[?25l\x1b[?2004hok\x1b[?25h\x1b(Bdone"]);
+}
+
+#[test]
+fn utf8_and_wide_chars() {
+  assert_conformance("cjk", &["你好，世界\r\nok".as_bytes()]);
+  assert_conformance("cjk-wrap", &["一二三四五六七八九十壹".as_bytes()]);
+  // 多字节字符跨 chunk 拆分。
+  let bytes = "状态：完成".as_bytes();
+  assert_conformance("utf8-split", &[&bytes[..4], &bytes[4..8], &bytes[8..]]);
+}
+
+#[test]
+fn cli_prompt_redraw() {
+  // 模拟 CLI 底部输入框的反复重绘：移动到固定行、清行、重写。
+  assert_conformance_sized(
+    "redraw",
+    8,
+    30,
+    &[
+      b"Working on task...\r\n",
+      b"\x1b[7;1H\x1b[2K> typing",
+      b"\x1b[7;1H\x1b[2K> typing more\x1b[8;1H\x1b[2K  esc to interrupt",
+      b"\x1b[1;1H\x1b[2KDone.\x1b[7;15H",
+    ],
+  );
+}
+
+#[test]
+fn resize_grow_and_shrink_keeps_cursor_row_visible() {
+  assert_resize_conformance(
+    "grow",
+    &[Step::Output(b"one\r\ntwo\r\nthree"), Step::Resize(10, 30), Step::Output(b"\r\nfour")],
+  );
+  // 缩小行数时移走光标上方的行，光标所在行保持可见。
+  assert_resize_conformance(
+    "shrink rows",
+    &[
+      Step::Output(b"l1\r\nl2\r\nl3\r\nl4\r\nl5"),
+      Step::Resize(3, COLS),
+      Step::Output(b"\r\nl6"),
+    ],
+  );
+  // 光标在顶部时缩小行数直接截掉下方空行。
+  assert_resize_conformance(
+    "shrink below cursor",
+    &[Step::Output(b"top"), Step::Resize(2, COLS), Step::Output(b"!")],
+  );
+}
+
+#[test]
+fn resize_columns_reflows_soft_wrapped_lines() {
+  // 光标停在重排范围之外的提示行，避开 wezterm 重排后光标列可等于列宽的特例。
+  assert_resize_conformance(
+    "narrow then widen",
+    &[
+      Step::Output(b"short\r\n0123456789abcdef\r\n$ "),
+      Step::Resize(ROWS, 8),
+      Step::Resize(ROWS, COLS),
+      Step::Output(b"ls"),
+    ],
+  );
+  assert_resize_conformance(
+    "soft wrap widen",
+    &[Step::Output(b"0123456789abcdefghijKLMN\r\n$ "), Step::Resize(ROWS, 30)],
+  );
+  assert_resize_conformance(
+    "trailing blanks",
+    &[Step::Output(b"ab      \r\nxy\r\n$ "), Step::Resize(ROWS, 4)],
+  );
+  assert_resize_conformance(
+    "pending wrap",
+    &[Step::Output(b"01234567890123456789"), Step::Resize(ROWS, 10), Step::Output(b"!")],
+  );
+  assert_resize_conformance(
+    "cursor inside wrapped line",
+    &[Step::Output(b"0123456789abcdefghijKLMN\r\nnext\x1b[1;3H"), Step::Resize(ROWS, 30)],
+  );
+}
+
+#[test]
+fn resize_reflow_moves_overflow_into_scrollback() {
+  assert_resize_conformance(
+    "overflow rows",
+    &[
+      Step::Output(b"0123456789abcdefghij\r\n0123456789abcdefghij\r\n0123456789abcdefghij\r\nx"),
+      Step::Resize(ROWS, 5),
+      Step::Output(b"y"),
+    ],
+  );
+}
+
+#[test]
+fn resize_reflow_wraps_wide_chars_whole() {
+  assert_resize_conformance(
+    "wide narrow",
+    &[Step::Output("ab好好好\r\n$ ".as_bytes()), Step::Resize(ROWS, 3)],
+  );
+}
+
+#[test]
+fn resize_alt_screen_truncates_and_reflows_primary() {
+  assert_resize_conformance(
+    "alt screen",
+    &[
+      Step::Output(b"0123456789abcdef\r\n$ \x1b[?1049h\x1b[HALT0123456789\r\n>"),
+      Step::Resize(ROWS, 8),
+      Step::Output(b"\x1b[?1049l"),
+    ],
+  );
+}
+
+#[test]
+fn resize_columns_keeps_cursor_column() {
+  assert_resize_conformance(
+    "widen",
+    &[Step::Output(b"abc"), Step::Resize(ROWS, 40), Step::Output(b"\x1b[30Gx")],
+  );
+  assert_resize_conformance(
+    "noop",
+    &[Step::Output(b"same"), Step::Resize(ROWS, COLS), Step::Output(b"!")],
+  );
+}
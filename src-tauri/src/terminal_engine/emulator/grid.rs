@@ -0,0 +1,951 @@
+//! 轻量 vt100 网格仿真：只维护字符网格与光标，不记录颜色/样式，供语义分析使用。
+//! 约束：覆盖 CLI 常用的光标移动、擦除、滚动区域与备用屏；列宽变化时按软换行重排主屏，
+//! 回滚缓冲只保留纯文本，不参与重排；备用屏只截断或补齐。
+
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+
+use super::{EmulatorConfig, RowRangeSnapshot, TerminalEmulator};
+
+const TAB_WIDTH: usize = 8;
+const MAX_CSI_PARAMS: usize = 16;
+const REPLACEMENT_CHAR: char = '\u{fffd}';
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Cell {
+  Char(String),
+  // 宽字符占用的第二列，渲染时跳过。
+  WideTail,
+}
+
+impl Cell {
+  fn blank() -> Self {
+    Cell::Char(" ".to_string())
+  }
+}
+
+#[derive(Clone, Debug)]
+struct Row {
+  cells: Vec<Cell>,
+  // 末列写满后自动换行到下一行（软换行），resize 时与下一行合并为同一逻辑行重排。
+  wrapped: bool,
+}
+
+impl Deref for Row {
+  type Target = Vec<Cell>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.cells
+  }
+}
+
+impl DerefMut for Row {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.cells
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct SavedCursor {
+  row: usize,
+  col: usize,
+}
+
+#[derive(Debug)]
+enum ParseState {
+  Ground,
+  Escape,
+  // 字符集指定等 `ESC ( B` 形式：吞掉下一个字节。
+  EscapeCharset,
+  Csi,
+  Osc,
+  // DCS/SOS/PM/APC 字符串：直到 ST 结束。
+  Passthrough,
+}
+
+/// vt100 风格网格仿真器：无颜色、无回写，回滚只保留纯文本。
+pub(crate) struct GridEmulator {
+  rows: usize,
+  cols: usize,
+  grid: Vec<Row>,
+  cursor_row: usize,
+  cursor_col: usize,
+  // 末列写入后的延迟换行标记（DEC 语义），下一个可见字符才真正换行。
+  wrap_pending: bool,
+  saved_cursor: SavedCursor,
+  scroll_top: usize,
+  scroll_bottom: usize,
+  scrollback: VecDeque<String>,
+  scrollback_limit: usize,
+  // 已移出回滚缓冲的行数，用于换算绝对行号。
+  evicted_rows: u64,
+  // 进入备用屏时保存的主屏内容与光标。
+  primary: Option<(Vec<Row>, SavedCursor)>,
+  bracketed_paste: bool,
+  state: ParseState,
+  // ESC 出现在 OSC/DCS 字符串中，等待 `\` 组成 ST。
+  string_escape: bool,
+  csi_private: Option<u8>,
+  csi_intermediate: bool,
+  csi_params: Vec<u16>,
+  csi_current: Option<u16>,
+  utf8_buffer: Vec<u8>,
+  utf8_expected: usize,
+}
+
+impl GridEmulator {
+  pub(crate) fn new(config: EmulatorConfig) -> Self {
+    let rows = (config.rows as usize).max(1);
+    let cols = (config.cols as usize).max(1);
+    Self {
+      rows,
+      cols,
+      grid: blank_grid(rows, cols),
+      cursor_row: 0,
+      cursor_col: 0,
+      wrap_pending: false,
+      saved_cursor: SavedCursor::default(),
+      scroll_top: 0,
+      scroll_bottom: rows - 1,
+      scrollback: VecDeque::new(),
+      scrollback_limit: config.scrollback_limit,
+      evicted_rows: 0,
+      primary: None,
+      bracketed_paste: false,
+      state: ParseState::Ground,
+      string_escape: false,
+      csi_private: None,
+      csi_intermediate: false,
+      csi_params: Vec::new(),
+      csi_current: None,
+      utf8_buffer: Vec::new(),
+      utf8_expected: 0,
+    }
+  }
+
+  fn advance(&mut self, byte: u8) {
+    match self.state {
+      ParseState::Ground => self.advance_ground(byte),
+      ParseState::Escape => self.advance_escape(byte),
+      ParseState::EscapeCharset => self.state = ParseState::Ground,
+      ParseState::Csi => self.advance_csi(byte),
+      ParseState::Osc | ParseState::Passthrough => self.advance_string(byte),
+    }
+  }
+
+  fn advance_ground(&mut self, byte: u8) {
+    if byte >= 0x80 {
+      self.advance_utf8(byte);
+      return;
+    }
+    self.flush_utf8();
+    match byte {
+      0x1b => self.state = ParseState::Escape,
+      b'\r' => self.carriage_return(),
+      b'\n' | 0x0b | 0x0c => self.linefeed(),
+      0x08 => self.backspace(),
+      b'\t' => self.tab(),
+      0x20..=0x7e => self.print(char::from(byte).to_string(), 1),
+      _ => {}
+    }
+  }
+
+  fn advance_utf8(&mut self, byte: u8) {
+    if self.utf8_buffer.is_empty() {
+      self.utf8_expected = match byte {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => {
+          self.print(REPLACEMENT_CHAR.to_string(), 1);
+          return;
+        }
+      };
+    } else if byte & 0xc0 != 0x80 {
+      // 续字节缺失：丢弃残缺序列后按新首字节重新解析。
+      self.flush_utf8();
+      self.advance_utf8(byte);
+      return;
+    }
+    self.utf8_buffer.push(byte);
+    if self.utf8_buffer.len() < self.utf8_expected {
+      return;
+    }
+    let decoded = std::str::from_utf8(&self.utf8_buffer)
+      .ok()
+      .and_then(|text| text.chars().next())
+      .unwrap_or(REPLACEMENT_CHAR);
+    self.utf8_buffer.clear();
+    self.print_char(decoded);
+  }
+
+  fn flush_utf8(&mut self) {
+    if !self.utf8_buffer.is_empty() {
+      self.utf8_buffer.clear();
+      self.print(REPLACEMENT_CHAR.to_string(), 1);
+    }
+  }
+
+  fn advance_escape(&mut self, byte: u8) {
+    self.state = ParseState::Ground;
+    match byte {
+      b'[' => {
+        self.state = ParseState::Csi;
+        self.csi_private = None;
+        self.csi_intermediate = false;
+        self.csi_params.clear();
+        self.csi_current = None;
+      }
+      b']' => {
+        self.state = ParseState::Osc;
+        self.string_escape = false;
+      }
+      b'P' | b'X' | b'^' | b'_' => {
+        self.state = ParseState::Passthrough;
+        self.string_escape = false;
+      }
+      b'(' | b')' | b'*' | b'+' | b'#' | b'%' => self.state = ParseState::EscapeCharset,
+      b'7' => self.save_cursor(),
+      b'8' => self.restore_cursor(),
+      b'D' => self.linefeed(),
+      b'E' => {
+        self.carriage_return();
+        self.linefeed();
+      }
+      b'M' => self.reverse_index(),
+      b'c' => self.reset(),
+      0x1b => self.state = ParseState::Escape,
+      _ => {}
+    }
+  }
+
+  fn advance_string(&mut self, byte: u8) {
+    let is_osc = matches!(self.state, ParseState::Osc);
+    if self.string_escape {
+      self.string_escape = false;
+      if byte == b'\\' {
+        self.state = ParseState::Ground;
+        return;
+      }
+    }
+    match byte {
+      0x07 if is_osc => self.state = ParseState::Ground,
+      0x1b => self.string_escape = true,
+      _ => {}
+    }
+  }
+
+  fn advance_csi(&mut self, byte: u8) {
+    match byte {
+      b'0'..=b'9' => {
+        let digit = (byte - b'0') as u16;
+        let current = self.csi_current.unwrap_or(0);
+        self.csi_current = Some(current.saturating_mul(10).saturating_add(digit));
+      }
+      b';' | b':' => {
+        if self.csi_params.len() < MAX_CSI_PARAMS {
+          self.csi_params.push(self.csi_current.unwrap_or(0));
+        }
+        self.csi_current = None;
+      }
+      b'<'..=b'?' if self.csi_params.is_empty() && self.csi_current.is_none() => {
+        self.csi_private = Some(byte);
+      }
+      0x20..=0x2f => self.csi_intermediate = true,
+      0x40..=0x7e => {
+        if let Some(current) = self.csi_current.take() {
+          if self.csi_params.len() < MAX_CSI_PARAMS {
+            self.csi_params.push(current);
+          }
+        }
+        self.state = ParseState::Ground;
+        if !self.csi_intermediate {
+          self.dispatch_csi(byte);
+        }
+      }
+      0x1b => self.state = ParseState::Escape,
+      0x18 | 0x1a => self.state = ParseState::Ground,
+      // CSI 中夹带的 C0 控制字符按 ECMA-48 立即执行。
+      b'\r' => self.carriage_return(),
+      b'\n' | 0x0b | 0x0c => self.linefeed(),
+      0x08 => self.backspace(),
+      _ => {}
+    }
+  }
+
+  fn param(&self, index: usize, default: usize) -> usize {
+    match self.csi_params.get(index).copied() {
+      Some(0) | None => default,
+      Some(value) => value as usize,
+    }
+  }
+
+  fn raw_param(&self, index: usize) -> usize {
+    self.csi_params.get(index).copied().unwrap_or(0) as usize
+  }
+
+  fn dispatch_csi(&mut self, action: u8) {
+    if let Some(private) = self.csi_private {
+      if private == b'?' && matches!(action, b'h' | b'l') {
+        let enable = action == b'h';
+        for index in 0..self.csi_params.len() {
+          self.set_private_mode(self.raw_param(index), enable);
+        }
+      }
+      return;
+    }
+    let count = self.param(0, 1);
+    match action {
+      b'A' => self.cursor_up(count),
+      b'B' | b'e' => self.cursor_down(count),
+      b'C' | b'a' => self.set_cursor(self.cursor_row, self.cursor_col.saturating_add(count)),
+      b'D' => self.set_cursor(self.cursor_row, self.cursor_col.saturating_sub(count)),
+      b'E' => {
+        self.cursor_down(count);
+        self.cursor_col = 0;
+      }
+      b'F' => {
+        self.cursor_up(count);
+        self.cursor_col = 0;
+      }
+      b'G' | b'`' => self.set_cursor(self.cursor_row, count - 1),
+      b'd' => self.set_cursor(count - 1, self.cursor_col),
+      b'H' | b'f' => self.set_cursor(count - 1, self.param(1, 1) - 1),
+      b'J' => self.erase_display(self.raw_param(0)),
+      b'K' => self.erase_line(self.raw_param(0)),
+      b'L' => self.insert_lines(count),
+      b'M' => self.delete_lines(count),
+      b'@' => self.insert_chars(count),
+      b'P' => self.delete_chars(count),
+      b'X' => self.erase_chars(count),
+      b'S' => self.scroll_up(count),
+      b'T' => self.scroll_down(count),
+      b'r' => self.set_scroll_region(),
+      b's' => self.save_cursor(),
+      b'u' => self.restore_cursor(),
+      _ => {}
+    }
+  }
+
+  fn set_private_mode(&mut self, mode: usize, enable: bool) {
+    match mode {
+      1049 => {
+        if enable {
+          self.save_cursor();
+          self.enter_alt_screen();
+        } else {
+          self.leave_alt_screen();
+          self.restore_cursor();
+        }
+      }
+      47 | 1047 => {
+        if enable {
+          self.enter_alt_screen();
+        } else {
+          self.leave_alt_screen();
+        }
+      }
+      2004 => self.bracketed_paste = enable,
+      _ => {}
+    }
+  }
+
+  fn enter_alt_screen(&mut self) {
+    if self.primary.is_some() {
+      return;
+    }
+    let cursor = SavedCursor {
+      row: self.cursor_row,
+      col: self.cursor_col,
+    };
+    let primary = std::mem::replace(&mut self.grid, blank_grid(self.rows, self.cols));
+    self.primary = Some((primary, cursor));
+    self.wrap_pending = false;
+  }
+
+  fn leave_alt_screen(&mut self) {
+    let Some((primary, cursor)) = self.primary.take() else {
+      return;
+    };
+    self.grid = primary;
+    self.set_cursor(cursor.row, cursor.col);
+  }
+
+  fn reset(&mut self) {
+    self.grid = blank_grid(self.rows, self.cols);
+    self.primary = None;
+    self.scroll_top = 0;
+    self.scroll_bottom = self.rows - 1;
+    self.saved_cursor = SavedCursor::default();
+    self.set_cursor(0, 0);
+  }
+
+  fn print_char(&mut self, ch: char) {
+    let width = char_width(ch);
+    if width == 0 {
+      self.append_combining(ch);
+      return;
+    }
+    self.print(ch.to_string(), width);
+  }
+
+  fn print(&mut self, text: String, width: usize) {
+    if self.wrap_pending || (width > 1 && self.cursor_col + width > self.cols) {
+      self.grid[self.cursor_row].wrapped = true;
+      self.carriage_return();
+      self.linefeed();
+    }
+    let width = width.min(self.cols);
+    let row = self.cursor_row;
+    let col = self.cursor_col;
+    self.clear_wide_overlap(row, col, width);
+    self.grid[row][col] = Cell::Char(text);
+    for offset in 1..width {
+      self.grid[row][col + offset] = Cell::WideTail;
+    }
+    if col + width >= self.cols {
+      self.cursor_col = self.cols - 1;
+      self.wrap_pending = true;
+    } else {
+      self.cursor_col = col + width;
+    }
+  }
+
+  fn append_combining(&mut self, ch: char) {
+    let row = self.cursor_row;
+    let mut col = if self.wrap_pending {
+      self.cursor_col
+    } else if self.cursor_col > 0 {
+      self.cursor_col - 1
+    } else {
+      return;
+    };
+    while col > 0 && self.grid[row][col] == Cell::WideTail {
+      col -= 1;
+    }
+    if let Cell::Char(text) = &mut self.grid[row][col] {
+      text.push(ch);
+    }
+  }
+
+  // 覆盖宽字符的任一半时清掉另一半，避免残留半个字符。
+  fn clear_wide_overlap(&mut self, row: usize, col: usize, width: usize) {
+    if col > 0 && self.grid[row][col] == Cell::WideTail {
+      self.grid[row][col - 1] = Cell::blank();
+    }
+    let end = col + width;
+    if end < self.cols && self.grid[row][end] == Cell::WideTail {
+      self.grid[row][end] = Cell::blank();
+    }
+  }
+
+  fn carriage_return(&mut self) {
+    self.cursor_col = 0;
+    self.wrap_pending = false;
+  }
+
+  fn linefeed(&mut self) {
+    self.wrap_pending = false;
+    if self.cursor_row == self.scroll_bottom {
+      self.scroll_up(1);
+    } else if self.cursor_row + 1 < self.rows {
+      self.cursor_row += 1;
+    }
+  }
+
+  fn reverse_index(&mut self) {
+    self.wrap_pending = false;
+    if self.cursor_row == self.scroll_top {
+      self.scroll_down(1);
+    } else if self.cursor_row > 0 {
+      self.cursor_row -= 1;
+    }
+  }
+
+  fn backspace(&mut self) {
+    if self.wrap_pending {
+      self.wrap_pending = false;
+    } else if self.cursor_col > 0 {
+      self.cursor_col -= 1;
+    }
+  }
+
+  fn tab(&mut self) {
+    let next = (self.cursor_col / TAB_WIDTH + 1) * TAB_WIDTH;
+    self.cursor_col = next.min(self.cols - 1);
+  }
+
+  fn set_cursor(&mut self, row: usize, col: usize) {
+    self.cursor_row = row.min(self.rows - 1);
+    self.cursor_col = col.min(self.cols - 1);
+    self.wrap_pending = false;
+  }
+
+  fn cursor_up(&mut self, count: usize) {
+    let floor = if self.cursor_row >= self.scroll_top {
+      self.scroll_top
+    } else {
+      0
+    };
+    let row = self.cursor_row.saturating_sub(count).max(floor);
+    self.set_cursor(row, self.cursor_col);
+  }
+
+  fn cursor_down(&mut self, count: usize) {
+    let ceiling = if self.cursor_row <= self.scroll_bottom {
+      self.scroll_bottom
+    } else {
+      self.rows - 1
+    };
+    let row = self.cursor_row.saturating_add(count).min(ceiling);
+    self.set_cursor(row, self.cursor_col);
+  }
+
+  fn save_cursor(&mut self) {
+    self.saved_cursor = SavedCursor {
+      row: self.cursor_row,
+      col: self.cursor_col,
+    };
+  }
+
+  fn restore_cursor(&mut self) {
+    let saved = self.saved_cursor;
+    self.set_cursor(saved.row, saved.col);
+  }
+
+  fn set_scroll_region(&mut self) {
+    let top = self.param(0, 1) - 1;
+    let bottom = self.param(1, self.rows).min(self.rows) - 1;
+    if top < bottom {
+      self.scroll_top = top;
+      self.scroll_bottom = bottom;
+    } else {
+      self.scroll_top = 0;
+      self.scroll_bottom = self.rows - 1;
+    }
+    self.set_cursor(0, 0);
+  }
+
+  fn scroll_up(&mut self, count: usize) {
+    let count = count.min(self.scroll_bottom - self.scroll_top + 1);
+    for _ in 0..count {
+      let removed = self.grid.remove(self.scroll_top);
+      // 只有整屏滚动且位于主屏时才进入回滚，与常见终端一致。
+      if self.scroll_top == 0 && self.primary.is_none() {
+        self.push_scrollback(&removed);
+      }
+      self.grid.insert(self.scroll_bottom, blank_row(self.cols));
+    }
+  }
+
+  fn scroll_down(&mut self, count: usize) {
+    let count = count.min(self.scroll_bottom - self.scroll_top + 1);
+    for _ in 0..count {
+      self.grid.remove(self.scroll_bottom);
+      self.grid.insert(self.scroll_top, blank_row(self.cols));
+    }
+  }
+
+  fn push_scrollback(&mut self, row: &Row) {
+    if self.scrollback_limit == 0 {
+      self.evicted_rows += 1;
+      return;
+    }
+    self.scrollback.push_back(row_text(row));
+    while self.scrollback.len() > self.scrollback_limit {
+      self.scrollback.pop_front();
+      self.evicted_rows += 1;
+    }
+  }
+
+  fn insert_lines(&mut self, count: usize) {
+    if self.cursor_row < self.scroll_top || self.cursor_row > self.scroll_bottom {
+      return;
+    }
+    let count = count.min(self.scroll_bottom - self.cursor_row + 1);
+    for _ in 0..count {
+      self.grid.remove(self.scroll_bottom);
+      self.grid.insert(self.cursor_row, blank_row(self.cols));
+    }
+    self.carriage_return();
+  }
+
+  fn delete_lines(&mut self, count: usize) {
+    if self.cursor_row < self.scroll_top || self.cursor_row > self.scroll_bottom {
+      return;
+    }
+    let count = count.min(self.scroll_bottom - self.cursor_row + 1);
+    for _ in 0..count {
+      self.grid.remove(self.cursor_row);
+      self.grid.insert(self.scroll_bottom, blank_row(self.cols));
+    }
+    self.carriage_return();
+  }
+
+  fn insert_chars(&mut self, count: usize) {
+    let row = &mut self.grid[self.cursor_row];
+    let col = self.cursor_col;
+    let count = count.min(self.cols - col);
+    for _ in 0..count {
+      row.pop();
+      row.insert(col, Cell::blank());
+    }
+    self.wrap_pending = false;
+  }
+
+  fn delete_chars(&mut self, count: usize) {
+    let row = &mut self.grid[self.cursor_row];
+    let col = self.cursor_col;
+    let count = count.min(self.cols - col);
+    for _ in 0..count {
+      row.remove(col);
+      row.push(Cell::blank());
+    }
+    self.wrap_pending = false;
+  }
+
+  fn erase_chars(&mut self, count: usize) {
+    let end = self.cursor_col.saturating_add(count).min(self.cols);
+    self.clear_cells(self.cursor_row, self.cursor_col, end);
+    self.wrap_pending = false;
+  }
+
+  fn erase_line(&mut self, mode: usize) {
+    let row = self.cursor_row;
+    match mode {
+      0 => self.clear_cells(row, self.cursor_col, self.cols),
+      1 => self.clear_cells(row, 0, self.cursor_col + 1),
+      2 => self.clear_cells(row, 0, self.cols),
+      _ => {}
+    }
+  }
+
+  fn erase_display(&mut self, mode: usize) {
+    match mode {
+      0 => {
+        self.clear_cells(self.cursor_row, self.cursor_col, self.cols);
+        for row in self.cursor_row + 1..self.rows {
+          self.grid[row] = blank_row(self.cols);
+        }
+      }
+      1 => {
+        for row in 0..self.cursor_row {
+          self.grid[row] = blank_row(self.cols);
+        }
+        self.clear_cells(self.cursor_row, 0, self.cursor_col + 1);
+      }
+      2 => self.grid = blank_grid(self.rows, self.cols),
+      3 => {
+        self.grid = blank_grid(self.rows, self.cols);
+        self.evicted_rows += self.scrollback.len() as u64;
+        self.scrollback.clear();
+      }
+      _ => {}
+    }
+  }
+
+  fn clear_cells(&mut self, row: usize, start: usize, end: usize) {
+    let end = end.min(self.cols);
+    if start >= end {
+      return;
+    }
+    if start > 0 && self.grid[row][start] == Cell::WideTail {
+      self.grid[row][start - 1] = Cell::blank();
+    }
+    if end < self.cols && self.grid[row][end] == Cell::WideTail {
+      self.grid[row][end] = Cell::blank();
+    }
+    for cell in &mut self.grid[row][start..end] {
+      *cell = Cell::blank();
+    }
+    // 擦到行尾后该行不再延续到下一行。
+    if end == self.cols {
+      self.grid[row].wrapped = false;
+    }
+  }
+
+  fn resize_grid(grid: &mut [Row], cols: usize) {
+    for row in grid.iter_mut() {
+      // 截断点正好切开宽字符时清掉残留的头部，避免越界渲染。
+      if row.len() > cols && row[cols] == Cell::WideTail {
+        row[cols - 1] = Cell::blank();
+      }
+      row.resize(cols, Cell::blank());
+      row.wrapped = false;
+    }
+  }
+
+  // 调整当前屏行数：缩小时优先移走光标上方的行，保证光标所在行仍可见。
+  fn resize_rows(&mut self, rows: usize, cols: usize) {
+    if rows < self.rows {
+      let overflow = (self.cursor_row + 1).saturating_sub(rows);
+      for _ in 0..overflow {
+        let removed = self.grid.remove(0);
+        if self.primary.is_none() {
+          self.push_scrollback(&removed);
+        }
+      }
+      self.grid.truncate(rows);
+      self.cursor_row -= overflow;
+    } else {
+      self.grid.resize(rows, blank_row(cols));
+    }
+  }
+
+  // 按新尺寸重排主屏：行数超出时先丢弃光标下方的空行，再把顶部行移入回滚缓冲。
+  fn reflow_screen(
+    &mut self,
+    grid: &[Row],
+    cursor: (usize, usize),
+    rows: usize,
+    cols: usize,
+  ) -> (Vec<Row>, usize, usize) {
+    let (mut grid, mut cursor_row, cursor_col) = reflow_rows(grid, cursor, cols);
+    while grid.len() > rows
+      && grid.len() - 1 > cursor_row
+      && grid.last().map(is_blank_row).unwrap_or(false)
+    {
+      grid.pop();
+    }
+    while grid.len() > rows {
+      let removed = grid.remove(0);
+      self.push_scrollback(&removed);
+      cursor_row -= 1;
+    }
+    grid.resize(rows, blank_row(cols));
+    (grid, cursor_row, cursor_col)
+  }
+}
+
+impl TerminalEmulator for GridEmulator {
+  fn apply_output(&mut self, bytes: &[u8]) {
+    for &byte in bytes {
+      self.advance(byte);
+    }
+  }
+
+  fn set_size(&mut self, rows: u16, cols: u16) {
+    let rows = (rows as usize).max(1);
+    let cols = (cols as usize).max(1);
+    if rows == self.rows && cols == self.cols {
+      return;
+    }
+    let reflow = cols != self.cols;
+    if let Some((mut primary, saved)) = self.primary.take() {
+      // 备用屏期间主屏内容随之重排，备用屏本身只截断或补齐。
+      let saved = if reflow {
+        let (grid, row, col) = self.reflow_screen(&primary, (saved.row, saved.col), rows, cols);
+        primary = grid;
+        // 1049 进入时 DECSC 保存的也是主屏光标，离开时据此恢复，需要同步换算。
+        if self.saved_cursor.row == saved.row && self.saved_cursor.col == saved.col {
+          self.saved_cursor = SavedCursor { row, col };
+        }
+        SavedCursor { row, col }
+      } else {
+        primary.resize(rows, blank_row(cols));
+        saved
+      };
+      self.primary = Some((primary, saved));
+      Self::resize_grid(&mut self.grid, cols);
+      self.resize_rows(rows, cols);
+    } else if reflow {
+      let grid = std::mem::take(&mut self.grid);
+      let (grid, row, col) =
+        self.reflow_screen(&grid, (self.cursor_row, self.cursor_col), rows, cols);
+      self.grid = grid;
+      self.cursor_row = row;
+      self.cursor_col = col;
+    } else {
+      self.resize_rows(rows, cols);
+    }
+    self.rows = rows;
+    self.cols = cols;
+    self.scroll_top = 0;
+    self.scroll_bottom = rows - 1;
+    self.set_cursor(self.cursor_row, self.cursor_col);
+  }
+
+  fn cursor_position(&self) -> (u16, u16) {
+    let row = self.cursor_row.min(u16::MAX as usize) as u16;
+    let col = self.cursor_col.min(u16::MAX as usize) as u16;
+    (row, col)
+  }
+
+  fn absolute_cursor_row(&self) -> u64 {
+    self.evicted_rows + self.scrollback.len() as u64 + self.cursor_row as u64
+  }
+
+  fn rows_in_range(&self, start_row: u64, end_row: u64) -> Option<RowRangeSnapshot> {
+    let first_row = self.evicted_rows;
+    let total_rows = (self.scrollback.len() + self.grid.len()) as u64;
+    let start = start_row.max(first_row);
+    let end = end_row.min(first_row + total_rows);
+    if start >= end {
+      return None;
+    }
+    let lines: Vec<String> = self
+      .scrollback
+      .iter()
+      .cloned()
+      .chain(self.grid.iter().map(row_text))
+      .skip((start - first_row) as usize)
+      .take((end - start) as usize)
+      .collect();
+    // 网格后端不记录样式，ANSI 形式即按行拼接的纯文本。
+    let ansi = lines.join("\r\n").into_bytes();
+    Some(RowRangeSnapshot {
+      start_row: start,
+      end_row: end,
+      lines,
+      ansi,
+    })
+  }
+
+  fn snapshot_lines(&self) -> Vec<String> {
+    self.grid.iter().map(row_text).collect()
+  }
+
+  fn bracketed_paste_enabled(&self) -> bool {
+    self.bracketed_paste
+  }
+
+  fn snapshot_ansi(&self) -> Vec<u8> {
+    let mut output = String::new();
+    let lines: Vec<String> = self
+      .scrollback
+      .iter()
+      .cloned()
+      .chain(self.grid.iter().map(row_text))
+      .collect();
+    let end = lines
+      .iter()
+      .rposition(|line| !line.is_empty())
+      .map(|index| index + 1)
+      .unwrap_or(0);
+    output.push_str(&lines[..end].join("\r\n"));
+    let (row, col) = self.cursor_position();
+    output.push_str(&format!("\x1b[{};{}H", row as u32 + 1, col as u32 + 1));
+    output.into_bytes()
+  }
+}
+
+fn blank_row(cols: usize) -> Row {
+  Row {
+    cells: vec![Cell::blank(); cols],
+    wrapped: false,
+  }
+}
+
+fn is_blank_row(row: &Row) -> bool {
+  !row.wrapped && row.iter().all(|cell| *cell == Cell::blank())
+}
+
+// 按新列宽重排：软换行连接的物理行合并为逻辑行，去掉行尾空白后按打印规则重新折行
+// （宽字符放不下时整体换到下一行）。光标按其在逻辑行内的偏移定位，光标所在逻辑行保留到光标处。
+fn reflow_rows(grid: &[Row], cursor: (usize, usize), cols: usize) -> (Vec<Row>, usize, usize) {
+  let mut output: Vec<Row> = Vec::new();
+  let mut new_cursor = (0, 0);
+  let mut index = 0;
+  while index < grid.len() {
+    let mut cells: Vec<Cell> = Vec::new();
+    let mut cursor_offset = None;
+    loop {
+      let row = &grid[index];
+      if index == cursor.0 {
+        cursor_offset = Some(cells.len() + cursor.1);
+      }
+      cells.extend(row.iter().cloned());
+      index += 1;
+      if !row.wrapped || index >= grid.len() {
+        break;
+      }
+    }
+    while cells.last() == Some(&Cell::blank()) {
+      cells.pop();
+    }
+    if let Some(offset) = cursor_offset {
+      if cells.len() < offset {
+        cells.resize(offset, Cell::blank());
+      }
+    }
+    output.push(blank_row(cols));
+    let mut col = 0;
+    for (offset, cell) in cells.iter().enumerate() {
+      if *cell == Cell::WideTail {
+        if cursor_offset == Some(offset) {
+          new_cursor = (output.len() - 1, col.min(cols - 1));
+        }
+        continue;
+      }
+      let wide = cells.get(offset + 1) == Some(&Cell::WideTail);
+      let width = if wide { 2 } else { 1 }.min(cols);
+      if col + width > cols {
+        if let Some(row) = output.last_mut() {
+          row.wrapped = true;
+        }
+        output.push(blank_row(cols));
+        col = 0;
+      }
+      let row_index = output.len() - 1;
+      if cursor_offset == Some(offset) {
+        new_cursor = (row_index, col);
+      }
+      output[row_index][col] = cell.clone();
+      for tail in 1..width {
+        output[row_index][col + tail] = Cell::WideTail;
+      }
+      col += width;
+    }
+    if cursor_offset == Some(cells.len()) {
+      new_cursor = (output.len() - 1, col.min(cols - 1));
+    }
+  }
+  (output, new_cursor.0, new_cursor.1)
+}
+
+fn blank_grid(rows: usize, cols: usize) -> Vec<Row> {
+  vec![blank_row(cols); rows]
+}
+
+fn row_text(row: &Row) -> String {
+  let mut text = String::new();
+  for cell in row.iter() {
+    if let Cell::Char(value) = cell {
+      text.push_str(value);
+    }
+  }
+  text.trim_end().to_string()
+}
+
+// 近似的 East Asian Width：覆盖 CJK、全角、韩文与常见 emoji，组合字符宽度为 0。
+fn char_width(ch: char) -> usize {
+  let code = ch as u32;
+  if matches!(
+    code,
+    0x0300..=0x036f | 0x1ab0..=0x1aff | 0x1dc0..=0x1dff | 0x200b..=0x200f | 0x20d0..=0x20ff
+      | 0xfe00..=0xfe0f | 0xfe20..=0xfe2f
+  ) {
+    return 0;
+  }
+  if matches!(
+    code,
+    0x1100..=0x115f
+      | 0x2e80..=0x303e
+      | 0x3041..=0x33ff
+      | 0x3400..=0x4dbf
+      | 0x4e00..=0x9fff
+      | 0xa000..=0xa4cf
+      | 0xac00..=0xd7a3
+      | 0xf900..=0xfaff
+      | 0xfe30..=0xfe4f
+      | 0xff00..=0xff60
+      | 0xffe0..=0xffe6
+      | 0x1f300..=0x1f64f
+      | 0x1f900..=0x1f9ff
+      | 0x20000..=0x2fffd
+      | 0x30000..=0x3fffd
+  ) {
+    return 2;
+  }
+  1
+}
@@ -7,6 +7,7 @@ pub(crate) enum TerminalErrorCode {
   ShellBinaryNotFound,
   ShellLaunchFailed,
   TerminalLaunchFailed,
+  SandboxUnavailable,
 }
 
 impl TerminalErrorCode {
@@ -15,6 +16,7 @@ impl TerminalErrorCode {
       TerminalErrorCode::ShellBinaryNotFound => "terminal.shell.binary_not_found",
       TerminalErrorCode::ShellLaunchFailed => "terminal.shell.launch_failed",
       TerminalErrorCode::TerminalLaunchFailed => "terminal.launch_failed",
+      TerminalErrorCode::SandboxUnavailable => "terminal.sandbox.unavailable",
     }
   }
 }
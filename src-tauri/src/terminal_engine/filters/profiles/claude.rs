@@ -0,0 +1,28 @@
+//! Claude Code 过滤：输入回显为 `> 输入`，回复以 `⏺` 开头，工具调用为 `⏺ Name(args)` + `⎿` 输出。
+//! 边界：回复区之后是框线输入框（旧版 `╭─╮` 或新版 `───` 分隔线）。
+
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::rules::sanitize::is_box_chrome_line;
+use super::super::rules::transcript::{extract_transcript_reply, TranscriptStyle};
+use super::super::types::FilterContext;
+
+const SPINNER_GLYPHS: [char; 6] = ['·', '✢', '✳', '✶', '✻', '✽'];
+
+const STYLE: TranscriptStyle = TranscriptStyle {
+  input_prefixes: &[">"],
+  reply_markers: &["⏺", "●"],
+  tool_prefixes: &["⎿"],
+  is_boundary: is_box_chrome_line,
+  is_noise,
+  stream_segments: 2,
+};
+
+pub(crate) fn apply_snapshot(context: &FilterContext<'_>, lines: &[String]) -> PromptBlockResult {
+  extract_transcript_reply(lines, context.last_input_lines, &STYLE, context.mode)
+}
+
+// `✻ Pondering… (3s · esc to interrupt)` 等 spinner 行。
+fn is_noise(line: &str) -> bool {
+  let trimmed = line.trim_start();
+  trimmed.starts_with(SPINNER_GLYPHS) && trimmed.contains("esc to interrupt")
+}
@@ -0,0 +1,21 @@
+//! Codex 过滤：`›` 提示符 + `•` bullet 回复，沿用提示符分段规则。
+//! 差异：Working spinner 同样以 `•` 开头，流式阶段会被当成最新回复，需先剔除。
+
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::types::FilterContext;
+use super::generic;
+
+pub(crate) fn apply_snapshot(context: &FilterContext<'_>, lines: &[String]) -> PromptBlockResult {
+  let lines: Vec<String> = lines
+    .iter()
+    .filter(|line| !is_status_line(line))
+    .cloned()
+    .collect();
+  generic::apply_snapshot(context, &lines)
+}
+
+// `• Working (12s • esc to interrupt)` 等运行状态行。
+fn is_status_line(line: &str) -> bool {
+  let trimmed = line.trim_start();
+  trimmed.starts_with('•') && trimmed.contains("esc to interrupt")
+}
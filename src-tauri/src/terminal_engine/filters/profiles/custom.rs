@@ -0,0 +1,14 @@
+//! 声明式规则过滤：行分类来自配置，提取流程与内置对话记录类 profile 一致。
+
+use super::super::rules::declarative::DeclarativeRules;
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::rules::transcript::extract_transcript_reply;
+use super::super::types::FilterContext;
+
+pub(crate) fn apply_snapshot(
+  rules: &DeclarativeRules,
+  context: &FilterContext<'_>,
+  lines: &[String],
+) -> PromptBlockResult {
+  extract_transcript_reply(lines, context.last_input_lines, rules, context.mode)
+}
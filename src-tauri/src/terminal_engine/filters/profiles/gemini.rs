@@ -0,0 +1,35 @@
+//! Gemini CLI 过滤：输入回显为 `> 输入`，回复以 `✦` 开头，工具调用渲染为框线卡片。
+//! 边界：底部输入框的占位提示行（`> Type your message`）。
+
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::rules::sanitize::strip_box_border;
+use super::super::rules::transcript::{extract_transcript_reply, TranscriptStyle};
+use super::super::types::FilterContext;
+
+const SPINNER_GLYPHS: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+pub(super) const STYLE: TranscriptStyle = TranscriptStyle {
+  input_prefixes: &[">"],
+  reply_markers: &["✦"],
+  tool_prefixes: &[],
+  is_boundary: is_input_placeholder,
+  is_noise,
+  stream_segments: 2,
+};
+
+pub(crate) fn apply_snapshot(context: &FilterContext<'_>, lines: &[String]) -> PromptBlockResult {
+  extract_transcript_reply(lines, context.last_input_lines, &STYLE, context.mode)
+}
+
+// 输入框占位行：`│ >   Type your message or @path/to/file │`。
+fn is_input_placeholder(line: &str) -> bool {
+  let normalized = strip_box_border(line);
+  let trimmed = normalized.trim_start();
+  trimmed.starts_with('>') && trimmed.contains("Type your message")
+}
+
+// `⠋ Thinking... (esc to cancel, 3s)` 等 spinner 行。
+fn is_noise(line: &str) -> bool {
+  let trimmed = line.trim_start();
+  trimmed.starts_with(SPINNER_GLYPHS) && trimmed.contains("esc to cancel")
+}
@@ -0,0 +1,117 @@
+//! 黄金文件测试：以真实 CLI 屏幕快照为输入，逐行比对各 profile 的提取结果。
+//! 夹具格式：`# key: value` 头部（profile/mode/input/decision/reason，input 可多行），
+//! 之后是 `--- screen` 与 `--- expected` 两段；expected 仅在 allow 时出现。
+//! profile 为 custom 时另需 `--- rules` 段，内容为全局设置中单个终端类型的声明式规则 JSON。
+
+use super::apply_snapshot;
+use super::super::registry::resolve_profile;
+use super::super::rules::declarative::DeclarativeRules;
+use super::super::types::{FilterContext, FilterMode, FilterSource, TerminalFilterProfile};
+
+struct Golden {
+  profile: String,
+  mode: FilterMode,
+  input: Vec<String>,
+  decision: String,
+  reason: String,
+  screen: Vec<String>,
+  expected: Vec<String>,
+  rules: Vec<String>,
+}
+
+fn parse_golden(name: &str, text: &str) -> Golden {
+  let mut golden = Golden {
+    profile: String::new(),
+    mode: FilterMode::Final,
+    input: Vec::new(),
+    decision: String::new(),
+    reason: String::new(),
+    screen: Vec::new(),
+    expected: Vec::new(),
+    rules: Vec::new(),
+  };
+  let mut section = None;
+  for line in text.lines() {
+    if let Some(name) = line.strip_prefix("--- ") {
+      section = Some(name.trim().to_string());
+      continue;
+    }
+    match section.as_deref() {
+      None => {
+        let Some((key, value)) = line.strip_prefix("# ").and_then(|rest| rest.split_once(':'))
+        else {
+          continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+          "profile" => golden.profile = value,
+          "mode" => {
+            golden.mode = match value.as_str() {
+              "stream" => FilterMode::Stream,
+              _ => FilterMode::Final,
+            }
+          }
+          "input" => golden.input.push(value),
+          "decision" => golden.decision = value,
+          "reason" => golden.reason = value,
+          other => panic!("{name}: unknown header {other}"),
+        }
+      }
+      Some("screen") => golden.screen.push(line.to_string()),
+      Some("expected") => golden.expected.push(line.to_string()),
+      Some("rules") => golden.rules.push(line.to_string()),
+      Some(other) => panic!("{name}: unknown section {other}"),
+    }
+  }
+  golden
+}
+
+fn run_golden(name: &str, text: &str) {
+  let golden = parse_golden(name, text);
+  let custom = (!golden.rules.is_empty()).then(|| {
+    let value = serde_json::from_str(&golden.rules.join("\n")).expect("rules JSON");
+    DeclarativeRules::from_value(&value).expect("compile rules")
+  });
+  let profile = if custom.is_some() {
+    TerminalFilterProfile::Custom
+  } else {
+    resolve_profile(&golden.profile)
+  };
+  assert_eq!(profile.as_str(), golden.profile, "{name}: profile");
+  let context = FilterContext {
+    terminal_id: "golden",
+    terminal_type: &golden.profile,
+    last_command: None,
+    last_input_lines: Some(&golden.input),
+    now_ms: 0,
+    source: FilterSource::Snapshot,
+    mode: golden.mode,
+  };
+  let result = apply_snapshot(profile, custom.as_ref(), &context, &golden.screen);
+  assert_eq!(result.decision.as_str(), golden.decision, "{name}: decision");
+  assert_eq!(result.reason, golden.reason, "{name}: reason");
+  if golden.decision == "allow" {
+    assert_eq!(result.lines.unwrap_or_default(), golden.expected, "{name}: lines");
+  }
+}
+
+macro_rules! golden_test {
+  ($test:ident, $file:literal) => {
+    #[test]
+    fn $test() {
+      run_golden($file, include_str!(concat!("golden/", $file)));
+    }
+  };
+}
+
+golden_test!(codex_final_keeps_last_bullet, "codex_final.txt");
+golden_test!(codex_stream_skips_working_status, "codex_stream_working.txt");
+golden_test!(claude_final_skips_tool_calls, "claude_tool_calls.txt");
+golden_test!(claude_stream_keeps_recent_replies, "claude_stream_spinner.txt");
+golden_test!(claude_spinner_only_drops, "claude_spinner_only.txt");
+golden_test!(gemini_final_skips_tool_card, "gemini_tool_card.txt");
+golden_test!(gemini_stale_echo_defers, "gemini_input_unmatched.txt");
+golden_test!(qwen_stream_keeps_recent_replies, "qwen_stream.txt");
+golden_test!(opencode_final_extracts_plain_reply, "opencode_final.txt");
+golden_test!(custom_rules_strip_status_lines, "custom_rules.txt");
+golden_test!(custom_rules_without_markers, "custom_rules_plain.txt");
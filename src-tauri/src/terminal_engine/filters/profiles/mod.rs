@@ -1,13 +1,65 @@
 //! Profile 入口：按 CLI 类型选择规则集合。
 
+pub(crate) mod claude;
+pub(crate) mod codex;
+pub(crate) mod custom;
+pub(crate) mod gemini;
 pub(crate) mod generic;
+pub(crate) mod opencode;
+pub(crate) mod qwen;
 
-use super::types::{FilterContext, FilterResult, TerminalFilterProfile};
+#[cfg(test)]
+mod golden_tests;
 
+use super::rules::declarative::DeclarativeRules;
+use super::rules::prompt_block::PromptBlockResult;
+use super::types::{
+  FilterContext, FilterDecision, FilterResult, FilterSource, TerminalFilterProfile,
+};
+
+/// `custom` 仅在 profile 为 Custom 时使用；缺失时回退通用规则。
 pub(crate) fn apply_snapshot(
   profile: TerminalFilterProfile,
+  custom: Option<&DeclarativeRules>,
   context: &FilterContext<'_>,
   lines: &[String],
 ) -> FilterResult {
-  generic::apply_snapshot(profile, context, lines)
+  if context.source == FilterSource::CommandOutput {
+    return FilterResult::allow_with_lines(profile, "command_output", lines.to_vec());
+  }
+  let result = match profile {
+    TerminalFilterProfile::Codex => codex::apply_snapshot(context, lines),
+    TerminalFilterProfile::Claude => claude::apply_snapshot(context, lines),
+    TerminalFilterProfile::Gemini => gemini::apply_snapshot(context, lines),
+    TerminalFilterProfile::Qwen => qwen::apply_snapshot(context, lines),
+    TerminalFilterProfile::Opencode => opencode::apply_snapshot(context, lines),
+    TerminalFilterProfile::Custom => match custom {
+      Some(rules) => custom::apply_snapshot(rules, context, lines),
+      None => generic::apply_snapshot(context, lines),
+    },
+    TerminalFilterProfile::Generic | TerminalFilterProfile::Shell => {
+      generic::apply_snapshot(context, lines)
+    }
+  };
+  into_filter_result(profile, result, lines)
+}
+
+// 规则结果统一转为过滤结果：Allow 未给出行时沿用原始行。
+fn into_filter_result(
+  profile: TerminalFilterProfile,
+  result: PromptBlockResult,
+  lines: &[String],
+) -> FilterResult {
+  match result.decision {
+    FilterDecision::Allow => {
+      let lines = result.lines.unwrap_or_else(|| lines.to_vec());
+      FilterResult::allow_with_lines(profile, result.reason, lines)
+    }
+    decision => FilterResult {
+      decision,
+      reason: result.reason,
+      profile,
+      lines: None,
+    },
+  }
 }
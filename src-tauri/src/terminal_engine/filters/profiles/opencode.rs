@@ -0,0 +1,33 @@
+//! OpenCode 过滤：用户消息渲染为左侧 `┃` 竖条块，回复为无标记的正文，工具调用行以 `⚙`/`✓` 等开头。
+//! 边界：下一个 `┃` 竖条块（下一轮输入或底部输入框）或框线行。
+
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::rules::sanitize::is_box_chrome_line;
+use super::super::rules::transcript::{extract_transcript_reply, TranscriptStyle};
+use super::super::types::FilterContext;
+
+const GUTTER: char = '┃';
+
+/// OpenCode 回复没有段落标记，流式与 final 都取回显之后的整块正文。
+const STYLE: TranscriptStyle = TranscriptStyle {
+  input_prefixes: &["┃"],
+  reply_markers: &[],
+  tool_prefixes: &["⚙", "✓", "✗", "|"],
+  is_boundary,
+  is_noise,
+  stream_segments: 1,
+};
+
+pub(crate) fn apply_snapshot(context: &FilterContext<'_>, lines: &[String]) -> PromptBlockResult {
+  extract_transcript_reply(lines, context.last_input_lines, &STYLE, context.mode)
+}
+
+fn is_boundary(line: &str) -> bool {
+  line.trim_start().starts_with(GUTTER) || is_box_chrome_line(line)
+}
+
+// 回复尾部的 `opencode (model) 10:42` 署名行与 `Working…` 状态行。
+fn is_noise(line: &str) -> bool {
+  let trimmed = line.trim();
+  trimmed.starts_with("opencode (") || trimmed.contains("esc interrupt")
+}
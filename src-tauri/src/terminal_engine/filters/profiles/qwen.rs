@@ -0,0 +1,11 @@
+//! Qwen Code 过滤：界面派生自 Gemini CLI，回显、`✦` 回复、工具卡片与输入框占位一致，直接复用其样式。
+//! spinner 文案随机（如 “Reticulating splines…”），但同样带 `esc to cancel` 提示，噪声规则无需改动。
+
+use super::super::rules::prompt_block::PromptBlockResult;
+use super::super::rules::transcript::extract_transcript_reply;
+use super::super::types::FilterContext;
+use super::gemini;
+
+pub(crate) fn apply_snapshot(context: &FilterContext<'_>, lines: &[String]) -> PromptBlockResult {
+  extract_transcript_reply(lines, context.last_input_lines, &gemini::STYLE, context.mode)
+}
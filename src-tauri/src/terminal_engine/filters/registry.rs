@@ -3,12 +3,19 @@
 use super::types::TerminalFilterProfile;
 
 pub(crate) fn resolve_profile(terminal_type: &str) -> TerminalFilterProfile {
-  match terminal_type.trim().to_lowercase().as_str() {
-    // [TODO/core, 2026-01-26] Codex 暂无独立规则，仍复用通用规则集；保留 profile 以便后续分支。
-    "codex" => TerminalFilterProfile::Codex,
-    "gemini" => TerminalFilterProfile::Gemini,
-    "claude" => TerminalFilterProfile::Claude,
-    "shell" => TerminalFilterProfile::Shell,
-    _ => TerminalFilterProfile::Generic,
+  parse_profile(terminal_type).unwrap_or(TerminalFilterProfile::Generic)
+}
+
+/// 按名称解析内置 profile；供成员类型定义显式选择过滤规则，未知名称返回 None。
+pub(crate) fn parse_profile(name: &str) -> Option<TerminalFilterProfile> {
+  match name.trim().to_lowercase().as_str() {
+    "codex" => Some(TerminalFilterProfile::Codex),
+    "gemini" => Some(TerminalFilterProfile::Gemini),
+    "claude" => Some(TerminalFilterProfile::Claude),
+    "opencode" => Some(TerminalFilterProfile::Opencode),
+    "qwen" => Some(TerminalFilterProfile::Qwen),
+    "shell" => Some(TerminalFilterProfile::Shell),
+    "generic" => Some(TerminalFilterProfile::Generic),
+    _ => None,
   }
 }
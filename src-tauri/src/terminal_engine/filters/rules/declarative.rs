@@ -0,0 +1,123 @@
+//! 声明式过滤规则：从全局设置 `terminalFilterRules.<terminal_type>` 加载，供自研 CLI 免发版接入。
+//! 约束：规则只描述行分类（提示符、回复标记、噪声、边界），提取流程复用 `transcript`。
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::sanitize::strip_box_border;
+use super::transcript::TranscriptMatcher;
+
+const DEFAULT_STREAM_BULLETS: usize = 2;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StoredFilterRules {
+  prompt_pattern: String,
+  #[serde(default)]
+  bullet_markers: Vec<String>,
+  #[serde(default)]
+  tool_markers: Vec<String>,
+  #[serde(default)]
+  strip_patterns: Vec<String>,
+  #[serde(default)]
+  boundary_patterns: Vec<String>,
+  stream_bullets: Option<usize>,
+}
+
+/// 编译后的声明式规则。
+/// - `promptPattern`：匹配输入回显行；有捕获组时取第 1 组为回显文本，否则取匹配之后的剩余文本。
+///   同一模式命中的后续行（下一轮提示符/输入框）同时视为回复区边界。
+/// - `bulletMarkers`：回复段首标记；为空时回显之后整块都是回复。
+/// - `toolMarkers`：工具输出行前缀，命中即丢弃。
+/// - `stripPatterns`：噪声行（spinner、状态栏、token 计数），任何位置命中即丢弃。
+/// - `boundaryPatterns`：额外的回复区结束边界。
+/// - `streamBullets`：流式阶段保留最近几段回复，默认 2。
+pub(crate) struct DeclarativeRules {
+  prompt: Regex,
+  bullet_markers: Vec<String>,
+  tool_markers: Vec<String>,
+  strip: Vec<Regex>,
+  boundaries: Vec<Regex>,
+  stream_bullets: usize,
+}
+
+impl DeclarativeRules {
+  /// 解析并编译规则；字段缺失、未知字段或正则非法时返回错误，由调用方回退内置规则。
+  pub(crate) fn from_value(value: &Value) -> Result<Self, String> {
+    let stored: StoredFilterRules = serde_json::from_value(value.clone())
+      .map_err(|err| format!("failed to parse filter rules: {err}"))?;
+    Ok(Self {
+      prompt: compile_pattern(&stored.prompt_pattern)?,
+      bullet_markers: non_empty(stored.bullet_markers),
+      tool_markers: non_empty(stored.tool_markers),
+      strip: compile_patterns(&stored.strip_patterns)?,
+      boundaries: compile_patterns(&stored.boundary_patterns)?,
+      stream_bullets: stored.stream_bullets.unwrap_or(DEFAULT_STREAM_BULLETS).max(1),
+    })
+  }
+}
+
+impl TranscriptMatcher for DeclarativeRules {
+  fn input_echo(&self, line: &str) -> Option<String> {
+    let normalized = strip_box_border(line);
+    let captures = self.prompt.captures(&normalized)?;
+    let text = match captures.get(1) {
+      Some(group) => group.as_str(),
+      None => &normalized[captures.get(0)?.end()..],
+    };
+    Some(text.trim().to_string())
+  }
+
+  fn reply_start(&self, line: &str) -> Option<String> {
+    let normalized = strip_box_border(line);
+    let trimmed = normalized.trim_start();
+    self
+      .bullet_markers
+      .iter()
+      .find_map(|marker| trimmed.strip_prefix(marker.as_str()))
+      .map(|rest| rest.trim_start().to_string())
+  }
+
+  fn has_reply_markers(&self) -> bool {
+    !self.bullet_markers.is_empty()
+  }
+
+  fn is_tool_line(&self, line: &str) -> bool {
+    let normalized = strip_box_border(line);
+    let trimmed = normalized.trim_start();
+    self
+      .tool_markers
+      .iter()
+      .any(|marker| trimmed.starts_with(marker.as_str()))
+  }
+
+  fn is_boundary(&self, line: &str) -> bool {
+    self.prompt.is_match(&strip_box_border(line))
+      || self.boundaries.iter().any(|pattern| pattern.is_match(line))
+  }
+
+  fn is_noise(&self, line: &str) -> bool {
+    self.strip.iter().any(|pattern| pattern.is_match(line))
+  }
+
+  fn stream_segments(&self) -> usize {
+    self.stream_bullets
+  }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+  Regex::new(pattern).map_err(|err| format!("invalid filter pattern {pattern:?}: {err}"))
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>, String> {
+  patterns.iter().map(|pattern| compile_pattern(pattern)).collect()
+}
+
+fn non_empty(values: Vec<String>) -> Vec<String> {
+  values
+    .into_iter()
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+    .collect()
+}
@@ -1,3 +1,6 @@
 //! 过滤规则集合：保持纯函数，便于 profile 组合。
 
+pub(crate) mod declarative;
 pub(crate) mod prompt_block;
+pub(crate) mod sanitize;
+pub(crate) mod transcript;
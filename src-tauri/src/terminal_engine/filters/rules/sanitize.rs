@@ -0,0 +1,150 @@
+//! 文本清洗规则：剥离控制序列、框线 UI 与多余空行。
+//! 约束：纯函数，不依赖会话状态；快照与消息流水线共用同一套规则。
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+
+/// 剥离全部 ESC 序列（CSI/OSC/DCS 等）与 C0/C1 控制字符，仅保留 `\t` 与 `\n`。
+/// `\r\n` 会折叠为 `\n`，孤立的 `\r` 视为换行。
+pub(crate) fn strip_control_sequences(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut chars = text.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      ESC => skip_escape_sequence(&mut chars),
+      '\r' => {
+        if chars.peek() != Some(&'\n') {
+          output.push('\n');
+        }
+      }
+      '\n' | '\t' => output.push(ch),
+      _ if is_control_char(ch) => {}
+      _ => output.push(ch),
+    }
+  }
+  output
+}
+
+/// 仅剥离不参与渲染的字符串序列（OSC/DCS/SOS/PM/APC）与游离控制字符。
+/// CSI（SGR/光标定位）、`\r`、`\n`、`\t` 原样保留，适用于需要重放的 ANSI 快照。
+pub(crate) fn strip_non_rendering_sequences(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut chars = text.chars().peekable();
+  while let Some(ch) = chars.next() {
+    match ch {
+      ESC => match chars.peek().copied() {
+        Some('[') => {
+          output.push(ESC);
+          output.push('[');
+          chars.next();
+          for next in chars.by_ref() {
+            output.push(next);
+            if ('\u{40}'..='\u{7e}').contains(&next) {
+              break;
+            }
+          }
+        }
+        _ => skip_escape_sequence(&mut chars),
+      },
+      '\r' | '\n' | '\t' => output.push(ch),
+      _ if is_control_char(ch) => {}
+      _ => output.push(ch),
+    }
+  }
+  output
+}
+
+/// 判断整行是否只由框线/块元素与空白组成（如 TUI 边框、分隔条）。
+pub(crate) fn is_box_chrome_line(line: &str) -> bool {
+  let mut has_box = false;
+  for ch in line.chars() {
+    if is_box_drawing_char(ch) {
+      has_box = true;
+      continue;
+    }
+    if !ch.is_whitespace() {
+      return false;
+    }
+  }
+  has_box
+}
+
+/// 去掉左右成对的竖向边框（`│ text │`），单侧竖线（如目录树）保持原样。
+pub(crate) fn strip_box_border(line: &str) -> String {
+  let trimmed = line.trim();
+  let mut chars = trimmed.chars();
+  let (Some(first), Some(last)) = (chars.next(), chars.next_back()) else {
+    return line.to_string();
+  };
+  if !is_vertical_border(first) || !is_vertical_border(last) {
+    return line.to_string();
+  }
+  chars.as_str().trim().to_string()
+}
+
+/// 统一清洗行列表：去框线行与边框、去行尾空白、合并连续空行并去掉首尾空行。
+pub(crate) fn clean_lines(lines: &[String]) -> Vec<String> {
+  let cleaned: Vec<String> = lines
+    .iter()
+    .filter(|line| !is_box_chrome_line(line))
+    .map(|line| strip_box_border(line).trim_end().to_string())
+    .collect();
+  collapse_blank_runs(cleaned)
+}
+
+/// 连续空行只保留一行，并去掉首尾空行。
+pub(crate) fn collapse_blank_runs(lines: Vec<String>) -> Vec<String> {
+  let mut output: Vec<String> = Vec::with_capacity(lines.len());
+  let mut previous_blank = true;
+  for line in lines {
+    let is_blank = line.trim().is_empty();
+    if is_blank && previous_blank {
+      continue;
+    }
+    previous_blank = is_blank;
+    output.push(if is_blank { String::new() } else { line });
+  }
+  while output.last().map(|line| line.is_empty()).unwrap_or(false) {
+    output.pop();
+  }
+  output
+}
+
+fn skip_escape_sequence(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+  match chars.next() {
+    // CSI：参数/中间字节直到终止字节 0x40-0x7E。
+    Some('[') => {
+      for next in chars.by_ref() {
+        if ('\u{40}'..='\u{7e}').contains(&next) {
+          break;
+        }
+      }
+    }
+    // OSC/DCS/SOS/PM/APC：直到 BEL 或 ST（ESC \）。
+    Some(']') | Some('P') | Some('X') | Some('^') | Some('_') => {
+      let terminator = chars.by_ref().find(|next| *next == BEL || *next == ESC);
+      if terminator == Some(ESC) && chars.peek() == Some(&'\\') {
+        chars.next();
+      }
+    }
+    // 字符集选择等带一个中间字节的序列（如 `ESC ( B`）。
+    Some('(') | Some(')') | Some('*') | Some('+') | Some('#') | Some('%') => {
+      chars.next();
+    }
+    // 其余双字节序列（`ESC =`、`ESC 7` 等）到此结束。
+    _ => {}
+  }
+}
+
+fn is_control_char(ch: char) -> bool {
+  let code = ch as u32;
+  code < 0x20 || code == 0x7f || (0x80..=0x9f).contains(&code)
+}
+
+fn is_box_drawing_char(ch: char) -> bool {
+  matches!(ch as u32, 0x2500..=0x259f)
+}
+
+fn is_vertical_border(ch: char) -> bool {
+  matches!(ch, '│' | '┃' | '║' | '▌' | '▐')
+}
@@ -0,0 +1,259 @@
+//! 基于输入回显定位的对话记录提取规则：适配以“回显行 + 回复标记”渲染对话的 CLI。
+//! 与 `prompt_block` 的区别：不依赖 `›` 提示符分段，回复边界由各 CLI 的输入框/界面行决定。
+
+use super::super::types::{FilterDecision, FilterMode};
+use super::prompt_block::PromptBlockResult;
+use super::sanitize::{is_box_chrome_line, strip_box_border};
+
+/// CLI 对话渲染样式：由 profile 提供，规则本身不感知具体 CLI。
+pub(crate) struct TranscriptStyle {
+  /// 输入回显行前缀（去掉成对边框后匹配），其后的文本需与输入一致。
+  pub(crate) input_prefixes: &'static [&'static str],
+  /// 回复段落起始标记；为空表示回显之后整块都是回复。
+  pub(crate) reply_markers: &'static [&'static str],
+  /// 工具输出行前缀：命中的行直接丢弃。
+  pub(crate) tool_prefixes: &'static [&'static str],
+  /// 回复区结束边界（输入框、下一轮输入等）。
+  pub(crate) is_boundary: fn(&str) -> bool,
+  /// 噪声行（spinner、状态提示），在任何位置都丢弃。
+  pub(crate) is_noise: fn(&str) -> bool,
+  /// 流式阶段保留最近几段回复；final 阶段只取最后一段。
+  pub(crate) stream_segments: usize,
+}
+
+/// 对话记录的行分类接口：内置样式与配置加载的声明式规则共用同一套提取流程。
+pub(crate) trait TranscriptMatcher {
+  /// 回显行去掉提示符后的文本；不是回显行时返回 None。
+  fn input_echo(&self, line: &str) -> Option<String>;
+  /// 回复段首行去掉标记后的文本；不是段首时返回 None。
+  fn reply_start(&self, line: &str) -> Option<String>;
+  /// 是否配置了回复标记；未配置时回显之后整块视为一段回复。
+  fn has_reply_markers(&self) -> bool;
+  fn is_tool_line(&self, line: &str) -> bool;
+  fn is_boundary(&self, line: &str) -> bool;
+  fn is_noise(&self, line: &str) -> bool;
+  /// 流式阶段保留的回复段数。
+  fn stream_segments(&self) -> usize;
+}
+
+impl TranscriptMatcher for TranscriptStyle {
+  fn input_echo(&self, line: &str) -> Option<String> {
+    let normalized = strip_box_border(line);
+    let trimmed = normalized.trim_start();
+    self
+      .input_prefixes
+      .iter()
+      .find_map(|prefix| trimmed.strip_prefix(prefix))
+      .map(|rest| rest.trim().to_string())
+  }
+
+  fn reply_start(&self, line: &str) -> Option<String> {
+    let normalized = strip_box_border(line);
+    let trimmed = normalized.trim_start();
+    self
+      .reply_markers
+      .iter()
+      .find_map(|marker| trimmed.strip_prefix(marker))
+      .map(|rest| rest.trim_start().to_string())
+  }
+
+  fn has_reply_markers(&self) -> bool {
+    !self.reply_markers.is_empty()
+  }
+
+  fn is_tool_line(&self, line: &str) -> bool {
+    let normalized = strip_box_border(line);
+    let trimmed = normalized.trim_start();
+    self
+      .tool_prefixes
+      .iter()
+      .any(|prefix| trimmed.starts_with(prefix))
+  }
+
+  fn is_boundary(&self, line: &str) -> bool {
+    (self.is_boundary)(line)
+  }
+
+  fn is_noise(&self, line: &str) -> bool {
+    (self.is_noise)(line)
+  }
+
+  fn stream_segments(&self) -> usize {
+    self.stream_segments
+  }
+}
+
+struct Segment {
+  lines: Vec<String>,
+  tool: bool,
+}
+
+/// 规则：定位最近一次输入回显，截取其后到边界之前的回复区，保留最近 N 段非工具回复
+/// （流式阶段 N 由样式决定，final 阶段为 1）。
+/// - 输入未知或找不到回显时返回 Defer，等待屏幕稳定。
+/// - 回复区内没有可用段落（只有工具调用或噪声）时返回 Drop。
+/// - 段首的回复标记会被去掉；框线内容（如工具卡片）截断当前段落直到下一个标记。
+pub(crate) fn extract_transcript_reply(
+  lines: &[String],
+  input_lines: Option<&[String]>,
+  style: &impl TranscriptMatcher,
+  mode: FilterMode,
+) -> PromptBlockResult {
+  let Some(input_lines) = input_lines.filter(|lines| !lines.is_empty()) else {
+    return defer("transcript-input-missing");
+  };
+  let Some(echo_end) = find_input_echo(lines, input_lines, style) else {
+    return defer("transcript-input-unmatched");
+  };
+  let mut block_start = echo_end + 1;
+  // 回显块内的附加行（如时间、发送者）与回显共用前缀，一并跳过。
+  while block_start < lines.len() && style.input_echo(&lines[block_start]).is_some() {
+    block_start += 1;
+  }
+  let block_end = lines[block_start..]
+    .iter()
+    .position(|line| style.is_boundary(line))
+    .map(|offset| block_start + offset)
+    .unwrap_or(lines.len());
+  let segments = split_segments(&lines[block_start..block_end], style);
+  let replies: Vec<&Segment> = segments
+    .iter()
+    .filter(|segment| !segment.tool && segment.lines.iter().any(|line| !line.trim().is_empty()))
+    .collect();
+  if replies.is_empty() {
+    return PromptBlockResult {
+      decision: FilterDecision::Drop,
+      reason: "transcript-no-reply",
+      lines: None,
+    };
+  }
+  let max_segments = match mode {
+    FilterMode::Stream => style.stream_segments(),
+    FilterMode::Final => 1,
+  };
+  let start = replies.len().saturating_sub(max_segments.max(1));
+  let mut extracted = Vec::new();
+  for segment in &replies[start..] {
+    extracted.extend(segment.lines.iter().cloned());
+  }
+  PromptBlockResult {
+    decision: FilterDecision::Allow,
+    reason: "transcript-reply",
+    lines: Some(extracted),
+  }
+}
+
+fn defer(reason: &'static str) -> PromptBlockResult {
+  PromptBlockResult {
+    decision: FilterDecision::Defer,
+    reason,
+    lines: None,
+  }
+}
+
+// 从尾部往前找最近一次完整回显，避免旧输入匹配到当前输出；返回回显最后一行的下标。
+fn find_input_echo(
+  lines: &[String],
+  input_lines: &[String],
+  style: &impl TranscriptMatcher,
+) -> Option<usize> {
+  let first = input_lines[0].trim();
+  for start in (0..lines.len()).rev() {
+    if style.input_echo(&lines[start]).as_deref() != Some(first) {
+      continue;
+    }
+    if let Some(end) = match_input_continuation(lines, start, &input_lines[1..], style) {
+      return Some(end);
+    }
+  }
+  None
+}
+
+// 多行输入的后续行可能带回显前缀或缩进，忽略空行后逐行比对。
+fn match_input_continuation(
+  lines: &[String],
+  start: usize,
+  rest: &[String],
+  style: &impl TranscriptMatcher,
+) -> Option<usize> {
+  let mut end = start;
+  let mut expected = rest.iter();
+  let mut next = expected.next();
+  while let Some(wanted) = next {
+    end += 1;
+    let line = lines.get(end)?;
+    let text = style
+      .input_echo(line)
+      .unwrap_or_else(|| strip_box_border(line).trim().to_string());
+    if text.is_empty() {
+      continue;
+    }
+    if text != wanted.trim() {
+      return None;
+    }
+    next = expected.next();
+  }
+  Some(end)
+}
+
+fn split_segments(block: &[String], style: &impl TranscriptMatcher) -> Vec<Segment> {
+  let mut segments: Vec<Segment> = Vec::new();
+  if !style.has_reply_markers() {
+    segments.push(Segment {
+      lines: Vec::new(),
+      tool: false,
+    });
+  }
+  // 框线截断当前段落：其后的卡片内容不属于回复，直到下一个回复标记。
+  let mut closed = false;
+  for line in block {
+    if style.is_noise(line) {
+      continue;
+    }
+    if is_box_chrome_line(line) {
+      closed = style.has_reply_markers();
+      continue;
+    }
+    if style.is_tool_line(line) {
+      continue;
+    }
+    if let Some(text) = style.reply_start(line) {
+      closed = false;
+      segments.push(Segment {
+        tool: is_tool_call(&text),
+        lines: vec![text],
+      });
+      continue;
+    }
+    if closed {
+      continue;
+    }
+    if let Some(segment) = segments.last_mut() {
+      segment.lines.push(strip_box_border(line).trim_end().to_string());
+    }
+  }
+  for segment in segments.iter_mut() {
+    while segment.lines.last().map(|line| line.trim().is_empty()).unwrap_or(false) {
+      segment.lines.pop();
+    }
+    let leading = segment
+      .lines
+      .iter()
+      .take_while(|line| line.trim().is_empty())
+      .count();
+    segment.lines.drain(..leading);
+  }
+  segments
+}
+
+// 工具调用段首形如 `Bash(ls -la)` / `Update(src/main.rs)`：名称后紧跟括号参数。
+fn is_tool_call(text: &str) -> bool {
+  let Some(open) = text.find('(') else {
+    return false;
+  };
+  let name = &text[..open];
+  !name.is_empty()
+    && name.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == ' ')
+    && name.starts_with(|ch: char| ch.is_ascii_uppercase())
+    && text.trim_end().ends_with(')')
+}
@@ -1,18 +1,40 @@
-//! 会话级过滤运行时：保存 profile，便于后续扩展流式状态。
+//! 会话级过滤运行时：保存 profile 与配置加载的声明式规则，便于后续扩展流式状态。
 
 use super::profiles;
-use super::registry::resolve_profile;
+use super::registry::{parse_profile, resolve_profile};
+use super::rules::declarative::DeclarativeRules;
 use super::types::{FilterContext, FilterResult, TerminalFilterProfile};
+use crate::ports::settings::TerminalSettingsPort;
+use crate::terminal_engine::default_members::resolve_default_member;
 
 pub(crate) struct FilterRuntime {
   profile: TerminalFilterProfile,
+  custom: Option<DeclarativeRules>,
 }
 
 impl FilterRuntime {
-  pub(crate) fn new(terminal_type: &str) -> Self {
-    Self {
-      profile: resolve_profile(terminal_type),
-    }
+  /// 全局设置中为该终端类型配置了声明式规则时优先使用（含覆盖内置 CLI），
+  /// 规则非法则记录告警并回退；插件成员类型按其声明的 `filterProfile` 选择内置 profile。
+  pub(crate) fn new(terminal_type: &str, settings: Option<&dyn TerminalSettingsPort>) -> Self {
+    let key = terminal_type.trim().to_lowercase();
+    let custom = settings
+      .and_then(|service| service.get_terminal_filter_rules(&key))
+      .and_then(|value| match DeclarativeRules::from_value(&value) {
+        Ok(rules) => Some(rules),
+        Err(err) => {
+          log::warn!("terminal filter rules ignored terminal_type={} err={}", key, err);
+          None
+        }
+      });
+    let profile = if custom.is_some() {
+      TerminalFilterProfile::Custom
+    } else {
+      resolve_default_member(&key)
+        .and_then(|member| member.filter_profile)
+        .and_then(parse_profile)
+        .unwrap_or_else(|| resolve_profile(&key))
+    };
+    Self { profile, custom }
   }
 
   pub(crate) fn apply_snapshot(
@@ -20,6 +42,6 @@ impl FilterRuntime {
     context: &FilterContext<'_>,
     lines: &[String],
   ) -> FilterResult {
-    profiles::apply_snapshot(self.profile, context, lines)
+    profiles::apply_snapshot(self.profile, self.custom.as_ref(), context, lines)
   }
 }
@@ -3,6 +3,8 @@
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum FilterSource {
   Snapshot,
+  // shell 集成标记定界的命令输出，边界已精确，无需按提示符分段。
+  CommandOutput,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -17,7 +19,11 @@ pub(crate) enum TerminalFilterProfile {
   Codex,
   Gemini,
   Claude,
+  Opencode,
+  Qwen,
   Shell,
+  // 全局设置中为该终端类型配置的声明式规则。
+  Custom,
 }
 
 impl TerminalFilterProfile {
@@ -27,7 +33,10 @@ impl TerminalFilterProfile {
       TerminalFilterProfile::Codex => "codex",
       TerminalFilterProfile::Gemini => "gemini",
       TerminalFilterProfile::Claude => "claude",
+      TerminalFilterProfile::Opencode => "opencode",
+      TerminalFilterProfile::Qwen => "qwen",
       TerminalFilterProfile::Shell => "shell",
+      TerminalFilterProfile::Custom => "custom",
     }
   }
 }
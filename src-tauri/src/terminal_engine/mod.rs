@@ -11,6 +11,6 @@ pub(crate) mod default_members;
 
 // 统一对外导出会话管理与命令接口，避免上层直接依赖内部模块细节。
 pub(crate) use session::{
-  cleanup_ephemeral_sessions_for_window, has_active_sessions, shutdown_sessions, spawn_snapshot_dumper,
-  spawn_status_poller, TerminalManager,
+  cleanup_ephemeral_sessions_for_window, has_active_sessions, shutdown_sessions, spawn_hibernation_sweeper,
+  spawn_snapshot_dumper, spawn_status_poller, TerminalManager,
 };
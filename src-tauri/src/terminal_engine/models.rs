@@ -34,6 +34,9 @@ pub(crate) struct TerminalStatusPayload {
   pub(crate) member_id: Option<String>,
   #[serde(rename = "workspaceId")]
   pub(crate) workspace_id: Option<String>,
+  /// 等待审批时识别到的提示文本，仅 `awaiting_input` 状态携带。
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) prompt: Option<String>,
 }
 
 #[derive(Serialize, Clone)]
@@ -63,6 +66,26 @@ pub(crate) struct TerminalSnapshotLinesPayload {
   pub(crate) lines: Vec<String>,
 }
 
+#[derive(Serialize, Clone)]
+/// 消息行区间载荷：按绝对行号取回的原文，`cursorRow` 供前端换算滚动位置。
+/// 约束：区间为 `[startRow, endRow)`；早于回滚缓冲的行已丢失时 `truncated=true`。
+pub(crate) struct TerminalMessageRowsPayload {
+  #[serde(rename = "terminalId")]
+  pub(crate) terminal_id: String,
+  #[serde(rename = "messageId")]
+  pub(crate) message_id: Option<String>,
+  #[serde(rename = "startRow")]
+  pub(crate) start_row: u64,
+  #[serde(rename = "endRow")]
+  pub(crate) end_row: u64,
+  #[serde(rename = "cursorRow")]
+  pub(crate) cursor_row: u64,
+  pub(crate) truncated: bool,
+  pub(crate) lines: Vec<String>,
+  pub(crate) text: String,
+  pub(crate) ansi: String,
+}
+
 #[derive(Serialize, Clone)]
 /// 会话错误载荷，用于提示 UI 并决定是否禁止输入。
 /// 约束：`fatal=true` 代表会话已不可恢复，需要重新打开。
@@ -73,3 +96,37 @@ pub(crate) struct TerminalErrorPayload {
   pub(crate) fatal: bool,
 }
 
+
+#[derive(Serialize, Clone)]
+/// 录制回放载荷：重建指定时刻的屏幕文本，供审计查看。
+/// 约束：`at_ms` 与 `duration_ms` 均相对录制开始计时。
+pub(crate) struct TerminalRecordingReplayPayload {
+  #[serde(rename = "fileName")]
+  pub(crate) file_name: String,
+  #[serde(rename = "atMs")]
+  pub(crate) at_ms: u64,
+  #[serde(rename = "durationMs")]
+  pub(crate) duration_ms: u64,
+  #[serde(rename = "appliedEvents")]
+  pub(crate) applied_events: usize,
+  pub(crate) rows: u16,
+  pub(crate) cols: u16,
+  #[serde(rename = "cursorRow")]
+  pub(crate) cursor_row: u16,
+  #[serde(rename = "cursorCol")]
+  pub(crate) cursor_col: u16,
+  pub(crate) lines: Vec<String>,
+}
+
+#[derive(Serialize, Clone)]
+/// 插件成员类型载荷：供邀请面板列出启动时注册的成员类型。
+pub(crate) struct TerminalMemberTypePayload {
+  pub(crate) id: String,
+  #[serde(rename = "terminalType")]
+  pub(crate) terminal_type: String,
+  #[serde(rename = "defaultCommand")]
+  pub(crate) default_command: String,
+  #[serde(rename = "unlimitedAccess")]
+  pub(crate) unlimited_access: bool,
+  pub(crate) resumable: bool,
+}
@@ -1,12 +1,13 @@
 //! 终端语义层：把终端内容整理为聊天侧可读的快照消息。
 //! 边界：不执行命令，仅解析输出与上下文信息。
 
-use super::emulator::{create_emulator, EmulatorConfig, TerminalEmulator};
+use super::emulator::{create_emulator, EmulatorBackend, EmulatorConfig, TerminalEmulator};
 use super::filters::FilterRuntime;
 use crate::contracts::terminal_message::{
   TerminalCursorPayload, TerminalMessageMeta, TerminalMessagePayload,
 };
 use crate::now_millis;
+use crate::ports::settings::TerminalSettingsPort;
 
 // 语义层滚动历史上限：用于 chat 路线在一次会话内保留更长输出。
 const SEMANTIC_SCROLLBACK_LINES: usize = 5000;
@@ -70,10 +71,15 @@ pub(crate) struct SemanticState {
   pub(crate) chat_last_command: Option<String>,
   pub(crate) chat_last_input_lines: Option<Vec<String>>,
   pub(crate) chat_stream_enabled: bool,
+  // 本轮内容来自 shell 集成标记的命令区间（而非整屏快照）。
+  pub(crate) chat_command_output: bool,
+  pub(crate) chat_exit_code: Option<i32>,
+  // 本轮内容在会话快照中的绝对行区间 `[start, end)`。
+  pub(crate) chat_row_range: Option<(u64, u64)>,
 }
 
 impl SemanticState {
-  /// 构造语义状态并初始化无回滚的终端仿真器。
+  /// 构造语义状态并初始化轻量网格仿真器（语义只读文本，不需要样式）。
   pub(crate) fn new(
     terminal_id: String,
     member_id: Option<String>,
@@ -81,8 +87,10 @@ impl SemanticState {
     rows: u16,
     cols: u16,
     terminal_type: String,
+    settings: Option<&dyn TerminalSettingsPort>,
   ) -> Self {
     let emulator = create_emulator(EmulatorConfig {
+      backend: EmulatorBackend::Grid,
       rows,
       cols,
       scrollback_limit: SEMANTIC_SCROLLBACK_LINES,
@@ -95,7 +103,7 @@ impl SemanticState {
       terminal_rows: rows,
       terminal_cols: cols,
       emulator,
-      filter: FilterRuntime::new(&terminal_type),
+      filter: FilterRuntime::new(&terminal_type, settings),
       chat_block_pending: false,
       chat_context: None,
       chat_seq: 0,
@@ -103,12 +111,16 @@ impl SemanticState {
       chat_last_command: None,
       chat_last_input_lines: None,
       chat_stream_enabled: true,
+      chat_command_output: false,
+      chat_exit_code: None,
+      chat_row_range: None,
     }
   }
 
   /// 重置语义仿真器，以最新尺寸重建干净视口。
   pub(crate) fn reset_emulator(&mut self, rows: u16, cols: u16) {
     self.emulator = create_emulator(EmulatorConfig {
+      backend: EmulatorBackend::Grid,
       rows,
       cols,
       scrollback_limit: SEMANTIC_SCROLLBACK_LINES,
@@ -168,8 +180,9 @@ pub(crate) fn build_semantic_payload(
       row: cursor.0 as u16,
       col: cursor.1 as u16,
     }),
-    start_row: None,
-    end_row: None,
+    start_row: state.chat_row_range.map(|(start, _)| start),
+    end_row: state.chat_row_range.map(|(_, end)| end),
+    exit_code: state.chat_exit_code,
   };
   Some(TerminalMessagePayload {
     terminal_id: state.terminal_id.clone(),
@@ -0,0 +1,369 @@
+//! 审批提示：成员 CLI 停在权限确认（执行命令、修改文件等）时识别屏幕尾部的提示文本，
+//! 切换为 AwaitingInput 并通知成员所在会话；批准/拒绝按成员类型登记的按键写回终端。
+//! 识别只依赖可见屏幕，提示特征见 `TerminalApprovalPrompt`，插件成员类型可自行声明；
+//! 提示出现后先按成员审批策略（见 `approval_policy`）决定自动作答还是等待人工确认。
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::now_millis;
+use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
+use crate::runtime::StorageManager;
+use crate::terminal_engine::default_members::resolve_default_member;
+
+use super::approval_policy::{append_approval_audit, ApprovalDecision, ApprovalSubject};
+use super::supervisor::post_member_system_notice;
+use super::{
+    lock_sessions, terminal_write, TerminalManager, TerminalSession, TerminalSessionStatus,
+    APPROVAL_PROMPT_SETTLE_MS,
+};
+
+const APPROVAL_PROMPT_TAIL_LINES: usize = 8; // 只看屏幕尾部的非空行，提示框总是贴近输入区。
+const APPROVAL_PROMPT_MAX_CHARS: usize = 200; // 提示文本截断长度，避免超长命令撑爆通知。
+const APPROVAL_SUBJECT_CONTEXT_LINES: usize = 4; // 提示行上方计入审批对象的行数（命令、文件路径所在行）。
+const NOTICE_KEY_AWAITING: &str = "chat.messages.system.approval.awaiting";
+const NOTICE_KEY_AUTO_APPROVED: &str = "chat.messages.system.approval.autoApproved";
+const NOTICE_KEY_AUTO_DENIED: &str = "chat.messages.system.approval.autoDenied";
+
+/// 采集审批识别所需的屏幕尾部；成员类型未声明审批提示或状态不适用时返回 None。
+/// 仅在输出停顿且自上次采集后有新输出时取屏幕行，避免每个轮询周期都在锁内生成快照。
+pub(super) fn approval_screen_tail(
+    session: &mut TerminalSession,
+    now: u64,
+) -> Option<(&'static [&'static str], Vec<String>)> {
+    let settled = session
+        .last_output_at
+        .map(|value| now.saturating_sub(value) >= APPROVAL_PROMPT_SETTLE_MS)
+        .unwrap_or(true);
+    if !settled || session.output_seq == session.approval_checked_seq {
+        return None;
+    }
+    if session.status_locked
+        || !matches!(
+            session.status,
+            TerminalSessionStatus::Online
+                | TerminalSessionStatus::Working
+                | TerminalSessionStatus::AwaitingInput
+        )
+    {
+        return None;
+    }
+    let approval = resolve_default_member(session.member_type())?.approval_prompt?;
+    session.approval_checked_seq = session.output_seq;
+    let mut tail: Vec<String> = session
+        .snapshot
+        .snapshot_lines()
+        .into_iter()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .take(APPROVAL_PROMPT_TAIL_LINES)
+        .collect();
+    tail.reverse();
+    Some((approval.patterns, tail))
+}
+
+/// 在屏幕尾部查找审批提示，返回命中的提示行（去掉边框字符与首尾空白，超长截断）。
+/// 多行命中时取最靠下的一行，即最新出现的提示。
+pub(super) fn detect_approval_prompt(lines: &[String], patterns: &[&str]) -> Option<String> {
+    lines.iter().rev().find_map(|line| {
+        let lowered = line.to_lowercase();
+        if !patterns.iter().any(|pattern| lowered.contains(pattern)) {
+            return None;
+        }
+        Some(strip_border(line).chars().take(APPROVAL_PROMPT_MAX_CHARS).collect())
+    })
+}
+
+/// 审批对象：提示行及其上方紧邻的几行（遇到提示框上边框即停）作为上下文，并从中提取待执行的命令行。
+pub(super) fn approval_subject(lines: &[String], prompt: &str) -> ApprovalSubject {
+    let Some(index) = lines.iter().rposition(|line| line.contains(prompt)) else {
+        return ApprovalSubject {
+            command: inline_command(prompt),
+            context: prompt.to_string(),
+        };
+    };
+    let start = lines[..index]
+        .iter()
+        .rposition(|line| line.trim_start().starts_with(['╭', '┌']))
+        .map(|border| border + 1)
+        .unwrap_or(0)
+        .max(index.saturating_sub(APPROVAL_SUBJECT_CONTEXT_LINES));
+    let context: Vec<&str> = lines[start..=index]
+        .iter()
+        .map(|line| strip_border(line))
+        .filter(|line| !line.is_empty())
+        .collect();
+    let above = &context[..context.len() - 1];
+    ApprovalSubject {
+        command: inline_command(strip_border(&lines[index])).or_else(|| boxed_command(above)),
+        context: context.join("\n"),
+    }
+}
+
+// 提示行内联的命令，如 `Allow command? cargo test (y/n)`：取问号之后、末尾选项说明之前的文本。
+// 没有选项说明时命令可能已折行到下一行，不提取，避免只看到被截断的前半段。
+fn inline_command(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once('?')?;
+    let rest = rest.trim();
+    let open = rest.rfind(['(', '['])?;
+    if !rest.ends_with([')', ']']) || !rest[open..].contains('/') {
+        return None;
+    }
+    let command = rest[..open].trim();
+    (!command.is_empty()).then(|| command.to_string())
+}
+
+// 提示框内的命令：`Bash command` 等标题行或 `$ ` 开头的行之后直到提示行的全部内容。
+// 折行或附带的说明行一并计入，由策略逐行整行匹配，多出的说明只会让自动批准落空。
+fn boxed_command(lines: &[&str]) -> Option<String> {
+    let start = lines.iter().rposition(|line| {
+        line.starts_with("$ ") || line.to_lowercase().ends_with(" command")
+    })?;
+    let mut command: Vec<&str> = lines[start + 1..].to_vec();
+    if let Some(inline) = lines[start].strip_prefix("$ ") {
+        command.insert(0, inline);
+    }
+    (!command.is_empty()).then(|| command.join("\n"))
+}
+
+fn strip_border(line: &str) -> &str {
+    line.trim_matches(|ch: char| {
+        ch.is_whitespace() || matches!(ch, '│' | '┃' | '║' | '|' | '╭' | '╰' | '╮' | '╯')
+    })
+}
+
+/// 审批提示相关的会话上下文，用于通知与审计。
+struct ApprovalContext {
+    member_type: String,
+    member_id: Option<String>,
+    member_name: Option<String>,
+    workspace_id: Option<String>,
+    prompt: Option<String>,
+}
+
+impl ApprovalContext {
+    fn from_session(session: &TerminalSession) -> Self {
+        Self {
+            member_type: session.member_type().to_string(),
+            member_id: session.member_id.clone(),
+            member_name: session.member_name.clone(),
+            workspace_id: session.workspace_id.clone(),
+            prompt: session.approval_prompt.clone(),
+        }
+    }
+}
+
+/// 成员进入等待审批后按其审批策略处理：命中批准/拒绝规则时直接作答并告知会话，
+/// 其余情况（或自动作答失败）通知会话等待人工确认。每个决定都写入审计日志。
+pub(super) fn handle_approval_prompt(
+    app: &AppHandle,
+    terminal_id: &str,
+    prompt: &str,
+    subject: &ApprovalSubject,
+) {
+    let state = app.state::<TerminalManager>();
+    let evaluated = {
+        let guard = lock_sessions(&state.sessions);
+        guard.sessions.get(terminal_id).map(|session| {
+            let (decision, rule) = session.approval_policy.evaluate(subject);
+            (decision, rule.map(str::to_string), ApprovalContext::from_session(session))
+        })
+    };
+    let Some((decision, rule, context)) = evaluated else {
+        return;
+    };
+    log::info!(
+        "terminal awaiting approval terminal_id={} decision={:?} rule={:?} prompt={}",
+        terminal_id,
+        decision,
+        rule,
+        prompt
+    );
+    if decision != ApprovalDecision::Ask {
+        let approve = decision == ApprovalDecision::Approve;
+        match answer_approval(app, terminal_id, approve, "policy", rule.as_deref(), Some(subject)) {
+            Ok(()) => {
+                let key = if approve {
+                    NOTICE_KEY_AUTO_APPROVED
+                } else {
+                    NOTICE_KEY_AUTO_DENIED
+                };
+                post_approval_notice(
+                    app,
+                    &context,
+                    key,
+                    [
+                        ("prompt", prompt.to_string()),
+                        ("rule", rule.clone().unwrap_or_else(|| "default".to_string())),
+                    ],
+                );
+                return;
+            }
+            Err(err) => {
+                log::warn!(
+                    "terminal approval policy answer failed terminal_id={} err={}",
+                    terminal_id,
+                    err
+                );
+            }
+        }
+    }
+    record_approval_decision(
+        app,
+        terminal_id,
+        &context,
+        ApprovalRecord {
+            prompt: Some(prompt),
+            subject: Some(subject),
+            decision: "asked",
+            source: "policy",
+            rule: rule.as_deref(),
+        },
+    );
+    post_approval_notice(
+        app,
+        &context,
+        NOTICE_KEY_AWAITING,
+        [("prompt", prompt.to_string())],
+    );
+}
+
+/// 批准或拒绝成员当前的审批提示：按成员类型登记的按键写入终端。
+/// 错误：会话不存在、未处于等待审批状态，或成员类型未声明审批按键。
+pub(crate) fn terminal_respond_approval(
+    app: AppHandle,
+    terminal_id: String,
+    approve: bool,
+) -> Result<(), String> {
+    answer_approval(&app, &terminal_id, approve, "user", None, None)
+}
+
+fn answer_approval(
+    app: &AppHandle,
+    terminal_id: &str,
+    approve: bool,
+    source: &str,
+    rule: Option<&str>,
+    subject: Option<&ApprovalSubject>,
+) -> Result<(), String> {
+    let state = app.state::<TerminalManager>();
+    let context = {
+        let guard = lock_sessions(&state.sessions);
+        let session = guard
+            .sessions
+            .get(terminal_id)
+            .ok_or_else(|| "terminal session not found".to_string())?;
+        if session.status != TerminalSessionStatus::AwaitingInput {
+            return Err("terminal session is not awaiting approval".to_string());
+        }
+        ApprovalContext::from_session(session)
+    };
+    let approval = resolve_default_member(&context.member_type)
+        .and_then(|member| member.approval_prompt)
+        .ok_or_else(|| format!("member type {} has no approval keys", context.member_type))?;
+    let input = if approve {
+        approval.approve_input
+    } else {
+        approval.deny_input
+    };
+    terminal_write(app.clone(), state, terminal_id.to_string(), input.to_string())?;
+    log::info!(
+        "terminal approval answered terminal_id={} approve={} source={}",
+        terminal_id,
+        approve,
+        source
+    );
+    record_approval_decision(
+        app,
+        terminal_id,
+        &context,
+        ApprovalRecord {
+            prompt: context.prompt.as_deref(),
+            subject,
+            decision: if approve { "approved" } else { "denied" },
+            source,
+            rule,
+        },
+    );
+    Ok(())
+}
+
+/// 一次审批决定：`decision` 为 asked/approved/denied，`source` 为 policy/user，`rule` 为命中的策略规则。
+struct ApprovalRecord<'a> {
+    prompt: Option<&'a str>,
+    subject: Option<&'a ApprovalSubject>,
+    decision: &'a str,
+    source: &'a str,
+    rule: Option<&'a str>,
+}
+
+// 审计日志落在应用数据目录（成员沙箱不可写），同时写一条诊断事件。
+fn record_approval_decision(
+    app: &AppHandle,
+    terminal_id: &str,
+    context: &ApprovalContext,
+    record: ApprovalRecord,
+) {
+    let entry = json!({
+      "at": now_millis().unwrap_or_default(),
+      "terminalId": terminal_id,
+      "memberId": context.member_id,
+      "memberName": context.member_name,
+      "workspaceId": context.workspace_id,
+      "prompt": record.prompt,
+      "command": record.subject.and_then(|subject| subject.command.as_deref()),
+      "subject": record.subject.map(|subject| subject.context.as_str()),
+      "decision": record.decision,
+      "source": record.source,
+      "rule": record.rule
+    });
+    let storage = app.state::<StorageManager>();
+    let workspace_id = context.workspace_id.as_deref();
+    if let Err(err) = append_approval_audit(storage.inner(), workspace_id, &entry) {
+        log::warn!(
+            "terminal approval audit failed terminal_id={} err={}",
+            terminal_id,
+            err
+        );
+    }
+    diagnostics_log_backend_event(
+        &app.state::<DiagnosticsState>(),
+        context.member_id.clone(),
+        Some(terminal_id.to_string()),
+        None,
+        None,
+        context.workspace_id.clone(),
+        "terminal_approval",
+        entry,
+    );
+}
+
+fn post_approval_notice<const N: usize>(
+    app: &AppHandle,
+    context: &ApprovalContext,
+    key: &str,
+    args: [(&str, String); N],
+) {
+    let (Some(workspace_id), Some(member_id)) =
+        (context.workspace_id.as_deref(), context.member_id.as_deref())
+    else {
+        return;
+    };
+    let args: HashMap<String, String> = args
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    post_member_system_notice(
+        app,
+        workspace_id,
+        member_id,
+        context.member_name.as_deref(),
+        key,
+        args,
+    );
+}
+
+#[cfg(test)]
+mod detect_tests;
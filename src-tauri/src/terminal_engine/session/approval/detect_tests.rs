@@ -0,0 +1,87 @@
+use super::{approval_subject, detect_approval_prompt};
+use crate::terminal_engine::default_members::claude::CLAUDE_DEFAULT_MEMBER;
+use crate::terminal_engine::default_members::shell::SHELL_DEFAULT_MEMBER;
+
+fn lines(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn claude_permission_box_is_detected() {
+    let patterns = CLAUDE_DEFAULT_MEMBER.approval_prompt.unwrap().patterns;
+    let screen = lines(&[
+        "╭──────────────────────────────────────────╮",
+        "│ Bash command                             │",
+        "│   cargo test --workspace                 │",
+        "│ Do you want to proceed?                  │",
+        "│ ❯ 1. Yes                                 │",
+        "│   2. No, and tell Claude what to do (esc)│",
+        "╰──────────────────────────────────────────╯",
+    ]);
+    assert_eq!(
+        detect_approval_prompt(&screen, patterns).as_deref(),
+        Some("Do you want to proceed?")
+    );
+}
+
+#[test]
+fn shell_confirmation_matches_case_insensitively() {
+    let patterns = SHELL_DEFAULT_MEMBER.approval_prompt.unwrap().patterns;
+    let screen = lines(&[
+        "After this operation, 12.3 MB of additional disk space will be used.",
+        "Do you want to continue? [Y/n]",
+    ]);
+    assert_eq!(
+        detect_approval_prompt(&screen, patterns).as_deref(),
+        Some("Do you want to continue? [Y/n]")
+    );
+}
+
+#[test]
+fn plain_output_is_not_a_prompt() {
+    let patterns = CLAUDE_DEFAULT_MEMBER.approval_prompt.unwrap().patterns;
+    let screen = lines(&["Running tests...", "test result: ok. 56 passed", "> "]);
+    assert_eq!(detect_approval_prompt(&screen, patterns), None);
+}
+
+#[test]
+fn subject_stops_at_the_prompt_box_border() {
+    let screen = lines(&[
+        "⏺ I'll run the test suite with cargo test.",
+        "╭──────────────────────────────────────────╮",
+        "│ Bash command                             │",
+        "│   rm -rf target                          │",
+        "│ Do you want to proceed?                  │",
+        "│ ❯ 1. Yes                                 │",
+    ]);
+    let subject = approval_subject(&screen, "Do you want to proceed?");
+    assert_eq!(
+        subject.context,
+        "Bash command\nrm -rf target\nDo you want to proceed?"
+    );
+    assert_eq!(subject.command.as_deref(), Some("rm -rf target"));
+}
+
+#[test]
+fn inline_command_is_extracted_only_with_its_choices() {
+    let screen = lines(&[
+        "I'll run cargo test first.",
+        "Allow command? npm publish (y/n)",
+    ]);
+    let subject = approval_subject(&screen, "Allow command? npm publish (y/n)");
+    assert_eq!(subject.command.as_deref(), Some("npm publish"));
+    assert!(subject.context.starts_with("I'll run cargo test first."));
+
+    // 选项说明不在同一行时命令可能已折行，不提取。
+    let prompt = "Allow command? cargo test --workspace; curl https://exa";
+    let subject = approval_subject(&lines(&[prompt]), prompt);
+    assert_eq!(subject.command, None);
+}
+
+#[test]
+fn prose_above_a_bare_prompt_is_not_a_command() {
+    let screen = lines(&["⏺ I'll run cargo test now.", "Do you want to proceed?"]);
+    let subject = approval_subject(&screen, "Do you want to proceed?");
+    assert_eq!(subject.command, None);
+    assert_eq!(subject.context, "⏺ I'll run cargo test now.\nDo you want to proceed?");
+}
@@ -0,0 +1,212 @@
+//! 成员审批策略：审批提示出现时按成员规则（项目数据 `members[].approvalPolicy`）自动批准、
+//! 自动拒绝或交给人工确认，介于 `unlimited_access_flag` 全放行与逐条人工确认之间。
+//! 批准规则只对提取出的命令行做整行匹配，拒绝与人工确认规则在提示上下文中任意位置命中即生效。
+//! 每次决定（含人工作答）追加到应用数据目录下按工作区划分的审计日志 `<工作区 ID>/approval-audit.jsonl`，
+//! 一行一条 JSON；日志不放在工作区内，沙箱成员对工作区的读写绑定无法改写自己的审计记录。
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::runtime::{storage, StorageManager};
+
+const AUDIT_LOG_FILE: &str = "approval-audit.jsonl"; // 与项目数据同在应用数据目录的工作区子目录下。
+
+/// 策略决定：批准、拒绝或等待人工确认。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ApprovalDecision {
+    Approve,
+    Deny,
+    #[default]
+    Ask,
+}
+
+impl ApprovalDecision {
+    fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "approve" => Some(ApprovalDecision::Approve),
+            "deny" => Some(ApprovalDecision::Deny),
+            "ask" => Some(ApprovalDecision::Ask),
+            _ => None,
+        }
+    }
+}
+
+/// 审批对象：`command` 为从提示中提取的待执行命令行，未能可靠提取时为 None；
+/// `context` 为提示行及其上方紧邻的几行（命令、文件路径、成员的说明文字）。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ApprovalSubject {
+    pub(crate) command: Option<String>,
+    pub(crate) context: String,
+}
+
+/// 成员审批策略，会话注册时解析一次。
+/// 规则按「拒绝 > 人工确认 > 批准」的优先级匹配，任一拒绝规则命中即拒绝，未命中任何规则时按 `default` 处理。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ApprovalPolicy {
+    approve: Vec<String>,
+    deny: Vec<String>,
+    ask: Vec<String>,
+    default: ApprovalDecision,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApprovalPolicyOverrides {
+    #[serde(default)]
+    approve: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+    #[serde(default)]
+    ask: Vec<String>,
+    default: Option<String>,
+}
+
+impl ApprovalPolicy {
+    /// 解析成员策略；格式错误或默认决定未知时记录日志并回退为全部人工确认，避免误配导致放行。
+    pub(crate) fn from_value(value: Option<&Value>) -> Self {
+        let Some(value) = value else {
+            return Self::default();
+        };
+        let overrides = match serde_json::from_value::<ApprovalPolicyOverrides>(value.clone()) {
+            Ok(overrides) => overrides,
+            Err(err) => {
+                log::warn!("terminal approval policy ignored: {err}");
+                return Self::default();
+            }
+        };
+        let default = match overrides.default.as_deref().map(ApprovalDecision::from_str) {
+            Some(Some(decision)) => decision,
+            Some(None) => {
+                log::warn!(
+                    "terminal approval policy ignored: unknown default {}",
+                    overrides.default.as_deref().unwrap_or("")
+                );
+                return Self::default();
+            }
+            None => ApprovalDecision::Ask,
+        };
+        let normalize = |patterns: Vec<String>| -> Vec<String> {
+            patterns
+                .into_iter()
+                .map(|pattern| pattern.trim().to_string())
+                .filter(|pattern| !pattern.is_empty())
+                .collect()
+        };
+        Self {
+            approve: normalize(overrides.approve),
+            deny: normalize(overrides.deny),
+            ask: normalize(overrides.ask),
+            default,
+        }
+    }
+
+    /// 评估审批对象；返回决定与命中的规则。
+    /// 拒绝/人工确认规则匹配整个上下文，宁可多拦；批准规则只匹配提取出的命令，见 `approving_rule`。
+    pub(crate) fn evaluate(&self, subject: &ApprovalSubject) -> (ApprovalDecision, Option<&str>) {
+        let guards = [
+            (ApprovalDecision::Deny, &self.deny),
+            (ApprovalDecision::Ask, &self.ask),
+        ];
+        for (decision, patterns) in guards {
+            if let Some(pattern) = patterns
+                .iter()
+                .find(|pattern| pattern_occurs(pattern, &subject.context))
+            {
+                return (decision, Some(pattern.as_str()));
+            }
+        }
+        if let Some(pattern) = subject
+            .command
+            .as_deref()
+            .and_then(|command| self.approving_rule(command))
+        {
+            return (ApprovalDecision::Approve, Some(pattern));
+        }
+        (self.default, None)
+    }
+
+    // 命令按 `;`、`&`、`|` 与换行拆成单条，每条都须整行匹配某条批准规则，返回首条命令命中的规则。
+    // 含命令替换（`$(`、反引号）、进程替换或 `>`/`<` 重定向时，实际执行内容或写入目标无法由规则约束，不自动批准。
+    fn approving_rule(&self, command: &str) -> Option<&str> {
+        if command.contains("$(") || command.contains(['`', '>', '<']) {
+            return None;
+        }
+        let segments: Vec<String> = command
+            .split([';', '&', '|', '\n'])
+            .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let mut first = None;
+        for segment in segments.iter() {
+            let pattern = self
+                .approve
+                .iter()
+                .find(|pattern| pattern_matches_whole(pattern, segment))?;
+            first.get_or_insert(pattern.as_str());
+        }
+        first
+    }
+}
+
+/// 上下文匹配：文本中任一片段符合模式即命中；`*` 匹配任意字符，其余按字面大小写敏感比较。
+fn pattern_occurs(pattern: &str, text: &str) -> bool {
+    let mut rest = text;
+    for part in pattern.split('*').filter(|part| !part.is_empty()) {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// 整行匹配：文本首尾都须落在模式内，`*` 匹配任意字符（可为空），其余按字面大小写敏感比较。
+fn pattern_matches_whole(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if parts.len() == 1 {
+        return text == first;
+    }
+    if text.len() < first.len() + last.len() || !text.starts_with(first) || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in parts[1..parts.len() - 1].iter().filter(|part| !part.is_empty()) {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// 追加一条审计记录；工作区未知时跳过。写入失败只返回错误，由调用方记录日志。
+pub(super) fn append_approval_audit(
+    storage: &StorageManager,
+    workspace_id: Option<&str>,
+    entry: &Value,
+) -> Result<(), String> {
+    let Some(workspace_id) = workspace_id.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(());
+    };
+    let relative = format!("{workspace_id}/{AUDIT_LOG_FILE}");
+    let path = storage::resolve_app_data_path(storage, &relative)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create audit log dir: {err}"))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("failed to open audit log: {err}"))?;
+    writeln!(file, "{entry}").map_err(|err| format!("failed to write audit log: {err}"))
+}
+
+#[cfg(test)]
+mod policy_tests;
@@ -0,0 +1,47 @@
+//! 会话时钟：轮询规则、触发调度、稳定性检测与 post_ready 统一从这里取毫秒时间戳。
+//! 默认使用系统时间；测试注入手动时钟，推进时间无需真实等待。
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::now_millis;
+
+pub(crate) trait Clock: Send + Sync {
+    /// 当前时间（Unix 毫秒）；读取失败时返回 0，与既有 `unwrap_or(0)` 语义一致。
+    fn now_ms(&self) -> u64;
+}
+
+/// 系统时钟：直接读取墙上时间。
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        now_millis().unwrap_or(0)
+    }
+}
+
+/// 手动时钟：仅在显式推进时前进，供无头测试驱动时序规则。
+#[cfg(test)]
+pub(crate) struct ManualClock {
+    now: AtomicU64,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub(crate) fn new(start_ms: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start_ms),
+        }
+    }
+
+    pub(crate) fn advance(&self, delta_ms: u64) -> u64 {
+        self.now.fetch_add(delta_ms, Ordering::SeqCst) + delta_ms
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
@@ -9,39 +9,75 @@ use std::{
     thread,
 };
 
+use serde::Deserialize;
 use serde_json::json;
 use tauri::{AppHandle, Manager, State, WebviewWindow};
 
-use crate::now_millis;
+use crate::message_service::project_members::{ProjectMemberRecord, ProjectMemberStore};
 use crate::orchestration::terminal_friend_invite;
 use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
 use crate::runtime::{resize_pty, StorageManager};
 
 use super::super::models::{
+    TerminalMemberTypePayload, TerminalMessageRowsPayload, TerminalRecordingReplayPayload,
     TerminalSnapshotLinesPayload, TerminalSnapshotPayload, TerminalStatusPayload,
 };
-use super::launch::launch_terminal_with_fallback;
+use super::launch::{launch_terminal_with_fallback, TerminalLaunchRequest};
+use super::recording::{self, SessionRecorder};
+use super::session_discovery::spawn_session_id_discovery;
+use super::shell_marks::ShellTurnTracker;
 use super::snapshot_service;
 use super::state::{PostReadyMode, PostReadyState, TerminalSessionStatus, TerminalType, TerminalSnapshot};
 use super::{
-    build_status_payload, ensure_session_active, flush_input_buffer, handle_buffered_write,
-    lock_session_status_precreate, lock_sessions, mark_session_working_on_input, register_session,
-    resolve_terminal_type, spawn_exit_watcher, spawn_pty_reader,
-    subtract_unacked_bytes, terminal_trace_detail, unlock_session_status_precreate,
-    update_session_status, InitialWriteState, SemanticEvent,
-    REDRAW_SUPPRESSION_WINDOW_MS, SESSION_COUNTER,
+    build_status_payload, clock_now, ensure_session_active, flush_input_buffer, handle_buffered_write,
+    lock_session_status_precreate, lock_sessions, mark_session_working_on_input, record_session_event,
+    register_session,
+    resolve_custom_terminal_type, resolve_terminal_tuning, resolve_terminal_type, spawn_exit_watcher, spawn_pty_reader,
+    encode_key_sequence, subtract_unacked_bytes, terminal_trace_detail, unlock_session_status_precreate,
+    update_session_status, ApprovalPolicy, InitialWriteState, PostReadyScript, RestartPolicy, SemanticEvent,
+    SessionRegistration, TerminalTuning,
+    SESSION_COUNTER,
 };
 use super::{TerminalDispatchContext, TerminalManager};
+use crate::terminal_engine::default_members::onboarding::OnboardingContext;
 use crate::terminal_engine::default_members::{
-    apply_resume_command, apply_unlimited_access_command,
+    apply_resume_command, apply_unlimited_access_command, plugin_members, resolve_default_member,
 };
 
-/// 创建终端会话并启动进程。
-/// 输入：`cols/rows` 为可选尺寸；`cwd` 为工作目录；`terminal_id` 可选用于指定会话标识；
-/// `terminal_type/terminal_command/terminal_path` 决定启动方式与二进制来源；shell 类型时 terminal_path 可覆盖默认 shell。
-/// `strict_shell` 为 true 时仅按指定路径/系统默认启动，不再启用兜底候选。
-/// 邀请元信息：`member_name/default_command/instance_count/unlimited_access/sandboxed` 仅用于编排记录；
-/// `post_ready_mode` 控制是否执行启动后流程。
+/// 终端创建参数：UI 以 `options` 对象传入，编排层按需填写，未填字段取默认值。
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TerminalCreateOptions {
+    pub(crate) cols: Option<u16>,
+    pub(crate) rows: Option<u16>,
+    /// 工作目录；成员独立工作树时为工作树路径。
+    pub(crate) cwd: Option<String>,
+    /// 项目工作区根目录，用于读取项目数据，缺省时同 `cwd`。
+    pub(crate) workspace_path: Option<String>,
+    pub(crate) member_id: Option<String>,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) keep_alive: Option<bool>,
+    /// 可选的会话标识；缺省时由成员或计数器派生。
+    pub(crate) terminal_id: Option<String>,
+    /// 与 `terminal_command/terminal_path` 一起决定启动方式与二进制来源；
+    /// shell 类型时 `terminal_path` 可覆盖默认 shell。
+    pub(crate) terminal_type: Option<String>,
+    pub(crate) terminal_command: Option<String>,
+    pub(crate) terminal_path: Option<String>,
+    /// 为 true 时仅按指定路径/系统默认启动，不再启用兜底候选。
+    pub(crate) strict_shell: Option<bool>,
+    /// 控制是否执行启动后流程。
+    pub(crate) post_ready_mode: Option<String>,
+    /// 邀请元信息：`member_name/default_command/instance_count/unlimited_access` 仅用于编排记录。
+    pub(crate) member_name: Option<String>,
+    pub(crate) default_command: Option<String>,
+    pub(crate) invite_instance_count: Option<u32>,
+    pub(crate) invite_unlimited_access: Option<bool>,
+    /// 为 true 时以 `cwd` 为工作区在沙箱内启动（仅 Linux，沙箱不可用时创建失败）。
+    pub(crate) invite_sandboxed: Option<bool>,
+}
+
+/// 创建终端会话并启动进程，参数见 `TerminalCreateOptions`。
 /// 约束：非 shell 类型会使用 `terminal_command` 的参数（如包含旗标）。
 /// 返回：新会话 ID。
 /// 错误：会话 ID 冲突、二进制不可用、PTY 启动失败或命令解析失败。
@@ -49,24 +85,28 @@ pub(crate) fn terminal_create(
     app: AppHandle,
     window: WebviewWindow,
     state: State<'_, TerminalManager>,
-    cols: Option<u16>,
-    rows: Option<u16>,
-    cwd: Option<String>,
-    member_id: Option<String>,
-    workspace_id: Option<String>,
-    keep_alive: Option<bool>,
-    terminal_id: Option<String>,
-    terminal_type: Option<String>,
-    terminal_command: Option<String>,
-    terminal_path: Option<String>,
-    strict_shell: Option<bool>,
-    post_ready_mode: Option<String>,
-    member_name: Option<String>,
-    default_command: Option<String>,
-    invite_instance_count: Option<u32>,
-    invite_unlimited_access: Option<bool>,
-    invite_sandboxed: Option<bool>,
+    options: TerminalCreateOptions,
 ) -> Result<String, String> {
+    let TerminalCreateOptions {
+        cols,
+        rows,
+        cwd,
+        workspace_path,
+        member_id,
+        workspace_id,
+        keep_alive,
+        terminal_id,
+        terminal_type,
+        terminal_command,
+        terminal_path,
+        strict_shell,
+        post_ready_mode,
+        member_name,
+        default_command,
+        invite_instance_count,
+        invite_unlimited_access,
+        invite_sandboxed,
+    } = options;
     // 好友创建相关流程（后端侧）：
     // 1) 若带邀请元信息，创建前先预锁状态，避免 UI 误判为已连接。
     // 2) 启动 PTY 并注册会话，注册时消费预锁并进入 Connecting。
@@ -92,11 +132,23 @@ pub(crate) fn terminal_create(
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(|value| value.to_string());
+    let custom_type = resolve_custom_terminal_type(terminal_type.as_deref());
     let terminal_type = resolve_terminal_type(terminal_type.as_deref(), command.as_deref());
     let requested_terminal_type = terminal_type;
+    // 成员类型：插件/自定义类型名优先，用于查找成员配置（命令模板、post_ready、调优）。
+    let member_type = custom_type
+        .clone()
+        .unwrap_or_else(|| terminal_type.as_str().to_string());
     let post_ready_mode = PostReadyMode::from_str(post_ready_mode.as_deref());
+    // 插件成员类型按 shell 启动，未指定命令时由 shell 执行其默认命令。
+    let command = command.or_else(|| {
+        custom_type
+            .as_deref()
+            .and_then(resolve_default_member)
+            .map(|member| member.default_command.to_string())
+    });
     let command = apply_unlimited_access_command(
-        terminal_type.as_str(),
+        member_type.as_str(),
         command,
         invite_unlimited_access.unwrap_or(false),
     );
@@ -117,11 +169,11 @@ pub(crate) fn terminal_create(
         if session_id.is_some() {
             log::info!(
                 "terminal resume session found terminal_type={} session_id={}",
-                terminal_type.as_str(),
+                member_type,
                 session_id.as_deref().unwrap_or("")
             );
         }
-        apply_resume_command(terminal_type.as_str(), command, session_id.as_deref())
+        apply_resume_command(member_type.as_str(), command, session_id.as_deref())
     };
     let terminal_path = terminal_path
         .as_deref()
@@ -129,6 +181,7 @@ pub(crate) fn terminal_create(
         .filter(|value| !value.is_empty())
         .map(|value| value.to_string());
     let strict_shell = strict_shell.unwrap_or(false);
+    let sandboxed = invite_sandboxed.unwrap_or(false);
     let invite_member_name = member_name
         .as_deref()
         .map(str::trim)
@@ -176,6 +229,19 @@ pub(crate) fn terminal_create(
         }
     }
 
+    let workspace_path = workspace_path
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| cwd.clone());
+    let member_record = load_member_project_record(
+        app.state::<StorageManager>().inner(),
+        workspace_path.as_deref(),
+        workspace_id.as_deref(),
+        member_id.as_deref(),
+    );
+    // 自定义启动后脚本在拉起进程前校验，脚本有误时直接拒绝创建。
+    let post_ready_script = resolve_post_ready_script(member_record.as_ref())?;
+
     // 创建前预锁：只要有邀请元信息就先锁状态，确保创建期间保持 Connecting。
     let should_prelock = member_id_for_log.is_some() && post_ready_mode.should_run();
     if should_prelock {
@@ -189,13 +255,17 @@ pub(crate) fn terminal_create(
     let launch_cwd = cwd.clone();
     let launch_result = match launch_terminal_with_fallback(
         storage.inner(),
-        cols,
-        rows,
-        cwd,
-        terminal_type,
-        terminal_path.as_deref(),
-        command.as_deref(),
-        strict_shell,
+        TerminalLaunchRequest {
+            cols,
+            rows,
+            cwd,
+            terminal_type,
+            member_type: member_type.as_str(),
+            terminal_path: terminal_path.as_deref(),
+            terminal_command: command.as_deref(),
+            strict_shell,
+            sandboxed,
+        },
     ) {
         Ok(result) => result,
         Err(err) => {
@@ -209,7 +279,11 @@ pub(crate) fn terminal_create(
     let terminal_type = launch_result.terminal_type;
     let fallback_used = launch_result.fallback_used;
     let default_terminal = launch_result.default_terminal;
-    let terminal_type_for_log = terminal_type.as_str().to_string();
+    // 实际启动后的成员类型：回退到 shell 时保留插件/自定义类型名，用于调优查找与邀请事件。
+    let resolved_member_type = match custom_type.as_deref() {
+        Some(custom_type) if terminal_type == TerminalType::Shell => custom_type.to_string(),
+        _ => terminal_type.as_str().to_string(),
+    };
     if fallback_used && terminal_type != requested_terminal_type {
         log::warn!(
             "terminal type fallback from {} to {}",
@@ -223,26 +297,36 @@ pub(crate) fn terminal_create(
     let mut cleanup_killer = spawned.child.clone_killer();
     let event_port = state.event_port();
     let semantic_tx = None;
-    let status_payload = match register_session(
-        &state,
-        &terminal_id,
+    let member_record = member_record.as_ref();
+    let member_field = |key: &str| member_record.and_then(|member| member.record.object_field(key));
+    let registration = SessionRegistration {
         member_id,
-        invite_member_name.clone(),
+        member_name: invite_member_name.clone(),
         workspace_id,
-        launch_cwd.clone(),
-        command.clone(),
-        terminal_path.clone(),
-        strict_shell,
+        workspace_path,
+        launch_cwd: launch_cwd.clone(),
+        launch_command: command.clone(),
+        launch_path: terminal_path.clone(),
+        launch_strict_shell: strict_shell,
+        launch_sandboxed: sandboxed,
         post_ready_mode,
         rows,
         cols,
         terminal_type,
+        custom_type,
         keep_alive,
         owner_window_label,
         output_window_label,
-        semantic_tx.clone(),
-        spawned.handle,
-    ) {
+        semantic_tx: semantic_tx.clone(),
+        handle: Some(spawned.handle),
+        tuning: resolve_session_tuning(&state, &resolved_member_type, member_record),
+        // 策略格式错误时各自记录日志并回退为不重启/全部人工确认。
+        restart_policy: RestartPolicy::from_value(member_field("restartPolicy")),
+        approval_policy: ApprovalPolicy::from_value(member_field("approvalPolicy")),
+        onboarding: resolve_onboarding_context(member_record),
+        post_ready_script,
+    };
+    let status_payload = match register_session(&state, &terminal_id, registration) {
         Ok(payload) => payload,
         Err(err) => {
             let _ = cleanup_killer.kill();
@@ -256,7 +340,7 @@ pub(crate) fn terminal_create(
         terminal_id: terminal_id.clone(),
         member_id: member_id_for_log.clone(),
         workspace_id: workspace_id_for_log.clone(),
-        terminal_type: terminal_type_for_log.clone(),
+        terminal_type: resolved_member_type.clone(),
         member_name: invite_member_name,
         default_command: invite_default_command,
         instance_count: invite_instance_count,
@@ -334,12 +418,19 @@ pub(crate) fn terminal_create(
             .unwrap_or(0)
     };
     spawn_exit_watcher(
+        app.clone(),
         spawned.child,
         Arc::clone(&event_port),
         state.sessions.clone(),
         terminal_id.clone(),
         spawn_epoch,
     );
+    spawn_session_id_discovery(
+        state.sessions.clone(),
+        state.session_repository(),
+        terminal_id.clone(),
+        spawn_epoch,
+    );
 
     // 若处于 Connecting，立刻广播一次状态，确保跨窗口/订阅方同步。
     if status_payload.status == "connecting" {
@@ -359,7 +450,7 @@ pub(crate) fn terminal_create(
           "terminalId": terminal_id,
           "memberId": member_id_for_log,
           "workspaceId": workspace_id_for_log,
-          "terminalType": terminal_type_for_log,
+          "terminalType": resolved_member_type,
           "terminalCommand": command_for_log,
           "terminalPath": terminal_path_for_log,
           "cols": cols,
@@ -373,12 +464,133 @@ pub(crate) fn terminal_create(
     Ok(terminal_id)
 }
 
+/// 成员在项目数据中的记录：创建会话时读取一次，调优、重启/审批策略、引导词与启动后脚本均由此解析。
+struct MemberProjectRecord {
+    workspace_id: String,
+    workspace_path: String,
+    member_id: String,
+    record: ProjectMemberRecord,
+}
+
+/// 读取成员记录；缺少工作区或成员信息时返回 None，读取失败只记录日志，各项按未配置处理。
+fn load_member_project_record(
+    storage: &StorageManager,
+    workspace_path: Option<&str>,
+    workspace_id: Option<&str>,
+    member_id: Option<&str>,
+) -> Option<MemberProjectRecord> {
+    let (Some(workspace_path), Some(workspace_id), Some(member_id)) =
+        (workspace_path, workspace_id, member_id)
+    else {
+        return None;
+    };
+    match ProjectMemberStore::new(storage).member_record(workspace_id, workspace_path, member_id) {
+        Ok(record) => Some(MemberProjectRecord {
+            workspace_id: workspace_id.to_string(),
+            workspace_path: workspace_path.to_string(),
+            member_id: member_id.to_string(),
+            record,
+        }),
+        Err(err) => {
+            log::warn!(
+                "terminal member project data unavailable member_id={} err={}",
+                member_id,
+                err
+            );
+            None
+        }
+    }
+}
+
+/// 解析会话调优档位：全局设置按终端类型覆盖，成员可在项目数据中再覆盖。
+fn resolve_session_tuning(
+    state: &TerminalManager,
+    terminal_type: &str,
+    member: Option<&MemberProjectRecord>,
+) -> TerminalTuning {
+    let settings_service = state
+        .settings_service
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_else(|err| err.into_inner().clone());
+    resolve_terminal_tuning(
+        terminal_type,
+        settings_service.as_deref(),
+        member.and_then(|member| member.record.object_field("terminalTuning")),
+    )
+}
+
+/// 解析引导词上下文（项目数据中的成员列表与 `onboarding` 配置）；
+/// 成员不在列表中时返回 None，引导步骤回退为仅含成员信息的内置模板。
+fn resolve_onboarding_context(member: Option<&MemberProjectRecord>) -> Option<OnboardingContext> {
+    let member = member?;
+    OnboardingContext::from_project(
+        &member.workspace_id,
+        Some(&member.workspace_path),
+        &member.member_id,
+        &member.record.members,
+        member.record.onboarding.as_ref(),
+    )
+}
+
+/// 解析并校验成员自定义启动后脚本（项目数据 `members[].postReadyScript`）；脚本校验失败时返回错误。
+fn resolve_post_ready_script(
+    member: Option<&MemberProjectRecord>,
+) -> Result<Option<PostReadyScript>, String> {
+    let Some(member) = member else {
+        return Ok(None);
+    };
+    member
+        .record
+        .post_ready_script()
+        .map(PostReadyScript::from_value)
+        .transpose()
+        .map_err(|err| {
+            format!("invalid postReadyScript for member {}: {err}", member.member_id)
+        })
+}
+
 /// post_ready 解析会话 ID 超时后的重启：关闭当前进程并在原会话上重启。
 pub(crate) fn terminal_restart_post_ready(
     app: AppHandle,
     state: State<'_, TerminalManager>,
     terminal_id: &str,
     reason: &str,
+) -> Result<(), String> {
+    {
+        let mut guard = state
+            .sessions
+            .lock()
+            .map_err(|_| "terminal session lock poisoned".to_string())?;
+        let session = guard
+            .sessions
+            .get_mut(terminal_id)
+            .ok_or_else(|| "terminal session not found".to_string())?;
+        if !session.post_ready_restart_pending {
+            return Ok(());
+        }
+        session.post_ready_restart_pending = false;
+    }
+    relaunch_terminal_session(app, state, terminal_id, reason, RelaunchOverrides::default())
+}
+
+/// 单次重启的覆盖项：只作用于本次启动，不改写会话记录的启动命令与启动后模式。
+#[derive(Default)]
+pub(crate) struct RelaunchOverrides {
+    /// 替换本次启动命令（如追加 resume）。
+    pub(crate) command: Option<String>,
+    /// 本次启动跳过启动后流程（如续接已有会话时不再重复引导）。
+    pub(crate) skip_post_ready: bool,
+}
+
+/// 在原会话上重启进程：结束旧进程（若仍在运行）后按原启动参数拉起，保留会话 ID 与成员绑定。
+/// `overrides` 只影响本次启动，见 `RelaunchOverrides`。
+pub(crate) fn relaunch_terminal_session(
+    app: AppHandle,
+    state: State<'_, TerminalManager>,
+    terminal_id: &str,
+    reason: &str,
+    overrides: RelaunchOverrides,
 ) -> Result<(), String> {
     let (
         rows,
@@ -388,6 +600,8 @@ pub(crate) fn terminal_restart_post_ready(
         terminal_path,
         cwd,
         strict_shell,
+        sandboxed,
+        member_type,
         member_id,
         workspace_id,
         keep_alive,
@@ -405,19 +619,18 @@ pub(crate) fn terminal_restart_post_ready(
             .sessions
             .get_mut(terminal_id)
             .ok_or_else(|| "terminal session not found".to_string())?;
-        if !session.post_ready_restart_pending {
-            return Ok(());
-        }
         session.post_ready_restart_pending = false;
         session.flow_paused = true;
         (
             session.screen_rows,
             session.screen_cols,
             session.terminal_type,
-            session.launch_command.clone(),
+            overrides.command.or_else(|| session.launch_command.clone()),
             session.launch_path.clone(),
             session.launch_cwd.clone(),
             session.launch_strict_shell,
+            session.launch_sandboxed,
+            session.member_type().to_string(),
             session.member_id.clone(),
             session.workspace_id.clone(),
             session.keep_alive,
@@ -444,13 +657,17 @@ pub(crate) fn terminal_restart_post_ready(
     let storage = app.state::<StorageManager>();
     let launch_result = match launch_terminal_with_fallback(
         storage.inner(),
-        cols,
-        rows,
-        cwd.clone(),
-        terminal_type,
-        terminal_path.as_deref(),
-        command.as_deref(),
-        strict_shell,
+        TerminalLaunchRequest {
+            cols,
+            rows,
+            cwd: cwd.clone(),
+            terminal_type,
+            member_type: member_type.as_str(),
+            terminal_path: terminal_path.as_deref(),
+            terminal_command: command.as_deref(),
+            strict_shell,
+            sandboxed,
+        },
     ) {
         Ok(result) => result,
         Err(err) => {
@@ -480,9 +697,10 @@ pub(crate) fn terminal_restart_post_ready(
     let fallback_used = launch_result.fallback_used;
     if fallback_used {
         log::warn!(
-            "terminal post_ready restart fallback terminal_id={} type={}",
+            "terminal restart fallback terminal_id={} type={} reason={}",
             terminal_id,
-            terminal_type.as_str()
+            terminal_type.as_str(),
+            reason
         );
     }
 
@@ -522,7 +740,9 @@ pub(crate) fn terminal_restart_post_ready(
             session.redraw_suppression_until = None;
             session.flow_paused = false;
             session.broken = false;
-            session.post_ready_state = if session.post_ready_mode.should_run() {
+            session.post_ready_state = if !overrides.skip_post_ready
+                && session.post_ready_mode.should_run()
+            {
                 PostReadyState::Idle
             } else {
                 PostReadyState::Done
@@ -539,6 +759,10 @@ pub(crate) fn terminal_restart_post_ready(
             session.handle = Some(spawned.handle);
             session.semantic_tx = semantic_tx.clone();
             session.snapshot = TerminalSnapshot::new(rows, cols, Some(Arc::clone(&writer)));
+            // 新快照的绝对行号从 0 重新计数，旧区间与命令跟踪一并作废。
+            session.shell_turns = ShellTurnTracker::default();
+            session.chat_start_row = None;
+            session.message_rows.clear();
             session.status = if status_locked {
                 TerminalSessionStatus::Connecting
             } else {
@@ -608,12 +832,19 @@ pub(crate) fn terminal_restart_post_ready(
         settings_service,
     );
     spawn_exit_watcher(
+        app.clone(),
         spawned.child,
         Arc::clone(&event_port),
         state.sessions.clone(),
         terminal_id.to_string(),
         spawn_epoch,
     );
+    spawn_session_id_discovery(
+        state.sessions.clone(),
+        state.session_repository(),
+        terminal_id.to_string(),
+        spawn_epoch,
+    );
 
     let _ = event_port.emit_status(status_payload);
     diagnostics_log_backend_event(
@@ -623,7 +854,7 @@ pub(crate) fn terminal_restart_post_ready(
         None,
         output_window_label.clone(),
         workspace_id.clone(),
-        "terminal_restart_post_ready",
+        "terminal_restart",
         json!({
           "terminalId": terminal_id,
           "memberId": member_id,
@@ -666,14 +897,14 @@ pub(crate) fn terminal_attach(
         cursor_col,
     ) = {
         let mut guard = lock_sessions(&state.sessions);
+        let now = guard.clock.now_ms();
         let session = guard
             .sessions
             .get_mut(&terminal_id)
             .ok_or_else(|| "terminal session not found".to_string())?;
         session.output_window_label = Some(output_window_label.clone());
         // 为了解决切换标签/attach 误触发 Working，布局期短暂抑制状态触发。
-        let now = now_millis().unwrap_or(0);
-        session.redraw_suppression_until = Some(now.saturating_add(REDRAW_SUPPRESSION_WINDOW_MS));
+        session.redraw_suppression_until = Some(now.saturating_add(session.tuning.redraw_suppression_window_ms));
         let snapshot = snapshot_service::snapshot_text_with_history(&session.snapshot);
         let data_len = snapshot.data.as_bytes().len();
         let history_len = snapshot
@@ -848,6 +1079,57 @@ pub(crate) fn terminal_snapshot_lines(
     })
 }
 
+/// 按消息或绝对行区间取回会话快照中的原文（纯文本与 ANSI）。
+/// 输入：`message_id` 优先，查已写回消息记录的区间；否则使用显式的 `start_row`/`end_row`。
+/// 错误：会话不存在、消息无区间记录或区间已滚出回滚缓冲。
+pub(crate) fn terminal_message_rows(
+    state: State<'_, TerminalManager>,
+    terminal_id: String,
+    message_id: Option<String>,
+    start_row: Option<u64>,
+    end_row: Option<u64>,
+) -> Result<TerminalMessageRowsPayload, String> {
+    let guard = lock_sessions(&state.sessions);
+    let session = guard
+        .sessions
+        .get(&terminal_id)
+        .ok_or_else(|| "terminal session not found".to_string())?;
+    let (start_row, end_row) = match (message_id.as_deref(), start_row, end_row) {
+        (Some(message_id), _, _) => session
+            .message_rows
+            .iter()
+            .rev()
+            .find(|range| range.message_id == message_id)
+            .map(|range| (range.start_row, range.end_row))
+            .ok_or_else(|| "message row range not found".to_string())?,
+        (None, Some(start_row), Some(end_row)) => (start_row, end_row),
+        _ => return Err("message id or row range required".to_string()),
+    };
+    let rows = session
+        .snapshot
+        .rows_in_range(start_row, end_row)
+        .ok_or_else(|| "message rows no longer available".to_string())?;
+    let text = rows
+        .lines
+        .iter()
+        .map(|line| line.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string();
+    Ok(TerminalMessageRowsPayload {
+        terminal_id,
+        message_id,
+        start_row: rows.start_row,
+        end_row: rows.end_row,
+        cursor_row: session.snapshot.absolute_cursor().0,
+        truncated: rows.start_row > start_row,
+        lines: rows.lines,
+        text,
+        ansi: String::from_utf8_lossy(&rows.ansi).to_string(),
+    })
+}
+
 /// 只读获取会话快照文本，不改变输出窗口路由。
 /// 返回：ANSI 快照与当前序列号。
 /// 错误：会话不存在或快照生成失败。
@@ -946,7 +1228,7 @@ pub(crate) fn terminal_write(
         None,
         None,
     );
-    let now = now_millis()?;
+    let now = clock_now(&state.sessions);
     let (should_write, buffered, writer, shell_ready, member_id, workspace_id, terminal_type) = {
         let mut guard = lock_sessions(&state.sessions);
         let session = guard
@@ -1007,6 +1289,67 @@ pub(crate) fn terminal_write(
     Ok(())
 }
 
+/// 按按键序列 DSL（见 `encode_key_sequence`）向终端写入，如 `<C-c>` 中断、`<Down><Enter>` 选择菜单项。
+/// 错误：序列无法解析，或同 `terminal_write`。
+pub(crate) fn terminal_send_keys(
+    app: AppHandle,
+    state: State<'_, TerminalManager>,
+    terminal_id: String,
+    keys: String,
+) -> Result<(), String> {
+    let data = encode_key_sequence(&keys)?;
+    terminal_write(app, state, terminal_id, data)
+}
+
+/// 按成员向其终端发送按键序列（供 `golutra keys` 命令使用）；返回目标终端 id。
+/// 等待审批的终端不接受按键，审批只能在应用内作答；每次发送记录发送方到诊断日志。
+/// 错误：成员在该工作区没有已打开的终端、终端正在等待审批，或同 `terminal_send_keys`。
+pub(crate) fn terminal_send_member_keys(
+    app: AppHandle,
+    workspace_id: &str,
+    sender_id: &str,
+    member_id: &str,
+    keys: String,
+) -> Result<String, String> {
+    let state = app.state::<TerminalManager>();
+    let terminal_id = {
+        let guard = lock_sessions(&state.sessions);
+        let (terminal_id, session) = guard
+            .member_sessions
+            .get(member_id)
+            .and_then(|terminal_id| {
+                guard
+                    .sessions
+                    .get(terminal_id.as_str())
+                    .map(|session| (terminal_id.clone(), session))
+            })
+            .filter(|(_, session)| session.workspace_id.as_deref() == Some(workspace_id))
+            .ok_or_else(|| format!("member {member_id} has no open terminal"))?;
+        if session.status == TerminalSessionStatus::AwaitingInput {
+            return Err(format!("member {member_id} is awaiting approval"));
+        }
+        terminal_id
+    };
+    diagnostics_log_backend_event(
+        &app.state::<DiagnosticsState>(),
+        Some(member_id.to_string()),
+        Some(terminal_id.clone()),
+        None,
+        None,
+        Some(workspace_id.to_string()),
+        "terminal_member_keys",
+        json!({
+          "terminalId": terminal_id,
+          "memberId": member_id,
+          "senderId": sender_id,
+          "workspaceId": workspace_id,
+          "keys": keys
+        }),
+    );
+    terminal_send_keys(app.clone(), state, terminal_id.clone(), keys)?;
+    Ok(terminal_id)
+}
+
 /// 设置会话在 UI 中是否活跃，用于流控与输出节流策略。
 /// 错误：会话不存在。
 pub(crate) fn terminal_set_active(
@@ -1015,6 +1358,7 @@ pub(crate) fn terminal_set_active(
     active: bool,
 ) -> Result<(), String> {
     let mut guard = lock_sessions(&state.sessions);
+    let now = guard.clock.now_ms();
     let session = guard
         .sessions
         .get_mut(&terminal_id)
@@ -1022,9 +1366,8 @@ pub(crate) fn terminal_set_active(
     session.ui_active = active;
     if active {
         session.idle_candidate_at = None;
-        let now = now_millis()?;
         // 切换为活跃时短暂抑制 Working，避免布局重绘导致误触发。
-        session.redraw_suppression_until = Some(now.saturating_add(REDRAW_SUPPRESSION_WINDOW_MS));
+        session.redraw_suppression_until = Some(now.saturating_add(session.tuning.redraw_suppression_window_ms));
     }
     if terminal_trace_detail() {
         log::info!(
@@ -1191,6 +1534,7 @@ pub(crate) fn terminal_resize(
         .sessions
         .lock()
         .map_err(|_| "terminal session lock poisoned".to_string())?;
+    let now = guard.clock.now_ms();
     let session = guard
         .sessions
         .get_mut(&terminal_id)
@@ -1203,9 +1547,9 @@ pub(crate) fn terminal_resize(
     session.screen_rows = rows;
     session.screen_cols = cols;
     session.snapshot.set_size(rows, cols);
+    record_session_event(session, |recorder| recorder.record_resize(rows, cols));
     // 为了解决切换标签/resize 误触发 Working，布局期短暂抑制状态触发。
-    let now = now_millis().unwrap_or(0);
-    session.redraw_suppression_until = Some(now.saturating_add(REDRAW_SUPPRESSION_WINDOW_MS));
+    session.redraw_suppression_until = Some(now.saturating_add(session.tuning.redraw_suppression_window_ms));
     let handle = session
         .handle
         .as_ref()
@@ -1243,6 +1587,102 @@ pub(crate) fn terminal_resize(
     Ok(())
 }
 
+/// 开启会话录制，写入工作区 `.golutra/recordings` 下的 asciicast v2 文件。
+/// 输入：`workspace_path` 缺省时使用会话启动目录（成员会话即工作区路径）。
+/// 返回：录制文件路径；已在录制时直接返回当前文件。
+/// 错误：会话不存在、无法定位工作区或文件创建失败。
+pub(crate) fn terminal_recording_start(
+    state: State<'_, TerminalManager>,
+    terminal_id: String,
+    workspace_path: Option<String>,
+) -> Result<String, String> {
+    let mut guard = lock_sessions(&state.sessions);
+    let session = guard
+        .sessions
+        .get_mut(&terminal_id)
+        .ok_or_else(|| "terminal session not found".to_string())?;
+    if let Some(recorder) = session.recorder.as_ref() {
+        return Ok(recorder.path().to_string_lossy().to_string());
+    }
+    let workspace_path = workspace_path
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .or_else(|| session.workspace_path.clone())
+        .ok_or_else(|| "terminal recording requires a workspace path".to_string())?;
+    let recorder = SessionRecorder::start(
+        &workspace_path,
+        &terminal_id,
+        session.screen_rows,
+        session.screen_cols,
+        session.member_name.as_deref(),
+    )?;
+    let path = recorder.path().to_string_lossy().to_string();
+    session.recorder = Some(recorder);
+    log::info!(
+        "terminal recording started terminal_id={} path={}",
+        terminal_id,
+        path
+    );
+    Ok(path)
+}
+
+/// 停止会话录制。
+/// 返回：已关闭的录制文件路径；未在录制时返回 None。
+/// 错误：会话不存在。
+pub(crate) fn terminal_recording_stop(
+    state: State<'_, TerminalManager>,
+    terminal_id: String,
+) -> Result<Option<String>, String> {
+    let recorder = {
+        let mut guard = lock_sessions(&state.sessions);
+        let session = guard
+            .sessions
+            .get_mut(&terminal_id)
+            .ok_or_else(|| "terminal session not found".to_string())?;
+        session.recorder.take()
+    };
+    // 在锁外结束录制器，等待写线程刷盘不阻塞其他会话操作。
+    Ok(recorder.map(|recorder| {
+        let path = recorder.path().to_string_lossy().to_string();
+        recorder.finish();
+        log::info!(
+            "terminal recording stopped terminal_id={} path={}",
+            terminal_id,
+            path
+        );
+        path
+    }))
+}
+
+/// 列出启动时注册的插件成员类型，按加载顺序返回。
+pub(crate) fn terminal_list_member_types() -> Vec<TerminalMemberTypePayload> {
+    plugin_members()
+        .iter()
+        .map(|member| TerminalMemberTypePayload {
+            id: member.id.to_string(),
+            terminal_type: member.terminal_type.to_string(),
+            default_command: member.default_command.to_string(),
+            unlimited_access: member.unlimited_access_flag.is_some(),
+            resumable: member.resume_command_template.is_some() || member.session_store.is_some(),
+        })
+        .collect()
+}
+
+/// 列出工作区内的录制文件名，最新在前。
+pub(crate) fn terminal_recording_list(workspace_path: String) -> Result<Vec<String>, String> {
+    recording::list_recordings(&workspace_path)
+}
+
+/// 回放录制文件，重建 `at_ms`（相对录制开始）时刻的屏幕快照；缺省为录制结尾。
+/// 错误：文件名非法、文件不存在或录制头损坏。
+pub(crate) fn terminal_recording_replay(
+    workspace_path: String,
+    file_name: String,
+    at_ms: Option<u64>,
+) -> Result<TerminalRecordingReplayPayload, String> {
+    recording::replay_recording(&workspace_path, &file_name, at_ms)
+}
+
 /// 关闭会话；`preserve=true` 仅关闭进程但保留会话条目。
 /// 错误：会话不存在。
 pub(crate) fn terminal_close(
@@ -1354,6 +1794,7 @@ pub(crate) fn terminal_close(
                         status: TerminalSessionStatus::Offline.as_str().to_string(),
                         member_id: removed.member_id.clone(),
                         workspace_id: removed.workspace_id.clone(),
+                        prompt: None,
                     });
                 }
                 member_id = removed.member_id;
@@ -0,0 +1,199 @@
+//! 脚本化的假 CLI：按虚拟时间产出贴近 codex/claude/gemini 的提示符与回复字节流。
+//! 另含一个带 shell 集成的假 shell，按 OSC 133 输出提示符与命令边界标记。
+//! 约束：只模拟过滤与状态规则关心的画面结构（提示符、bullet、spinner），不追求像素级还原。
+
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum FakeCliKind {
+    Codex,
+    Claude,
+    Gemini,
+    Shell,
+}
+
+impl FakeCliKind {
+    pub(super) fn terminal_type(self) -> &'static str {
+        match self {
+            FakeCliKind::Codex => "codex",
+            FakeCliKind::Claude => "claude",
+            FakeCliKind::Gemini => "gemini",
+            FakeCliKind::Shell => "shell",
+        }
+    }
+}
+
+const ECHO_DELAY_MS: u64 = 20; // 输入回显延迟，贴近真实 CLI 的重绘节奏。
+const SPINNER_START_MS: u64 = 100;
+const SPINNER_INTERVAL_MS: u64 = 250;
+const CODEX_COMPOSER: &str = "› Ask Codex to do anything";
+const PROMPT_BOX_TOP: &str = "╭──────────────────────────────╮";
+const CLAUDE_PROMPT_EMPTY: &str = "│ >                            │";
+const PROMPT_BOX_BOTTOM: &str = "╰──────────────────────────────╯";
+const GEMINI_PROMPT_EMPTY: &str = "│ >   Type your message        │";
+const SHIM_READY: &str = "\x1b]633;A\x07";
+const SHELL_PROMPT: &str = "\x1b]133;A\x07$ \x1b]133;B\x07";
+
+/// 假 CLI：维护按绝对虚拟时间排序的待输出脚本。
+pub(super) struct FakeCli {
+    kind: FakeCliKind,
+    script: VecDeque<(u64, Vec<u8>)>,
+    exit_code: i32,
+}
+
+impl FakeCli {
+    /// 创建假 CLI 并排入启动横幅与空闲提示符。
+    pub(super) fn new(kind: FakeCliKind, started_at: u64) -> Self {
+        let mut cli = Self {
+            kind,
+            script: VecDeque::new(),
+            exit_code: 0,
+        };
+        let (banner, prompt) = match kind {
+            FakeCliKind::Codex => (
+                ">_ OpenAI Codex (v0.0.0)\r\n\r\n".to_string(),
+                format!("To get started, describe a task.\r\n\r\n{CODEX_COMPOSER}"),
+            ),
+            FakeCliKind::Claude => (
+                "✻ Welcome to Claude Code!\r\n\r\n".to_string(),
+                claude_prompt_box(),
+            ),
+            FakeCliKind::Gemini => (
+                "Tips for getting started:\r\n\r\n".to_string(),
+                gemini_prompt_box(),
+            ),
+            FakeCliKind::Shell => (SHIM_READY.to_string(), SHELL_PROMPT.to_string()),
+        };
+        cli.push(started_at + 50, banner);
+        cli.push(started_at + 600, prompt);
+        cli
+    }
+
+    /// 收到一轮提交输入：回显输入、持续输出 spinner，`work_ms` 后写出回复与新提示符。
+    pub(super) fn submit(&mut self, now: u64, input: &str, reply: &[&str], work_ms: u64) {
+        let input = input.trim_end_matches(['\r', '\n']);
+        if self.kind == FakeCliKind::Shell {
+            self.submit_shell(now, input, reply, work_ms);
+            return;
+        }
+        let echo = match self.kind {
+            FakeCliKind::Codex => format!("\r\x1b[2K› {input}\r\n\r\n"),
+            // 光标回到提示框顶部并清到屏尾，输入回显为普通行，新提示框随回复重绘。
+            FakeCliKind::Claude | FakeCliKind::Gemini => {
+                format!("\x1b[2A\r\x1b[J> {input}\r\n\r\n")
+            }
+            FakeCliKind::Shell => format!("{input}\r\n"),
+        };
+        self.push(now + ECHO_DELAY_MS, echo);
+        let mut offset = SPINNER_START_MS;
+        let mut frame = 0u64;
+        while offset < work_ms {
+            self.push(now + offset, self.spinner_frame(frame));
+            offset += SPINNER_INTERVAL_MS;
+            frame += 1;
+        }
+        let work_ms = work_ms.max(SPINNER_START_MS);
+        self.push(now + work_ms, self.reply_block(reply));
+    }
+
+    /// 设置假 shell 下一条命令的退出码。
+    pub(super) fn set_exit_code(&mut self, exit_code: i32) {
+        self.exit_code = exit_code;
+    }
+
+    // 假 shell：回显后输出 C 标记与命令输出，`work_ms` 后输出 D 标记与新提示符。
+    // D 标记刻意拆在两个 chunk 中，覆盖跨 chunk 的序列解析。
+    fn submit_shell(&mut self, now: u64, input: &str, output: &[&str], work_ms: u64) {
+        self.push(now + ECHO_DELAY_MS, format!("{input}\r\n\x1b]133;C\x07"));
+        let mut body = String::new();
+        for line in output {
+            body.push_str(&format!("{line}\r\n"));
+        }
+        self.push(now + SPINNER_START_MS, body);
+        let work_ms = work_ms.max(SPINNER_START_MS);
+        self.push(now + work_ms, "\x1b]13".to_string());
+        self.push(
+            now + work_ms,
+            format!("3;D;{}\x07{SHELL_PROMPT}", self.exit_code),
+        );
+    }
+
+    /// 追加一段自定义输出，用于模拟重绘噪声等场景。
+    pub(super) fn emit_at(&mut self, at: u64, data: &str) {
+        self.push(at, data.to_string());
+    }
+
+    /// 取出截至 `now` 应输出的全部字节块。
+    pub(super) fn take_due(&mut self, now: u64) -> Vec<Vec<u8>> {
+        let mut due = Vec::new();
+        while let Some((at, _)) = self.script.front() {
+            if *at > now {
+                break;
+            }
+            if let Some((_, bytes)) = self.script.pop_front() {
+                due.push(bytes);
+            }
+        }
+        due
+    }
+
+    fn push(&mut self, at: u64, data: String) {
+        let index = self
+            .script
+            .iter()
+            .position(|(existing, _)| *existing > at)
+            .unwrap_or(self.script.len());
+        self.script.insert(index, (at, data.into_bytes()));
+    }
+
+    fn spinner_frame(&self, frame: u64) -> String {
+        let seconds = frame * SPINNER_INTERVAL_MS / 1000;
+        match self.kind {
+            FakeCliKind::Codex => {
+                format!("\r\x1b[2K• Working ({seconds}s • esc to interrupt)")
+            }
+            FakeCliKind::Claude => {
+                let glyph = ['·', '✢', '✳', '✶', '✻', '✽'][(frame % 6) as usize];
+                format!("\r\x1b[2K{glyph} Pondering… ({seconds}s · esc to interrupt)")
+            }
+            FakeCliKind::Gemini => {
+                let glyph = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴'][(frame % 6) as usize];
+                format!("\r\x1b[2K{glyph} Thinking... (esc to cancel, {seconds}s)")
+            }
+            FakeCliKind::Shell => String::new(),
+        }
+    }
+
+    fn reply_block(&self, reply: &[&str]) -> String {
+        let bullet = match self.kind {
+            FakeCliKind::Codex => "•",
+            FakeCliKind::Claude => "⏺",
+            FakeCliKind::Gemini => "✦",
+            FakeCliKind::Shell => "",
+        };
+        let mut output = String::from("\r\x1b[2K");
+        for (index, line) in reply.iter().enumerate() {
+            if index == 0 {
+                output.push_str(&format!("{bullet} {line}\r\n"));
+            } else {
+                output.push_str(&format!("  {line}\r\n"));
+            }
+        }
+        output.push_str("\r\n");
+        output.push_str(&match self.kind {
+            FakeCliKind::Codex => CODEX_COMPOSER.to_string(),
+            FakeCliKind::Claude => claude_prompt_box(),
+            FakeCliKind::Gemini => gemini_prompt_box(),
+            FakeCliKind::Shell => SHELL_PROMPT.to_string(),
+        });
+        output
+    }
+}
+
+fn claude_prompt_box() -> String {
+    format!("{PROMPT_BOX_TOP}\r\n{CLAUDE_PROMPT_EMPTY}\r\n{PROMPT_BOX_BOTTOM}")
+}
+
+fn gemini_prompt_box() -> String {
+    format!("{PROMPT_BOX_TOP}\r\n{GEMINI_PROMPT_EMPTY}\r\n{PROMPT_BOX_BOTTOM}")
+}
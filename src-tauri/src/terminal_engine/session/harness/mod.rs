@@ -0,0 +1,475 @@
+//! 无头终端引擎测试工具：不创建 Tauri 窗口与 PTY，用内存端口、脚本化假 CLI 与虚拟时钟
+//! 驱动会话注册、输出活跃度、轮询规则与语义 flush，便于在 CI 中捕获时序规则回归。
+//! 虚拟时钟以手动时钟注入 `TerminalManager`，引擎内部取时与测试推进共用同一时间轴。
+//! PTY 输出块处理与语义事件处理直接复用生产函数，语义事件在调用线程内同步处理。
+//! 边界：依赖 AppHandle 的路径（诊断、派发队列、post_ready 注入）只记录动作，不执行副作用。
+
+mod fake_cli;
+mod ports;
+mod tests;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::super::filters::FilterDecision;
+use super::super::semantic::{SemanticState, TerminalChatContext};
+use super::polling::actions::{ChatFlushMeta, PollAction};
+use super::polling::dispatcher::apply_session_update;
+use super::polling::{build_poll_actions, collect_poll_snapshots_by_ids, RuleMask};
+use super::clock::{Clock, ManualClock};
+use super::semantic_worker::{collect_flush_seed, SemanticEvent, SemanticStep, SemanticWorker};
+use super::state::{PostReadyMode, PostReadyState, TerminalSessionStatus, TerminalType};
+use super::{
+    ingest_output_chunk, lock_sessions, mark_session_working_on_input, register_session,
+    resolve_terminal_tuning, ApprovalPolicy, OutputIngest, RestartPolicy, SessionRegistration, TerminalManager,
+    STATUS_POLL_INTERVAL_MS,
+};
+use crate::ports::terminal_event::TerminalEventPort;
+use crate::ports::settings::TerminalSettingsPort;
+use crate::ports::terminal_message::TerminalMessagePipeline;
+use crate::ports::terminal_session::TerminalSessionRepository;
+use crate::terminal_engine::default_members::apply_resume_command;
+use crate::terminal_engine::emulator::RowRangeSnapshot;
+use crate::terminal_engine::models::TerminalErrorPayload;
+
+use fake_cli::{FakeCli, FakeCliKind};
+use ports::{MemoryEventPort, MemoryMessagePipeline, MemorySessionRepository, MemorySettings};
+
+const HARNESS_ROWS: u16 = 24;
+const HARNESS_COLS: u16 = 80;
+const HARNESS_WORKSPACE_ID: &str = "harness-workspace";
+const HARNESS_TICK_MS: u64 = 50; // 输出喂入粒度；轮询仍按 STATUS_POLL_INTERVAL_MS 执行。
+const HARNESS_START_MS: u64 = 1_000_000; // 虚拟时钟起点，避免 0 时刻与“未设置”语义混淆。
+
+/// 一次语义 flush 的观测记录。
+struct FlushRecord {
+    terminal_id: String,
+    at: u64,
+    silence_ms: Option<u64>,
+    decision: FilterDecision,
+    reason: &'static str,
+    content: Option<String>,
+}
+
+struct HeadlessTerminal {
+    cli: FakeCli,
+    semantic: SemanticWorker,
+}
+
+/// 无头引擎：持有真实 `TerminalManager` 与内存端口，时间只在 `advance` 时前进。
+struct HeadlessEngine {
+    manager: TerminalManager,
+    events: Arc<MemoryEventPort>,
+    messages: Arc<MemoryMessagePipeline>,
+    repository: Arc<MemorySessionRepository>,
+    settings: Arc<MemorySettings>,
+    clock: Arc<ManualClock>,
+    last_poll_at: u64,
+    next_terminal_index: usize,
+    terminals: HashMap<String, HeadlessTerminal>,
+    terminal_order: Vec<String>,
+    actions: Vec<(u64, PollAction)>,
+    flushes: Vec<FlushRecord>,
+}
+
+impl HeadlessEngine {
+    fn new() -> Self {
+        let manager = TerminalManager::default();
+        let events = Arc::new(MemoryEventPort::default());
+        let messages = Arc::new(MemoryMessagePipeline::default());
+        let repository = Arc::new(MemorySessionRepository::default());
+        let settings = Arc::new(MemorySettings::default());
+        let clock = Arc::new(ManualClock::new(HARNESS_START_MS));
+        manager.set_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        manager.set_event_port(Arc::clone(&events) as Arc<dyn TerminalEventPort>);
+        manager.set_message_pipeline(Arc::clone(&messages) as Arc<dyn TerminalMessagePipeline>);
+        manager
+            .set_session_repository(Arc::clone(&repository) as Arc<dyn TerminalSessionRepository>);
+        Self {
+            manager,
+            events,
+            messages,
+            repository,
+            settings,
+            clock,
+            last_poll_at: HARNESS_START_MS,
+            next_terminal_index: 1,
+            terminals: HashMap::new(),
+            terminal_order: Vec::new(),
+            actions: Vec::new(),
+            flushes: Vec::new(),
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// 按终端创建流程注册会话：非邀请模式下从会话映射查询 session_id 并拼接 resume 命令。
+    fn spawn(&mut self, kind: FakeCliKind, member_id: &str, mode: PostReadyMode) -> String {
+        self.spawn_with_member_tuning(kind, member_id, mode, None)
+    }
+
+    /// 同 `spawn`，额外模拟项目数据中的成员调优覆盖（`members[].terminalTuning`）。
+    fn spawn_with_member_tuning(
+        &mut self,
+        kind: FakeCliKind,
+        member_id: &str,
+        mode: PostReadyMode,
+        member_tuning: Option<serde_json::Value>,
+    ) -> String {
+        let terminal_type = kind.terminal_type();
+        let terminal_id = format!("harness-{terminal_type}-{}", self.next_terminal_index);
+        self.next_terminal_index += 1;
+        let launch_command = if mode.should_run() {
+            None
+        } else {
+            let session_id = self
+                .manager
+                .session_repository()
+                .get_terminal_session(HARNESS_WORKSPACE_ID, member_id)
+                .ok()
+                .flatten();
+            apply_resume_command(terminal_type, None, session_id.as_deref())
+        };
+        let tuning = resolve_terminal_tuning(
+            terminal_type,
+            Some(self.settings.as_ref() as &dyn TerminalSettingsPort),
+            member_tuning.as_ref(),
+        );
+        let registration = SessionRegistration {
+            member_id: Some(member_id.to_string()),
+            member_name: Some(member_id.to_string()),
+            workspace_id: Some(HARNESS_WORKSPACE_ID.to_string()),
+            workspace_path: None,
+            launch_cwd: None,
+            launch_command,
+            launch_path: None,
+            launch_strict_shell: false,
+            launch_sandboxed: false,
+            post_ready_mode: mode,
+            rows: HARNESS_ROWS,
+            cols: HARNESS_COLS,
+            terminal_type: TerminalType::from_str(Some(terminal_type)).unwrap_or(TerminalType::Shell),
+            custom_type: None,
+            keep_alive: false,
+            owner_window_label: None,
+            output_window_label: None,
+            semantic_tx: None,
+            handle: None,
+            tuning,
+            restart_policy: RestartPolicy::default(),
+            approval_policy: ApprovalPolicy::default(),
+            onboarding: None,
+            post_ready_script: None,
+        };
+        let payload = register_session(&self.manager, &terminal_id, registration)
+            .expect("register headless session");
+        let _ = self.manager.event_port().emit_status(payload);
+        let semantic = SemanticWorker::new(SemanticState::new(
+            terminal_id.clone(),
+            Some(member_id.to_string()),
+            Some(HARNESS_WORKSPACE_ID.to_string()),
+            HARNESS_ROWS,
+            HARNESS_COLS,
+            terminal_type.to_string(),
+            Some(self.settings.as_ref() as &dyn TerminalSettingsPort),
+        ));
+        self.terminals.insert(
+            terminal_id.clone(),
+            HeadlessTerminal {
+                cli: FakeCli::new(kind, self.now()),
+                semantic,
+            },
+        );
+        self.terminal_order.push(terminal_id.clone());
+        terminal_id
+    }
+
+    /// 以聊天派发的方式提交输入：会话进入 chat pending，假 CLI 在 `work_ms` 后写出回复。
+    fn send_chat(
+        &mut self,
+        terminal_id: &str,
+        conversation_id: &str,
+        input: &str,
+        reply: &[&str],
+        work_ms: u64,
+    ) {
+        assert!(
+            self.with_session(terminal_id, |session| session.shell_ready),
+            "headless session must be shell ready before chat input"
+        );
+        let data = format!("{input}\r");
+        let context = TerminalChatContext {
+            conversation_id: conversation_id.to_string(),
+            conversation_type: "direct".to_string(),
+            sender_id: "harness-user".to_string(),
+            sender_name: "Harness".to_string(),
+        };
+        let event_port = self.manager.event_port();
+        mark_session_working_on_input(
+            &self.manager.sessions,
+            event_port.as_ref(),
+            terminal_id,
+            &data,
+            Some(context.clone()),
+            None,
+            None,
+        );
+        let now = self.now();
+        let pipeline = self.manager.message_pipeline();
+        let sessions = Arc::clone(&self.manager.sessions);
+        let settings = Arc::clone(&self.settings);
+        let terminal = self.terminal_mut(terminal_id);
+        terminal.semantic.handle(
+            SemanticEvent::UserInput { data, context },
+            pipeline.as_ref(),
+            &sessions,
+            Some(settings.as_ref() as &dyn TerminalSettingsPort),
+            now,
+        );
+        terminal.cli.submit(now, input, reply, work_ms);
+    }
+
+    /// 以用户按键的方式写入输入（不进入 chat pending），如审批提示的作答。
+    fn write_input(&mut self, terminal_id: &str, data: &str) {
+        let event_port = self.manager.event_port();
+        mark_session_working_on_input(
+            &self.manager.sessions,
+            event_port.as_ref(),
+            terminal_id,
+            data,
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// 设置假 shell 下一条命令的退出码。
+    fn set_exit_code(&mut self, terminal_id: &str, exit_code: i32) {
+        self.terminal_mut(terminal_id).cli.set_exit_code(exit_code);
+    }
+
+    /// 在 `delay_ms` 后让假 CLI 输出一段任意内容（如布局重绘）。
+    fn emit_output_after(&mut self, terminal_id: &str, delay_ms: u64, data: &str) {
+        let at = self.now().saturating_add(delay_ms);
+        self.terminal_mut(terminal_id).cli.emit_at(at, data);
+    }
+
+    /// 推进虚拟时间：按 tick 喂入到期输出，并按轮询间隔执行规则与动作。
+    fn advance(&mut self, duration_ms: u64) {
+        let end = self.now().saturating_add(duration_ms);
+        while self.now() < end {
+            let now = self.clock.advance(HARNESS_TICK_MS.min(end - self.now()));
+            self.feed_due_output();
+            if now.saturating_sub(self.last_poll_at) >= STATUS_POLL_INTERVAL_MS {
+                self.last_poll_at = now;
+                self.poll();
+            }
+        }
+    }
+
+    /// 推进直到条件满足或超出上限；返回是否满足。
+    fn advance_until(
+        &mut self,
+        limit_ms: u64,
+        mut predicate: impl FnMut(&HeadlessEngine) -> bool,
+    ) -> bool {
+        let end = self.now().saturating_add(limit_ms);
+        while self.now() < end {
+            if predicate(self) {
+                return true;
+            }
+            self.advance(HARNESS_TICK_MS);
+        }
+        predicate(self)
+    }
+
+    fn status(&self, terminal_id: &str) -> TerminalSessionStatus {
+        self.with_session(terminal_id, |session| session.status)
+    }
+
+    fn chat_pending(&self, terminal_id: &str) -> bool {
+        self.with_session(terminal_id, |session| session.chat_pending)
+    }
+
+    /// 按已写回消息的 id 取回其在会话快照中的行区间内容。
+    fn message_rows(&self, terminal_id: &str, message_id: &str) -> Option<RowRangeSnapshot> {
+        self.with_session(terminal_id, |session| {
+            let range = session
+                .message_rows
+                .iter()
+                .find(|range| range.message_id == message_id)?;
+            session.snapshot.rows_in_range(range.start_row, range.end_row)
+        })
+    }
+
+    fn shell_ready(&self, terminal_id: &str) -> bool {
+        self.with_session(terminal_id, |session| session.shell_ready)
+    }
+
+    fn launch_command(&self, terminal_id: &str) -> Option<String> {
+        self.with_session(terminal_id, |session| session.launch_command.clone())
+    }
+
+    fn last_output_at(&self, terminal_id: &str) -> Option<u64> {
+        self.with_session(terminal_id, |session| session.last_output_at)
+    }
+
+    fn screen_lines(&self, terminal_id: &str) -> Vec<String> {
+        self.with_session(terminal_id, |session| session.snapshot.snapshot_lines())
+    }
+
+    fn flushes(&self, terminal_id: &str) -> Vec<&FlushRecord> {
+        self.flushes
+            .iter()
+            .filter(|record| record.terminal_id == terminal_id)
+            .collect()
+    }
+
+    /// 返回满足条件的轮询动作及其发生时间。
+    fn actions_matching(&self, predicate: impl Fn(&PollAction) -> bool) -> Vec<(u64, PollAction)> {
+        self.actions
+            .iter()
+            .filter(|(_, action)| predicate(action))
+            .cloned()
+            .collect()
+    }
+
+    fn with_session<R>(
+        &self,
+        terminal_id: &str,
+        read: impl FnOnce(&mut super::state::TerminalSession) -> R,
+    ) -> R {
+        let mut guard = lock_sessions(&self.manager.sessions);
+        let session = guard
+            .sessions
+            .get_mut(terminal_id)
+            .expect("headless session registered");
+        read(session)
+    }
+
+    fn terminal_mut(&mut self, terminal_id: &str) -> &mut HeadlessTerminal {
+        self.terminals
+            .get_mut(terminal_id)
+            .expect("headless terminal registered")
+    }
+
+    // 与 PTY 处理线程共用 `ingest_output_chunk`；输出事件推送与输入缓冲释放依赖真实线程，不在此模拟。
+    fn feed_due_output(&mut self) {
+        let now = self.now();
+        let event_port = self.manager.event_port();
+        for terminal_id in self.terminal_order.clone() {
+            let chunks = self.terminal_mut(&terminal_id).cli.take_due(now);
+            for chunk in chunks {
+                let data = String::from_utf8_lossy(&chunk).to_string();
+                let OutputIngest {
+                    status_payload,
+                    shim_error,
+                    ..
+                } = {
+                    let mut guard = lock_sessions(&self.manager.sessions);
+                    let working_sessions = Arc::clone(&guard.working_sessions);
+                    let Some(session) = guard.sessions.get_mut(&terminal_id) else {
+                        continue;
+                    };
+                    ingest_output_chunk(&working_sessions, session, &chunk, &data, now)
+                };
+                if let Some(payload) = status_payload {
+                    let _ = event_port.emit_status(payload);
+                }
+                if let Some(error) = shim_error {
+                    let _ = event_port.emit_error(TerminalErrorPayload {
+                        terminal_id: terminal_id.clone(),
+                        error,
+                        fatal: true,
+                    });
+                }
+            }
+        }
+    }
+
+    // 对齐状态轮询器：采集快照 -> 规则判定 -> 执行动作（依赖 AppHandle 的动作仅记录）。
+    fn poll(&mut self) {
+        let mask = RuleMask::STATUS_FALLBACK
+            | RuleMask::SEMANTIC_FLUSH
+            | RuleMask::POST_READY
+            | RuleMask::APPROVAL_PROMPT;
+        let now = self.now();
+        let snapshots =
+            collect_poll_snapshots_by_ids(&self.manager.sessions, &self.terminal_order, now, mask);
+        let actions = build_poll_actions(&snapshots, now, mask);
+        let mut status_payloads = Vec::new();
+        {
+            let mut guard = lock_sessions(&self.manager.sessions);
+            let working_sessions = Arc::clone(&guard.working_sessions);
+            for action in actions.iter() {
+                match action {
+                    PollAction::SessionUpdate(update) => {
+                        if let Some(session) = guard.sessions.get_mut(&update.terminal_id) {
+                            apply_session_update(
+                                &working_sessions,
+                                session,
+                                update,
+                                &mut status_payloads,
+                            );
+                        }
+                    }
+                    // 无头模式不执行注入步骤，只记录触发并结束流程，避免每轮重复触发。
+                    PollAction::PostReadyStart { terminal_id, .. } => {
+                        if let Some(session) = guard.sessions.get_mut(terminal_id) {
+                            session.post_ready_state = PostReadyState::Done;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let event_port = self.manager.event_port();
+        for payload in status_payloads {
+            let _ = event_port.emit_status(payload);
+        }
+        for action in actions {
+            if let PollAction::SemanticFlush(meta) = &action {
+                self.run_semantic_flush(meta);
+            }
+            self.actions.push((now, action));
+        }
+    }
+
+    // 与轮询派发器、语义线程共用种子采集与事件处理；诊断与派发队列推进依赖 AppHandle，不在此执行。
+    fn run_semantic_flush(&mut self, meta: &ChatFlushMeta) {
+        let seed = self.with_session(&meta.terminal_id, |session| {
+            collect_flush_seed(session, meta.chat_pending_since)
+        });
+        let now = self.now();
+        let pipeline = self.manager.message_pipeline();
+        let sessions = Arc::clone(&self.manager.sessions);
+        let settings = Arc::clone(&self.settings);
+        let terminal = self.terminal_mut(&meta.terminal_id);
+        let mut report = None;
+        for event in seed.events {
+            let step = terminal.semantic.handle(
+                event,
+                pipeline.as_ref(),
+                &sessions,
+                Some(settings.as_ref() as &dyn TerminalSettingsPort),
+                now,
+            );
+            if let SemanticStep::Flushed(flushed) = step {
+                report = Some(flushed);
+            }
+        }
+        let Some(report) = report else {
+            return;
+        };
+        self.flushes.push(FlushRecord {
+            terminal_id: meta.terminal_id.clone(),
+            at: now,
+            silence_ms: meta.silence_ms,
+            decision: report.filter_result.decision,
+            reason: report.filter_result.reason,
+            content: report.content,
+        });
+    }
+}
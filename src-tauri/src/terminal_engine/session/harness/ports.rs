@@ -0,0 +1,176 @@
+//! 内存端口实现：替代 Tauri 事件通道、消息服务与会话映射存储，供无头测试断言。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use serde_json::Value;
+
+use crate::contracts::terminal_message::TerminalMessagePayload;
+use crate::ports::message_service::TerminalMessageAppendResult;
+use crate::ports::settings::TerminalSettingsPort;
+use crate::ports::terminal_event::TerminalEventPort;
+use crate::ports::terminal_message::TerminalMessagePipeline;
+use crate::ports::terminal_session::TerminalSessionRepository;
+use crate::terminal_engine::models::{
+    TerminalErrorPayload, TerminalExitPayload, TerminalOutputPayload, TerminalStatusPayload,
+};
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// 记录状态与错误事件；输出事件由引擎直接喂给快照，这里不保留字节。
+#[derive(Default)]
+pub(super) struct MemoryEventPort {
+    statuses: Mutex<Vec<(String, String)>>,
+    errors: Mutex<Vec<String>>,
+}
+
+impl MemoryEventPort {
+    /// 返回指定终端的状态变更序列。
+    pub(super) fn statuses(&self, terminal_id: &str) -> Vec<String> {
+        lock(&self.statuses)
+            .iter()
+            .filter(|(id, _)| id == terminal_id)
+            .map(|(_, status)| status.clone())
+            .collect()
+    }
+
+    pub(super) fn errors(&self) -> Vec<String> {
+        lock(&self.errors).clone()
+    }
+}
+
+impl TerminalEventPort for MemoryEventPort {
+    fn emit_output(
+        &self,
+        _window_label: Option<&str>,
+        _payload: TerminalOutputPayload,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn emit_status(&self, payload: TerminalStatusPayload) -> Result<(), String> {
+        lock(&self.statuses).push((payload.terminal_id, payload.status));
+        Ok(())
+    }
+
+    fn emit_error(&self, payload: TerminalErrorPayload) -> Result<(), String> {
+        lock(&self.errors).push(payload.error);
+        Ok(())
+    }
+
+    fn emit_exit(&self, _payload: TerminalExitPayload) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 记录 stream/final 消息；final 按顺序分配消息 id 并视为已落库。
+#[derive(Default)]
+pub(super) struct MemoryMessagePipeline {
+    streams: Mutex<Vec<TerminalMessagePayload>>,
+    finals: Mutex<Vec<TerminalMessagePayload>>,
+}
+
+impl MemoryMessagePipeline {
+    pub(super) fn finals(&self) -> Vec<TerminalMessagePayload> {
+        lock(&self.finals).clone()
+    }
+}
+
+impl TerminalMessagePipeline for MemoryMessagePipeline {
+    fn process_stream(&self, payload: TerminalMessagePayload) -> Result<(), String> {
+        lock(&self.streams).push(payload);
+        Ok(())
+    }
+
+    fn process_final(
+        &self,
+        payload: TerminalMessagePayload,
+    ) -> Result<TerminalMessageAppendResult, String> {
+        let mut finals = lock(&self.finals);
+        finals.push(payload);
+        Ok(TerminalMessageAppendResult::persisted(format!(
+            "harness-message-{}",
+            finals.len()
+        )))
+    }
+}
+
+/// 以 (workspace_id, member_id) 为键的内存会话映射。
+#[derive(Default)]
+pub(super) struct MemorySessionRepository {
+    entries: Mutex<HashMap<(String, String), String>>,
+}
+
+impl TerminalSessionRepository for MemorySessionRepository {
+    fn upsert_terminal_session(
+        &self,
+        workspace_id: &str,
+        member_id: &str,
+        session_id: &str,
+    ) -> Result<(), String> {
+        lock(&self.entries).insert(
+            (workspace_id.to_string(), member_id.to_string()),
+            session_id.to_string(),
+        );
+        Ok(())
+    }
+
+    fn delete_terminal_session(&self, workspace_id: &str, member_id: &str) -> Result<(), String> {
+        lock(&self.entries).remove(&(workspace_id.to_string(), member_id.to_string()));
+        Ok(())
+    }
+
+    fn get_terminal_session(
+        &self,
+        workspace_id: &str,
+        member_id: &str,
+    ) -> Result<Option<String>, String> {
+        Ok(lock(&self.entries)
+            .get(&(workspace_id.to_string(), member_id.to_string()))
+            .cloned())
+    }
+}
+
+/// 全局设置替身：仅提供按终端类型的调优覆盖与声明式过滤规则。
+#[derive(Default)]
+pub(super) struct MemorySettings {
+    tuning: Mutex<HashMap<String, Value>>,
+    filter_rules: Mutex<HashMap<String, Value>>,
+}
+
+impl MemorySettings {
+    pub(super) fn set_terminal_tuning(&self, terminal_type: &str, value: Value) {
+        lock(&self.tuning).insert(terminal_type.to_string(), value);
+    }
+
+    pub(super) fn set_terminal_filter_rules(&self, terminal_type: &str, value: Value) {
+        lock(&self.filter_rules).insert(terminal_type.to_string(), value);
+    }
+}
+
+impl TerminalSettingsPort for MemorySettings {
+    fn get_language(&self) -> Option<String> {
+        None
+    }
+
+    fn get_chat_stream_enabled(&self) -> Option<bool> {
+        None
+    }
+
+    fn get_terminal_tuning(&self, terminal_type: &str) -> Option<Value> {
+        lock(&self.tuning).get(terminal_type).cloned()
+    }
+
+    fn get_terminal_filter_rules(&self, terminal_type: &str) -> Option<Value> {
+        lock(&self.filter_rules).get(terminal_type).cloned()
+    }
+
+    fn get_member_hibernate_after_minutes(&self) -> Option<u64> {
+        None
+    }
+}
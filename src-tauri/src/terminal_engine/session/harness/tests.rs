@@ -0,0 +1,551 @@
+//! 无头引擎场景测试：覆盖语义 flush 时序、Working 回落、post_ready 触发与过滤结果。
+
+use serde_json::json;
+
+use super::super::hibernation::hibernation_due;
+use super::super::keyboard_input::paste_multiline_input;
+use super::super::polling::actions::PollAction;
+use super::super::post_ready_script::{PostReadyScript, PostReadyScriptStep};
+use super::super::state::{PostReadyMode, TerminalSessionStatus, TerminalSnapshot};
+use super::super::{
+    APPROVAL_PROMPT_SETTLE_MS, CHAT_IDLE_DEBOUNCE_MS, CHAT_PENDING_FORCE_FLUSH_MS, CHAT_SILENCE_TIMEOUT_MS,
+    STATUS_IDLE_DEBOUNCE_MS, STATUS_POLL_INTERVAL_MS, STATUS_WORKING_SILENCE_TIMEOUT_MS,
+    WORKING_INTENT_WINDOW_MS,
+};
+use super::{FakeCliKind, HeadlessEngine, HARNESS_WORKSPACE_ID};
+use crate::ports::terminal_session::TerminalSessionRepository;
+use crate::terminal_engine::filters::FilterDecision;
+
+const STARTUP_MS: u64 = 1000;
+const FLUSH_SETTLE_MS: u64 =
+    CHAT_SILENCE_TIMEOUT_MS + CHAT_IDLE_DEBOUNCE_MS + 2 * STATUS_POLL_INTERVAL_MS;
+
+fn spawn_ready(engine: &mut HeadlessEngine, kind: FakeCliKind, member_id: &str) -> String {
+    let terminal_id = engine.spawn(kind, member_id, PostReadyMode::Disabled);
+    engine.advance(STARTUP_MS);
+    assert!(
+        engine.shell_ready(&terminal_id),
+        "fake {kind:?} should become shell ready"
+    );
+    terminal_id
+}
+
+#[test]
+fn codex_reply_becomes_final_message() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(
+        &terminal_id,
+        "conv-1",
+        "fix the parser",
+        &["Fixed the null check in parser.rs.", "", "All tests pass."],
+        2000,
+    );
+    engine.advance(2000 + FLUSH_SETTLE_MS);
+
+    let flushes = engine.flushes(&terminal_id);
+    assert_eq!(flushes.len(), 1);
+    assert_eq!(flushes[0].decision, FilterDecision::Allow);
+    let finals = engine.messages.finals();
+    assert_eq!(finals.len(), 1);
+    let message = &finals[0];
+    assert_eq!(
+        flushes[0].content.as_deref(),
+        Some(message.content.as_str())
+    );
+    assert_eq!(message.conversation_id.as_deref(), Some("conv-1"));
+    assert_eq!(message.member_id.as_deref(), Some("member-codex"));
+    assert_eq!(message.mode, "final");
+    assert_eq!(
+        message.content.lines().map(str::trim).collect::<Vec<_>>(),
+        vec!["Fixed the null check in parser.rs.", "", "All tests pass."]
+    );
+    assert!(!engine.chat_pending(&terminal_id));
+}
+
+#[test]
+fn input_path_reads_injected_clock() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    let sent_at = engine.now();
+    engine.send_chat(&terminal_id, "conv-1", "quick", &["Ok."], 1000);
+    let (pending_since, intent_until, created_at) = engine.with_session(&terminal_id, |session| {
+        (
+            session.chat_pending_since,
+            session.working_intent_until,
+            session.created_at,
+        )
+    });
+    assert_eq!(pending_since, Some(sent_at));
+    assert_eq!(intent_until, Some(sent_at + WORKING_INTENT_WINDOW_MS));
+    assert_eq!(created_at, sent_at - STARTUP_MS);
+}
+
+#[test]
+fn semantic_flush_waits_for_silence_and_debounce() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "long task", &["Done."], 6000);
+
+    // spinner 持续输出期间不应 flush。
+    engine.advance(6000);
+    assert!(engine.flushes(&terminal_id).is_empty());
+    assert!(engine.chat_pending(&terminal_id));
+
+    let reply_at = engine.last_output_at(&terminal_id).expect("reply output");
+    assert!(engine.advance_until(FLUSH_SETTLE_MS, |engine| {
+        !engine.flushes(&terminal_id).is_empty()
+    }));
+    let flush = engine.flushes(&terminal_id)[0];
+    let waited = flush.at - reply_at;
+    assert!(
+        waited >= CHAT_SILENCE_TIMEOUT_MS + CHAT_IDLE_DEBOUNCE_MS,
+        "flushed too early after {waited}ms"
+    );
+    assert!(
+        waited <= FLUSH_SETTLE_MS,
+        "flushed too late after {waited}ms"
+    );
+    assert!(flush.silence_ms.unwrap_or(0) >= CHAT_SILENCE_TIMEOUT_MS);
+    assert_eq!(engine.messages.finals().len(), 1);
+}
+
+#[test]
+fn semantic_flush_is_forced_when_output_never_settles() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    let sent_at = engine.now();
+    engine.send_chat(
+        &terminal_id,
+        "conv-1",
+        "endless task",
+        &["Never printed."],
+        60_000,
+    );
+
+    assert!(
+        engine.advance_until(CHAT_PENDING_FORCE_FLUSH_MS + 1000, |engine| {
+            !engine.flushes(&terminal_id).is_empty()
+        })
+    );
+    let flush = engine.flushes(&terminal_id)[0];
+    let waited = flush.at - sent_at;
+    assert!(waited >= CHAT_PENDING_FORCE_FLUSH_MS);
+    assert!(waited <= CHAT_PENDING_FORCE_FLUSH_MS + STATUS_POLL_INTERVAL_MS);
+    // spinner 尚未被回复替换，也没有下一条提示符：过滤器等待边界，不写回。
+    assert_eq!(flush.decision, FilterDecision::Defer);
+    assert!(engine.messages.finals().is_empty());
+    assert!(!engine.chat_pending(&terminal_id));
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Working);
+}
+
+#[test]
+fn working_falls_back_to_online_after_silence() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "quick", &["Ok."], 1000);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Working);
+
+    engine.advance(1000);
+    let reply_at = engine.last_output_at(&terminal_id).expect("reply output");
+    engine.advance(STATUS_WORKING_SILENCE_TIMEOUT_MS - 500);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Working);
+
+    assert!(engine.advance_until(
+        STATUS_IDLE_DEBOUNCE_MS + 3 * STATUS_POLL_INTERVAL_MS,
+        |engine| engine.status(&terminal_id) == TerminalSessionStatus::Online
+    ));
+    let waited = engine.now() - reply_at;
+    assert!(waited >= STATUS_WORKING_SILENCE_TIMEOUT_MS + STATUS_IDLE_DEBOUNCE_MS);
+    assert_eq!(
+        engine.events.statuses(&terminal_id),
+        vec!["online", "working", "online"]
+    );
+}
+
+#[test]
+fn background_redraw_does_not_enter_working() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "quick", &["Ok."], 500);
+    engine.advance(500 + FLUSH_SETTLE_MS + STATUS_WORKING_SILENCE_TIMEOUT_MS);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Online);
+
+    // 意图窗口之外的布局重绘只更新活跃时间，不切回 Working。
+    engine.emit_output_after(
+        &terminal_id,
+        WORKING_INTENT_WINDOW_MS,
+        "\x1b[24;1H\x1b[2K› Ask Codex to do anything",
+    );
+    engine.advance(WORKING_INTENT_WINDOW_MS + 1000);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Online);
+    assert_eq!(
+        engine.events.statuses(&terminal_id),
+        vec!["online", "working", "online"]
+    );
+}
+
+#[test]
+fn invite_mode_starts_post_ready_once_shell_ready() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = engine.spawn(FakeCliKind::Codex, "member-codex", PostReadyMode::Invite);
+    let is_start = |action: &PollAction| matches!(action, PollAction::PostReadyStart { .. });
+
+    engine.advance(400);
+    assert!(!engine.shell_ready(&terminal_id));
+    assert!(engine.actions_matching(is_start).is_empty());
+
+    engine.advance(STARTUP_MS);
+    assert!(engine.shell_ready(&terminal_id));
+    let starts = engine.actions_matching(is_start);
+    assert_eq!(starts.len(), 1);
+    match &starts[0].1 {
+        PollAction::PostReadyStart {
+            terminal_id: started,
+            terminal_type,
+        } => {
+            assert_eq!(started, &terminal_id);
+            assert_eq!(terminal_type, "codex");
+        }
+        other => panic!("unexpected action {other:?}"),
+    }
+
+    let disabled = engine.spawn(FakeCliKind::Codex, "member-other", PostReadyMode::Disabled);
+    engine.advance(STARTUP_MS);
+    assert!(engine.shell_ready(&disabled));
+    assert_eq!(engine.actions_matching(is_start).len(), 1);
+}
+
+#[test]
+fn stored_session_id_resumes_only_outside_invite() {
+    let mut engine = HeadlessEngine::new();
+    engine
+        .repository
+        .upsert_terminal_session(HARNESS_WORKSPACE_ID, "member-codex", "session-42")
+        .expect("upsert session");
+
+    let resumed = engine.spawn(FakeCliKind::Codex, "member-codex", PostReadyMode::Disabled);
+    let command = engine.launch_command(&resumed).unwrap_or_default();
+    assert!(
+        command.contains("resume session-42"),
+        "command was {command:?}"
+    );
+
+    let invited = engine.spawn(FakeCliKind::Codex, "member-codex", PostReadyMode::Invite);
+    assert_eq!(engine.launch_command(&invited), None);
+
+    let fresh = engine.spawn(FakeCliKind::Codex, "member-new", PostReadyMode::Disabled);
+    assert_eq!(engine.launch_command(&fresh), None);
+}
+
+#[test]
+fn claude_and_gemini_replies_follow_flush_timing() {
+    for (kind, bullet_text) in [
+        (FakeCliKind::Claude, "I updated the README."),
+        (FakeCliKind::Gemini, "The build is green."),
+    ] {
+        let mut engine = HeadlessEngine::new();
+        let terminal_id = spawn_ready(&mut engine, kind, "member-ai");
+        engine.send_chat(&terminal_id, "conv-1", "status?", &[bullet_text], 1500);
+        engine.advance(1500);
+        assert!(engine.flushes(&terminal_id).is_empty());
+        assert!(engine
+            .screen_lines(&terminal_id)
+            .iter()
+            .any(|line| line.contains(bullet_text)));
+
+        engine.advance(FLUSH_SETTLE_MS);
+        let flushes = engine.flushes(&terminal_id);
+        assert_eq!(flushes.len(), 1, "{kind:?} should flush exactly once");
+        // 各自的 profile 按 `>` 回显定位回复，截到输入框为止。
+        assert_eq!(flushes[0].decision, FilterDecision::Allow, "{kind:?}");
+        assert_eq!(flushes[0].reason, "transcript-reply", "{kind:?}");
+        let finals = engine.messages.finals();
+        assert_eq!(finals.len(), 1, "{kind:?}");
+        assert_eq!(finals[0].content.trim(), bullet_text, "{kind:?}");
+        assert!(!engine.chat_pending(&terminal_id));
+
+        engine.advance(STATUS_WORKING_SILENCE_TIMEOUT_MS);
+        assert_eq!(
+            engine.status(&terminal_id),
+            TerminalSessionStatus::Online,
+            "{kind:?}"
+        );
+        assert!(engine.events.errors().is_empty());
+    }
+}
+
+#[test]
+fn configured_filter_rules_replace_builtin_profile() {
+    let mut engine = HeadlessEngine::new();
+    engine.settings.set_terminal_filter_rules(
+        "codex",
+        json!({
+            "promptPattern": "^›\\s*(.*)$",
+            "bulletMarkers": ["•"],
+            "stripPatterns": ["^\\s*tokens used"]
+        }),
+    );
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(
+        &terminal_id,
+        "conv-1",
+        "fix the parser",
+        &["Fixed the null check.", "tokens used: 1.2k"],
+        1500,
+    );
+    engine.advance(1500 + FLUSH_SETTLE_MS);
+
+    let flushes = engine.flushes(&terminal_id);
+    assert_eq!(flushes.len(), 1);
+    assert_eq!(flushes[0].decision, FilterDecision::Allow);
+    assert_eq!(flushes[0].reason, "transcript-reply");
+    let finals = engine.messages.finals();
+    assert_eq!(finals.len(), 1);
+    assert_eq!(finals[0].content.trim(), "Fixed the null check.");
+}
+
+#[test]
+fn tuning_profile_follows_terminal_type() {
+    let mut engine = HeadlessEngine::new();
+    engine.settings.set_terminal_tuning(
+        "gemini",
+        json!({ "chatSilenceTimeoutMs": 1000, "chatIdleDebounceMs": 500 }),
+    );
+    let gemini = spawn_ready(&mut engine, FakeCliKind::Gemini, "member-gemini");
+    let claude = spawn_ready(&mut engine, FakeCliKind::Claude, "member-claude");
+    engine.send_chat(&gemini, "conv-1", "status?", &["The build is green."], 1000);
+    engine.send_chat(&claude, "conv-2", "status?", &["I updated the README."], 1000);
+
+    // gemini 按缩短后的静默门禁 flush；claude 仍使用默认阈值。
+    engine.advance(1000 + 1000 + 500 + 2 * STATUS_POLL_INTERVAL_MS);
+    assert_eq!(engine.flushes(&gemini).len(), 1);
+    assert!(engine.flushes(&claude).is_empty());
+    assert!(engine.chat_pending(&claude));
+
+    engine.advance(FLUSH_SETTLE_MS);
+    assert_eq!(engine.flushes(&claude).len(), 1);
+}
+
+#[test]
+fn member_tuning_overrides_global_profile() {
+    let mut engine = HeadlessEngine::new();
+    engine.settings.set_terminal_tuning(
+        "codex",
+        json!({ "chatSilenceTimeoutMs": 1000, "chatIdleDebounceMs": 500 }),
+    );
+    let terminal_id = engine.spawn_with_member_tuning(
+        FakeCliKind::Codex,
+        "member-codex",
+        PostReadyMode::Disabled,
+        Some(json!({
+            "chatSilenceTimeoutMs": 2000,
+            "flowControlHighWatermark": 10,
+            "flowControlLowWatermark": 0
+        })),
+    );
+    let tuning = engine.with_session(&terminal_id, |session| session.tuning);
+    assert_eq!(tuning.chat_silence_timeout_ms, 2000);
+    assert_eq!(tuning.chat_idle_debounce_ms, 500);
+    assert_eq!(
+        tuning.working_silence_timeout_ms,
+        STATUS_WORKING_SILENCE_TIMEOUT_MS
+    );
+    // 非法水位被归一化为 low < high，避免流控反复抖动。
+    assert!(tuning.flow_control_low_watermark > 0);
+    assert!(tuning.flow_control_high_watermark > tuning.flow_control_low_watermark);
+
+    // 格式错误的覆盖项被忽略，回退到全局档位。
+    let fallback = engine.spawn_with_member_tuning(
+        FakeCliKind::Codex,
+        "member-other",
+        PostReadyMode::Disabled,
+        Some(json!({ "chatSilenceTimeoutMs": "fast" })),
+    );
+    let tuning = engine.with_session(&fallback, |session| session.tuning);
+    assert_eq!(tuning.chat_silence_timeout_ms, 1000);
+}
+
+#[test]
+fn shell_command_output_flushes_on_finish_mark() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Shell, "member-shell");
+
+    for (input, output, exit_code) in [
+        ("ls missing", "ls: missing: No such file or directory", 2),
+        ("echo ok", "ok", 0),
+    ] {
+        engine.set_exit_code(&terminal_id, exit_code);
+        let sent_at = engine.now();
+        let flushes_before = engine.flushes(&terminal_id).len();
+        engine.send_chat(&terminal_id, "conv-1", input, &[output], 400);
+        assert!(engine.advance_until(CHAT_SILENCE_TIMEOUT_MS, |engine| {
+            engine.flushes(&terminal_id).len() > flushes_before
+        }));
+
+        // D 标记闭合命令区间后，下一轮轮询即 flush，不等待静默门禁与防抖。
+        let flush = engine.flushes(&terminal_id)[flushes_before];
+        assert!(flush.at - sent_at <= 400 + 2 * STATUS_POLL_INTERVAL_MS);
+        assert_eq!(flush.decision, FilterDecision::Allow);
+        assert_eq!(flush.reason, "command_output");
+        // 内容只包含 C..D 之间的输出：不含提示符与输入回显。
+        assert_eq!(flush.content.as_deref(), Some(output));
+        let finals = engine.messages.finals();
+        let meta = finals
+            .last()
+            .and_then(|message| message.meta.as_ref())
+            .expect("final message meta");
+        assert_eq!(meta.exit_code, Some(exit_code));
+        assert!(!engine.chat_pending(&terminal_id));
+    }
+}
+
+#[test]
+fn shell_command_rows_map_back_to_terminal_lines() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Shell, "member-shell");
+
+    let mut previous_end = 0;
+    for (input, output) in [("echo one", &["one"][..]), ("printf 'a\\nb\\n'", &["a", "b"][..])] {
+        let flushes_before = engine.flushes(&terminal_id).len();
+        engine.send_chat(&terminal_id, "conv-1", input, output, 400);
+        assert!(engine.advance_until(CHAT_SILENCE_TIMEOUT_MS, |engine| {
+            engine.flushes(&terminal_id).len() > flushes_before
+        }));
+
+        let finals = engine.messages.finals();
+        let meta = finals
+            .last()
+            .and_then(|message| message.meta.as_ref())
+            .expect("final message meta");
+        let (start_row, end_row) = (meta.start_row.unwrap(), meta.end_row.unwrap());
+        // 区间只覆盖 C..D 之间的输出行，且随会话输出单调后移。
+        assert_eq!(end_row - start_row, output.len() as u64);
+        assert!(start_row >= previous_end);
+        previous_end = end_row;
+
+        let message_id = format!("harness-message-{}", finals.len());
+        let rows = engine
+            .message_rows(&terminal_id, &message_id)
+            .expect("message rows recorded");
+        assert_eq!((rows.start_row, rows.end_row), (start_row, end_row));
+        assert_eq!(rows.lines, output);
+    }
+}
+
+#[test]
+fn idle_member_becomes_due_for_hibernation() {
+    const IDLE_MS: u64 = 60_000;
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "fix the parser", &["Fixed."], 2000);
+    engine.advance(2000 + FLUSH_SETTLE_MS + STATUS_WORKING_SILENCE_TIMEOUT_MS);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Online);
+
+    let now = engine.now();
+    assert!(!engine.with_session(&terminal_id, |session| hibernation_due(session, now, IDLE_MS)));
+    let last_output_at = engine.last_output_at(&terminal_id).expect("reply output");
+    let due_at = last_output_at + IDLE_MS;
+    assert!(engine.with_session(&terminal_id, |session| hibernation_due(session, due_at, IDLE_MS)));
+    // 前端正在查看或仍有排队派发时保持在线。
+    assert!(!engine.with_session(&terminal_id, |session| {
+        session.ui_active = true;
+        hibernation_due(session, due_at, IDLE_MS)
+    }));
+    assert!(!engine.with_session(&terminal_id, |session| {
+        session.ui_active = false;
+        session.last_dispatch_at = Some(due_at - 1);
+        hibernation_due(session, due_at, IDLE_MS)
+    }));
+}
+
+#[test]
+fn frozen_snapshot_keeps_visible_screen() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "fix the parser", &["Fixed."], 2000);
+    engine.advance(2000 + FLUSH_SETTLE_MS);
+
+    let (rows, cols, screen, lines) = engine.with_session(&terminal_id, |session| {
+        (
+            session.screen_rows,
+            session.screen_cols,
+            session.snapshot.snapshot_segments().data,
+            session.snapshot.snapshot_lines(),
+        )
+    });
+    let frozen = TerminalSnapshot::frozen(rows, cols, &screen);
+    let trim = |lines: Vec<String>| {
+        lines
+            .into_iter()
+            .map(|line| line.trim_end().to_string())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+    };
+    let frozen_lines = trim(frozen.snapshot_lines());
+    assert!(frozen_lines.iter().any(|line| line.contains("Fixed.")));
+    assert!(trim(lines).ends_with(&frozen_lines));
+}
+
+#[test]
+fn approval_prompt_enters_and_leaves_awaiting_input() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.send_chat(&terminal_id, "conv-1", "run the tests", &["Running cargo test."], 500);
+    engine.emit_output_after(&terminal_id, 800, "\r\nAllow command? cargo test (y/n)\r\n");
+    engine.advance(800 + APPROVAL_PROMPT_SETTLE_MS + 2 * STATUS_POLL_INTERVAL_MS);
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::AwaitingInput);
+    let prompts = engine.actions_matching(|action| matches!(action, PollAction::ApprovalPrompt { .. }));
+    assert_eq!(prompts.len(), 1);
+    let PollAction::ApprovalPrompt { prompt, subject, .. } = &prompts[0].1 else {
+        unreachable!();
+    };
+    assert_eq!(prompt, "Allow command? cargo test (y/n)");
+    assert!(subject.context.ends_with(prompt.as_str()), "{subject:?}");
+    assert_eq!(subject.command.as_deref(), Some("cargo test"));
+
+    // 作答后回到 Working；提示行仍留在屏幕上也不再重复识别，静默后正常回落。
+    engine.write_input(&terminal_id, "y");
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Working);
+    engine.advance(
+        STATUS_WORKING_SILENCE_TIMEOUT_MS + STATUS_IDLE_DEBOUNCE_MS + 2 * STATUS_POLL_INTERVAL_MS,
+    );
+    assert_eq!(engine.status(&terminal_id), TerminalSessionStatus::Online);
+    let prompts = engine.actions_matching(|action| matches!(action, PollAction::ApprovalPrompt { .. }));
+    assert_eq!(prompts.len(), 1);
+}
+
+#[test]
+fn post_ready_input_is_not_pasted_twice() {
+    let mut engine = HeadlessEngine::new();
+    let terminal_id = spawn_ready(&mut engine, FakeCliKind::Codex, "member-codex");
+    engine.emit_output_after(&terminal_id, 0, "\x1b[?2004h");
+    engine.advance(STATUS_POLL_INTERVAL_MS);
+    let script = PostReadyScript::from_value(&json!([
+        { "type": "keys", "keys": "paste{line 1\nfn main() {\\}}<Enter>" },
+        { "type": "input", "input": "line 1\nline 2\r" }
+    ]))
+    .expect("valid script");
+    let inputs = script
+        .steps
+        .into_iter()
+        .map(|step| match step {
+            PostReadyScriptStep::Input { input, .. } => input,
+            other => panic!("unexpected step {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    let sent = engine.with_session(&terminal_id, |session| {
+        assert!(session.snapshot.bracketed_paste_enabled());
+        inputs
+            .into_iter()
+            .map(|input| paste_multiline_input(session, input))
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        sent,
+        [
+            "\x1b[200~line 1\nfn main() {}\x1b[201~\r",
+            "\x1b[200~line 1\nline 2\x1b[201~\r",
+        ]
+    );
+}
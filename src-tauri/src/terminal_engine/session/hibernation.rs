@@ -0,0 +1,255 @@
+//! 成员终端空闲休眠：Online 且持续无派发超过 `members.hibernateAfterMinutes` 的成员冻结屏幕快照、
+//! 保存远端会话 ID 后关闭 PTY 与语义管线，状态切为 Hibernating，释放模拟器回滚缓冲与工作线程。
+//! 派发指向休眠成员时以 resume 命令原地唤醒，排队消息在唤醒流程结束、状态解锁后投递。
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
+use crate::terminal_engine::default_members::apply_resume_command;
+
+use super::{
+    build_status_payload, lock_sessions, relaunch_terminal_session, update_session_status,
+    PostReadyMode, PostReadyState, RelaunchOverrides, SemanticEvent, TerminalManager,
+    TerminalSession, TerminalSessionStatus, TerminalSnapshot,
+};
+
+const HIBERNATION_SWEEP_INTERVAL_MS: u64 = 30_000; // 扫描间隔：阈值以分钟计，无需更细粒度。
+
+/// 启动休眠扫描线程；设置未开启（阈值缺省或为 0）时每轮直接跳过。
+pub(crate) fn spawn_hibernation_sweeper(app: AppHandle, manager: &TerminalManager) {
+    let sessions = Arc::clone(&manager.sessions);
+    let clock = manager.clock();
+    let settings_service = {
+        let guard = manager
+            .settings_service
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        guard.clone()
+    };
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_millis(HIBERNATION_SWEEP_INTERVAL_MS));
+        let Some(minutes) = settings_service
+            .as_ref()
+            .and_then(|settings| settings.get_member_hibernate_after_minutes())
+        else {
+            continue;
+        };
+        let idle_ms = minutes.saturating_mul(60_000);
+        let now = clock.now_ms();
+        let candidates: Vec<String> = {
+            let guard = lock_sessions(&sessions);
+            guard
+                .sessions
+                .values()
+                .filter(|session| session.handle.is_some() && hibernation_due(session, now, idle_ms))
+                .map(|session| session.id.clone())
+                .collect()
+        };
+        for terminal_id in candidates {
+            hibernate_session(&app, &terminal_id, now, idle_ms);
+        }
+    });
+}
+
+/// 休眠判定：成员会话 Online 且无排队/在途派发、无未 flush 的聊天块、前端未查看，
+/// 最近一次派发或输出距今超过阈值。
+pub(super) fn hibernation_due(session: &TerminalSession, now: u64, idle_ms: u64) -> bool {
+    if session.member_id.is_none()
+        || session.status != TerminalSessionStatus::Online
+        || session.status_locked
+        || !session.active
+        || session.broken
+        || session.ui_active
+        || session.recorder.is_some()
+        || session.post_ready_state != PostReadyState::Done
+        || session.post_ready_restart_pending
+        || !session.dispatch_queue.is_empty()
+        || session.dispatch_inflight
+        || session.chat_pending
+        || session.semantic_active
+    {
+        return false;
+    }
+    let idle_since = session
+        .created_at
+        .max(session.last_dispatch_at.unwrap_or(0))
+        .max(session.last_output_at.unwrap_or(0));
+    now.saturating_sub(idle_since) >= idle_ms
+}
+
+fn hibernate_session(app: &AppHandle, terminal_id: &str, now: u64, idle_ms: u64) {
+    let state = app.state::<TerminalManager>();
+    let hibernated = {
+        let mut guard = lock_sessions(&state.sessions);
+        let working_sessions = Arc::clone(&guard.working_sessions);
+        let Some(session) = guard.sessions.get_mut(terminal_id) else {
+            return;
+        };
+        // 扫描与加锁之间可能有新派发或前端接入，重新校验。
+        if session.handle.is_none() || !hibernation_due(session, now, idle_ms) {
+            return;
+        }
+        let screen = session.snapshot.snapshot_segments().data;
+        session.snapshot = TerminalSnapshot::frozen(session.screen_rows, session.screen_cols, &screen);
+        session.active = false;
+        session.idle_candidate_at = None;
+        session.working_intent_until = None;
+        session.redraw_suppression_until = None;
+        session.flow_paused = false;
+        session.input_buffer.clear();
+        update_session_status(&working_sessions, session, TerminalSessionStatus::Hibernating);
+        (
+            build_status_payload(session),
+            session.handle.take().map(|handle| handle.killer),
+            session.semantic_tx.take(),
+            session.workspace_id.clone(),
+            session.member_id.clone(),
+            session.remote_session_id.clone(),
+        )
+    };
+    let (status_payload, killer, semantic_tx, workspace_id, member_id, remote_session_id) = hibernated;
+    if let Some(mut killer) = killer {
+        thread::spawn(move || {
+            let _ = killer.kill();
+        });
+    }
+    if let Some(tx) = semantic_tx {
+        let _ = tx.send(SemanticEvent::Shutdown);
+    }
+    if let (Some(workspace_id), Some(member_id), Some(session_id)) = (
+        workspace_id.as_deref(),
+        member_id.as_deref(),
+        remote_session_id.as_deref(),
+    ) {
+        if let Err(err) = state
+            .session_repository()
+            .upsert_terminal_session(workspace_id, member_id, session_id)
+        {
+            log::warn!(
+                "terminal hibernate session map update failed member_id={} err={}",
+                member_id,
+                err
+            );
+        }
+    }
+    let _ = state.event_port().emit_status(status_payload);
+    log::info!(
+        "terminal hibernated terminal_id={} idle_ms={}",
+        terminal_id,
+        idle_ms
+    );
+    log_hibernation_event(
+        app,
+        terminal_id,
+        member_id,
+        workspace_id,
+        "hibernated",
+        json!({ "idleMs": idle_ms, "sessionId": remote_session_id }),
+    );
+}
+
+/// 唤醒休眠成员：以 resume 命令重新拉起进程并锁定 Connecting，唤醒流程结束后解锁并投递排队消息。
+/// 返回是否执行了唤醒；会话不存在或未休眠时不做处理。
+pub(crate) fn wake_hibernating_session(app: &AppHandle, terminal_id: &str) -> Result<bool, String> {
+    let state = app.state::<TerminalManager>();
+    let (status_payload, member_type, launch_command, remote_session_id, workspace_id, member_id) = {
+        let mut guard = lock_sessions(&state.sessions);
+        let working_sessions = Arc::clone(&guard.working_sessions);
+        let Some(session) = guard.sessions.get_mut(terminal_id) else {
+            return Ok(false);
+        };
+        if session.status != TerminalSessionStatus::Hibernating {
+            return Ok(false);
+        }
+        // 先切到锁定的 Connecting：并发派发不会重复唤醒，也不会在 CLI 就绪前写入消息。
+        session.status_locked = true;
+        update_session_status(&working_sessions, session, TerminalSessionStatus::Connecting);
+        (
+            build_status_payload(session),
+            session.member_type().to_string(),
+            session.launch_command.clone(),
+            session.remote_session_id.clone(),
+            session.workspace_id.clone(),
+            session.member_id.clone(),
+        )
+    };
+    let _ = state.event_port().emit_status(status_payload);
+    let session_id = remote_session_id.or_else(|| {
+        workspace_id
+            .as_deref()
+            .zip(member_id.as_deref())
+            .and_then(|(workspace_id, member_id)| {
+                state
+                    .session_repository()
+                    .get_terminal_session(workspace_id, member_id)
+                    .ok()
+                    .flatten()
+            })
+    });
+    let command = apply_resume_command(&member_type, launch_command.clone(), session_id.as_deref());
+    let resumed = session_id.is_some() && command != launch_command;
+    {
+        let mut guard = lock_sessions(&state.sessions);
+        if let Some(session) = guard.sessions.get_mut(terminal_id) {
+            // 续接原会话或原本无引导时只等待就绪；未能续接的会话按原模式重新邀请。
+            if resumed || !session.post_ready_mode.should_run() {
+                session.post_ready_mode = PostReadyMode::Wake;
+            }
+        }
+    }
+    relaunch_terminal_session(
+        app.clone(),
+        app.state::<TerminalManager>(),
+        terminal_id,
+        "hibernation_wake",
+        RelaunchOverrides {
+            command,
+            ..RelaunchOverrides::default()
+        },
+    )?;
+    log::info!(
+        "terminal woke terminal_id={} resumed={}",
+        terminal_id,
+        resumed
+    );
+    log_hibernation_event(
+        app,
+        terminal_id,
+        member_id,
+        workspace_id,
+        "woke",
+        json!({ "resumed": resumed, "sessionId": session_id }),
+    );
+    Ok(true)
+}
+
+fn log_hibernation_event(
+    app: &AppHandle,
+    terminal_id: &str,
+    member_id: Option<String>,
+    workspace_id: Option<String>,
+    outcome: &str,
+    detail: serde_json::Value,
+) {
+    diagnostics_log_backend_event(
+        &app.state::<DiagnosticsState>(),
+        member_id.clone(),
+        Some(terminal_id.to_string()),
+        None,
+        None,
+        workspace_id.clone(),
+        "terminal_hibernation",
+        json!({
+          "terminalId": terminal_id,
+          "memberId": member_id,
+          "workspaceId": workspace_id,
+          "outcome": outcome,
+          "detail": detail
+        }),
+    );
+}
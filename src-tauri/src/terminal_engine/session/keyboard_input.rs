@@ -2,16 +2,190 @@
 
 use std::sync::{Arc, Mutex};
 
-use crate::now_millis;
 use crate::ports::terminal_event::TerminalEventPort;
 
 use super::{
-    flush_input_buffer, handle_buffered_write, lock_sessions, mark_session_working_on_input,
-    terminal_trace_detail, SessionRegistry,
+    clock_now, flush_input_buffer, handle_buffered_write, lock_sessions,
+    mark_session_working_on_input, terminal_trace_detail, SessionRegistry, TerminalSession,
 };
 
 /// 模拟回车前的输入间隔，避免 CLI 丢提交。
 pub(super) const ENTER_INPUT_DELAY_MS: u64 = 100;
+/// 提交键，与按键序列 `<Enter>` 一致。
+pub(super) const KEY_ENTER: &str = "\r";
+
+const BRACKETED_PASTE_START: &str = "\x1b[200~";
+const BRACKETED_PASTE_END: &str = "\x1b[201~";
+const PASTE_BLOCK_PREFIX: &str = "paste{";
+
+/// 解析按键序列 DSL，返回写入终端的原始字节串。
+/// 语法：`<Enter>`、`<Esc>`、`<Up>` 等命名按键（大小写不敏感），`<C-c>` 为 Ctrl 组合，
+/// `<M-x>`/`<A-x>` 为 Alt 组合（前缀 ESC），`<S-Tab>` 为反向 Tab，`<lt>` 输出字面 `<`；
+/// `paste{...}` 以 bracketed paste 发送其中文本（`\}`、`\\` 转义），其余字符原样发送。
+/// 错误：未知按键、缺少 `>` 或 paste 块未闭合。
+pub(crate) fn encode_key_sequence(sequence: &str) -> Result<String, String> {
+    let mut encoded = String::with_capacity(sequence.len());
+    let mut rest = sequence;
+    while let Some(ch) = rest.chars().next() {
+        if rest.starts_with(PASTE_BLOCK_PREFIX) {
+            let (text, remaining) = parse_paste_block(&rest[PASTE_BLOCK_PREFIX.len()..])?;
+            encoded.push_str(&bracketed_paste(&text));
+            rest = remaining;
+            continue;
+        }
+        if ch == '<' {
+            let end = rest
+                .find('>')
+                .ok_or_else(|| format!("unterminated key {rest:?}"))?;
+            let name = &rest[1..end];
+            let key = encode_key(name).ok_or_else(|| format!("unknown key <{name}>"))?;
+            encoded.push_str(&key);
+            rest = &rest[end + 1..];
+            continue;
+        }
+        encoded.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    Ok(encoded)
+}
+
+/// 以 bracketed paste 包裹文本，CLI 会把整段视为一次粘贴而不是逐行提交；
+/// 文本中的结束标记会被移除，避免提前结束粘贴。
+pub(crate) fn bracketed_paste(text: &str) -> String {
+    format!(
+        "{BRACKETED_PASTE_START}{}{BRACKETED_PASTE_END}",
+        text.replace(BRACKETED_PASTE_END, "")
+    )
+}
+
+/// 多行输入在终端开启 bracketed paste（DECSET 2004）时整段粘贴，否则原样返回。
+pub(super) fn paste_multiline_input(session: &TerminalSession, data: String) -> String {
+    if session.snapshot.bracketed_paste_enabled() {
+        paste_multiline_text(&data)
+    } else {
+        data
+    }
+}
+
+/// 将多行文本包裹为粘贴：已含粘贴起始标记的输入（按键序列编码结果）原样发送；
+/// 单独的 `\r` 视为提交键保留在粘贴之外，`\r\n` 仍属于文本。
+pub(super) fn paste_multiline_text(data: &str) -> String {
+    if !data.contains('\n') || data.contains(BRACKETED_PASTE_START) {
+        return data.to_string();
+    }
+    let mut pasted = String::with_capacity(data.len() + 2 * BRACKETED_PASTE_START.len());
+    let mut segment_start = 0;
+    for (index, _) in data.match_indices('\r') {
+        if data[index + 1..].starts_with('\n') {
+            continue;
+        }
+        push_paste_segment(&mut pasted, &data[segment_start..index]);
+        pasted.push_str(KEY_ENTER);
+        segment_start = index + 1;
+    }
+    push_paste_segment(&mut pasted, &data[segment_start..]);
+    pasted
+}
+
+fn push_paste_segment(pasted: &mut String, segment: &str) {
+    if segment.contains('\n') {
+        pasted.push_str(&bracketed_paste(segment));
+    } else {
+        pasted.push_str(segment);
+    }
+}
+
+fn parse_paste_block(value: &str) -> Result<(String, &str), String> {
+    let mut text = String::new();
+    let mut chars = value.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '}' => return Ok((text, &value[index + 1..])),
+            '\\' => match chars.next() {
+                Some((_, next)) if next == '}' || next == '\\' => text.push(next),
+                Some((_, next)) => {
+                    text.push('\\');
+                    text.push(next);
+                }
+                None => text.push('\\'),
+            },
+            _ => text.push(ch),
+        }
+    }
+    Err("unterminated paste{...} block".to_string())
+}
+
+fn encode_key(name: &str) -> Option<String> {
+    if let Some(key) = name.strip_prefix("C-").or_else(|| name.strip_prefix("c-")) {
+        return encode_ctrl(key).map(|ch| ch.to_string());
+    }
+    if let Some(key) = ["M-", "m-", "A-", "a-"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+    {
+        let mut chars = key.chars();
+        let encoded = match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch.to_string(),
+            _ => encode_key(key)?,
+        };
+        return Some(format!("\x1b{encoded}"));
+    }
+    let key = match name.to_lowercase().as_str() {
+        "enter" | "cr" | "return" => KEY_ENTER,
+        "tab" => "\t",
+        "s-tab" => "\x1b[Z",
+        "esc" | "escape" => "\x1b",
+        "bs" | "backspace" => "\x7f",
+        "space" => " ",
+        "lt" => "<",
+        "up" => "\x1b[A",
+        "down" => "\x1b[B",
+        "right" => "\x1b[C",
+        "left" => "\x1b[D",
+        "home" => "\x1b[H",
+        "end" => "\x1b[F",
+        "pageup" => "\x1b[5~",
+        "pagedown" => "\x1b[6~",
+        "insert" => "\x1b[2~",
+        "del" | "delete" => "\x1b[3~",
+        "f1" => "\x1bOP",
+        "f2" => "\x1bOQ",
+        "f3" => "\x1bOR",
+        "f4" => "\x1bOS",
+        "f5" => "\x1b[15~",
+        "f6" => "\x1b[17~",
+        "f7" => "\x1b[18~",
+        "f8" => "\x1b[19~",
+        "f9" => "\x1b[20~",
+        "f10" => "\x1b[21~",
+        "f11" => "\x1b[23~",
+        "f12" => "\x1b[24~",
+        _ => return None,
+    };
+    Some(key.to_string())
+}
+
+// Ctrl 组合按 ASCII 控制字符编码：字母取低 5 位，`@`/Space 为 NUL，`?` 为 DEL。
+fn encode_ctrl(key: &str) -> Option<char> {
+    if key.eq_ignore_ascii_case("space") {
+        return Some('\0');
+    }
+    let mut chars = key.chars();
+    let (Some(ch), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    match ch.to_ascii_lowercase() {
+        letter @ 'a'..='z' => Some(((letter as u8) & 0x1f) as char),
+        '@' => Some('\0'),
+        '[' => Some('\x1b'),
+        '\\' => Some('\x1c'),
+        ']' => Some('\x1d'),
+        '^' => Some('\x1e'),
+        '_' => Some('\x1f'),
+        '?' => Some('\x7f'),
+        _ => None,
+    }
+}
 
 /// 发送键盘输入到终端会话。
 /// 输入：终端 id、输入字符串与原因标记。
@@ -23,7 +197,7 @@ pub(super) fn send_post_ready_input(
     input: &str,
     reason: &str,
 ) -> Result<(), String> {
-    let now = now_millis()?;
+    let now = clock_now(sessions);
     {
         let guard = lock_sessions(sessions);
         let session = guard
@@ -52,7 +226,8 @@ pub(super) fn send_post_ready_input(
             .as_ref()
             .ok_or_else(|| "terminal session handle missing".to_string())?;
         let writer = Arc::clone(&handle.writer);
-        let (should_write, buffered) = handle_buffered_write(session, input.to_string(), now);
+        let data = paste_multiline_input(session, input.to_string());
+        let (should_write, buffered) = handle_buffered_write(session, data, now);
         (should_write, buffered, writer, session.shell_ready)
     };
     if terminal_trace_detail() {
@@ -152,3 +327,6 @@ fn find_keyword_case_insensitive(line: &str, keyword: &str, offset: usize) -> Op
     }
     None
 }
+
+#[cfg(test)]
+mod key_sequence_tests;
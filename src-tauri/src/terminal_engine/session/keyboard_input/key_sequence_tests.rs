@@ -0,0 +1,48 @@
+use super::{bracketed_paste, encode_key_sequence, paste_multiline_text};
+
+#[test]
+fn named_keys_and_modifiers_encode_to_terminal_bytes() {
+    assert_eq!(encode_key_sequence("<C-c>").unwrap(), "\x03");
+    assert_eq!(encode_key_sequence("<esc><ENTER>").unwrap(), "\x1b\r");
+    assert_eq!(encode_key_sequence("<Up><Down><Left><Right>").unwrap(), "\x1b[A\x1b[B\x1b[D\x1b[C");
+    assert_eq!(encode_key_sequence("<S-Tab><C-[><C-Space>").unwrap(), "\x1b[Z\x1b\0");
+    assert_eq!(encode_key_sequence("<M-x><A-Enter>").unwrap(), "\x1bx\x1b\r");
+    assert_eq!(encode_key_sequence("y<Enter>").unwrap(), "y\r");
+    assert_eq!(encode_key_sequence("a <lt> b").unwrap(), "a < b");
+}
+
+#[test]
+fn paste_blocks_wrap_text_and_honor_escapes() {
+    assert_eq!(
+        encode_key_sequence("paste{line 1\nfn main() {\\}}<Enter>").unwrap(),
+        "\x1b[200~line 1\nfn main() {}\x1b[201~\r"
+    );
+    assert_eq!(encode_key_sequence("paste{a\\\\b}").unwrap(), "\x1b[200~a\\b\x1b[201~");
+    // 文本中的结束标记被移除，防止提前结束粘贴。
+    assert_eq!(bracketed_paste("x\x1b[201~y"), "\x1b[200~xy\x1b[201~");
+}
+
+#[test]
+fn invalid_sequences_are_rejected() {
+    assert_eq!(encode_key_sequence("<Foo>"), Err("unknown key <Foo>".to_string()));
+    assert_eq!(encode_key_sequence("<C-1>"), Err("unknown key <C-1>".to_string()));
+    assert_eq!(encode_key_sequence("<Esc"), Err("unterminated key \"<Esc\"".to_string()));
+    assert_eq!(
+        encode_key_sequence("paste{open"),
+        Err("unterminated paste{...} block".to_string())
+    );
+}
+
+#[test]
+fn multiline_paste_keeps_encoded_sequences_and_submit_keys_outside() {
+    // 按键序列已编码的粘贴块原样发送，不再二次包裹。
+    let encoded = encode_key_sequence("paste{line 1\nfn main() {\\}}<Enter>").unwrap();
+    assert_eq!(paste_multiline_text(&encoded), encoded);
+    // 单独的 `\r` 是提交键，留在粘贴之外；`\r\n` 属于文本。
+    assert_eq!(paste_multiline_text("a\nb\r"), "\x1b[200~a\nb\x1b[201~\r");
+    assert_eq!(
+        paste_multiline_text("a\r\nb\rc\rd\ne"),
+        "\x1b[200~a\r\nb\x1b[201~\rc\r\x1b[200~d\ne\x1b[201~"
+    );
+    assert_eq!(paste_multiline_text("single line\r"), "single line\r");
+}
@@ -1,6 +1,7 @@
 //! 终端启动策略：封装默认终端与兜底链路，避免命令入口堆积分支。
 
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
@@ -9,7 +10,11 @@ use crate::runtime::{
   StorageManager,
 };
 use crate::runtime::pty::SpawnedPty;
+use crate::runtime::git;
+use crate::runtime::sandbox::SandboxPolicy;
 
+use crate::terminal_engine::default_members::resolve_default_member;
+use crate::terminal_engine::default_members::session_store::home_dir;
 use crate::terminal_engine::errors::{TerminalError, TerminalErrorCode};
 
 use super::state::TerminalType;
@@ -22,6 +27,19 @@ pub(crate) struct DefaultTerminalPreference {
   pub(crate) path: Option<String>,
 }
 
+/// 终端启动参数；`member_type` 为插件/自定义类型名或内置类型名，用于查找沙箱内需读写的状态目录。
+pub(crate) struct TerminalLaunchRequest<'a> {
+  pub(crate) cols: u16,
+  pub(crate) rows: u16,
+  pub(crate) cwd: Option<String>,
+  pub(crate) terminal_type: TerminalType,
+  pub(crate) member_type: &'a str,
+  pub(crate) terminal_path: Option<&'a str>,
+  pub(crate) terminal_command: Option<&'a str>,
+  pub(crate) strict_shell: bool,
+  pub(crate) sandboxed: bool,
+}
+
 pub(crate) struct TerminalLaunchResult {
   pub(crate) spawned: SpawnedPty,
   pub(crate) terminal_type: TerminalType,
@@ -40,6 +58,7 @@ struct StoredGlobalSettings {
 struct StoredMemberSettings {
   default_terminal_name: Option<String>,
   default_terminal_path: Option<String>,
+  sandbox_deny_network: Option<bool>,
 }
 
 struct ShellCandidate {
@@ -87,6 +106,29 @@ fn load_default_terminal_preference(storage: &StorageManager) -> DefaultTerminal
   DefaultTerminalPreference::default()
 }
 
+// 沙箱成员是否禁用网络：读取失败时按默认（允许网络）处理。
+fn load_sandbox_deny_network(storage: &StorageManager) -> bool {
+  let parsed = read_app_json(storage, GLOBAL_SETTINGS_PATH)
+    .ok()
+    .flatten()
+    .and_then(|raw| serde_json::from_value::<StoredGlobalSettings>(raw).ok());
+  parsed
+    .and_then(|settings| settings.members)
+    .and_then(|members| members.sandbox_deny_network)
+    .unwrap_or(false)
+}
+
+// 沙箱内需读写的工作区外路径：成员 CLI 状态目录（无 HOME 或成员未登记时跳过），
+// 以及 cwd 所在仓库的 git 公共目录（成员独立工作树的提交写入主仓库的对象库与引用）。
+fn sandbox_writable_paths(member_type: &str, cwd: Option<&str>) -> Vec<PathBuf> {
+  let mut paths = Vec::new();
+  if let (Some(home), Some(member)) = (home_dir(), resolve_default_member(member_type)) {
+    paths.extend(member.state_dirs.iter().map(|dir| home.join(dir)));
+  }
+  paths.extend(cwd.and_then(|cwd| git::common_dir(Path::new(cwd))));
+  paths
+}
+
 fn normalize_candidate_key(path: Option<&str>) -> String {
   let key = path.unwrap_or("<system-default>");
   if cfg!(windows) {
@@ -154,10 +196,11 @@ fn spawn_shell_with_candidates(
   rows: u16,
   cwd: Option<String>,
   candidates: Vec<ShellCandidate>,
+  sandbox: Option<&SandboxPolicy>,
 ) -> Result<(SpawnedPty, bool), String> {
   let mut errors = Vec::new();
   for (index, candidate) in candidates.iter().enumerate() {
-    let attempt = spawn_shell(cols, rows, cwd.clone(), candidate.path.as_deref());
+    let attempt = spawn_shell(cols, rows, cwd.clone(), candidate.path.as_deref(), sandbox);
     match attempt {
       Ok(spawned) => {
         let fallback_used = index > 0;
@@ -188,6 +231,7 @@ fn spawn_shell_strict(
   rows: u16,
   cwd: Option<String>,
   terminal_path: Option<&str>,
+  sandbox: Option<&SandboxPolicy>,
 ) -> Result<SpawnedPty, TerminalError> {
   let trimmed = terminal_path.map(str::trim).filter(|value| !value.is_empty());
   if let Some(path) = trimmed {
@@ -199,7 +243,7 @@ fn spawn_shell_strict(
       ));
     }
   }
-  spawn_shell(cols, rows, cwd, trimmed).map_err(|err| {
+  spawn_shell(cols, rows, cwd, trimmed, sandbox).map_err(|err| {
     TerminalError::new(TerminalErrorCode::ShellLaunchFailed, err)
   })
 }
@@ -212,17 +256,15 @@ fn split_command(command: &str) -> (String, Vec<String>) {
 }
 
 fn spawn_direct_terminal_with_command(
-  cols: u16,
-  rows: u16,
-  cwd: Option<String>,
-  terminal_type: TerminalType,
-  terminal_path: Option<&str>,
-  terminal_command: Option<&str>,
+  request: &TerminalLaunchRequest,
+  sandbox: Option<&SandboxPolicy>,
 ) -> Result<SpawnedPty, String> {
-  let default_binary = terminal_type
+  let default_binary = request
+    .terminal_type
     .default_binary()
     .ok_or_else(|| "terminal binary missing".to_string())?;
-  let command = terminal_command
+  let command = request
+    .terminal_command
     .map(str::trim)
     .filter(|value| !value.is_empty());
   let (program, args) = if let Some(command) = command {
@@ -230,27 +272,43 @@ fn spawn_direct_terminal_with_command(
   } else {
     (default_binary.to_string(), Vec::new())
   };
-  let resolved = lookup_binary(&program, terminal_path)?;
-  spawn_command(cols, rows, cwd, &resolved, &args)
+  let resolved = lookup_binary(&program, request.terminal_path)?;
+  spawn_command(request.cols, request.rows, request.cwd.clone(), &resolved, &args, sandbox)
 }
 
 /// 启动终端并在失败时执行兜底链路。
 /// 规则：非 shell 先直连二进制（支持 `terminal_command` 参数）；失败后退到默认终端；仍失败则按候选列表尝试。
 /// 当 `strict_shell` 为 true 时，shell 仅尝试指定路径/系统默认，不执行兜底。
+/// `sandboxed` 为 true 时以 `cwd` 为工作区在沙箱内启动（含兜底链路）；沙箱不可用直接报错，不会无沙箱启动。
 pub(crate) fn launch_terminal_with_fallback(
   storage: &StorageManager,
-  cols: u16,
-  rows: u16,
-  cwd: Option<String>,
-  terminal_type: TerminalType,
-  terminal_path: Option<&str>,
-  terminal_command: Option<&str>,
-  strict_shell: bool,
+  request: TerminalLaunchRequest,
 ) -> Result<TerminalLaunchResult, TerminalError> {
   let default_terminal = load_default_terminal_preference(storage);
+  let sandbox = if request.sandboxed {
+    let policy = SandboxPolicy::new(
+      request.cwd.as_deref(),
+      sandbox_writable_paths(request.member_type, request.cwd.as_deref()),
+      load_sandbox_deny_network(storage),
+    )
+    .map_err(|err| TerminalError::new(TerminalErrorCode::SandboxUnavailable, err))?;
+    Some(policy)
+  } else {
+    None
+  };
+  let sandbox = sandbox.as_ref();
+  let TerminalLaunchRequest {
+    cols,
+    rows,
+    terminal_type,
+    terminal_path,
+    strict_shell,
+    ..
+  } = request;
   if terminal_type == TerminalType::Shell {
+    let cwd = request.cwd;
     if strict_shell {
-      let spawned = spawn_shell_strict(cols, rows, cwd, terminal_path)?;
+      let spawned = spawn_shell_strict(cols, rows, cwd, terminal_path, sandbox)?;
       return Ok(TerminalLaunchResult {
         spawned,
         terminal_type: TerminalType::Shell,
@@ -266,7 +324,7 @@ pub(crate) fn launch_terminal_with_fallback(
       None
     };
     let candidates = build_shell_candidates(primary_path, secondary_default);
-    let (spawned, fallback_used) = spawn_shell_with_candidates(cols, rows, cwd, candidates).map_err(|err| {
+    let (spawned, fallback_used) = spawn_shell_with_candidates(cols, rows, cwd, candidates, sandbox).map_err(|err| {
       TerminalError::new(TerminalErrorCode::ShellLaunchFailed, err)
     })?;
     return Ok(TerminalLaunchResult {
@@ -277,14 +335,7 @@ pub(crate) fn launch_terminal_with_fallback(
     });
   }
 
-  let direct_result = spawn_direct_terminal_with_command(
-    cols,
-    rows,
-    cwd.clone(),
-    terminal_type,
-    terminal_path,
-    terminal_command,
-  )
+  let direct_result = spawn_direct_terminal_with_command(&request, sandbox)
     .map_err(|err| TerminalError::new(TerminalErrorCode::TerminalLaunchFailed, err));
   match direct_result {
     Ok(spawned) => Ok(TerminalLaunchResult {
@@ -301,7 +352,8 @@ pub(crate) fn launch_terminal_with_fallback(
       );
       let default_terminal_path = default_terminal.path.as_deref();
       let candidates = build_shell_candidates(default_terminal_path, None);
-      let (spawned, _) = spawn_shell_with_candidates(cols, rows, cwd, candidates).map_err(|fallback_err| {
+      let spawned = spawn_shell_with_candidates(cols, rows, request.cwd, candidates, sandbox);
+      let (spawned, _) = spawned.map_err(|fallback_err| {
         TerminalError::new(
           TerminalErrorCode::TerminalLaunchFailed,
           format!("terminal launch failed: primary={}; fallback={fallback_err}", primary_err.message),
@@ -21,13 +21,19 @@ use super::models::{
     TerminalErrorPayload, TerminalExitPayload, TerminalOutputPayload, TerminalStatusPayload,
 };
 use super::semantic::TerminalChatContext;
-use crate::now_millis;
 use crate::platform::backend_passive_enabled;
 use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
 use crate::ports::settings::TerminalSettingsPort;
 use crate::ports::terminal_event::TerminalEventPort;
 use crate::runtime::TerminalHandle;
+use crate::terminal_engine::default_members::onboarding::OnboardingContext;
+mod approval;
+mod approval_policy;
+mod clock;
 mod commands;
+#[cfg(test)]
+mod harness;
+mod hibernation;
 mod keyboard_input;
 mod stability;
 use stability::record_output_sample;
@@ -35,24 +41,42 @@ mod launch;
 mod poller;
 mod polling;
 mod post_ready;
+mod post_ready_script;
+mod recording;
 mod semantic_worker;
+mod session_discovery;
+mod shell_marks;
 mod snapshot_dump;
 mod snapshot_service;
 mod state;
+mod supervisor;
 mod trigger;
+mod tuning;
 
-use state::{DispatchQueueItem, TerminalDispatchEnvelope};
+use state::{DispatchQueueItem, MessageRowRange, TerminalDispatchEnvelope};
 
+pub(crate) use approval::terminal_respond_approval;
 pub(crate) use commands::*;
+pub(crate) use hibernation::{spawn_hibernation_sweeper, wake_hibernating_session};
+pub(crate) use keyboard_input::encode_key_sequence;
+use keyboard_input::{paste_multiline_input, KEY_ENTER};
 pub(crate) use poller::spawn_status_poller;
 use semantic_worker::{spawn_semantic_worker, SemanticEvent};
+use shell_marks::{CompletedCommand, ShellMark, ShellTurnTracker};
 pub(crate) use snapshot_dump::spawn_snapshot_dumper;
 use state::{
-    lock_sessions, PostReadyMode, PostReadyState, SessionRegistry, TerminalSession,
+    clock_now, lock_sessions, PostReadyMode, PostReadyState, SessionRegistry, TerminalSession,
     TerminalSessionStatus, TerminalSnapshot, TerminalType,
 };
 pub(crate) use state::{TerminalDispatchContext, TerminalManager};
 use trigger::FactEvent;
+pub(crate) use tuning::{resolve_terminal_tuning, TerminalTuning};
+use supervisor::{apply_restart_decision, RestartDecision, SupervisorExit, SupervisorState};
+pub(crate) use supervisor::RestartPolicy;
+pub(crate) use approval_policy::ApprovalPolicy;
+pub(crate) use post_ready_script::{
+    parse_post_ready_steps, PostReadyScript, PostReadyScriptStep, StoredPostReadyStep,
+};
 
 // 行为与性能参数说明：
 // - 工作状态回落、chat 静默门禁、输出批次、语义批次与流控阈值均为体验/性能折中。
@@ -68,12 +92,14 @@ const DISPATCH_RECENT_LIMIT: usize = 128; // 去重窗口，防止重复派发
 // 积压合并分隔符：用空行保留原始消息边界，避免引入额外语义。
 const DISPATCH_BATCH_SEPARATOR: &str = "\n\n";
 const COMMAND_CONFIRM_DELAY_MS: u64 = 100; // 派发确认延迟，避免 CLI 输入模式误判。
-const COMMAND_CONFIRM_SUFFIX: &str = "\r"; // 延迟补发回车用于提交指令。
+const COMMAND_CONFIRM_SUFFIX: &str = KEY_ENTER; // 延迟补发回车用于提交指令。
 const REDRAW_SUPPRESSION_WINDOW_MS: u64 = 400; // 布局期抑制 Working 触发，覆盖切换标签/attach/resize 场景。
 const POST_READY_STABLE_MS: u64 = 1200; // 启动后流程门禁，避免刚启动就触发后置步骤。
 const POST_READY_TICK_MS: u64 = 600; // post_ready 无输出时的轻量兜底触发间隔。
+const APPROVAL_PROMPT_SETTLE_MS: u64 = 800; // 输出停顿多久后识别审批提示，避开流式输出中途的误判。
 const STATUS_POLL_INTERVAL_MS: u64 = 500; // 状态轮询频率，避免频繁更新导致 UI 抖动。
 const SESSION_SCROLLBACK_LINES: usize = 2000; // 快照保留行数，上限过大将显著增大内存与快照大小。
+const MESSAGE_ROW_RANGE_LIMIT: usize = 256; // 每个会话保留的消息行区间数量，早于回滚缓冲的区间已无法取回。
 const SHIM_READY_SIGNAL: &str = "\x1b]633;A"; // shim 就绪信号（OSC 633;A）。
 const SHIM_LAUNCH_ERROR_MARKER: &str = "SHIM_LAUNCH_ERROR"; // shim 启动失败的标记前缀。
 const SHELL_READY_TIMEOUT_MS: u64 = 3000; // 超时仍未就绪时放行输入，避免用户阻塞。
@@ -120,6 +146,17 @@ fn resolve_terminal_type(raw: Option<&str>, command: Option<&str>) -> TerminalTy
     TerminalType::Shell
 }
 
+// 自定义终端类型：保留不在内置枚举中的类型名（含插件成员类型），按 shell 启动，
+// 供成员配置、post_ready 与过滤规则按名称查找。插件类型只按注册的 terminal_type 识别，
+// 不从启动命令反推，避免同名命令或带参数的默认命令误判。
+fn resolve_custom_terminal_type(raw: Option<&str>) -> Option<String> {
+    let value = raw.unwrap_or("").trim().to_lowercase();
+    if value.is_empty() || TerminalType::from_str(Some(&value)).is_some() {
+        return None;
+    }
+    Some(value)
+}
+
 // DND 时跳过终端派发，避免打扰用户的主动输入。
 fn is_member_dnd(sessions: &Arc<Mutex<SessionRegistry>>, terminal_id: &str) -> bool {
     let guard = lock_sessions(sessions);
@@ -352,7 +389,7 @@ fn dispatch_input_with_context(
         Some(app),
         settings_service,
     );
-    let now = now_millis()?;
+    let now = clock_now(sessions);
     let (
         should_write,
         buffered,
@@ -368,6 +405,7 @@ fn dispatch_input_with_context(
             .sessions
             .get_mut(terminal_id)
             .ok_or_else(|| "terminal session not found".to_string())?;
+        session.last_dispatch_at = Some(now);
         let shell_ready = session.shell_ready;
         let handle = session
             .handle
@@ -375,6 +413,7 @@ fn dispatch_input_with_context(
             .ok_or_else(|| "terminal session handle missing".to_string())?;
         let writer = Arc::clone(&handle.writer);
         let data_len = data.len();
+        let data = paste_multiline_input(session, data);
         let (should_write, buffered) = handle_buffered_write(session, data, now);
         (
             should_write,
@@ -478,7 +517,7 @@ pub(super) fn dispatch_chat_or_queue(
     };
     ensure_envelope_message_ids(&mut envelope);
     let mut envelope_slot = Some(envelope);
-    let now = now_millis().unwrap_or(0);
+    let now = clock_now(sessions);
     let mut kick_flush = false;
     let should_dispatch = {
         let mut guard = lock_sessions(sessions);
@@ -486,6 +525,7 @@ pub(super) fn dispatch_chat_or_queue(
             .sessions
             .get_mut(terminal_id)
             .ok_or_else(|| "terminal session not found".to_string())?;
+        session.last_dispatch_at = Some(now);
         {
             let envelope_ref = envelope_slot
                 .as_ref()
@@ -542,6 +582,39 @@ pub(super) fn dispatch_chat_or_queue(
     Ok(DispatchOutcome::Dispatched)
 }
 
+/// 当前 chat 块覆盖的绝对行区间：命令区间已闭合时取 C..D，否则取 chat 开始行到当前光标。
+pub(super) fn chat_block_row_range(
+    session: &TerminalSession,
+    command: Option<&CompletedCommand>,
+) -> Option<(u64, u64)> {
+    if let Some(command) = command {
+        return Some((command.start_row, command.end_row));
+    }
+    let start_row = session.chat_start_row?;
+    Some((start_row, session.snapshot.block_end_row().max(start_row)))
+}
+
+/// 记录已写回消息的行区间，供按消息取回原文与界面跳转。
+pub(super) fn record_message_rows(
+    sessions: &Arc<Mutex<SessionRegistry>>,
+    terminal_id: &str,
+    message_id: String,
+    (start_row, end_row): (u64, u64),
+) {
+    let mut guard = lock_sessions(sessions);
+    let Some(session) = guard.sessions.get_mut(terminal_id) else {
+        return;
+    };
+    session.message_rows.push_back(MessageRowRange {
+        message_id,
+        start_row,
+        end_row,
+    });
+    while session.message_rows.len() > MESSAGE_ROW_RANGE_LIMIT {
+        session.message_rows.pop_front();
+    }
+}
+
 pub(super) fn complete_dispatch_and_flush(
     app: &AppHandle,
     sessions: &Arc<Mutex<SessionRegistry>>,
@@ -581,6 +654,7 @@ pub(super) fn complete_dispatch_and_flush(
     if let Err(err) = dispatch_chat_sequence(app, sessions, event_port, terminal_id, envelope.clone())
     {
         let mut guard = lock_sessions(sessions);
+        let now = guard.clock.now_ms();
         if let Some(session) = guard.sessions.get_mut(terminal_id) {
             session.dispatch_inflight = false;
             session.dispatch_inflight_message_id = None;
@@ -588,7 +662,7 @@ pub(super) fn complete_dispatch_and_flush(
             if session.dispatch_queue.len() < DISPATCH_QUEUE_LIMIT {
                 session.dispatch_queue.push_front(DispatchQueueItem {
                     envelope,
-                    enqueued_at: now_millis().unwrap_or(0),
+                    enqueued_at: now,
                 });
             }
         }
@@ -627,6 +701,7 @@ pub(super) fn flush_dispatch_queue_if_ready(
     if let Err(err) = dispatch_chat_sequence(app, sessions, event_port, terminal_id, envelope.clone())
     {
         let mut guard = lock_sessions(sessions);
+        let now = guard.clock.now_ms();
         if let Some(session) = guard.sessions.get_mut(terminal_id) {
             session.dispatch_inflight = false;
             session.dispatch_inflight_message_id = None;
@@ -634,7 +709,7 @@ pub(super) fn flush_dispatch_queue_if_ready(
             if session.dispatch_queue.len() < DISPATCH_QUEUE_LIMIT {
                 session.dispatch_queue.push_front(DispatchQueueItem {
                     envelope,
-                    enqueued_at: now_millis().unwrap_or(0),
+                    enqueued_at: now,
                 });
             }
         }
@@ -649,13 +724,14 @@ pub(super) fn flush_dispatch_queue_if_ready(
 fn get_flow_control_state(
     sessions: &Arc<Mutex<SessionRegistry>>,
     terminal_id: &str,
-) -> Option<(usize, bool, bool)> {
+) -> Option<(usize, bool, bool, TerminalTuning)> {
     let guard = lock_sessions(sessions);
     guard.sessions.get(terminal_id).map(|session| {
         (
             session.unacked_bytes,
             session.ui_active,
             session.chat_pending,
+            session.tuning,
         )
     })
 }
@@ -703,7 +779,7 @@ fn mark_session_working_on_input(
 ) {
     let is_command = data.contains('\n') || data.contains('\r');
     let mut status_payload = None;
-    let now = now_millis().ok();
+    let now = clock_now(sessions);
     let mut chat_context = chat_context;
     let mut chat_pending_at = None;
     {
@@ -713,20 +789,21 @@ fn mark_session_working_on_input(
             Some(session) => session,
             None => return,
         };
-        if !is_command && session.status != TerminalSessionStatus::Working {
+        // 等待审批时任意按键都视为作答（含方向键选择、单键确认），回到 Working 等待 CLI 继续输出。
+        let answered_approval = session.status == TerminalSessionStatus::AwaitingInput;
+        if !is_command && session.status != TerminalSessionStatus::Working && !answered_approval {
             return;
         }
         if is_command {
             if let Some(context) = chat_context.take() {
                 session.chat_pending = true;
-                session.chat_pending_since = now;
+                session.chat_pending_since = Some(now);
+                session.chat_start_row = Some(session.snapshot.absolute_cursor().0);
                 session.chat_candidate_at = None;
                 session.semantic_active = false;
                 session.chat_stream_enabled =
                     resolve_chat_stream_enabled(settings_service.as_deref());
-                if let Some(now) = now {
-                    chat_pending_at = Some(now);
-                }
+                chat_pending_at = Some((now, session.tuning));
                 let semantic_tx = if let Some(tx) = session.semantic_tx.clone() {
                     Some(tx)
                 } else if let Some(app) = app {
@@ -748,7 +825,7 @@ fn mark_session_working_on_input(
                         session.workspace_id.clone(),
                         session.screen_rows,
                         session.screen_cols,
-                        session.terminal_type.as_str().to_string(),
+                        session.member_type().to_string(),
                         pipeline,
                         settings_service,
                         Arc::clone(sessions),
@@ -785,33 +862,34 @@ fn mark_session_working_on_input(
                     }
                 }
             }
-            if let Some(now) = now {
-                session.working_intent_until =
-                    Some(now.saturating_add(WORKING_INTENT_WINDOW_MS));
-            }
+            session.working_intent_until =
+                Some(now.saturating_add(session.tuning.working_intent_window_ms));
             if !session.status_locked && session.status != TerminalSessionStatus::Working {
                 if update_session_status(&working_sessions, session, TerminalSessionStatus::Working)
                 {
                     status_payload = Some(build_status_payload(session));
                 }
             }
+        } else if answered_approval
+            && update_session_status(&working_sessions, session, TerminalSessionStatus::Working)
+        {
+            status_payload = Some(build_status_payload(session));
         }
         // 输入在 Working 中也续命，避免无输出的交互型场景被误判回落。
-        if let Some(now) = now {
-            session.last_activity_at = Some(now);
-        }
+        session.last_activity_at = Some(now);
         session.idle_candidate_at = None;
         session.chat_candidate_at = None;
     }
     if let Some(payload) = status_payload {
         let _ = event_port.emit_status(payload);
     }
-    if let Some(observed_at) = chat_pending_at {
+    if let Some((observed_at, tuning)) = chat_pending_at {
         emit_trigger_signal(
             sessions,
             FactEvent::ChatPending {
                 terminal_id: terminal_id.to_string(),
                 observed_at,
+                tuning,
             },
         );
     }
@@ -890,6 +968,7 @@ fn build_status_payload(session: &TerminalSession) -> TerminalStatusPayload {
         status: session.status.as_str().to_string(),
         member_id: session.member_id.clone(),
         workspace_id: session.workspace_id.clone(),
+        prompt: session.approval_prompt.clone(),
     }
 }
 
@@ -903,6 +982,10 @@ fn update_session_status(
     }
     let prev = session.status;
     session.status = next;
+    if prev == TerminalSessionStatus::AwaitingInput {
+        // 离开等待审批即视为提示已处理；记下提示文本，避免屏幕残留的同一提示被再次识别。
+        session.approval_answered = session.approval_prompt.take();
+    }
     if next == TerminalSessionStatus::Offline {
         session.dispatch_inflight = false;
         session.dispatch_inflight_message_id = None;
@@ -1042,6 +1125,57 @@ fn contains_lone_carriage_return(bytes: &[u8]) -> bool {
     false
 }
 
+/// 输出到达时更新活跃时间，并按 Working 触发门禁决定是否切到 Working。
+/// 返回：状态发生变化时的状态载荷。
+fn apply_output_activity(
+    working_sessions: &Arc<Mutex<HashSet<String>>>,
+    session: &mut TerminalSession,
+    now: u64,
+) -> Option<TerminalStatusPayload> {
+    if let Some(until) = session.redraw_suppression_until {
+        if now > until {
+            session.redraw_suppression_until = None;
+        }
+    }
+    // Working 触发门禁（输出路径）：
+    // 1) 布局期（切换标签/attach/resize）内无条件抑制；
+    // 2) 仅在命令意图窗口或派发/聊天进行中才允许输出触发；
+    // 3) 其余输出视为背景噪声，保持 Online。
+    // 备注：命令提交会立即切 Working，这里只负责“输出触发”分支。
+    let suppress_working = session
+        .redraw_suppression_until
+        .map(|until| now <= until)
+        .unwrap_or(false);
+    let mut working_intent_active = session.chat_pending || session.dispatch_inflight;
+    if let Some(until) = session.working_intent_until {
+        if now <= until {
+            working_intent_active = true;
+        } else {
+            session.working_intent_until = None;
+        }
+    }
+    let status_payload = if !session.status_locked
+        && !suppress_working
+        && working_intent_active
+        && session.status != TerminalSessionStatus::Working
+        && session.status != TerminalSessionStatus::AwaitingInput
+    {
+        if update_session_status(working_sessions, session, TerminalSessionStatus::Working) {
+            Some(build_status_payload(session))
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    session.last_activity_at = Some(now);
+    session.last_output_at = Some(now);
+    session.last_applied_at = Some(now);
+    session.idle_candidate_at = None;
+    session.chat_candidate_at = None;
+    status_payload
+}
+
 fn detect_shell_ready(session: &mut TerminalSession, output: &str, now: u64) -> Option<String> {
     // 通过 OSC 就绪信号或启动期输出量判断 shell 可交互，避免过早放行输入。
     let mut became_ready = false;
@@ -1051,7 +1185,7 @@ fn detect_shell_ready(session: &mut TerminalSession, output: &str, now: u64) ->
         reason = "osc";
     } else {
         session.ready_probe_bytes = session.ready_probe_bytes.saturating_add(output.len());
-        if now.saturating_sub(session.created_at) >= SHELL_READY_ACTIVITY_MS {
+        if now.saturating_sub(session.created_at) >= session.tuning.shell_ready_activity_ms {
             if session.ready_probe_bytes >= SHELL_READY_ACTIVITY_BYTES || output.contains('\n') {
                 became_ready = true;
                 reason = "activity";
@@ -1098,11 +1232,13 @@ fn handle_buffered_write(
     data: String,
     now: u64,
 ) -> (bool, Vec<String>) {
+    // 输入在提交时记录，缓冲中的输入也按用户/派发的实际发起时间出现在录制里。
+    record_session_event(session, |recorder| recorder.record_input(&data));
     if session.shell_ready {
         return (true, vec![data]);
     }
     // shell 未就绪时先缓冲输入，避免输入在启动期丢失或打断探测。
-    if now.saturating_sub(session.created_at) >= SHELL_READY_TIMEOUT_MS {
+    if now.saturating_sub(session.created_at) >= session.tuning.shell_ready_timeout_ms {
         log::warn!(
             "terminal session ready timeout terminal_id={} forcing ready",
             session.id
@@ -1120,38 +1256,151 @@ fn handle_buffered_write(
     (false, Vec::new())
 }
 
-fn register_session(
-    state: &TerminalManager,
-    terminal_id: &str,
+// 录制失败时仅停止录制，不影响会话 IO。
+fn record_session_event(
+    session: &mut TerminalSession,
+    record: impl FnOnce(&mut recording::SessionRecorder) -> Result<(), String>,
+) {
+    let Some(recorder) = session.recorder.as_mut() else {
+        return;
+    };
+    if let Err(err) = record(recorder) {
+        log::warn!("terminal recording stopped terminal_id={} err={}", session.id, err);
+        session.recorder = None;
+    }
+}
+
+/// 注册会话条目；`handle` 为空时仅登记状态而不绑定 PTY（用于无头测试）。
+/// 单个 PTY 输出块在会话锁内的处理结果。
+pub(super) struct OutputIngest {
+    pub(super) status_payload: Option<TerminalStatusPayload>,
+    pub(super) shim_error: Option<String>,
+    pub(super) shell_marks: Vec<ShellMark>,
+    pub(super) first_output: bool,
+    /// 本块使 shell 进入就绪时的时刻；调用方据此释放缓冲输入并推进 post_ready。
+    pub(super) shell_ready_observed_at: Option<u64>,
+    pub(super) output_updated_at: Option<(u64, TerminalTuning)>,
+}
+
+/// 在会话锁内处理一个 PTY 输出块：写快照与命令边界标记、录制、计数与采样、
+/// shell 就绪探测、输出活跃度与 Working 门禁。PTY 处理线程与无头测试共用此入口。
+pub(super) fn ingest_output_chunk(
+    working_sessions: &Arc<Mutex<HashSet<String>>>,
+    session: &mut TerminalSession,
+    chunk: &[u8],
+    data: &str,
+    now: u64,
+) -> OutputIngest {
+    let first_output = session.output_seq == 0;
+    let mut shell_marks = Vec::new();
+    if !chunk.is_empty() {
+        let snapshot = &mut session.snapshot;
+        shell_marks = session.shell_turns.feed(chunk, now, |segment| {
+            snapshot.apply_output(segment);
+            snapshot.absolute_cursor()
+        });
+        record_session_event(session, |recorder| recorder.record_output(chunk));
+    }
+    session.output_seq = session.output_seq.saturating_add(1);
+    session.output_bytes_total = session
+        .output_bytes_total
+        .saturating_add(chunk.len() as u64);
+    record_output_sample(session, now);
+    let mut shell_ready_observed_at = None;
+    let shim_error = if session.shell_ready {
+        None
+    } else {
+        let error = detect_shell_ready(session, data, now);
+        if session.shell_ready {
+            shell_ready_observed_at = Some(now);
+        }
+        error
+    };
+    let status_payload = apply_output_activity(working_sessions, session, now);
+    let output_updated_at = session.last_applied_at.map(|at| (at, session.tuning));
+    OutputIngest {
+        status_payload,
+        shim_error,
+        shell_marks,
+        first_output,
+        shell_ready_observed_at,
+        output_updated_at,
+    }
+}
+
+/// 会话注册参数：终端创建与无头测试共用；`workspace_path` 为项目工作区根目录，
+/// `launch_*` 为重启时复用的启动信息（`launch_cwd` 可能是成员独立工作树），
+/// 调优、策略、引导词与启动后脚本由调用方预先从成员配置解析。
+struct SessionRegistration {
     member_id: Option<String>,
     member_name: Option<String>,
     workspace_id: Option<String>,
+    workspace_path: Option<String>,
     launch_cwd: Option<String>,
     launch_command: Option<String>,
     launch_path: Option<String>,
     launch_strict_shell: bool,
+    launch_sandboxed: bool,
     post_ready_mode: PostReadyMode,
     rows: u16,
     cols: u16,
     terminal_type: TerminalType,
+    custom_type: Option<String>,
     keep_alive: bool,
     owner_window_label: Option<String>,
     output_window_label: Option<String>,
     semantic_tx: Option<mpsc::Sender<SemanticEvent>>,
-    handle: TerminalHandle,
+    handle: Option<TerminalHandle>,
+    tuning: TerminalTuning,
+    restart_policy: RestartPolicy,
+    approval_policy: ApprovalPolicy,
+    onboarding: Option<OnboardingContext>,
+    post_ready_script: Option<PostReadyScript>,
+}
+
+fn register_session(
+    state: &TerminalManager,
+    terminal_id: &str,
+    registration: SessionRegistration,
 ) -> Result<TerminalStatusPayload, String> {
+    let SessionRegistration {
+        member_id,
+        member_name,
+        workspace_id,
+        workspace_path,
+        launch_cwd,
+        launch_command,
+        launch_path,
+        launch_strict_shell,
+        launch_sandboxed,
+        post_ready_mode,
+        rows,
+        cols,
+        terminal_type,
+        custom_type,
+        keep_alive,
+        owner_window_label,
+        output_window_label,
+        semantic_tx,
+        handle,
+        tuning,
+        restart_policy,
+        approval_policy,
+        onboarding,
+        post_ready_script,
+    } = registration;
     // 注册时构建快照与语义管线，确保 attach/语义事件一致。
-    let created_at = now_millis().unwrap_or(0);
     let mut guard = state
         .sessions
         .lock()
         .map_err(|_| "terminal session lock poisoned".to_string())?;
+    let created_at = guard.clock.now_ms();
     if guard.sessions.contains_key(terminal_id) {
         return Err("terminal session already exists".to_string());
     }
     // 消费创建前预锁：注册时将 pending 标记转为真实锁定状态。
     let pending_locked = guard.pending_status_locks.remove(terminal_id);
-    let response_writer = Arc::clone(&handle.writer);
+    let response_writer = handle.as_ref().map(|handle| Arc::clone(&handle.writer));
     let pending_output_chunks = Arc::new(AtomicUsize::new(0));
     let status = if pending_locked {
         TerminalSessionStatus::Connecting
@@ -1166,6 +1415,7 @@ fn register_session(
     let session = TerminalSession {
         id: terminal_id.to_string(),
         terminal_type,
+        custom_type,
         status,
         output_bytes_total: 0,
         output_seq: 0,
@@ -1173,7 +1423,7 @@ fn register_session(
         screen_rows: rows,
         screen_cols: cols,
         member_id: member_id.clone(),
-        member_name: member_name,
+        member_name,
         workspace_id: workspace_id.clone(),
         status_locked: pending_locked,
         active: true,
@@ -1200,14 +1450,16 @@ fn register_session(
         post_ready_restart_pending: false,
         spawn_epoch: 1,
         ui_active: false,
-        handle: Some(handle),
-        snapshot: TerminalSnapshot::new(rows, cols, Some(response_writer)),
+        handle,
+        snapshot: TerminalSnapshot::new(rows, cols, response_writer),
         semantic_tx,
         keep_alive,
+        workspace_path,
         launch_cwd,
         launch_command,
         launch_path,
         launch_strict_shell,
+        launch_sandboxed,
         owner_window_label,
         output_window_label,
         shell_ready: false,
@@ -1221,6 +1473,20 @@ fn register_session(
         dispatch_inflight_message_id: None,
         dispatch_inflight_message_ids: Vec::new(),
         dispatch_recent_message_ids: VecDeque::new(),
+        recorder: None,
+        tuning,
+        shell_turns: ShellTurnTracker::default(),
+        chat_start_row: None,
+        message_rows: VecDeque::new(),
+        restart_policy,
+        supervisor: SupervisorState::default(),
+        last_dispatch_at: None,
+        approval_prompt: None,
+        approval_answered: None,
+        approval_checked_seq: 0,
+        approval_policy,
+        onboarding,
+        post_ready_script,
     };
     let payload = build_status_payload(&session);
     guard.sessions.insert(terminal_id.to_string(), session);
@@ -1377,7 +1643,7 @@ impl InitialWriteState {
             None,
             None,
         );
-        let now = now_millis().unwrap_or(0);
+        let now = clock_now(&self.sessions);
         let (should_write, buffered) = {
             let mut guard = lock_sessions(&self.sessions);
             let session = match guard.sessions.get_mut(&self.terminal_id) {
@@ -1539,8 +1805,9 @@ fn spawn_pty_reader(
                         state.schedule(initial_write_delay_ms, "first-data");
                     }
                 }
-                if let Ok(now) = now_millis() {
+                {
                     let mut guard = lock_sessions(&sessions);
+                    let now = guard.clock.now_ms();
                     if let Some(session) = guard.sessions.get_mut(&terminal_id) {
                         session.last_read_at = Some(now);
                     }
@@ -1582,7 +1849,7 @@ fn spawn_pty_processor(
             let mut flow_paused = false;
             loop {
                 // UI 不活跃且未确认输出过多时暂停处理，避免内存与 IPC 压力。
-                if let Some((unacked, ui_active, chat_pending)) =
+                if let Some((unacked, ui_active, chat_pending, tuning)) =
                     get_flow_control_state(&sessions, &terminal_id)
                 {
                     let mut next_flow_paused = flow_paused;
@@ -1592,10 +1859,10 @@ fn spawn_pty_processor(
                     } else if ui_active {
                         next_flow_paused = false;
                     } else if flow_paused {
-                        if unacked <= FLOW_CONTROL_LOW_WATERMARK {
+                        if unacked <= tuning.flow_control_low_watermark {
                             next_flow_paused = false;
                         }
-                    } else if unacked >= FLOW_CONTROL_HIGH_WATERMARK {
+                    } else if unacked >= tuning.flow_control_high_watermark {
                         next_flow_paused = true;
                     }
                     if next_flow_paused != flow_paused {
@@ -1624,8 +1891,9 @@ fn spawn_pty_processor(
                 } else {
                     detect_output_cleanup(&data)
                 };
-                let mut shell_ready_observed_at = None;
-                let output_updated_at: Option<u64>;
+                let shell_ready_observed_at: Option<u64>;
+                let shell_marks: Vec<ShellMark>;
+                let output_updated_at: Option<(u64, TerminalTuning)>;
                 let (
                     status_payload,
                     first_output,
@@ -1664,19 +1932,23 @@ fn spawn_pty_processor(
                     terminal_type,
                         ) = {
                         let working_sessions = Arc::clone(&guard.working_sessions);
+                        let now = guard.clock.now_ms();
                         let session = match guard.sessions.get_mut(&terminal_id) {
                             Some(session) => session,
                             None => break,
                         };
-                        let first_output = session.output_seq == 0;
-                        if !chunk.is_empty() {
-                            session.snapshot.apply_output(&chunk);
-                        }
-                        session.output_seq = session.output_seq.saturating_add(1);
-                        session.output_bytes_total = session
-                            .output_bytes_total
-                            .saturating_add(chunk.len() as u64);
-                        record_output_sample(session, now_millis().unwrap_or(0));
+                        let OutputIngest {
+                            status_payload,
+                            shim_error,
+                            shell_marks: marks,
+                            first_output,
+                            shell_ready_observed_at: observed_at,
+                            output_updated_at: updated_at,
+                        } = ingest_output_chunk(&working_sessions, session, &chunk, &data, now);
+                        shell_marks = marks;
+                        shell_ready_observed_at = observed_at;
+                        output_updated_at = updated_at;
+                        let flush_needed = observed_at.is_some();
                         let output_seq = session.output_seq;
                         let output_bytes_total = session.output_bytes_total;
                         let semantic_tx = session.semantic_tx.clone();
@@ -1688,68 +1960,7 @@ fn spawn_pty_processor(
                         let member_id = session.member_id.clone();
                         let workspace_id = session.workspace_id.clone();
                         let terminal_type = session.terminal_type.as_str().to_string();
-                        let mut flush_needed = false;
-                        let now = now_millis().unwrap_or(0);
-                        let shell_ready_before = session.shell_ready;
-                        let shim_error = if session.shell_ready {
-                            None
-                        } else {
-                            let error = detect_shell_ready(session, &data, now);
-                            if session.shell_ready {
-                                flush_needed = true;
-                            }
-                            error
-                        };
-                        if !shell_ready_before && session.shell_ready {
-                            shell_ready_observed_at = Some(now);
-                        }
-                        if let Some(until) = session.redraw_suppression_until {
-                            if now > until {
-                                session.redraw_suppression_until = None;
-                            }
-                        }
-                        // Working 触发门禁（输出路径）：
-                        // 1) 布局期（切换标签/attach/resize）内无条件抑制；
-                        // 2) 仅在命令意图窗口或派发/聊天进行中才允许输出触发；
-                        // 3) 其余输出视为背景噪声，保持 Online。
-                        // 备注：命令提交会立即切 Working，这里只负责“输出触发”分支。
-                        let suppress_working = session
-                            .redraw_suppression_until
-                            .map(|until| now <= until)
-                            .unwrap_or(false);
-                        let mut working_intent_active =
-                            session.chat_pending || session.dispatch_inflight;
-                        if let Some(until) = session.working_intent_until {
-                            if now <= until {
-                                working_intent_active = true;
-                            } else {
-                                session.working_intent_until = None;
-                            }
-                        }
-                        let status_payload = if !session.status_locked
-                            && !suppress_working
-                            && working_intent_active
-                            && session.status != TerminalSessionStatus::Working
-                        {
-                            if update_session_status(
-                                &working_sessions,
-                                session,
-                                TerminalSessionStatus::Working,
-                            ) {
-                                Some(build_status_payload(session))
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        };
-                        session.last_activity_at = Some(now);
-                        session.last_output_at = Some(now);
-                        session.last_applied_at = Some(now);
-                        session.idle_candidate_at = None;
-                        session.chat_candidate_at = None;
                         let output_at = session.last_applied_at;
-                        output_updated_at = output_at;
                         (
                             status_payload,
                             first_output,
@@ -1788,15 +1999,28 @@ fn spawn_pty_processor(
                         terminal_type,
                     )
                 };
-                if let Some(observed_at) = output_updated_at {
+                if let Some((observed_at, tuning)) = output_updated_at {
                     emit_trigger_signal(
                         &sessions,
                         FactEvent::OutputUpdated {
                             terminal_id: terminal_id.clone(),
                             observed_at,
+                            tuning,
                         },
                     );
                 }
+                if terminal_trace_detail() {
+                    for mark in shell_marks.iter() {
+                        if let ShellMark::CommandFinished { exit_code } = mark {
+                            log::info!(
+                                "terminal_shell_command_finished terminal_id={} seq={} exit_code={:?}",
+                                terminal_id,
+                                output_seq,
+                                exit_code
+                            );
+                        }
+                    }
+                }
                 if shell_ready_observed_at.is_some() {
                     emit_trigger_signal(
                         &sessions,
@@ -2023,6 +2247,7 @@ fn terminal_trace_detail() -> bool {
 }
 
 fn spawn_exit_watcher(
+    app: AppHandle,
     mut child: Box<dyn Child + Send + Sync>,
     event_port: Arc<dyn TerminalEventPort>,
     sessions: Arc<Mutex<SessionRegistry>>,
@@ -2052,10 +2277,11 @@ fn spawn_exit_watcher(
                 (None, Some("error".to_string()), "error".to_string())
             }
         };
-        let (exit_payload, status_payload, semantic_tx, notice_bytes, semantic_active, chat_stream_enabled, snapshot_seed, seed_rows, seed_cols) = {
+        let (exit_payload, status_payload, semantic_tx, notice_bytes, semantic_active, chat_stream_enabled, snapshot_seed, seed_rows, seed_cols, supervisor_exit) = {
             let mut guard = lock_sessions(&sessions);
             let working_sessions = Arc::clone(&guard.working_sessions);
-            let (exit_payload, status_payload, semantic_tx, notice_bytes, semantic_active, chat_stream_enabled, snapshot_seed, seed_rows, seed_cols) = {
+            let exited_at = guard.clock.now_ms();
+            let (exit_payload, status_payload, semantic_tx, notice_bytes, semantic_active, chat_stream_enabled, snapshot_seed, seed_rows, seed_cols, supervisor_exit) = {
                 let session = match guard.sessions.get_mut(&terminal_id) {
                     Some(session) => session,
                     None => return,
@@ -2069,8 +2295,31 @@ fn spawn_exit_watcher(
                     );
                     return;
                 }
+                // 休眠主动关闭的进程：保留冻结快照与休眠状态，不写入退出提示。
+                if session.status == TerminalSessionStatus::Hibernating {
+                    return;
+                }
                 let semantic_active = session.semantic_active;
                 let chat_stream_enabled = session.chat_stream_enabled;
+                // 句柄仍在说明不是关闭/重启主动结束的进程，交给守护按策略决定是否拉起。
+                let decision = if session.handle.is_some() {
+                    let failed = code != Some(0);
+                    session
+                        .supervisor
+                        .on_exit(&session.restart_policy, failed, exited_at)
+                } else {
+                    RestartDecision::Skip
+                };
+                let supervisor_exit = SupervisorExit {
+                    terminal_id: terminal_id.clone(),
+                    spawn_epoch,
+                    workspace_id: session.workspace_id.clone(),
+                    member_id: session.member_id.clone(),
+                    member_name: session.member_name.clone(),
+                    policy: session.restart_policy,
+                    reason: reason.clone(),
+                    decision,
+                };
                 session.active = false;
                 session.last_activity_at = None;
                 session.last_output_at = None;
@@ -2102,7 +2351,7 @@ fn spawn_exit_watcher(
                 let notice_bytes =
                     format!("\r\n\x1b[31m[Process exited with {reason}]\x1b[0m").into_bytes();
                 session.snapshot.apply_output(&notice_bytes);
-                let applied_at = now_millis().ok();
+                let applied_at = Some(exited_at);
                 session.last_output_at = applied_at;
                 session.last_applied_at = applied_at;
                 session.output_bytes_total = session
@@ -2126,6 +2375,7 @@ fn spawn_exit_watcher(
                     snapshot_seed,
                     seed_rows,
                     seed_cols,
+                    supervisor_exit,
                 )
             };
             (
@@ -2138,6 +2388,7 @@ fn spawn_exit_watcher(
                 snapshot_seed,
                 seed_rows,
                 seed_cols,
+                supervisor_exit,
             )
         };
         let _ = event_port.emit_exit(exit_payload);
@@ -2161,5 +2412,6 @@ fn spawn_exit_watcher(
                 });
             }
         }
+        apply_restart_decision(&app, supervisor_exit);
     });
 }
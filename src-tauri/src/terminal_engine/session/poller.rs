@@ -9,8 +9,6 @@ use std::{
 
 use tauri::AppHandle;
 
-use crate::now_millis;
-
 use super::polling::{
     build_poll_actions, collect_poll_snapshots_by_ids, collect_poll_snapshots_by_working_set,
     dispatch_poll_actions, RuleMask,
@@ -20,7 +18,7 @@ use super::trigger::{
     plan_trigger, DeferredStage, GuardianEvent, ScheduledTrigger, TriggerBus, TriggerEvent,
     TriggerScheduler, TriggerTargets,
 };
-use super::{lock_sessions, PostReadyState, SessionRegistry, TerminalManager, POST_READY_TICK_MS};
+use super::{lock_sessions, PostReadyState, SessionRegistry, TerminalManager};
 use crate::ports::terminal_event::TerminalEventPort;
 use crate::ports::terminal_session::TerminalSessionRepository;
 
@@ -29,6 +27,7 @@ pub(crate) fn spawn_status_poller(app: AppHandle, manager: &TerminalManager) {
     let sessions = Arc::clone(&manager.sessions);
     let event_port = manager.event_port();
     let session_repository = manager.session_repository();
+    let clock = manager.clock();
     let settings_service = {
         let guard = manager
             .settings_service
@@ -44,17 +43,12 @@ pub(crate) fn spawn_status_poller(app: AppHandle, manager: &TerminalManager) {
     }
     thread::spawn(move || {
         let mut scheduler = TriggerScheduler::default();
-        if let Ok(now) = now_millis() {
-            scheduler.schedule(ScheduledTrigger::immediate(
-                now,
-                TriggerEvent::Guardian(GuardianEvent::StatusFallbackTick),
-            ));
-        }
+        scheduler.schedule(ScheduledTrigger::immediate(
+            clock.now_ms(),
+            TriggerEvent::Guardian(GuardianEvent::StatusFallbackTick),
+        ));
         loop {
-            let now = match now_millis() {
-                Ok(value) => value,
-                Err(_) => continue,
-            };
+            let now = clock.now_ms();
             for event in scheduler.pop_due(now) {
                 handle_trigger_event(
                     event,
@@ -73,10 +67,7 @@ pub(crate) fn spawn_status_poller(app: AppHandle, manager: &TerminalManager) {
                 .unwrap_or(500);
             match rx.recv_timeout(Duration::from_millis(timeout_ms)) {
                 Ok(event) => {
-                    let event_now = match now_millis() {
-                        Ok(value) => value,
-                        Err(_) => now,
-                    };
+                    let event_now = clock.now_ms().max(now);
                     handle_trigger_event(
                         event,
                         event_now,
@@ -113,8 +104,12 @@ fn handle_trigger_event(
         return;
     }
     let snapshots = match plan.targets {
-        TriggerTargets::WorkingSet => collect_poll_snapshots_by_working_set(sessions),
-        TriggerTargets::SessionIds(ids) => collect_poll_snapshots_by_ids(sessions, &ids),
+        TriggerTargets::WorkingSet => {
+            collect_poll_snapshots_by_working_set(sessions, now, plan.mask)
+        }
+        TriggerTargets::SessionIds(ids) => {
+            collect_poll_snapshots_by_ids(sessions, &ids, now, plan.mask)
+        }
     };
     if snapshots.is_empty() {
         return;
@@ -143,7 +138,7 @@ fn schedule_post_ready_ticks(
             continue;
         }
         scheduler.schedule(ScheduledTrigger::deferred(
-            now.saturating_add(POST_READY_TICK_MS),
+            now.saturating_add(snapshot.tuning.post_ready_tick_ms),
             snapshot.terminal_id.clone(),
             RuleMask::POST_READY,
             DeferredStage::PostReadyTick,
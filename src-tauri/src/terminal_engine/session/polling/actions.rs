@@ -1,5 +1,6 @@
 //! 轮询动作与会话更新：用于拆分采集与业务执行。
 
+use super::super::approval_policy::ApprovalSubject;
 use super::super::TerminalSessionStatus;
 
 #[derive(Clone, Debug)]
@@ -22,6 +23,8 @@ pub(crate) struct SessionUpdate {
   pub(crate) set_chat_pending: Option<bool>,
   pub(crate) set_chat_pending_since: Option<Option<u64>>,
   pub(crate) set_semantic_active: Option<bool>,
+  pub(crate) set_approval_prompt: Option<Option<String>>,
+  pub(crate) set_approval_answered: Option<Option<String>>,
   pub(crate) emit_status: bool,
 }
 
@@ -35,6 +38,8 @@ impl SessionUpdate {
       set_chat_pending: None,
       set_chat_pending_since: None,
       set_semantic_active: None,
+      set_approval_prompt: None,
+      set_approval_answered: None,
       emit_status: false,
     }
   }
@@ -46,7 +51,9 @@ impl SessionUpdate {
       || self.set_chat_pending.is_some()
       || self.set_chat_pending_since.is_some()
       || self.set_semantic_active.is_some()
-}
+      || self.set_approval_prompt.is_some()
+      || self.set_approval_answered.is_some()
+  }
 }
 
 #[derive(Clone, Debug)]
@@ -56,4 +63,9 @@ pub(crate) enum PollAction {
   PostReadyStart { terminal_id: String, terminal_type: String },
   PostReadyStep { terminal_id: String },
   PostReadyRestart { terminal_id: String },
+  ApprovalPrompt {
+    terminal_id: String,
+    prompt: String,
+    subject: ApprovalSubject,
+  },
 }
@@ -1,44 +1,58 @@
-//! 轮询采集器：负责在锁内提取最小快照。
+//! 轮询采集器：负责在锁内提取最小快照（并记录审批尾部的采集位置）。
 
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::Ordering;
 
+use super::rule_mask::RuleMask;
 use super::snapshot::SessionPollSnapshot;
+use super::super::approval::approval_screen_tail;
 use super::super::{lock_sessions, SessionRegistry};
 
 pub(crate) fn collect_poll_snapshots_by_ids(
   sessions: &Arc<Mutex<SessionRegistry>>,
   terminal_ids: &[String],
+  now: u64,
+  mask: RuleMask,
 ) -> Vec<SessionPollSnapshot> {
-  let guard = lock_sessions(sessions);
-  terminal_ids
-    .iter()
-    .filter_map(|terminal_id| guard.sessions.get(terminal_id))
-    .filter(|session| session.active)
-    .map(build_snapshot)
-    .collect()
+  let mut guard = lock_sessions(sessions);
+  let mut snapshots = Vec::new();
+  for terminal_id in terminal_ids {
+    if let Some(session) = guard.sessions.get_mut(terminal_id).filter(|session| session.active) {
+      snapshots.push(build_snapshot(session, now, mask));
+    }
+  }
+  snapshots
 }
 
 pub(crate) fn collect_poll_snapshots_by_working_set(
   sessions: &Arc<Mutex<SessionRegistry>>,
+  now: u64,
+  mask: RuleMask,
 ) -> Vec<SessionPollSnapshot> {
   let guard = lock_sessions(sessions);
   let working_ids = match guard.working_sessions.lock() {
     Ok(value) => value.iter().cloned().collect::<Vec<_>>(),
     Err(err) => err.into_inner().iter().cloned().collect::<Vec<_>>(),
   };
-  working_ids
-    .into_iter()
-    .filter_map(|terminal_id| guard.sessions.get(&terminal_id))
-    .filter(|session| session.active)
-    .map(build_snapshot)
-    .collect()
+  drop(guard);
+  collect_poll_snapshots_by_ids(sessions, &working_ids, now, mask)
 }
 
-fn build_snapshot(session: &super::super::TerminalSession) -> SessionPollSnapshot {
+// 审批尾部只在本轮包含审批规则时采集，否则会提前记下采集位置而漏掉识别。
+fn build_snapshot(
+  session: &mut super::super::TerminalSession,
+  now: u64,
+  mask: RuleMask,
+) -> SessionPollSnapshot {
+  let approval = if mask.contains(RuleMask::APPROVAL_PROMPT) {
+    approval_screen_tail(session, now)
+  } else {
+    None
+  };
+  let (approval_patterns, screen_tail) = approval.unwrap_or((&[], Vec::new()));
   SessionPollSnapshot {
     terminal_id: session.id.clone(),
-    terminal_type: session.terminal_type.as_str().to_string(),
+    terminal_type: session.member_type().to_string(),
     status: session.status,
     status_locked: session.status_locked,
     ui_active: session.ui_active,
@@ -53,8 +67,16 @@ fn build_snapshot(session: &super::super::TerminalSession) -> SessionPollSnapsho
     chat_pending: session.chat_pending,
     chat_pending_since: session.chat_pending_since,
     chat_candidate_at: session.chat_candidate_at,
+    command_finished_at: session
+      .chat_pending_since
+      .and_then(|since| session.shell_turns.completed_at_since(since)),
     pending_output_chunks: session.pending_output_chunks.load(Ordering::SeqCst),
     member_id: session.member_id.clone(),
     workspace_id: session.workspace_id.clone(),
+    tuning: session.tuning,
+    approval_patterns,
+    screen_tail,
+    approval_prompt: session.approval_prompt.clone(),
+    approval_answered: session.approval_answered.clone(),
   }
 }
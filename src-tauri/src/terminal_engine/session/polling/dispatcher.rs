@@ -9,11 +9,13 @@ use crate::platform::{backend_passive_enabled, diagnostics_log_backend_event, Di
 use crate::ports::terminal_event::TerminalEventPort;
 use crate::ports::terminal_session::TerminalSessionRepository;
 
+use super::super::approval::handle_approval_prompt;
 use super::super::post_ready::{maybe_start_post_ready, maybe_step_post_ready};
 use super::super::{
-    build_status_payload, flush_dispatch_queue_if_ready, lock_sessions, terminal_restart_post_ready,
-    update_session_status, SemanticEvent, SessionRegistry,
+    build_status_payload, flush_dispatch_queue_if_ready, lock_sessions,
+    terminal_restart_post_ready, update_session_status, SessionRegistry,
 };
+use super::super::semantic_worker::{collect_flush_seed, FlushSeed};
 use super::actions::{ChatFlushMeta, PollAction, SessionUpdate};
 
 #[derive(Clone, Debug)]
@@ -31,6 +33,8 @@ struct ChatFlushLog {
     cursor_col: Option<u16>,
     screen_rows: Option<u16>,
     screen_cols: Option<u16>,
+    command_output: bool,
+    exit_code: Option<i32>,
 }
 
 pub(crate) fn dispatch_poll_actions(
@@ -50,6 +54,7 @@ pub(crate) fn dispatch_poll_actions(
     let mut post_ready_steps = Vec::new();
     let mut post_ready_restarts = Vec::new();
     let mut dispatch_flush_targets = Vec::new();
+    let mut approval_prompts = Vec::new();
     {
         let mut guard = lock_sessions(sessions);
         let working_sessions = Arc::clone(&guard.working_sessions);
@@ -83,6 +88,13 @@ pub(crate) fn dispatch_poll_actions(
                 PollAction::PostReadyRestart { terminal_id } => {
                     post_ready_restarts.push(terminal_id.clone());
                 }
+                PollAction::ApprovalPrompt {
+                    terminal_id,
+                    prompt,
+                    subject,
+                } => {
+                    approval_prompts.push((terminal_id.clone(), prompt.clone(), subject.clone()));
+                }
             }
         }
     }
@@ -95,6 +107,10 @@ pub(crate) fn dispatch_poll_actions(
     for payload in status_payloads {
         let _ = event_port.emit_status(payload);
     }
+    // 状态已在锁内切到 AwaitingInput，策略自动作答时写入的按键按正常作答处理。
+    for (terminal_id, prompt, subject) in approval_prompts {
+        handle_approval_prompt(app, &terminal_id, &prompt, &subject);
+    }
     for terminal_id in dispatch_flush_targets {
         flush_dispatch_queue_if_ready(app, sessions, event_port.as_ref(), &terminal_id);
     }
@@ -122,6 +138,8 @@ pub(crate) fn dispatch_poll_actions(
                 err
             );
         }
+        // 无注入步骤（如休眠唤醒）时流程当场结束，排队消息随即投递。
+        flush_dispatch_queue_if_ready(app, sessions, event_port.as_ref(), &terminal_id);
     }
     for terminal_id in post_ready_steps {
         if let Err(err) = maybe_step_post_ready(
@@ -162,19 +180,22 @@ pub(crate) fn dispatch_poll_actions(
               "cursorRow": log.cursor_row,
               "cursorCol": log.cursor_col,
               "screenRows": log.screen_rows,
-              "screenCols": log.screen_cols
+              "screenCols": log.screen_cols,
+              "commandOutput": log.command_output,
+              "exitCode": log.exit_code
             }),
         );
     }
 }
 
-fn apply_session_update(
+pub(crate) fn apply_session_update(
     working_sessions: &Arc<Mutex<std::collections::HashSet<String>>>,
     session: &mut super::super::TerminalSession,
     update: &SessionUpdate,
     status_payloads: &mut Vec<crate::terminal_engine::models::TerminalStatusPayload>,
 ) -> bool {
     let mut status_changed = false;
+    let mut prompt_changed = false;
     let mut became_online = false;
     if let Some(status) = update.set_status {
         if session.status != status {
@@ -199,7 +220,14 @@ fn apply_session_update(
     if let Some(value) = update.set_semantic_active {
         session.semantic_active = value;
     }
-    if update.emit_status && status_changed {
+    if let Some(value) = update.set_approval_prompt.as_ref() {
+        prompt_changed = session.approval_prompt != *value;
+        session.approval_prompt = value.clone();
+    }
+    if let Some(value) = update.set_approval_answered.as_ref() {
+        session.approval_answered = value.clone();
+    }
+    if update.emit_status && (status_changed || prompt_changed) {
         status_payloads.push(build_status_payload(session));
     }
     became_online
@@ -211,9 +239,7 @@ fn handle_semantic_flush(
 ) -> Option<ChatFlushLog> {
     let (
         semantic_tx,
-        snapshot_seed,
-        seed_rows,
-        seed_cols,
+        seed,
         snapshot_lines,
         snapshot_line_count,
         cursor_row,
@@ -223,10 +249,7 @@ fn handle_semantic_flush(
     ) = {
         let mut guard = lock_sessions(sessions);
         let session = guard.sessions.get_mut(meta.terminal_id.as_str())?;
-        // 强制用最新快照重建语义仿真器，避免语义线程滞后导致过期行混入。
-        let snapshot_seed = session.snapshot.snapshot_segments().data;
-        let seed_rows = session.screen_rows;
-        let seed_cols = session.screen_cols;
+        let seed = collect_flush_seed(session, meta.chat_pending_since);
         let (snapshot_lines, snapshot_line_count, cursor_row, cursor_col, screen_rows, screen_cols) =
             if backend_passive_enabled() {
                 // 仅在被动监控开启时采集快照细节，避免无意义复制。
@@ -246,9 +269,7 @@ fn handle_semantic_flush(
             };
         (
             session.semantic_tx.clone(),
-            snapshot_seed,
-            seed_rows,
-            seed_cols,
+            seed,
             snapshot_lines,
             snapshot_line_count,
             cursor_row,
@@ -257,16 +278,15 @@ fn handle_semantic_flush(
             screen_cols,
         )
     };
+    let FlushSeed {
+        events,
+        command_output,
+        exit_code,
+    } = seed;
     if let Some(tx) = semantic_tx {
-        let _ = tx.send(SemanticEvent::SeedSnapshot {
-            rows: seed_rows,
-            cols: seed_cols,
-            data: snapshot_seed,
-        });
-        let _ = tx.send(SemanticEvent::Flush {
-            message_type: "info",
-            source: "pty",
-        });
+        for event in events {
+            let _ = tx.send(event);
+        }
     }
     Some(ChatFlushLog {
         terminal_id: meta.terminal_id.clone(),
@@ -282,5 +302,7 @@ fn handle_semantic_flush(
         cursor_col,
         screen_rows,
         screen_cols,
+        command_output,
+        exit_code,
     })
 }
@@ -6,6 +6,7 @@ pub(crate) struct RuleMask(u8);
 impl RuleMask {
   pub(crate) const STATUS_FALLBACK: RuleMask = RuleMask(1 << 0);
   pub(crate) const POST_READY: RuleMask = RuleMask(1 << 1);
+  pub(crate) const APPROVAL_PROMPT: RuleMask = RuleMask(1 << 2);
   pub(crate) const SEMANTIC_FLUSH: RuleMask = RuleMask(1 << 3);
 
   pub(crate) fn contains(self, other: RuleMask) -> bool {
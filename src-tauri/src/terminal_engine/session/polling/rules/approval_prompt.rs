@@ -0,0 +1,57 @@
+//! 审批提示规则：输出停顿后识别屏幕尾部的权限确认提示，进入或退出 AwaitingInput。
+//! 停顿判定在采集时完成（见 `approval_screen_tail`），输出未变化时快照不带特征，规则直接跳过。
+//! 已作答的提示在从屏幕消失前不再识别，避免残留行反复触发通知。
+
+use super::super::actions::{PollAction, SessionUpdate};
+use super::super::snapshot::SessionPollSnapshot;
+use super::super::super::approval::{approval_subject, detect_approval_prompt};
+use super::super::super::TerminalSessionStatus;
+
+pub(crate) fn collect_approval_prompt_actions(
+  snapshot: &SessionPollSnapshot,
+  actions: &mut Vec<PollAction>,
+) {
+  if snapshot.approval_patterns.is_empty() {
+    return;
+  }
+  let mut update = SessionUpdate::new(&snapshot.terminal_id);
+  let detected = match detect_approval_prompt(&snapshot.screen_tail, snapshot.approval_patterns) {
+    Some(prompt) if snapshot.approval_answered.as_deref() == Some(prompt.as_str()) => None,
+    Some(prompt) => Some(prompt),
+    None => {
+      if snapshot.approval_answered.is_some() {
+        update.set_approval_answered = Some(None);
+      }
+      None
+    }
+  };
+  match (snapshot.status, detected) {
+    (TerminalSessionStatus::AwaitingInput, None) => {
+      // 提示已消失（终端内直接作答或 CLI 自行取消），交回 Working 由回落规则判断空闲。
+      update.set_status = Some(TerminalSessionStatus::Working);
+      update.emit_status = true;
+    }
+    (TerminalSessionStatus::AwaitingInput, Some(prompt)) => {
+      if snapshot.approval_prompt.as_deref() != Some(prompt.as_str()) {
+        update.set_approval_prompt = Some(Some(prompt));
+        update.emit_status = true;
+      }
+    }
+    (_, Some(prompt)) => {
+      update.set_status = Some(TerminalSessionStatus::AwaitingInput);
+      update.set_approval_prompt = Some(Some(prompt.clone()));
+      update.emit_status = true;
+      actions.push(PollAction::SessionUpdate(update));
+      actions.push(PollAction::ApprovalPrompt {
+        terminal_id: snapshot.terminal_id.clone(),
+        subject: approval_subject(&snapshot.screen_tail, &prompt),
+        prompt,
+      });
+      return;
+    }
+    (_, None) => {}
+  }
+  if update.has_changes() {
+    actions.push(PollAction::SessionUpdate(update));
+  }
+}
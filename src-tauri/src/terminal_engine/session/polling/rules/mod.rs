@@ -4,6 +4,7 @@ use super::actions::PollAction;
 use super::rule_mask::RuleMask;
 use super::snapshot::SessionPollSnapshot;
 
+mod approval_prompt;
 mod post_ready;
 mod semantic_flush;
 mod status_fallback;
@@ -13,6 +14,7 @@ enum RuleKind {
   StatusFallback,
   SemanticFlush,
   PostReady,
+  ApprovalPrompt,
 }
 
 pub(crate) fn build_poll_actions(
@@ -37,6 +39,9 @@ pub(crate) fn build_poll_actions(
         RuleKind::PostReady => {
           post_ready::collect_post_ready_actions(snapshot, now, &mut actions);
         }
+        RuleKind::ApprovalPrompt => {
+          approval_prompt::collect_approval_prompt_actions(snapshot, &mut actions);
+        }
       }
     }
   }
@@ -54,5 +59,8 @@ fn resolve_rules(mask: RuleMask) -> Vec<RuleKind> {
   if mask.contains(RuleMask::POST_READY) {
     rules.push(RuleKind::PostReady);
   }
+  if mask.contains(RuleMask::APPROVAL_PROMPT) {
+    rules.push(RuleKind::ApprovalPrompt);
+  }
   rules
 }
@@ -1,6 +1,6 @@
 //! post_ready 规则：根据稳定性与流程状态触发后置步骤。
 
-use super::super::super::{PostReadyMode, PostReadyState};
+use super::super::super::PostReadyState;
 use super::super::actions::PollAction;
 use super::super::snapshot::SessionPollSnapshot;
 
@@ -20,7 +20,7 @@ pub(crate) fn collect_post_ready_actions(
             terminal_id: snapshot.terminal_id.clone(),
         });
     }
-    if snapshot.post_ready_mode == PostReadyMode::Invite
+    if snapshot.post_ready_mode.should_run()
         && snapshot.post_ready_state == PostReadyState::Idle
         && snapshot.shell_ready
     {
@@ -1,10 +1,8 @@
 //! 语义 flush 规则：在静默窗口内触发语义缓冲落盘。
+//! shell 集成标记已报告命令结束时直接按命令边界 flush，不再等待静默窗口。
 
 use super::super::actions::{ChatFlushMeta, PollAction, SessionUpdate};
 use super::super::snapshot::SessionPollSnapshot;
-use super::super::super::{
-  CHAT_IDLE_DEBOUNCE_MS, CHAT_PENDING_FORCE_FLUSH_MS, CHAT_SILENCE_TIMEOUT_MS,
-};
 
 pub(crate) fn collect_semantic_flush_actions(
   snapshot: &SessionPollSnapshot,
@@ -14,10 +12,13 @@ pub(crate) fn collect_semantic_flush_actions(
   if !snapshot.chat_pending {
     return;
   }
-  let force_flush = snapshot
-    .chat_pending_since
-    .map(|since| now.saturating_sub(since) >= CHAT_PENDING_FORCE_FLUSH_MS)
-    .unwrap_or(false);
+  // 命令区间已由 D 标记闭合时，与超时兜底一样跳过静默门禁与防抖。
+  let command_finished = snapshot.command_finished_at.is_some();
+  let force_flush = command_finished
+    || snapshot
+      .chat_pending_since
+      .map(|since| now.saturating_sub(since) >= snapshot.tuning.chat_pending_force_flush_ms)
+      .unwrap_or(false);
   let mut update = SessionUpdate::new(&snapshot.terminal_id);
   if !force_flush && (snapshot.flow_paused || snapshot.pending_output_chunks != 0) {
     if snapshot.chat_candidate_at.is_some() {
@@ -37,7 +38,7 @@ pub(crate) fn collect_semantic_flush_actions(
       return;
     };
     let silence_ms = now.saturating_sub(last_output_at);
-    if silence_ms < CHAT_SILENCE_TIMEOUT_MS {
+    if silence_ms < snapshot.tuning.chat_silence_timeout_ms {
       if snapshot.chat_candidate_at.is_some() {
         update.set_chat_candidate_at = Some(None);
       }
@@ -56,7 +57,7 @@ pub(crate) fn collect_semantic_flush_actions(
         return;
       }
       Some(candidate_at) => {
-        if now.saturating_sub(candidate_at) < CHAT_IDLE_DEBOUNCE_MS {
+        if now.saturating_sub(candidate_at) < snapshot.tuning.chat_idle_debounce_ms {
           return;
         }
       }
@@ -2,9 +2,7 @@
 
 use super::super::actions::{PollAction, SessionUpdate};
 use super::super::snapshot::SessionPollSnapshot;
-use super::super::super::{
-  TerminalSessionStatus, STATUS_IDLE_DEBOUNCE_MS, STATUS_WORKING_SILENCE_TIMEOUT_MS,
-};
+use super::super::super::TerminalSessionStatus;
 
 pub(crate) fn collect_status_fallback_actions(
   snapshot: &SessionPollSnapshot,
@@ -33,7 +31,7 @@ pub(crate) fn collect_status_fallback_actions(
     return;
   };
   let silence_ms = now.saturating_sub(last_activity);
-  if silence_ms < STATUS_WORKING_SILENCE_TIMEOUT_MS {
+  if silence_ms < snapshot.tuning.working_silence_timeout_ms {
     if snapshot.idle_candidate_at.is_some() {
       update.set_idle_candidate_at = Some(None);
     }
@@ -59,7 +57,7 @@ pub(crate) fn collect_status_fallback_actions(
       update.set_idle_candidate_at = Some(Some(now));
     }
     Some(candidate_at) => {
-      if now.saturating_sub(candidate_at) >= STATUS_IDLE_DEBOUNCE_MS {
+      if now.saturating_sub(candidate_at) >= snapshot.tuning.status_idle_debounce_ms {
         // 静默持续到达防抖门槛后才回落。
         update.set_idle_candidate_at = Some(None);
         update.set_status = Some(TerminalSessionStatus::Online);
@@ -1,6 +1,6 @@
 //! 轮询快照：只读采集会话关键字段，避免在轮询线程内耦合业务逻辑。
 
-use super::super::{PostReadyMode, PostReadyState, TerminalSessionStatus};
+use super::super::{PostReadyMode, PostReadyState, TerminalSessionStatus, TerminalTuning};
 
 #[derive(Clone, Debug)]
 pub(crate) struct SessionPollSnapshot {
@@ -20,9 +20,17 @@ pub(crate) struct SessionPollSnapshot {
   pub(crate) chat_pending: bool,
   pub(crate) chat_pending_since: Option<u64>,
   pub(crate) chat_candidate_at: Option<u64>,
+  // 本轮 chat 期间 shell 集成标记报告的命令完成时间（C..D 区间已闭合）。
+  pub(crate) command_finished_at: Option<u64>,
   pub(crate) pending_output_chunks: usize,
   pub(crate) member_id: Option<String>,
   pub(crate) workspace_id: Option<String>,
+  pub(crate) tuning: TerminalTuning,
+  // 审批识别输入：成员类型的提示特征与屏幕尾部非空行；不适用、输出未停顿或未变化时特征为空。
+  pub(crate) approval_patterns: &'static [&'static str],
+  pub(crate) screen_tail: Vec<String>,
+  pub(crate) approval_prompt: Option<String>,
+  pub(crate) approval_answered: Option<String>,
 }
 
 impl SessionPollSnapshot {
@@ -2,16 +2,18 @@
 
 use std::sync::{Arc, Mutex};
 
-use crate::now_millis;
 use crate::ports::terminal_event::TerminalEventPort;
 use crate::ports::terminal_session::TerminalSessionRepository;
 use crate::terminal_engine::default_members::registry::TerminalPostReadyStep;
 use crate::terminal_engine::default_members::resolve_default_member;
 
 use super::keyboard_input::{parse_session_id_from_lines, send_post_ready_input, ENTER_INPUT_DELAY_MS};
+use super::post_ready_script::PostReadyScriptMode;
 use super::stability::is_output_stable;
 use super::state::{PostReadyAction, PostReadyQueueItem};
-use super::{lock_sessions, unlock_session_status_by_member, PostReadyState, SessionRegistry};
+use super::{
+    clock_now, lock_sessions, unlock_session_status_by_member, PostReadyMode, PostReadyState, SessionRegistry,
+};
 
 const POST_READY_STEP_REASON: &str = "post-ready-input";
 const EXTRACT_SESSION_ID_TIMEOUT_MS: u64 = 2000;
@@ -24,15 +26,10 @@ pub(super) fn maybe_start_post_ready(
     terminal_type: &str,
 ) -> Result<(), String> {
     // 启动顺序：shell_ready + 稳定门禁通过后进入 post_ready。
-    let plan = resolve_default_member(terminal_type).map(|member| member.post_ready_plan);
-    let plan = match plan {
-        Some(plan) => plan,
-        None => {
-            finish_post_ready(sessions, event_port, terminal_id)?;
-            return Ok(());
-        }
-    };
-    let should_finish = {
+    let plan_steps = resolve_default_member(terminal_type)
+        .map(|member| member.post_ready_plan.post_ready_steps)
+        .unwrap_or(&[]);
+    let (should_finish, items) = {
         let mut guard = lock_sessions(sessions);
         let session = guard
             .sessions
@@ -49,18 +46,35 @@ pub(super) fn maybe_start_post_ready(
         if !session.active || session.handle.is_none() {
             return Err("terminal session is not active".to_string());
         }
-        if plan.post_ready_steps.is_empty() {
-            true
+        // 内置流程与成员自定义脚本按脚本声明的位置拼接。
+        let mut items = Vec::new();
+        match session.post_ready_script.as_ref() {
+            None => push_plan_items(&mut items, plan_steps),
+            Some(script) => match script.mode {
+                PostReadyScriptMode::Before => {
+                    items.extend(script.queue_items());
+                    push_plan_items(&mut items, plan_steps);
+                }
+                PostReadyScriptMode::After => {
+                    push_plan_items(&mut items, plan_steps);
+                    items.extend(script.queue_items());
+                }
+                PostReadyScriptMode::Replace => items.extend(script.queue_items()),
+            },
+        }
+        // 唤醒休眠成员时续接原会话，邀请引导已执行过，直接结束流程。
+        if session.post_ready_mode == PostReadyMode::Wake || items.is_empty() {
+            (true, items)
         } else {
             session.post_ready_state = PostReadyState::Starting;
-            false
+            (false, items)
         }
     };
     if should_finish {
         finish_post_ready(sessions, event_port, terminal_id)?;
         return Ok(());
     }
-    queue_post_ready_steps(sessions, terminal_id, plan.post_ready_steps)?;
+    queue_post_ready_items(sessions, terminal_id, items)?;
     Ok(())
 }
 
@@ -74,7 +88,7 @@ pub(super) fn maybe_step_post_ready(
 ) -> Result<(), String> {
     // 逐条下发启动后输入：每个输入可选择是否等待画面稳定。
     let (action, require_stable) = {
-        let now = now_millis()?;
+        let now = clock_now(sessions);
         let mut guard = lock_sessions(sessions);
         let session = guard
             .sessions
@@ -119,7 +133,7 @@ pub(super) fn maybe_step_post_ready(
         )
         .map_err(|err| (PostReadyAction::Input(input), err)),
         PostReadyAction::Delay { ms, started_at } => {
-            let now = now_millis()?;
+            let now = clock_now(sessions);
             let started_at = started_at.unwrap_or(now);
             if now.saturating_sub(started_at) >= ms {
                 Ok(())
@@ -141,18 +155,26 @@ pub(super) fn maybe_step_post_ready(
                 _ => crate::terminal_engine::default_members::onboarding::PromptType::Onboarding,
             };
             let language = settings_service.and_then(|s| s.get_language());
-            let member_id = {
+            // 优先使用注册时解析的项目上下文；缺失时按成员名称生成最小引导词。
+            let context = {
                 let guard = super::lock_sessions(sessions);
-                guard
-                    .sessions
-                    .get(terminal_id)
-                    .and_then(|s| s.member_id.clone())
+                guard.sessions.get(terminal_id).and_then(|s| {
+                    s.onboarding.clone().or_else(|| {
+                        s.member_id.as_deref().map(|member_id| {
+                            crate::terminal_engine::default_members::onboarding::OnboardingContext::for_member(
+                                member_id,
+                                s.member_name.as_deref(),
+                                s.workspace_id.as_deref(),
+                            )
+                        })
+                    })
+                })
             };
-            match member_id {
-                Some(member_id) => {
+            match context {
+                Some(context) => {
                     let input = crate::terminal_engine::default_members::onboarding::generate_prompt(
                         type_enum,
-                        member_id.as_str(),
+                        &context,
                         language.as_deref(),
                     );
                     send_post_ready_input(
@@ -226,7 +248,7 @@ pub(super) fn maybe_step_post_ready(
                 }
                 Ok(())
             } else {
-                let now = now_millis()?;
+                let now = clock_now(sessions);
                 let mut guard = lock_sessions(sessions);
                 let session = guard
                     .sessions
@@ -272,14 +294,18 @@ pub(super) fn maybe_step_post_ready(
             pattern,
             attempts,
             started_at,
+            timeout_ms,
         } => {
-            let now = now_millis()?;
+            let now = clock_now(sessions);
             let started_at = started_at.unwrap_or(now);
             const WAIT_PATTERN_MAX_ATTEMPTS: u32 = 30;
             const WAIT_PATTERN_TIMEOUT_MS: u64 = 30_000;
-            if attempts >= WAIT_PATTERN_MAX_ATTEMPTS
-                || now.saturating_sub(started_at) >= WAIT_PATTERN_TIMEOUT_MS
-            {
+            let elapsed = now.saturating_sub(started_at);
+            let timed_out = match timeout_ms {
+                Some(timeout_ms) => elapsed >= timeout_ms,
+                None => attempts >= WAIT_PATTERN_MAX_ATTEMPTS || elapsed >= WAIT_PATTERN_TIMEOUT_MS,
+            };
+            if timed_out {
                 log::warn!(
           "terminal wait pattern timeout terminal_id={} pattern={} attempts={} elapsed_ms={}",
           terminal_id,
@@ -290,16 +316,7 @@ pub(super) fn maybe_step_post_ready(
                 // 超时后跳过，继续后续步骤
                 return Ok(());
             }
-            let has_pattern = {
-                let guard = lock_sessions(sessions);
-                let session = guard
-                    .sessions
-                    .get(terminal_id)
-                    .ok_or_else(|| "terminal session not found".to_string())?;
-                let lines = session.snapshot.snapshot_lines();
-                lines.iter().any(|line| line.contains(pattern.as_str()))
-            };
-            if has_pattern {
+            if snapshot_has_pattern(sessions, terminal_id, &pattern)? {
                 log::info!(
                     "terminal wait pattern matched terminal_id={} pattern={}",
                     terminal_id,
@@ -313,6 +330,45 @@ pub(super) fn maybe_step_post_ready(
                         pattern,
                         attempts: attempts.saturating_add(1),
                         started_at: Some(started_at),
+                        timeout_ms,
+                    },
+                    String::new(),
+                ))
+            }
+        }
+        PostReadyAction::Branch {
+            pattern,
+            timeout_ms,
+            started_at,
+            then_items,
+            else_items,
+        } => {
+            let now = clock_now(sessions);
+            let started_at = started_at.unwrap_or(now);
+            let matched = snapshot_has_pattern(sessions, terminal_id, &pattern)?;
+            if matched || now.saturating_sub(started_at) >= timeout_ms {
+                log::info!(
+                    "terminal post_ready branch resolved terminal_id={} pattern={} matched={}",
+                    terminal_id,
+                    pattern,
+                    matched
+                );
+                let branch = if matched { then_items } else { else_items };
+                let mut guard = lock_sessions(sessions);
+                if let Some(session) = guard.sessions.get_mut(terminal_id) {
+                    for item in branch.into_iter().rev() {
+                        session.post_ready_queue.push_front(item);
+                    }
+                }
+                Ok(())
+            } else {
+                Err((
+                    PostReadyAction::Branch {
+                        pattern,
+                        timeout_ms,
+                        started_at: Some(started_at),
+                        then_items,
+                        else_items,
                     },
                     String::new(),
                 ))
@@ -396,10 +452,10 @@ fn finish_post_ready(
     Ok(())
 }
 
-fn queue_post_ready_steps(
+fn queue_post_ready_items(
     sessions: &Arc<Mutex<SessionRegistry>>,
     terminal_id: &str,
-    steps: &[TerminalPostReadyStep],
+    items: Vec<PostReadyQueueItem>,
 ) -> Result<(), String> {
     // 启动后注入使用队列分步发送，避免一次性写入导致状态误判。
     let mut guard = lock_sessions(sessions);
@@ -407,32 +463,54 @@ fn queue_post_ready_steps(
         .sessions
         .get_mut(terminal_id)
         .ok_or_else(|| "terminal session not found".to_string())?;
-    session.post_ready_queue.clear();
+    session.post_ready_queue = items.into();
+    session.post_ready_state = PostReadyState::Starting;
+    Ok(())
+}
+
+fn snapshot_has_pattern(
+    sessions: &Arc<Mutex<SessionRegistry>>,
+    terminal_id: &str,
+    pattern: &str,
+) -> Result<bool, String> {
+    let guard = lock_sessions(sessions);
+    let session = guard
+        .sessions
+        .get(terminal_id)
+        .ok_or_else(|| "terminal session not found".to_string())?;
+    let lines = session.snapshot.snapshot_lines();
+    Ok(lines.iter().any(|line| line.contains(pattern)))
+}
+
+/// 输入步骤入队；单独的回车前先插入短暂延迟，避免与上一段输入粘连。
+pub(super) fn push_input_item(items: &mut Vec<PostReadyQueueItem>, input: String, require_stable: bool) {
+    if input == "\r" {
+        items.push(PostReadyQueueItem {
+            action: PostReadyAction::Delay {
+                ms: ENTER_INPUT_DELAY_MS,
+                started_at: None,
+            },
+            require_stable: false,
+        });
+    }
+    items.push(PostReadyQueueItem {
+        action: PostReadyAction::Input(input),
+        require_stable,
+    });
+}
+
+fn push_plan_items(items: &mut Vec<PostReadyQueueItem>, steps: &[TerminalPostReadyStep]) {
     for step in steps {
         match step {
             TerminalPostReadyStep::Input {
                 input,
                 require_stable,
-            } => {
-                if *input == "\r" {
-                    session.post_ready_queue.push_back(PostReadyQueueItem {
-                        action: PostReadyAction::Delay {
-                            ms: ENTER_INPUT_DELAY_MS,
-                            started_at: None,
-                        },
-                        require_stable: false,
-                    });
-                }
-                session.post_ready_queue.push_back(PostReadyQueueItem {
-                    action: PostReadyAction::Input((*input).to_string()),
-                    require_stable: *require_stable,
-                });
-            }
+            } => push_input_item(items, (*input).to_string(), *require_stable),
             TerminalPostReadyStep::ExtractSessionId {
                 keyword,
                 require_stable,
             } => {
-                session.post_ready_queue.push_back(PostReadyQueueItem {
+                items.push(PostReadyQueueItem {
                     action: PostReadyAction::ExtractSessionId {
                         keyword: (*keyword).to_string(),
                     },
@@ -443,11 +521,12 @@ fn queue_post_ready_steps(
                 pattern,
                 require_stable,
             } => {
-                session.post_ready_queue.push_back(PostReadyQueueItem {
+                items.push(PostReadyQueueItem {
                     action: PostReadyAction::WaitForPattern {
                         pattern: (*pattern).to_string(),
                         attempts: 0,
                         started_at: None,
+                        timeout_ms: None,
                     },
                     require_stable: *require_stable,
                 });
@@ -456,7 +535,7 @@ fn queue_post_ready_steps(
                 prompt_type,
                 require_stable,
             } => {
-                session.post_ready_queue.push_back(PostReadyQueueItem {
+                items.push(PostReadyQueueItem {
                     action: PostReadyAction::Introduction {
                         prompt_type: (*prompt_type).to_string(),
                     },
@@ -465,7 +544,4 @@ fn queue_post_ready_steps(
             }
         }
     }
-    session.post_ready_state = PostReadyState::Starting;
-    Ok(())
 }
-
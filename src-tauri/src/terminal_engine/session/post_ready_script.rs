@@ -0,0 +1,399 @@
+//! 成员自定义启动后脚本：项目数据 `members[].postReadyScript` 声明的后置步骤，
+//! 与默认成员的内置流程拼接执行（在其之前、之后或替换之）。
+//! 步骤类型与内置流程一致，另支持 `delay`、按键序列 `keys`、等待超时 `timeoutMs` 以及按特征字符串分支的 `ifPattern`。
+//! 脚本在会话启动前解析校验，校验失败时拒绝创建终端，避免半途执行出错的脚本。
+//! 步骤格式与校验同时供插件成员类型的 `postReadySteps` 使用（见 `default_members::plugins`）。
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::terminal_engine::default_members::onboarding::PROMPT_TYPE_ONBOARDING;
+
+use super::keyboard_input::encode_key_sequence;
+use super::post_ready::push_input_item;
+use super::state::{PostReadyAction, PostReadyQueueItem};
+
+const SCRIPT_MAX_STEPS: usize = 32; // 含分支内步骤的总数上限，防止误配导致启动流程过长。
+const SCRIPT_MAX_DEPTH: usize = 3; // 分支嵌套层数上限。
+const SCRIPT_MAX_DELAY_MS: u64 = 60_000;
+const SCRIPT_MAX_TIMEOUT_MS: u64 = 600_000;
+const DEFAULT_BRANCH_TIMEOUT_MS: u64 = 30_000; // 与内置 waitForPattern 的超时一致。
+
+/// 脚本相对默认成员内置流程的位置。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum PostReadyScriptMode {
+    Before,
+    #[default]
+    After,
+    Replace,
+}
+
+/// 校验后的脚本步骤。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PostReadyScriptStep {
+    Input {
+        input: String,
+        require_stable: bool,
+    },
+    Delay {
+        ms: u64,
+    },
+    ExtractSessionId {
+        keyword: String,
+        require_stable: bool,
+    },
+    /// 等待特征字符串；超时后跳过继续执行，`timeout_ms` 缺省时沿用内置超时。
+    WaitForPattern {
+        pattern: String,
+        timeout_ms: Option<u64>,
+        require_stable: bool,
+    },
+    Introduction {
+        prompt_type: String,
+        require_stable: bool,
+    },
+    /// 在超时内等待特征字符串：出现则执行 `then_steps`，超时执行 `else_steps`。
+    IfPattern {
+        pattern: String,
+        timeout_ms: u64,
+        require_stable: bool,
+        then_steps: Vec<PostReadyScriptStep>,
+        else_steps: Vec<PostReadyScriptStep>,
+    },
+}
+
+/// 成员启动后脚本，会话注册时解析一次。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct PostReadyScript {
+    pub(crate) mode: PostReadyScriptMode,
+    pub(crate) steps: Vec<PostReadyScriptStep>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StoredScriptObject {
+    #[serde(default)]
+    mode: Option<String>,
+    steps: Vec<StoredPostReadyStep>,
+}
+
+/// 启动后步骤的存储格式，项目数据脚本与插件成员类型共用。
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) enum StoredPostReadyStep {
+    #[serde(rename_all = "camelCase")]
+    Input {
+        input: String,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Keys {
+        keys: String,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Delay { ms: u64 },
+    #[serde(rename_all = "camelCase")]
+    ExtractSessionId {
+        keyword: String,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    WaitForPattern {
+        pattern: String,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Introduction {
+        prompt_type: String,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    IfPattern {
+        pattern: String,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+        #[serde(default = "default_require_stable")]
+        require_stable: bool,
+        #[serde(default)]
+        then: Vec<StoredPostReadyStep>,
+        #[serde(default, rename = "else")]
+        otherwise: Vec<StoredPostReadyStep>,
+    },
+}
+
+fn default_require_stable() -> bool {
+    true
+}
+
+impl PostReadyScript {
+    /// 解析并校验脚本：可直接写步骤数组，或 `{ "mode": "before" | "after" | "replace", "steps": [...] }`。
+    /// 错误信息带步骤路径（如 `steps[1].then[0]`），便于定位。
+    pub(crate) fn from_value(value: &Value) -> Result<Self, String> {
+        let parse_error = |err: serde_json::Error| format!("failed to parse postReadyScript: {err}");
+        let (mode, steps) = match value {
+            Value::Array(_) => (
+                PostReadyScriptMode::default(),
+                serde_json::from_value::<Vec<StoredPostReadyStep>>(value.clone()).map_err(parse_error)?,
+            ),
+            _ => {
+                let script =
+                    serde_json::from_value::<StoredScriptObject>(value.clone()).map_err(parse_error)?;
+                let mode = match script.mode.as_deref().map(|mode| mode.trim().to_lowercase()) {
+                    None => PostReadyScriptMode::default(),
+                    Some(mode) => match mode.as_str() {
+                        "before" => PostReadyScriptMode::Before,
+                        "after" => PostReadyScriptMode::After,
+                        "replace" => PostReadyScriptMode::Replace,
+                        other => return Err(format!("postReadyScript mode {other:?} is not supported")),
+                    },
+                };
+                (mode, script.steps)
+            }
+        };
+        if steps.is_empty() {
+            return Err("postReadyScript requires at least one step".to_string());
+        }
+        let steps = parse_post_ready_steps(steps, "steps")?;
+        Ok(Self { mode, steps })
+    }
+
+    /// 展开为 post_ready 队列元素；分支步骤携带两侧元素，命中后再插回队首。
+    pub(super) fn queue_items(&self) -> Vec<PostReadyQueueItem> {
+        queue_items(&self.steps)
+    }
+}
+
+fn queue_items(steps: &[PostReadyScriptStep]) -> Vec<PostReadyQueueItem> {
+    let mut items = Vec::new();
+    for step in steps {
+        let (action, require_stable) = match step {
+            PostReadyScriptStep::Input {
+                input,
+                require_stable,
+            } => {
+                push_input_item(&mut items, input.clone(), *require_stable);
+                continue;
+            }
+            PostReadyScriptStep::Delay { ms } => (
+                PostReadyAction::Delay {
+                    ms: *ms,
+                    started_at: None,
+                },
+                false,
+            ),
+            PostReadyScriptStep::ExtractSessionId {
+                keyword,
+                require_stable,
+            } => (
+                PostReadyAction::ExtractSessionId {
+                    keyword: keyword.clone(),
+                },
+                *require_stable,
+            ),
+            PostReadyScriptStep::WaitForPattern {
+                pattern,
+                timeout_ms,
+                require_stable,
+            } => (
+                PostReadyAction::WaitForPattern {
+                    pattern: pattern.clone(),
+                    attempts: 0,
+                    started_at: None,
+                    timeout_ms: *timeout_ms,
+                },
+                *require_stable,
+            ),
+            PostReadyScriptStep::Introduction {
+                prompt_type,
+                require_stable,
+            } => (
+                PostReadyAction::Introduction {
+                    prompt_type: prompt_type.clone(),
+                },
+                *require_stable,
+            ),
+            PostReadyScriptStep::IfPattern {
+                pattern,
+                timeout_ms,
+                require_stable,
+                then_steps,
+                else_steps,
+            } => (
+                PostReadyAction::Branch {
+                    pattern: pattern.clone(),
+                    timeout_ms: *timeout_ms,
+                    started_at: None,
+                    then_items: queue_items(then_steps),
+                    else_items: queue_items(else_steps),
+                },
+                *require_stable,
+            ),
+        };
+        items.push(PostReadyQueueItem {
+            action,
+            require_stable,
+        });
+    }
+    items
+}
+
+/// 校验一组启动后步骤；`path` 为错误信息中的字段名（如 `steps`、`postReadySteps`）。
+pub(crate) fn parse_post_ready_steps(
+    steps: Vec<StoredPostReadyStep>,
+    path: &str,
+) -> Result<Vec<PostReadyScriptStep>, String> {
+    let mut budget = SCRIPT_MAX_STEPS;
+    parse_steps(steps, path, 1, &mut budget)
+}
+
+fn parse_steps(
+    steps: Vec<StoredPostReadyStep>,
+    path: &str,
+    depth: usize,
+    budget: &mut usize,
+) -> Result<Vec<PostReadyScriptStep>, String> {
+    steps
+        .into_iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let path = format!("{path}[{index}]");
+            if *budget == 0 {
+                return Err(format!("postReadyScript exceeds {SCRIPT_MAX_STEPS} steps at {path}"));
+            }
+            *budget -= 1;
+            parse_step(step, &path, depth, budget)
+        })
+        .collect()
+}
+
+// 分支内步骤的错误已带完整路径，其余错误在此补上当前步骤路径。
+fn parse_step(
+    step: StoredPostReadyStep,
+    path: &str,
+    depth: usize,
+    budget: &mut usize,
+) -> Result<PostReadyScriptStep, String> {
+    let StoredPostReadyStep::IfPattern {
+        pattern,
+        timeout_ms,
+        require_stable,
+        then,
+        otherwise,
+    } = step
+    else {
+        return parse_leaf_step(step).map_err(|err| format!("{path}: {err}"));
+    };
+    if depth >= SCRIPT_MAX_DEPTH {
+        return Err(format!("{path}: ifPattern nesting exceeds {SCRIPT_MAX_DEPTH} levels"));
+    }
+    if then.is_empty() && otherwise.is_empty() {
+        return Err(format!("{path}: ifPattern step requires then or else steps"));
+    }
+    let pattern = parse_pattern(&pattern, "ifPattern").map_err(|err| format!("{path}: {err}"))?;
+    let timeout_ms = parse_timeout(timeout_ms.unwrap_or(DEFAULT_BRANCH_TIMEOUT_MS))
+        .map_err(|err| format!("{path}: {err}"))?;
+    Ok(PostReadyScriptStep::IfPattern {
+        pattern,
+        timeout_ms,
+        require_stable,
+        then_steps: parse_steps(then, &format!("{path}.then"), depth + 1, budget)?,
+        else_steps: parse_steps(otherwise, &format!("{path}.else"), depth + 1, budget)?,
+    })
+}
+
+fn parse_leaf_step(step: StoredPostReadyStep) -> Result<PostReadyScriptStep, String> {
+    let step = match step {
+        StoredPostReadyStep::Input {
+            input,
+            require_stable,
+        } => {
+            if input.is_empty() {
+                return Err("input step requires input".to_string());
+            }
+            PostReadyScriptStep::Input {
+                input,
+                require_stable,
+            }
+        }
+        // 按键步骤在解析时编码为原始输入，执行阶段与 input 步骤一致。
+        StoredPostReadyStep::Keys {
+            keys,
+            require_stable,
+        } => {
+            if keys.trim().is_empty() {
+                return Err("keys step requires keys".to_string());
+            }
+            PostReadyScriptStep::Input {
+                input: encode_key_sequence(&keys)?,
+                require_stable,
+            }
+        }
+        StoredPostReadyStep::Delay { ms } => {
+            if ms == 0 || ms > SCRIPT_MAX_DELAY_MS {
+                return Err(format!("delay ms must be between 1 and {SCRIPT_MAX_DELAY_MS}"));
+            }
+            PostReadyScriptStep::Delay { ms }
+        }
+        StoredPostReadyStep::ExtractSessionId {
+            keyword,
+            require_stable,
+        } => {
+            if keyword.trim().is_empty() {
+                return Err("extractSessionId step requires keyword".to_string());
+            }
+            PostReadyScriptStep::ExtractSessionId {
+                keyword: keyword.trim().to_string(),
+                require_stable,
+            }
+        }
+        StoredPostReadyStep::WaitForPattern {
+            pattern,
+            timeout_ms,
+            require_stable,
+        } => PostReadyScriptStep::WaitForPattern {
+            pattern: parse_pattern(&pattern, "waitForPattern")?,
+            timeout_ms: timeout_ms.map(parse_timeout).transpose()?,
+            require_stable,
+        },
+        StoredPostReadyStep::Introduction {
+            prompt_type,
+            require_stable,
+        } => {
+            if prompt_type.trim() != PROMPT_TYPE_ONBOARDING {
+                return Err(format!("introduction promptType {prompt_type:?} is not supported"));
+            }
+            PostReadyScriptStep::Introduction {
+                prompt_type: PROMPT_TYPE_ONBOARDING.to_string(),
+                require_stable,
+            }
+        }
+        StoredPostReadyStep::IfPattern { .. } => unreachable!("ifPattern is parsed by parse_step"),
+    };
+    Ok(step)
+}
+
+fn parse_pattern(pattern: &str, kind: &str) -> Result<String, String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(format!("{kind} step requires pattern"));
+    }
+    Ok(pattern.to_string())
+}
+
+fn parse_timeout(timeout_ms: u64) -> Result<u64, String> {
+    if timeout_ms == 0 || timeout_ms > SCRIPT_MAX_TIMEOUT_MS {
+        return Err(format!("timeoutMs must be between 1 and {SCRIPT_MAX_TIMEOUT_MS}"));
+    }
+    Ok(timeout_ms)
+}
+
+#[cfg(test)]
+mod script_tests;
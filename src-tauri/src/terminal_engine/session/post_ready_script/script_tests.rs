@@ -0,0 +1,130 @@
+use serde_json::json;
+
+use super::super::state::PostReadyAction;
+use super::{PostReadyScript, PostReadyScriptMode, PostReadyScriptStep};
+
+#[test]
+fn step_array_defaults_to_after_builtin_plan() {
+    let script = PostReadyScript::from_value(&json!([
+        { "type": "input", "input": "/model opus" },
+        { "type": "input", "input": "\r" },
+        { "type": "waitForPattern", "pattern": "Set model", "timeoutMs": 5000, "requireStable": false }
+    ]))
+    .expect("valid script");
+    assert_eq!(script.mode, PostReadyScriptMode::After);
+    assert_eq!(
+        script.steps[2],
+        PostReadyScriptStep::WaitForPattern {
+            pattern: "Set model".to_string(),
+            timeout_ms: Some(5000),
+            require_stable: false,
+        }
+    );
+}
+
+#[test]
+fn branch_expands_into_queue_items() {
+    let script = PostReadyScript::from_value(&json!({
+        "mode": "before",
+        "steps": [
+            { "type": "delay", "ms": 500 },
+            {
+                "type": "ifPattern",
+                "pattern": "CLAUDE.md",
+                "timeoutMs": 3000,
+                "then": [{ "type": "input", "input": "Follow CLAUDE.md" }, { "type": "input", "input": "\r" }],
+                "else": [{ "type": "introduction", "promptType": "onboarding" }]
+            }
+        ]
+    }))
+    .expect("valid script");
+    assert_eq!(script.mode, PostReadyScriptMode::Before);
+    let items = script.queue_items();
+    assert_eq!(items.len(), 2);
+    assert!(matches!(items[0].action, PostReadyAction::Delay { ms: 500, .. }));
+    let PostReadyAction::Branch {
+        pattern,
+        timeout_ms,
+        then_items,
+        else_items,
+        ..
+    } = &items[1].action
+    else {
+        panic!("expected branch, got {:?}", items[1].action);
+    };
+    assert_eq!((pattern.as_str(), *timeout_ms), ("CLAUDE.md", 3000));
+    // 单独的回车前插入延迟，与内置流程一致。
+    assert_eq!(then_items.len(), 3);
+    assert!(matches!(then_items[1].action, PostReadyAction::Delay { .. }));
+    assert!(matches!(&then_items[2].action, PostReadyAction::Input(input) if input == "\r"));
+    assert!(matches!(&else_items[0].action, PostReadyAction::Introduction { .. }));
+}
+
+#[test]
+fn keys_step_encodes_to_raw_input() {
+    let script = PostReadyScript::from_value(&json!([
+        { "type": "keys", "keys": "<Esc><C-u>" }
+    ]))
+    .expect("valid script");
+    assert_eq!(
+        script.steps[0],
+        PostReadyScriptStep::Input {
+            input: "\x1b\x15".to_string(),
+            require_stable: true,
+        }
+    );
+    assert_eq!(
+        PostReadyScript::from_value(&json!([{ "type": "keys", "keys": "<Nope>" }])),
+        Err("steps[0]: unknown key <Nope>".to_string())
+    );
+}
+
+#[test]
+fn invalid_steps_report_their_path() {
+    let cases = [
+        (json!([]), "postReadyScript requires at least one step"),
+        (json!({ "mode": "later", "steps": [{ "type": "delay", "ms": 1 }] }), "postReadyScript mode \"later\" is not supported"),
+        (json!([{ "type": "delay", "ms": 0 }]), "steps[0]: delay ms must be between 1 and 60000"),
+        (
+            json!([{ "type": "ifPattern", "pattern": "ok", "then": [{ "type": "input", "input": "" }] }]),
+            "steps[0].then[0]: input step requires input",
+        ),
+        (
+            json!([{ "type": "waitForPattern", "pattern": " ", "timeoutMs": 100 }]),
+            "steps[0]: waitForPattern step requires pattern",
+        ),
+        (
+            json!([{ "type": "introduction", "promptType": "custom" }]),
+            "steps[0]: introduction promptType \"custom\" is not supported",
+        ),
+        (json!([{ "type": "ifPattern", "pattern": "ok" }]), "steps[0]: ifPattern step requires then or else steps"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(PostReadyScript::from_value(&value), Err(expected.to_string()));
+    }
+    let unknown = PostReadyScript::from_value(&json!([{ "type": "shell", "command": "ls" }]));
+    assert!(unknown.unwrap_err().starts_with("failed to parse postReadyScript"));
+}
+
+#[test]
+fn nesting_and_step_count_are_bounded() {
+    let nested = json!([{
+        "type": "ifPattern", "pattern": "a",
+        "then": [{
+            "type": "ifPattern", "pattern": "b",
+            "then": [{
+                "type": "ifPattern", "pattern": "c",
+                "then": [{ "type": "delay", "ms": 1 }]
+            }]
+        }]
+    }]);
+    assert_eq!(
+        PostReadyScript::from_value(&nested),
+        Err("steps[0].then[0].then[0]: ifPattern nesting exceeds 3 levels".to_string())
+    );
+    let long: Vec<_> = (0..33).map(|_| json!({ "type": "delay", "ms": 1 })).collect();
+    assert_eq!(
+        PostReadyScript::from_value(&json!(long)),
+        Err("postReadyScript exceeds 32 steps at steps[32]".to_string())
+    );
+}
@@ -0,0 +1,384 @@
+//! 会话录制与回放：以 asciicast v2 记录输出/输入/尺寸事件，并可重放重建任意时刻的快照。
+//! 边界：录制事件经通道交给独立写线程落盘，PTY 读写与会话锁内只做入队；回放使用独立模拟器，不触碰会话状态。
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, LineWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use serde_json::{json, Value};
+
+use super::super::emulator::{create_emulator, EmulatorBackend, EmulatorConfig};
+use super::super::models::TerminalRecordingReplayPayload;
+use super::SESSION_SCROLLBACK_LINES;
+use crate::now_millis;
+use crate::runtime::storage;
+
+const RECORDINGS_DIR: &str = ".golutra/recordings"; // 录制文件目录，位于工作区 .golutra 下。
+const RECORDING_EXTENSION: &str = "cast";
+const ASCIICAST_VERSION: u64 = 2;
+const EVENT_OUTPUT: &str = "o";
+const EVENT_INPUT: &str = "i";
+const EVENT_RESIZE: &str = "r";
+
+/// 待写入的录制事件：时间戳在入队时取得，写线程延迟不影响回放时序。
+enum RecorderEvent {
+    Output { elapsed: f64, bytes: Vec<u8> },
+    Text { elapsed: f64, kind: &'static str, data: String },
+}
+
+/// asciicast 事件编码器：将录制事件转为事件行，输出按 UTF-8 边界切分。
+pub(super) struct AsciicastEncoder {
+    // 上一块输出末尾不完整的 UTF-8 字节，拼接到下一块再编码，避免多字节字符被截断成乱码。
+    pending_utf8: Vec<u8>,
+}
+
+impl AsciicastEncoder {
+    pub(super) fn new() -> Self {
+        Self {
+            pending_utf8: Vec::new(),
+        }
+    }
+
+    /// 编码输出块；末尾不完整的字符留待下一块，整块都不完整时不产生事件行。
+    pub(super) fn encode_output(&mut self, elapsed: f64, bytes: &[u8]) -> Option<String> {
+        self.pending_utf8.extend_from_slice(bytes);
+        let data = take_complete_utf8(&mut self.pending_utf8);
+        if data.is_empty() {
+            return None;
+        }
+        Some(encode_event(elapsed, EVENT_OUTPUT, &data))
+    }
+
+    /// 录制结束时输出残留字节，非法部分按替换字符落盘。
+    pub(super) fn finish(&mut self, elapsed: f64) -> Option<String> {
+        if self.pending_utf8.is_empty() {
+            return None;
+        }
+        let data = String::from_utf8_lossy(&self.pending_utf8).to_string();
+        self.pending_utf8.clear();
+        Some(encode_event(elapsed, EVENT_OUTPUT, &data))
+    }
+}
+
+pub(super) fn encode_event(elapsed: f64, kind: &str, data: &str) -> String {
+    json!([elapsed, kind, data]).to_string()
+}
+
+/// 单个会话的录制器：只负责计时与入队，文件写入由写线程完成；
+/// 写线程按行缓冲写入，保证进程异常退出时已写事件可读。
+pub(super) struct SessionRecorder {
+    path: PathBuf,
+    started_at: Instant,
+    sender: Option<Sender<RecorderEvent>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl SessionRecorder {
+    /// 在工作区录制目录创建新文件、写入 asciicast 头并启动写线程。
+    pub(super) fn start(
+        workspace_path: &str,
+        terminal_id: &str,
+        rows: u16,
+        cols: u16,
+        title: Option<&str>,
+    ) -> Result<Self, String> {
+        let dir = storage::resolve_workspace_path(workspace_path, RECORDINGS_DIR)?;
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("failed to create recordings dir: {err}"))?;
+        let now = now_millis()?;
+        let file_name = format!(
+            "{}-{}.{}",
+            sanitize_file_stem(terminal_id),
+            now,
+            RECORDING_EXTENSION
+        );
+        let path = dir.join(file_name);
+        let file = OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .map_err(|err| format!("failed to create recording: {err}"))?;
+        let mut header = json!({
+            "version": ASCIICAST_VERSION,
+            "width": cols,
+            "height": rows,
+            "timestamp": now / 1000,
+            "env": { "TERM": "xterm-256color" }
+        });
+        if let Some(title) = title.filter(|value| !value.trim().is_empty()) {
+            header["title"] = Value::String(title.to_string());
+        }
+        let mut writer = LineWriter::new(file);
+        writeln!(writer, "{header}").map_err(|err| format!("failed to write recording: {err}"))?;
+        let started_at = Instant::now();
+        let (sender, receiver) = mpsc::channel();
+        let worker_path = path.clone();
+        let worker = thread::Builder::new()
+            .name("terminal-recorder".to_string())
+            .spawn(move || run_recorder_writer(writer, receiver, started_at, worker_path))
+            .map_err(|err| format!("failed to spawn recording writer: {err}"))?;
+        Ok(Self {
+            path,
+            started_at,
+            sender: Some(sender),
+            worker: Some(worker),
+        })
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(super) fn record_output(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let elapsed = self.elapsed();
+        self.send(RecorderEvent::Output {
+            elapsed,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub(super) fn record_input(&mut self, data: &str) -> Result<(), String> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        self.send_text(EVENT_INPUT, data.to_string())
+    }
+
+    pub(super) fn record_resize(&mut self, rows: u16, cols: u16) -> Result<(), String> {
+        self.send_text(EVENT_RESIZE, format!("{cols}x{rows}"))
+    }
+
+    /// 结束录制并等待写线程落盘完毕；应在会话锁外调用。
+    pub(super) fn finish(mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+
+    fn elapsed(&self) -> f64 {
+        self.started_at.elapsed().as_secs_f64()
+    }
+
+    fn send_text(&mut self, kind: &'static str, data: String) -> Result<(), String> {
+        let elapsed = self.elapsed();
+        self.send(RecorderEvent::Text {
+            elapsed,
+            kind,
+            data,
+        })
+    }
+
+    // 写线程因写入失败退出后通道关闭，发送失败即视为录制已停止。
+    fn send(&mut self, event: RecorderEvent) -> Result<(), String> {
+        let Some(sender) = self.sender.as_ref() else {
+            return Err("recording writer stopped".to_string());
+        };
+        sender
+            .send(event)
+            .map_err(|_| "recording writer stopped".to_string())
+    }
+}
+
+// 写线程：顺序编码并追加事件；发送端全部释放后补写残留输出并刷盘退出。
+fn run_recorder_writer(
+    mut writer: LineWriter<File>,
+    receiver: Receiver<RecorderEvent>,
+    started_at: Instant,
+    path: PathBuf,
+) {
+    let mut encoder = AsciicastEncoder::new();
+    for event in receiver {
+        let line = match event {
+            RecorderEvent::Output { elapsed, bytes } => encoder.encode_output(elapsed, &bytes),
+            RecorderEvent::Text {
+                elapsed,
+                kind,
+                data,
+            } => Some(encode_event(elapsed, kind, &data)),
+        };
+        let Some(line) = line else {
+            continue;
+        };
+        if let Err(err) = writeln!(writer, "{line}") {
+            log::warn!(
+                "terminal recording write failed path={} err={}",
+                path.display(),
+                err
+            );
+            return;
+        }
+    }
+    if let Some(line) = encoder.finish(started_at.elapsed().as_secs_f64()) {
+        let _ = writeln!(writer, "{line}");
+    }
+    let _ = writer.flush();
+}
+
+/// 列出工作区内的录制文件名（按名称倒序，即最新在前）。
+pub(crate) fn list_recordings(workspace_path: &str) -> Result<Vec<String>, String> {
+    let dir = storage::resolve_workspace_path(workspace_path, RECORDINGS_DIR)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries =
+        fs::read_dir(&dir).map_err(|err| format!("failed to read recordings dir: {err}"))?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some(RECORDING_EXTENSION)
+        })
+        .filter_map(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.to_string())
+        })
+        .collect();
+    names.sort_by(|a, b| b.cmp(a));
+    Ok(names)
+}
+
+/// 回放录制文件，重建 `at_ms`（相对录制开始，缺省为结尾）时刻的屏幕快照。
+/// 约束：只重放输出与尺寸事件；输入事件仅用于审计，不参与重建。
+pub(crate) fn replay_recording(
+    workspace_path: &str,
+    file_name: &str,
+    at_ms: Option<u64>,
+) -> Result<TerminalRecordingReplayPayload, String> {
+    let file_name = validate_recording_name(file_name)?;
+    let path = storage::resolve_workspace_path(
+        workspace_path,
+        &format!("{RECORDINGS_DIR}/{file_name}"),
+    )?;
+    let file = File::open(&path).map_err(|err| format!("failed to open recording: {err}"))?;
+    let mut lines = BufReader::new(file).lines();
+    let header_line = lines
+        .next()
+        .ok_or_else(|| "recording is empty".to_string())?
+        .map_err(|err| format!("failed to read recording: {err}"))?;
+    let header: Value = serde_json::from_str(&header_line)
+        .map_err(|err| format!("failed to parse recording header: {err}"))?;
+    if header.get("version").and_then(|value| value.as_u64()) != Some(ASCIICAST_VERSION) {
+        return Err("unsupported recording version".to_string());
+    }
+    let mut rows = read_dimension(&header, "height")?;
+    let mut cols = read_dimension(&header, "width")?;
+    let mut emulator = create_emulator(EmulatorConfig {
+        backend: EmulatorBackend::Wezterm,
+        rows,
+        cols,
+        scrollback_limit: SESSION_SCROLLBACK_LINES,
+    });
+    let limit = at_ms.map(|value| value as f64 / 1000.0);
+    let mut duration_ms = 0u64;
+    let mut applied_events = 0usize;
+    for line in lines {
+        let line = line.map_err(|err| format!("failed to read recording: {err}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // 录制中断可能留下半行，跳过无法解析的事件而不是整体失败。
+        let Ok(event) = serde_json::from_str::<(f64, String, String)>(&line) else {
+            continue;
+        };
+        let (time, kind, data) = event;
+        duration_ms = duration_ms.max((time * 1000.0) as u64);
+        if limit.map(|limit| time > limit).unwrap_or(false) {
+            continue;
+        }
+        match kind.as_str() {
+            EVENT_OUTPUT => emulator.apply_output(data.as_bytes()),
+            EVENT_RESIZE => {
+                if let Some((next_cols, next_rows)) = parse_resize(&data) {
+                    cols = next_cols;
+                    rows = next_rows;
+                    emulator.set_size(rows, cols);
+                }
+            }
+            _ => continue,
+        }
+        applied_events += 1;
+    }
+    let (cursor_row, cursor_col) = emulator.cursor_position();
+    Ok(TerminalRecordingReplayPayload {
+        file_name: file_name.to_string(),
+        at_ms: at_ms.unwrap_or(duration_ms).min(duration_ms),
+        duration_ms,
+        applied_events,
+        rows,
+        cols,
+        cursor_row,
+        cursor_col,
+        lines: emulator.snapshot_lines(),
+    })
+}
+
+/// 校验回放文件名：只允许录制目录下的 `.cast` 文件，拒绝路径分隔符与隐藏/上级目录名。
+pub(super) fn validate_recording_name(file_name: &str) -> Result<&str, String> {
+    let file_name = file_name.trim();
+    let valid = !file_name.is_empty()
+        && !file_name.starts_with('.')
+        && !file_name.contains(['/', '\\'])
+        && Path::new(file_name).extension().and_then(|ext| ext.to_str())
+            == Some(RECORDING_EXTENSION);
+    if valid {
+        Ok(file_name)
+    } else {
+        Err("invalid recording file name".to_string())
+    }
+}
+
+fn read_dimension(header: &Value, key: &str) -> Result<u16, String> {
+    header
+        .get(key)
+        .and_then(|value| value.as_u64())
+        .and_then(|value| u16::try_from(value).ok())
+        .filter(|value| *value > 0)
+        .ok_or_else(|| format!("recording header missing {key}"))
+}
+
+fn parse_resize(data: &str) -> Option<(u16, u16)> {
+    let (cols, rows) = data.split_once('x')?;
+    let cols = cols.trim().parse::<u16>().ok()?.max(1);
+    let rows = rows.trim().parse::<u16>().ok()?.max(1);
+    Some((cols, rows))
+}
+
+fn sanitize_file_stem(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { ch } else { '_' })
+        .collect()
+}
+
+// 取出缓冲中完整的 UTF-8 前缀；非法字节按替换字符输出，仅保留末尾可能被截断的字节。
+fn take_complete_utf8(buffer: &mut Vec<u8>) -> String {
+    match std::str::from_utf8(buffer) {
+        Ok(text) => {
+            let text = text.to_string();
+            buffer.clear();
+            text
+        }
+        Err(err) if err.error_len().is_none() => {
+            let valid = err.valid_up_to();
+            let tail = buffer.split_off(valid);
+            let text = String::from_utf8_lossy(buffer).to_string();
+            *buffer = tail;
+            text
+        }
+        Err(_) => {
+            let text = String::from_utf8_lossy(buffer).to_string();
+            buffer.clear();
+            text
+        }
+    }
+}
+
+#[cfg(test)]
+mod recording_tests;
@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use ulid::Ulid;
+
+use super::{
+    encode_event, list_recordings, replay_recording, validate_recording_name, AsciicastEncoder,
+    SessionRecorder,
+};
+
+struct TempWorkspace {
+    path: PathBuf,
+}
+
+impl TempWorkspace {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("golutra-recording-{}", Ulid::new()));
+        fs::create_dir_all(&path).expect("create temp workspace");
+        Self { path }
+    }
+
+    fn as_str(&self) -> &str {
+        self.path.to_str().expect("utf-8 temp path")
+    }
+}
+
+impl Drop for TempWorkspace {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn event_data(line: &str) -> String {
+    let event: Value = serde_json::from_str(line).expect("event json");
+    event[2].as_str().expect("event data").to_string()
+}
+
+#[test]
+fn encoder_keeps_split_utf8_until_complete() {
+    let mut encoder = AsciicastEncoder::new();
+    let bytes = "a好".as_bytes();
+    let first = encoder.encode_output(0.5, &bytes[..2]).expect("complete prefix");
+    assert_eq!(event_data(&first), "a");
+    // 只剩半个字符时不产生事件行。
+    assert!(encoder.encode_output(0.6, &bytes[2..3]).is_none());
+    let second = encoder.encode_output(0.7, &bytes[3..]).expect("completed char");
+    assert_eq!(event_data(&second), "好");
+    assert!(encoder.finish(1.0).is_none());
+}
+
+#[test]
+fn encoder_flushes_truncated_tail_lossily() {
+    let mut encoder = AsciicastEncoder::new();
+    assert!(encoder.encode_output(0.1, &"好".as_bytes()[..2]).is_none());
+    let tail = encoder.finish(0.2).expect("pending tail");
+    assert_eq!(event_data(&tail), "\u{fffd}");
+    assert!(encoder.finish(0.3).is_none());
+}
+
+#[test]
+fn encode_event_writes_asciicast_triplet() {
+    let line = encode_event(1.25, "r", "80x24");
+    let event: Value = serde_json::from_str(&line).expect("event json");
+    assert_eq!(event[0].as_f64(), Some(1.25));
+    assert_eq!(event[1].as_str(), Some("r"));
+    assert_eq!(event[2].as_str(), Some("80x24"));
+}
+
+#[test]
+fn recording_name_rejects_paths_and_foreign_files() {
+    assert_eq!(validate_recording_name(" term-1-100.cast "), Ok("term-1-100.cast"));
+    for name in ["", "  ", "../x.cast", "a/b.cast", "a\\b.cast", "..", ".cast", "notes.txt", "x"] {
+        assert!(validate_recording_name(name).is_err(), "{name:?} should be rejected");
+    }
+}
+
+#[test]
+fn recorder_writes_through_worker_and_replays() {
+    let workspace = TempWorkspace::new();
+    let mut recorder = SessionRecorder::start(workspace.as_str(), "term/1", 5, 20, Some("coder"))
+        .expect("start recorder");
+    recorder.record_output(b"hello ").expect("output");
+    recorder.record_output(&"世界".as_bytes()[..4]).expect("partial output");
+    recorder.record_input("ls\r").expect("input");
+    recorder.record_resize(6, 30).expect("resize");
+    recorder.finish();
+
+    let names = list_recordings(workspace.as_str()).expect("list");
+    assert_eq!(names.len(), 1);
+    assert!(names[0].starts_with("term_1-"));
+    let replay = replay_recording(workspace.as_str(), &names[0], None).expect("replay");
+    assert_eq!((replay.rows, replay.cols), (6, 30));
+    // 输入事件只用于审计，不参与重建；截断的字符在结束时按替换字符补写。
+    assert_eq!(replay.applied_events, 4);
+    assert!(replay.lines[0].starts_with("hello 世"));
+    assert!(replay.lines[0].contains('\u{fffd}'));
+    assert!(replay_recording(workspace.as_str(), "../escape.cast", None).is_err());
+}
@@ -3,25 +3,26 @@
 use std::{
   sync::{mpsc, Arc, Mutex},
   thread,
-  time::{Duration, Instant},
 };
 
 use serde_json::json;
 use tauri::{AppHandle, Manager};
 use ulid::Ulid;
 
+use super::super::filters::types::FilterResult;
 use super::super::filters::{FilterContext, FilterDecision, FilterMode, FilterSource};
+use crate::contracts::terminal_message::TerminalMessagePayload;
 use super::super::semantic::{
   build_semantic_payload, extract_command_from_input, extract_input_lines, SemanticState,
   TerminalChatContext,
 };
 use super::snapshot_service;
+use super::state::{lock_sessions, SessionRegistry, TerminalSession};
 use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
 use crate::ports::terminal_dispatch_gate::TerminalDispatchGate;
 use crate::ports::terminal_event::TerminalEventPort;
 use crate::ports::terminal_message::TerminalMessagePipeline;
 use crate::ports::settings::TerminalSettingsPort;
-use crate::now_millis;
 
 const STREAM_EMIT_INTERVAL_MS: u64 = 160; // 流式更新节流，避免高频事件影响 UI。
 const STREAM_MESSAGE_TYPE: &str = "info"; // [TODO/terminal, 2026-01-26] 统一流式消息类型的业务口径。
@@ -31,6 +32,15 @@ const STREAM_SOURCE: &str = "pty"; // [TODO/terminal, 2026-01-26] 明确流式
 pub(super) enum SemanticEvent {
   Output(Vec<u8>),
   SeedSnapshot { rows: u16, cols: u16, data: Vec<u8> },
+  // shell 集成标记闭合的命令输出区间（C..D），替代整屏快照作为 final 内容。
+  CommandOutput {
+    rows: u16,
+    cols: u16,
+    data: Vec<u8>,
+    exit_code: Option<i32>,
+  },
+  // 本轮 chat 块在会话快照中的绝对行区间，随 final 写入消息元数据。
+  BlockRange { start_row: u64, end_row: u64 },
   UserInput {
     data: String,
     context: TerminalChatContext,
@@ -50,7 +60,7 @@ pub(super) fn spawn_semantic_worker(
   terminal_type: String,
   pipeline: Arc<dyn TerminalMessagePipeline>,
   settings_service: Option<Arc<dyn TerminalSettingsPort>>,
-  sessions: Arc<Mutex<super::state::SessionRegistry>>,
+  sessions: Arc<Mutex<SessionRegistry>>,
   event_port: Arc<dyn TerminalEventPort>,
   dispatch_gate: Arc<dyn TerminalDispatchGate>,
 ) -> mpsc::Sender<SemanticEvent> {
@@ -60,174 +70,394 @@ pub(super) fn spawn_semantic_worker(
   let event_port = Arc::clone(&event_port);
   thread::spawn(move || {
     // 独立线程处理语义快照，避免阻塞 PTY 读写主路径。
-    let mut state =
-      SemanticState::new(terminal_id, member_id, workspace_id, rows, cols, terminal_type);
-    let mut last_stream_emit_at = Instant::now();
-    let mut last_stream_content: Option<String> = None;
+    // 线程内再取会话时钟：创建语义线程时调用方可能仍持有会话锁。
+    let clock = Arc::clone(&lock_sessions(&sessions).clock);
+    let mut worker = SemanticWorker::new(SemanticState::new(
+      terminal_id,
+      member_id,
+      workspace_id,
+      rows,
+      cols,
+      terminal_type,
+      settings_service.as_deref(),
+    ));
     while let Ok(event) = rx.recv() {
-      match event {
-        SemanticEvent::Output(bytes) => {
-          state.emulator.apply_output(&bytes);
-          maybe_emit_stream(
-            pipeline.as_ref(),
-            &mut state,
-            &mut last_stream_emit_at,
-            &mut last_stream_content,
-          );
-        }
-        SemanticEvent::SeedSnapshot { rows, cols, data } => {
-          state.reset_emulator(rows, cols);
-          state.emulator.apply_output(&data);
+      let now_ms = clock.now_ms();
+      let step = worker.handle(
+        event,
+        pipeline.as_ref(),
+        &sessions,
+        settings_service.as_deref(),
+        now_ms,
+      );
+      match step {
+        SemanticStep::Continue => {}
+        SemanticStep::Shutdown => break,
+        SemanticStep::Flushed(report) => {
+          log_final_flush(&app, &worker.state, &report);
+          // Drop/Defer 与回写失败也要释放派发队列，避免后续消息被阻塞。
+          let terminal_id = worker.state.terminal_id.as_str();
+          dispatch_gate.on_semantic_flush_complete(&app, terminal_id);
+          super::complete_dispatch_and_flush(&app, &sessions, event_port.as_ref(), terminal_id);
         }
-        SemanticEvent::UserInput { data, context } => {
-          if !state.chat_block_pending {
-            state.chat_block_pending = true;
-            state.chat_context = Some(context);
-            state.chat_span_id = Some(Ulid::new().to_string());
-            state.chat_last_command = extract_command_from_input(&data);
-            state.chat_last_input_lines = extract_input_lines(&data);
-            state.chat_stream_enabled =
-              resolve_chat_stream_enabled(settings_service.as_deref());
-            last_stream_content = None;
-            last_stream_emit_at = Instant::now();
-          }
+      }
+    }
+  });
+  tx
+}
+
+/// 语义事件的处理结果；诊断记录与派发队列推进依赖 AppHandle，由语义线程执行。
+pub(super) enum SemanticStep {
+  Continue,
+  Flushed(Box<FinalFlushReport>),
+  Shutdown,
+}
+
+/// 一次 final flush 的结果，附带结束 chat 块之前的上下文，供诊断与无头测试使用。
+pub(super) struct FinalFlushReport {
+  pub(super) message_type: &'static str,
+  pub(super) source: &'static str,
+  pub(super) filter_result: FilterResult,
+  pub(super) filtered_line_count: usize,
+  pub(super) snapshot_lines: Vec<String>,
+  pub(super) content: Option<String>,
+  pub(super) append_error: Option<String>,
+  pub(super) conversation_id: Option<String>,
+  pub(super) span_id: Option<String>,
+  pub(super) last_command: Option<String>,
+  pub(super) command_output: bool,
+  pub(super) exit_code: Option<i32>,
+}
+
+/// 语义事件处理核心：不持有线程与 AppHandle，语义线程与无头测试共用。
+pub(super) struct SemanticWorker {
+  pub(super) state: SemanticState,
+  last_stream_emit_at: u64,
+  last_stream_content: Option<String>,
+}
+
+impl SemanticWorker {
+  pub(super) fn new(state: SemanticState) -> Self {
+    Self {
+      state,
+      last_stream_emit_at: 0,
+      last_stream_content: None,
+    }
+  }
+
+  pub(super) fn handle(
+    &mut self,
+    event: SemanticEvent,
+    pipeline: &dyn TerminalMessagePipeline,
+    sessions: &Arc<Mutex<SessionRegistry>>,
+    settings_service: Option<&dyn TerminalSettingsPort>,
+    now_ms: u64,
+  ) -> SemanticStep {
+    match event {
+      SemanticEvent::Output(bytes) => {
+        self.state.emulator.apply_output(&bytes);
+        maybe_emit_stream(
+          pipeline,
+          &mut self.state,
+          now_ms,
+          &mut self.last_stream_emit_at,
+          &mut self.last_stream_content,
+        );
+      }
+      SemanticEvent::SeedSnapshot { rows, cols, data } => {
+        self.state.reset_emulator(rows, cols);
+        self.state.emulator.apply_output(&data);
+      }
+      SemanticEvent::CommandOutput {
+        rows,
+        cols,
+        data,
+        exit_code,
+      } => {
+        seed_command_output(&mut self.state, rows, cols, &data, exit_code);
+      }
+      SemanticEvent::BlockRange { start_row, end_row } => {
+        self.state.chat_row_range = Some((start_row, end_row));
+      }
+      SemanticEvent::UserInput { data, context } => {
+        let stream_enabled = resolve_chat_stream_enabled(settings_service);
+        if begin_chat_block(&mut self.state, &data, context, stream_enabled) {
+          self.last_stream_content = None;
+          self.last_stream_emit_at = now_ms;
         }
-        SemanticEvent::Resize { rows, cols } => {
-          state.set_size(rows, cols);
+      }
+      SemanticEvent::Resize { rows, cols } => {
+        self.state.set_size(rows, cols);
+      }
+      SemanticEvent::Flush { message_type, source } => {
+        if self.state.chat_block_pending {
+          let report = self.final_flush(pipeline, sessions, message_type, source, now_ms);
+          return SemanticStep::Flushed(Box::new(report));
         }
-        SemanticEvent::Flush { message_type, source } => {
-          if state.chat_block_pending {
-            let now_ms = now_millis().unwrap_or(0);
-            let snapshot_lines = state.emulator.snapshot_lines();
-            let logical_lines =
-              snapshot_service::merge_semantic_lines(&snapshot_lines, state.terminal_cols);
-            let snapshot_line_count = snapshot_lines.len();
-            let filter_context = FilterContext {
-              terminal_id: state.terminal_id.as_str(),
-              terminal_type: state.terminal_type.as_str(),
-              last_command: state.chat_last_command.as_deref(),
-              last_input_lines: state.chat_last_input_lines.as_deref(),
-              now_ms,
-              source: FilterSource::Snapshot,
-              mode: FilterMode::Final,
-            };
-            // 过滤仅影响聊天写回，不改变终端真实输出。
-            let filter_result = state.filter.apply_snapshot(&filter_context, &logical_lines);
-            let filtered_lines = filter_result
-              .lines
-              .as_ref()
-              .map(|lines| lines.as_slice())
-              .unwrap_or(logical_lines.as_slice());
-            let filtered_line_count = filtered_lines.len();
-            let (payload, should_clear_context) = match filter_result.decision {
-              FilterDecision::Allow => (
-                build_semantic_payload(&mut state, message_type, source, "final", filtered_lines),
-                true,
-              ),
-              FilterDecision::Drop => (None, true),
-              FilterDecision::Defer => (None, true),
-            };
-            let member_id_for_log = state.member_id.clone();
-            let workspace_id_for_log = state.workspace_id.clone();
-            let conversation_id_for_log = state
-              .chat_context
-              .as_ref()
-              .map(|context| context.conversation_id.clone());
-            let span_id_for_log = state.chat_span_id.clone();
-            let last_command_for_log = state.chat_last_command.clone();
-            // 诊断链路需要完整记录语义 flush 的快照与上下文，便于定位聊天输出缺失。
-            diagnostics_log_backend_event(
-              &app.state::<DiagnosticsState>(),
-              member_id_for_log.clone(),
-              Some(state.terminal_id.clone()),
-              conversation_id_for_log.clone(),
-              None,
-              workspace_id_for_log.clone(),
-              "terminal_semantic_flush",
-              json!({
-                "terminalId": state.terminal_id,
-                "memberId": member_id_for_log,
-                "workspaceId": workspace_id_for_log,
-                "conversationId": conversation_id_for_log,
-                "messageType": message_type,
-                "source": source,
-                "hasPayload": payload.is_some(),
-                "filterProfile": filter_result.profile.as_str(),
-                "filterDecision": filter_result.decision.as_str(),
-                "filterReason": filter_result.reason,
-                "filteredLineCount": filtered_line_count,
-                "snapshotLines": snapshot_lines,
-                "lineCount": snapshot_line_count,
-                "content": payload.as_ref().map(|item| item.content.clone()),
-                "spanId": span_id_for_log,
-                "lastCommand": last_command_for_log
-              }),
-            );
-            let mut dispatch_completed = false;
-            if let Some(payload) = payload {
-              let content_for_log = payload.content.clone();
-              match pipeline.process_final(payload) {
-                Ok(_) => {
-                  dispatch_completed = true;
-                }
-                Err(err) => {
-                log::warn!("terminal chat append failed terminal_id={} err={}", state.terminal_id, err);
-                diagnostics_log_backend_event(
-                  &app.state::<DiagnosticsState>(),
-                  state.member_id.clone(),
-                  Some(state.terminal_id.clone()),
-                  state.chat_context.as_ref().map(|context| context.conversation_id.clone()),
-                  None,
-                  state.workspace_id.clone(),
-                  "terminal_chat_append_error",
-                  json!({
-                    "terminalId": state.terminal_id,
-                    "memberId": state.member_id,
-                    "workspaceId": state.workspace_id,
-                    "conversationId": state.chat_context.as_ref().map(|context| context.conversation_id.clone()),
-                    "error": err,
-                    "content": content_for_log
-                  }),
-                );
-                // 语义回写失败也要释放派发队列，避免后续消息被阻塞。
-                dispatch_completed = true;
-                }
-              }
-            } else if should_clear_context {
-              dispatch_completed = true;
-            }
-            if should_clear_context {
-              if dispatch_completed {
-                dispatch_gate.on_semantic_flush_complete(&app, &state.terminal_id);
-                super::complete_dispatch_and_flush(
-                  &app,
-                  &sessions,
-                  event_port.as_ref(),
-                  &state.terminal_id,
-                );
-              }
-              state.chat_block_pending = false;
-              state.chat_context = None;
-              state.chat_span_id = None;
-              state.chat_last_command = None;
-              state.chat_last_input_lines = None;
-              last_stream_content = None;
-              // 清空语义仿真器的屏幕与历史，避免后续 chat 使用旧上下文。
-              state.reset_emulator(state.terminal_rows, state.terminal_cols);
-            }
+      }
+      SemanticEvent::Shutdown => return SemanticStep::Shutdown,
+    }
+    SemanticStep::Continue
+  }
+
+  fn final_flush(
+    &mut self,
+    pipeline: &dyn TerminalMessagePipeline,
+    sessions: &Arc<Mutex<SessionRegistry>>,
+    message_type: &'static str,
+    source: &'static str,
+    now_ms: u64,
+  ) -> FinalFlushReport {
+    let FinalFlushOutcome {
+      payload,
+      filter_result,
+      filtered_line_count,
+      snapshot_lines,
+    } = evaluate_final_flush(&mut self.state, message_type, source, now_ms);
+    let state = &self.state;
+    let mut report = FinalFlushReport {
+      message_type,
+      source,
+      filter_result,
+      filtered_line_count,
+      snapshot_lines,
+      content: payload.as_ref().map(|item| item.content.clone()),
+      append_error: None,
+      conversation_id: state
+        .chat_context
+        .as_ref()
+        .map(|context| context.conversation_id.clone()),
+      span_id: state.chat_span_id.clone(),
+      last_command: state.chat_last_command.clone(),
+      command_output: state.chat_command_output,
+      exit_code: state.chat_exit_code,
+    };
+    if let Some(payload) = payload {
+      match pipeline.process_final(payload) {
+        Ok(result) => {
+          // 记录消息与终端行区间的对应关系，供按消息取回原文与界面跳转。
+          if let (Some(message_id), Some(row_range)) = (result.message_id, state.chat_row_range) {
+            super::record_message_rows(sessions, &state.terminal_id, message_id, row_range);
           }
         }
-        SemanticEvent::Shutdown => break,
+        Err(err) => report.append_error = Some(err),
       }
     }
+    // Drop/Defer 也结束本轮 chat，避免语义上下文滞留。
+    end_chat_block(&mut self.state);
+    self.last_stream_content = None;
+    report
+  }
+}
+
+// 诊断链路需要完整记录语义 flush 的快照与上下文，便于定位聊天输出缺失。
+fn log_final_flush(app: &AppHandle, state: &SemanticState, report: &FinalFlushReport) {
+  let diagnostics = app.state::<DiagnosticsState>();
+  diagnostics_log_backend_event(
+    &diagnostics,
+    state.member_id.clone(),
+    Some(state.terminal_id.clone()),
+    report.conversation_id.clone(),
+    None,
+    state.workspace_id.clone(),
+    "terminal_semantic_flush",
+    json!({
+      "terminalId": state.terminal_id,
+      "memberId": state.member_id,
+      "workspaceId": state.workspace_id,
+      "conversationId": report.conversation_id,
+      "messageType": report.message_type,
+      "source": report.source,
+      "hasPayload": report.content.is_some(),
+      "filterProfile": report.filter_result.profile.as_str(),
+      "filterDecision": report.filter_result.decision.as_str(),
+      "filterReason": report.filter_result.reason,
+      "filteredLineCount": report.filtered_line_count,
+      "snapshotLines": report.snapshot_lines,
+      "lineCount": report.snapshot_lines.len(),
+      "content": report.content,
+      "spanId": report.span_id,
+      "lastCommand": report.last_command,
+      "commandOutput": report.command_output,
+      "exitCode": report.exit_code
+    }),
+  );
+  let Some(err) = report.append_error.as_ref() else {
+    return;
+  };
+  log::warn!("terminal chat append failed terminal_id={} err={}", state.terminal_id, err);
+  diagnostics_log_backend_event(
+    &diagnostics,
+    state.member_id.clone(),
+    Some(state.terminal_id.clone()),
+    report.conversation_id.clone(),
+    None,
+    state.workspace_id.clone(),
+    "terminal_chat_append_error",
+    json!({
+      "terminalId": state.terminal_id,
+      "memberId": state.member_id,
+      "workspaceId": state.workspace_id,
+      "conversationId": report.conversation_id,
+      "error": err,
+      "content": report.content
+    }),
+  );
+}
+
+/// final flush 前送入语义线程的事件序列：命令区间输出或整屏快照、行区间与 Flush。
+pub(super) struct FlushSeed {
+  pub(super) events: Vec<SemanticEvent>,
+  pub(super) command_output: bool,
+  pub(super) exit_code: Option<i32>,
+}
+
+/// 在会话锁内采集 final flush 的种子；轮询派发器与无头测试共用。
+pub(super) fn collect_flush_seed(
+  session: &mut TerminalSession,
+  chat_pending_since: Option<u64>,
+) -> FlushSeed {
+  // shell 集成标记已闭合命令区间时，只用 C..D 之间的输出作为语义内容。
+  let completed_command =
+    chat_pending_since.and_then(|since| session.shell_turns.take_completed_since(since));
+  let row_range = super::chat_block_row_range(session, completed_command.as_ref());
+  let rows = session.screen_rows;
+  let cols = session.screen_cols;
+  let command_output = completed_command.is_some();
+  let exit_code = completed_command
+    .as_ref()
+    .and_then(|command| command.exit_code);
+  let seed = match completed_command {
+    Some(command) => SemanticEvent::CommandOutput {
+      rows,
+      cols,
+      data: command.output,
+      exit_code: command.exit_code,
+    },
+    // 强制用最新快照重建语义仿真器，避免语义线程滞后导致过期行混入。
+    None => SemanticEvent::SeedSnapshot {
+      rows,
+      cols,
+      data: session.snapshot.snapshot_segments().data,
+    },
+  };
+  let mut events = vec![seed];
+  if let Some((start_row, end_row)) = row_range {
+    events.push(SemanticEvent::BlockRange { start_row, end_row });
+  }
+  events.push(SemanticEvent::Flush {
+    message_type: "info",
+    source: "pty",
   });
-  tx
+  FlushSeed {
+    events,
+    command_output,
+    exit_code,
+  }
+}
+
+/// 开启一轮 chat 语义块；已有进行中的块时忽略后续输入。
+/// 返回：是否开启了新块。
+pub(super) fn begin_chat_block(
+  state: &mut SemanticState,
+  data: &str,
+  context: TerminalChatContext,
+  stream_enabled: bool,
+) -> bool {
+  if state.chat_block_pending {
+    return false;
+  }
+  state.chat_block_pending = true;
+  state.chat_context = Some(context);
+  state.chat_span_id = Some(Ulid::new().to_string());
+  state.chat_last_command = extract_command_from_input(data);
+  state.chat_last_input_lines = extract_input_lines(data);
+  state.chat_stream_enabled = stream_enabled;
+  true
+}
+
+/// 结束当前 chat 语义块并清空仿真器，避免后续 chat 使用旧上下文。
+pub(super) fn end_chat_block(state: &mut SemanticState) {
+  state.chat_block_pending = false;
+  state.chat_context = None;
+  state.chat_span_id = None;
+  state.chat_last_command = None;
+  state.chat_last_input_lines = None;
+  state.chat_command_output = false;
+  state.chat_exit_code = None;
+  state.chat_row_range = None;
+  state.reset_emulator(state.terminal_rows, state.terminal_cols);
+}
+
+/// 用命令输出区间重建语义仿真器，并记录退出码供消息元数据使用。
+pub(super) fn seed_command_output(
+  state: &mut SemanticState,
+  rows: u16,
+  cols: u16,
+  data: &[u8],
+  exit_code: Option<i32>,
+) {
+  state.reset_emulator(rows, cols);
+  state.emulator.apply_output(data);
+  state.chat_command_output = true;
+  state.chat_exit_code = exit_code;
+}
+
+/// final flush 的求值结果：过滤决策与可写回的负载。
+pub(super) struct FinalFlushOutcome {
+  pub(super) payload: Option<TerminalMessagePayload>,
+  pub(super) filter_result: FilterResult,
+  pub(super) filtered_line_count: usize,
+  pub(super) snapshot_lines: Vec<String>,
+}
+
+/// 对当前语义屏幕执行 final 过滤并构建写回负载；不产生副作用，便于无头测试复用。
+pub(super) fn evaluate_final_flush(
+  state: &mut SemanticState,
+  message_type: &str,
+  source: &str,
+  now_ms: u64,
+) -> FinalFlushOutcome {
+  let snapshot_lines = state.emulator.snapshot_lines();
+  let logical_lines = snapshot_service::merge_semantic_lines(&snapshot_lines, state.terminal_cols);
+  let filter_context = FilterContext {
+    terminal_id: state.terminal_id.as_str(),
+    terminal_type: state.terminal_type.as_str(),
+    last_command: state.chat_last_command.as_deref(),
+    last_input_lines: state.chat_last_input_lines.as_deref(),
+    now_ms,
+    source: if state.chat_command_output {
+      FilterSource::CommandOutput
+    } else {
+      FilterSource::Snapshot
+    },
+    mode: FilterMode::Final,
+  };
+  // 过滤仅影响聊天写回，不改变终端真实输出。
+  let filter_result = state.filter.apply_snapshot(&filter_context, &logical_lines);
+  let filtered_lines = filter_result
+    .lines
+    .clone()
+    .unwrap_or_else(|| logical_lines.clone());
+  let payload = match filter_result.decision {
+    FilterDecision::Allow => {
+      build_semantic_payload(state, message_type, source, "final", &filtered_lines)
+    }
+    FilterDecision::Drop | FilterDecision::Defer => None,
+  };
+  FinalFlushOutcome {
+    payload,
+    filter_result,
+    filtered_line_count: filtered_lines.len(),
+    snapshot_lines,
+  }
 }
 
 fn maybe_emit_stream(
   pipeline: &dyn crate::ports::terminal_message::TerminalMessagePipeline,
   state: &mut SemanticState,
-  last_stream_emit_at: &mut Instant,
+  now_ms: u64,
+  last_stream_emit_at: &mut u64,
   last_stream_content: &mut Option<String>,
 ) {
   if !state.chat_block_pending {
@@ -236,11 +466,9 @@ fn maybe_emit_stream(
   if !state.chat_stream_enabled {
     return;
   }
-  let now = Instant::now();
-  if now.duration_since(*last_stream_emit_at) < Duration::from_millis(STREAM_EMIT_INTERVAL_MS) {
+  if now_ms.saturating_sub(*last_stream_emit_at) < STREAM_EMIT_INTERVAL_MS {
     return;
   }
-  let now_ms = now_millis().unwrap_or(0);
   let snapshot_lines = state.emulator.snapshot_lines();
   let logical_lines = snapshot_service::merge_semantic_lines(&snapshot_lines, state.terminal_cols);
   let filter_context = FilterContext {
@@ -261,7 +489,7 @@ fn maybe_emit_stream(
   if filter_result.decision != FilterDecision::Allow {
     return;
   }
-  let Some(mut payload) = build_semantic_payload(
+  let Some(payload) = build_semantic_payload(
     state,
     STREAM_MESSAGE_TYPE,
     STREAM_SOURCE,
@@ -273,20 +501,13 @@ fn maybe_emit_stream(
   if last_stream_content.as_deref() == Some(payload.content.as_str()) {
     return;
   }
+  // 始终发送整段全文，增量由消息流水线在脱敏后计算。
   let next_content = payload.content.clone();
-  if let Some(previous) = last_stream_content.as_deref() {
-    if let Some(delta) = next_content.strip_prefix(previous) {
-      if !delta.is_empty() {
-        payload.content = delta.to_string();
-        payload.mode = "delta".to_string();
-      }
-    }
-  }
-  if let Err(err) = pipeline.process_stream(payload.clone()) {
+  if let Err(err) = pipeline.process_stream(payload) {
     log::warn!("terminal stream dispatch failed terminal_id={} err={}", state.terminal_id, err);
   }
   *last_stream_content = Some(next_content);
-  *last_stream_emit_at = now;
+  *last_stream_emit_at = now_ms;
 }
 
 fn resolve_chat_stream_enabled(
@@ -0,0 +1,137 @@
+//! 会话 ID 磁盘发现：没有 /status 可解析的 CLI（Claude Code、Gemini、Qwen、OpenCode）
+//! 启动后轮询其本地会话存储，发现会话 ID 后写入会话映射，重启时据此追加 resume 参数。
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::ports::terminal_session::TerminalSessionRepository;
+use crate::terminal_engine::default_members::resolve_default_member;
+use crate::terminal_engine::default_members::session_store::{discover_session_id, home_dir};
+
+use super::{lock_sessions, SessionRegistry};
+
+const DISCOVERY_INTERVAL_MS: u64 = 2000; // 轮询间隔：只读目录元数据，开销很小。
+const DISCOVERY_TIMEOUT_MS: u64 = 30 * 60 * 1000; // CLI 通常在首条消息后才落盘，窗口需覆盖到首次派发。
+const DISCOVERY_MTIME_SLACK_MS: u64 = 2000; // 文件时间精度与启动时刻的误差容忍。
+
+/// 为刚启动的成员进程启动会话 ID 发现；成员类型未配置会话存储时直接返回。
+/// 会话关闭、进程退出或重启（世代号变化）后停止轮询。
+pub(super) fn spawn_session_id_discovery(
+    sessions: Arc<Mutex<SessionRegistry>>,
+    session_repository: Arc<dyn TerminalSessionRepository>,
+    terminal_id: String,
+    spawn_epoch: u64,
+) {
+    let target = {
+        let guard = lock_sessions(&sessions);
+        guard.sessions.get(&terminal_id).and_then(|session| {
+            let store = resolve_default_member(session.member_type())?.session_store?;
+            Some((
+                store,
+                session.member_type().to_string(),
+                session.launch_cwd.clone()?,
+                session.workspace_id.clone()?,
+                session.member_id.clone()?,
+            ))
+        })
+    };
+    let Some((store, member_type, cwd, workspace_id, member_id)) = target else {
+        return;
+    };
+    let Some(home) = home_dir() else {
+        log::warn!(
+            "terminal session id discovery skipped missing home terminal_id={}",
+            terminal_id
+        );
+        return;
+    };
+    // CLI 记录的是解析符号链接后的工作目录，两种写法都尝试。
+    let mut cwds = vec![PathBuf::from(&cwd)];
+    if let Ok(canonical) = fs::canonicalize(&cwd) {
+        if !cwds.contains(&canonical) {
+            cwds.push(canonical);
+        }
+    }
+    let since = SystemTime::now() - Duration::from_millis(DISCOVERY_MTIME_SLACK_MS);
+    thread::spawn(move || {
+        let deadline = Instant::now() + Duration::from_millis(DISCOVERY_TIMEOUT_MS);
+        while Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(DISCOVERY_INTERVAL_MS));
+            // 同类型的其他会话已占用的 ID 不参与匹配，避免共用工作目录时串号。
+            let exclude: HashSet<String> = {
+                let guard = lock_sessions(&sessions);
+                match guard.sessions.get(&terminal_id) {
+                    Some(session) if session.spawn_epoch == spawn_epoch && session.handle.is_some() => {}
+                    _ => return,
+                }
+                guard
+                    .sessions
+                    .values()
+                    .filter(|session| session.id != terminal_id && session.member_type() == member_type)
+                    .filter_map(|session| session.remote_session_id.clone())
+                    .collect()
+            };
+            let found = cwds
+                .iter()
+                .find_map(|cwd| discover_session_id(&store, &home, cwd, since, &exclude));
+            if let Some(session_id) = found {
+                record_session_id(
+                    &sessions,
+                    session_repository.as_ref(),
+                    &terminal_id,
+                    spawn_epoch,
+                    &workspace_id,
+                    &member_id,
+                    session_id,
+                );
+                return;
+            }
+        }
+        log::info!(
+            "terminal session id discovery timed out terminal_id={}",
+            terminal_id
+        );
+    });
+}
+
+fn record_session_id(
+    sessions: &Arc<Mutex<SessionRegistry>>,
+    session_repository: &dyn TerminalSessionRepository,
+    terminal_id: &str,
+    spawn_epoch: u64,
+    workspace_id: &str,
+    member_id: &str,
+    session_id: String,
+) {
+    let previous = {
+        let mut guard = lock_sessions(sessions);
+        let Some(session) = guard.sessions.get_mut(terminal_id) else {
+            return;
+        };
+        if session.spawn_epoch != spawn_epoch {
+            return;
+        }
+        session.remote_session_id.replace(session_id.clone())
+    };
+    if previous.as_deref() == Some(session_id.as_str()) {
+        return;
+    }
+    match session_repository.upsert_terminal_session(workspace_id, member_id, &session_id) {
+        Ok(()) => log::info!(
+            "terminal session map updated member_id={} session_id={} source=store",
+            member_id,
+            session_id
+        ),
+        Err(err) => log::warn!(
+            "terminal session map update failed member_id={} err={}",
+            member_id,
+            err
+        ),
+    }
+}
@@ -0,0 +1,232 @@
+//! Shell 集成标记：解析 OSC 133 / 633 的提示符与命令边界（A 提示符开始、B 命令输入开始、
+//! C 命令输出开始、D[;code] 命令结束），并截取 C..D 之间的命令输出区间。
+//! 逐字节状态机解析，跨 chunk 的半截序列保留到下一次喂入；非标记的 OSC 原样计入输出。
+//! 输出在标记处分段写入会话仿真器，借此记录命令区间覆盖的绝对行号。
+
+const SHELL_MARK_OSC_LIMIT: usize = 512; // 单个 OSC 缓冲上限，超过即视为非标记序列，避免异常输出撑大缓冲。
+const COMMAND_OUTPUT_LIMIT: usize = 256 * 1024; // 单条命令输出保留上限，超出时丢弃最早部分。
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ShellMark {
+    PromptStart,
+    CommandStart,
+    CommandExecuted,
+    CommandFinished { exit_code: Option<i32> },
+}
+
+/// 一条已结束命令的输出区间与退出码。
+#[derive(Clone, Debug)]
+pub(crate) struct CompletedCommand {
+    pub(crate) output: Vec<u8>,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) finished_at: u64,
+    /// 命令输出覆盖的绝对行区间 `[start_row, end_row)`。
+    pub(crate) start_row: u64,
+    pub(crate) end_row: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ParseState {
+    #[default]
+    Ground,
+    Escape,
+    Osc,
+    OscEscape,
+}
+
+/// 会话级命令边界跟踪：只在 C..D 之间累积输出，D 到达后固定为最近一条完成命令。
+#[derive(Default)]
+pub(crate) struct ShellTurnTracker {
+    state: ParseState,
+    osc: Vec<u8>,
+    executing: bool,
+    output: Vec<u8>,
+    output_start_row: u64,
+    completed: Option<CompletedCommand>,
+}
+
+impl ShellTurnTracker {
+    /// 喂入一段 PTY 输出，返回本段内识别到的标记（按出现顺序）。
+    /// `apply` 负责把输出片段写入会话仿真器，并返回写入后光标的（绝对行号，列）；
+    /// 每个标记处切分一次，保证读到的行号恰好对应该边界。
+    pub(crate) fn feed(
+        &mut self,
+        chunk: &[u8],
+        now: u64,
+        mut apply: impl FnMut(&[u8]) -> (u64, u16),
+    ) -> Vec<ShellMark> {
+        let mut marks = Vec::new();
+        let mut applied = 0;
+        for (index, &byte) in chunk.iter().enumerate() {
+            let mark = match self.state {
+                ParseState::Ground => {
+                    if byte == ESC {
+                        self.state = ParseState::Escape;
+                    } else {
+                        self.capture(&[byte]);
+                    }
+                    None
+                }
+                ParseState::Escape => {
+                    self.step_escape(byte);
+                    None
+                }
+                ParseState::Osc => {
+                    if byte == BEL {
+                        self.finish_osc(&[BEL])
+                    } else {
+                        if byte == ESC {
+                            self.state = ParseState::OscEscape;
+                        } else if self.osc.len() >= SHELL_MARK_OSC_LIMIT {
+                            self.abort_osc(&[byte]);
+                        } else {
+                            self.osc.push(byte);
+                        }
+                        None
+                    }
+                }
+                ParseState::OscEscape => {
+                    if byte == b'\\' {
+                        self.finish_osc(&[ESC, b'\\'])
+                    } else {
+                        // ST 不完整：已缓冲内容按非标记放行，ESC 作为新序列起点重新解析。
+                        self.abort_osc(&[]);
+                        self.step_escape(byte);
+                        None
+                    }
+                }
+            };
+            if let Some(mark) = mark {
+                let cursor = apply(&chunk[applied..=index]);
+                applied = index + 1;
+                self.record_mark(mark, now, cursor, &mut marks);
+            }
+        }
+        if applied < chunk.len() {
+            apply(&chunk[applied..]);
+        }
+        marks
+    }
+
+    /// 返回最近一条在 `since` 之后完成的命令的完成时间。
+    pub(crate) fn completed_at_since(&self, since: u64) -> Option<u64> {
+        self.completed
+            .as_ref()
+            .map(|command| command.finished_at)
+            .filter(|finished_at| *finished_at >= since)
+    }
+
+    /// 取出 `since` 之后完成的命令；更早的完成记录视为与当前 chat 无关并丢弃。
+    pub(crate) fn take_completed_since(&mut self, since: u64) -> Option<CompletedCommand> {
+        let completed = self.completed.take()?;
+        if completed.finished_at >= since {
+            Some(completed)
+        } else {
+            None
+        }
+    }
+
+    fn step_escape(&mut self, byte: u8) {
+        if byte == b']' {
+            self.state = ParseState::Osc;
+            self.osc.clear();
+        } else if byte == ESC {
+            self.state = ParseState::Escape;
+            self.capture(&[ESC]);
+        } else {
+            self.state = ParseState::Ground;
+            self.capture(&[ESC, byte]);
+        }
+    }
+
+    fn capture(&mut self, bytes: &[u8]) {
+        if !self.executing {
+            return;
+        }
+        self.output.extend_from_slice(bytes);
+        if self.output.len() > COMMAND_OUTPUT_LIMIT {
+            let overflow = self.output.len() - COMMAND_OUTPUT_LIMIT;
+            self.output.drain(..overflow);
+        }
+    }
+
+    fn abort_osc(&mut self, tail: &[u8]) {
+        let mut raw = vec![ESC, b']'];
+        raw.append(&mut self.osc);
+        raw.extend_from_slice(tail);
+        self.state = ParseState::Ground;
+        self.capture(&raw);
+    }
+
+    fn finish_osc(&mut self, terminator: &[u8]) -> Option<ShellMark> {
+        self.state = ParseState::Ground;
+        let body = std::mem::take(&mut self.osc);
+        let mark = parse_shell_mark(&body);
+        if mark.is_none() {
+            let mut raw = vec![ESC, b']'];
+            raw.extend_from_slice(&body);
+            raw.extend_from_slice(terminator);
+            self.capture(&raw);
+        }
+        mark
+    }
+
+    fn record_mark(
+        &mut self,
+        mark: ShellMark,
+        now: u64,
+        (row, col): (u64, u16),
+        marks: &mut Vec<ShellMark>,
+    ) {
+        match mark {
+            ShellMark::CommandExecuted => {
+                self.executing = true;
+                self.output.clear();
+                self.output_start_row = row;
+                self.completed = None;
+            }
+            ShellMark::CommandFinished { exit_code } => {
+                // 没有 C 的 D（如 shim 在进程退出时发出的 633;D）不构成命令区间。
+                if !self.executing {
+                    marks.push(mark);
+                    return;
+                }
+                self.executing = false;
+                // 输出未以换行结尾时光标仍停在最后一行，该行也属于命令区间。
+                let end_row = if col > 0 { row + 1 } else { row };
+                self.completed = Some(CompletedCommand {
+                    output: std::mem::take(&mut self.output),
+                    exit_code,
+                    finished_at: now,
+                    start_row: self.output_start_row,
+                    end_row: end_row.max(self.output_start_row),
+                });
+            }
+            ShellMark::PromptStart | ShellMark::CommandStart => {}
+        }
+        marks.push(mark);
+    }
+}
+
+// OSC 133（FinalTerm）与 633（VS Code）共用 A/B/C/D 语义；633 的 E/P 等扩展项忽略。
+fn parse_shell_mark(body: &[u8]) -> Option<ShellMark> {
+    let text = std::str::from_utf8(body).ok()?;
+    let rest = text
+        .strip_prefix("133;")
+        .or_else(|| text.strip_prefix("633;"))?;
+    let mut parts = rest.split(';');
+    match parts.next()? {
+        "A" => Some(ShellMark::PromptStart),
+        "B" => Some(ShellMark::CommandStart),
+        "C" => Some(ShellMark::CommandExecuted),
+        "D" => Some(ShellMark::CommandFinished {
+            exit_code: parts.next().and_then(|value| value.trim().parse::<i32>().ok()),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod feed_tests;
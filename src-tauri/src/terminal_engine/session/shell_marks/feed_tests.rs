@@ -0,0 +1,126 @@
+use super::{ShellMark, ShellTurnTracker, COMMAND_OUTPUT_LIMIT, SHELL_MARK_OSC_LIMIT};
+
+/// 模拟会话仿真器：记录写入的字节；光标按去掉 OSC 后的可见字节计算，行号按换行计数。
+#[derive(Default)]
+struct FakeScreen {
+    applied: Vec<u8>,
+}
+
+impl FakeScreen {
+    fn cursor(&self) -> (u64, u16) {
+        let mut visible = Vec::new();
+        let mut bytes = self.applied.iter().copied().peekable();
+        while let Some(byte) = bytes.next() {
+            if byte == 0x1b && bytes.peek() == Some(&b']') {
+                while let Some(next) = bytes.next() {
+                    if next == 0x07 || (next == 0x1b && bytes.next_if_eq(&b'\\').is_some()) {
+                        break;
+                    }
+                }
+                continue;
+            }
+            visible.push(byte);
+        }
+        let row = visible.iter().filter(|byte| **byte == b'\n').count() as u64;
+        let col = visible.iter().rev().take_while(|byte| **byte != b'\n').count();
+        (row, col.min(u16::MAX as usize) as u16)
+    }
+}
+
+fn feed(tracker: &mut ShellTurnTracker, screen: &mut FakeScreen, chunk: &[u8]) -> Vec<ShellMark> {
+    tracker.feed(chunk, 10, |segment| {
+        screen.applied.extend_from_slice(segment);
+        screen.cursor()
+    })
+}
+
+#[test]
+fn marks_split_across_chunks_are_recognized_once_complete() {
+    let mut tracker = ShellTurnTracker::default();
+    let mut screen = FakeScreen::default();
+    assert!(feed(&mut tracker, &mut screen, b"$ ls\r\n\x1b]13").is_empty());
+    assert_eq!(
+        feed(&mut tracker, &mut screen, b"3;C\x07out\n\x1b"),
+        vec![ShellMark::CommandExecuted]
+    );
+    assert_eq!(
+        feed(&mut tracker, &mut screen, b"]133;D;2\x1b\\"),
+        vec![ShellMark::CommandFinished { exit_code: Some(2) }]
+    );
+    // 标记本身也要写入仿真器，切分只影响读取行号的时机。
+    assert_eq!(screen.applied, b"$ ls\r\n\x1b]133;C\x07out\n\x1b]133;D;2\x1b\\");
+    let completed = tracker.take_completed_since(10).expect("completed command");
+    assert_eq!(completed.output, b"out\n");
+    assert_eq!(completed.exit_code, Some(2));
+    assert_eq!((completed.start_row, completed.end_row), (1, 2));
+    assert!(tracker.take_completed_since(0).is_none());
+}
+
+#[test]
+fn bel_and_st_terminators_both_close_marks() {
+    let mut tracker = ShellTurnTracker::default();
+    let mut screen = FakeScreen::default();
+    assert_eq!(
+        feed(&mut tracker, &mut screen, b"\x1b]133;A\x07$ \x1b]633;B\x1b\\"),
+        vec![ShellMark::PromptStart, ShellMark::CommandStart]
+    );
+    // ESC 后不是 `\` 时 ST 不完整，缓冲内容按非标记放行。
+    assert!(feed(&mut tracker, &mut screen, b"\x1b]133;C\x1b[0m").is_empty());
+    assert!(tracker.take_completed_since(0).is_none());
+}
+
+#[test]
+fn non_mark_osc_passes_through_into_command_output() {
+    let mut tracker = ShellTurnTracker::default();
+    let mut screen = FakeScreen::default();
+    let body = b"\x1b]0;title\x07hi\x1b]633;E;ls\x1b\\\x1b[1mbold\x1b[0m";
+    let mut chunk = b"\x1b]133;C\x07".to_vec();
+    chunk.extend_from_slice(body);
+    chunk.extend_from_slice(b"\x1b]133;D;0\x07");
+    assert_eq!(
+        feed(&mut tracker, &mut screen, &chunk),
+        vec![
+            ShellMark::CommandExecuted,
+            ShellMark::CommandFinished { exit_code: Some(0) },
+        ]
+    );
+    let completed = tracker.take_completed_since(0).expect("completed command");
+    assert_eq!(completed.output, body);
+}
+
+#[test]
+fn oversized_osc_is_aborted_and_kept_as_output() {
+    let mut tracker = ShellTurnTracker::default();
+    let mut screen = FakeScreen::default();
+    let mut oversized = b"\x1b]".to_vec();
+    oversized.extend(std::iter::repeat_n(b'x', SHELL_MARK_OSC_LIMIT + 10));
+    oversized.push(0x07);
+    let mut chunk = b"\x1b]133;C\x07".to_vec();
+    chunk.extend_from_slice(&oversized);
+    chunk.extend_from_slice(b"\x1b]133;D\x07");
+    assert_eq!(
+        feed(&mut tracker, &mut screen, &chunk),
+        vec![
+            ShellMark::CommandExecuted,
+            ShellMark::CommandFinished { exit_code: None },
+        ]
+    );
+    let completed = tracker.take_completed_since(0).expect("completed command");
+    assert_eq!(completed.output, oversized);
+}
+
+#[test]
+fn command_output_keeps_only_the_latest_bytes() {
+    let mut tracker = ShellTurnTracker::default();
+    let mut screen = FakeScreen::default();
+    let output: Vec<u8> = (0..COMMAND_OUTPUT_LIMIT + 100)
+        .map(|index| b'a' + (index % 26) as u8)
+        .collect();
+    feed(&mut tracker, &mut screen, b"\x1b]133;C\x07");
+    feed(&mut tracker, &mut screen, &output);
+    feed(&mut tracker, &mut screen, b"\x1b]133;D;1\x07");
+    let completed = tracker.take_completed_since(0).expect("completed command");
+    assert_eq!(completed.output.len(), COMMAND_OUTPUT_LIMIT);
+    assert_eq!(completed.output, output[100..]);
+    assert_eq!(completed.exit_code, Some(1));
+}
@@ -2,6 +2,9 @@
 
 use super::state::TerminalSnapshot;
 use crate::terminal_engine::emulator::SnapshotMetrics;
+use crate::terminal_engine::filters::rules::sanitize::{
+  strip_control_sequences, strip_non_rendering_sequences,
+};
 use crate::platform::backend_passive_enabled;
 
 pub(super) fn snapshot_lines(snapshot: &TerminalSnapshot) -> Vec<String> {
@@ -30,14 +33,19 @@ pub(super) fn snapshot_text_with_history(snapshot: &TerminalSnapshot) -> Snapsho
   }
 }
 
+/// 行级快照归一化：剥离残留控制序列，空白行统一为空串。
+/// 约束：不增删行，确保行号与光标坐标仍可与前端视口对齐。
 pub(super) fn normalize_lines(lines: Vec<String>) -> Vec<String> {
-  // [TODO/terminal, 2026-01-26] 补齐 ANSI 清理与空行归一化逻辑，避免快照噪音影响语义。
   lines
+    .into_iter()
+    .map(|line| strip_control_sequences(&line).trim_end().to_string())
+    .collect()
 }
 
+/// ANSI 快照归一化：保留可重放的 CSI，剥离 OSC/DCS 等非渲染序列与游离控制字符。
+/// 约束：不改动换行，history 段末尾换行用于与可视区拼接。
 pub(super) fn normalize_text(text: String) -> String {
-  // [TODO/terminal, 2026-01-26] 统一 ANSI 清理与空行归一化规则，并与行级快照保持一致。
-  text
+  strip_non_rendering_sequences(&text)
 }
 
 pub(super) fn merge_semantic_lines(lines: &[String], _terminal_cols: u16) -> Vec<String> {
@@ -103,6 +111,7 @@ fn is_hard_break_line(raw: &str, trimmed: &str) -> bool {
     || is_table_line(trimmed)
     || is_separator_line(trimmed)
     || is_paragraph_start_line(trimmed)
+    || is_cli_marker_line(trimmed)
 }
 
 fn is_code_fence_line(line: &str) -> bool {
@@ -178,6 +187,15 @@ fn is_paragraph_start_line(line: &str) -> bool {
   false
 }
 
+// CLI 界面行：框线/竖条与回复、工具标记各自成行，不能并入上一段，否则过滤规则无法识别。
+fn is_cli_marker_line(line: &str) -> bool {
+  let Some(first) = line.trim_start().chars().next() else {
+    return false;
+  };
+  matches!(first as u32, 0x2500..=0x259f)
+    || matches!(first, '•' | '✦' | '⏺' | '●' | '⎿' | '⚙' | '✓' | '✗')
+}
+
 fn is_numbered_list_line(line: &str) -> bool {
   let bytes = line.as_bytes();
   let mut index = 0usize;
@@ -66,7 +66,7 @@ pub(crate) fn is_output_stable(session: &TerminalSession, force: bool, now: u64)
     }
     // Shell 未就绪前，必须等待超时或显式就绪
     if !session.shell_ready
-        && now.saturating_sub(session.created_at) < super::SHELL_READY_TIMEOUT_MS
+        && now.saturating_sub(session.created_at) < session.tuning.shell_ready_timeout_ms
     {
         return false;
     }
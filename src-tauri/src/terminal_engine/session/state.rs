@@ -9,7 +9,8 @@ use std::{
 use serde::Deserialize;
 
 use super::super::emulator::{
-    create_emulator_with_writer, EmulatorConfig, SnapshotSegments, TerminalEmulator,
+    create_emulator_with_writer, EmulatorBackend, EmulatorConfig, RowRangeSnapshot, SnapshotSegments,
+    TerminalEmulator,
 };
 use crate::ports::terminal_dispatch_gate::{
     default_terminal_dispatch_gate, TerminalDispatchGate,
@@ -20,8 +21,16 @@ use crate::ports::terminal_session::{
     default_terminal_session_repository, TerminalSessionRepository,
 };
 use crate::runtime::TerminalHandle;
-
+use crate::terminal_engine::default_members::onboarding::OnboardingContext;
+
+use super::clock::{Clock, SystemClock};
+use super::recording::SessionRecorder;
+use super::shell_marks::ShellTurnTracker;
+use super::approval_policy::ApprovalPolicy;
+use super::post_ready_script::PostReadyScript;
+use super::supervisor::{RestartPolicy, SupervisorState};
 use super::trigger::TriggerBus;
+use super::tuning::TerminalTuning;
 use super::{SemanticEvent, SESSION_SCROLLBACK_LINES};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -31,6 +40,10 @@ pub(super) enum TerminalSessionStatus {
     Online,
     Working,
     Offline,
+    // 空闲休眠：进程已关闭但保留快照与会话 ID，派发到达时原地唤醒。
+    Hibernating,
+    // 等待审批：CLI 停在权限确认提示，需要用户批准或拒绝后才会继续。
+    AwaitingInput,
 }
 
 impl TerminalSessionStatus {
@@ -40,6 +53,8 @@ impl TerminalSessionStatus {
             TerminalSessionStatus::Online => "online",
             TerminalSessionStatus::Working => "working",
             TerminalSessionStatus::Offline => "offline",
+            TerminalSessionStatus::Hibernating => "hibernating",
+            TerminalSessionStatus::AwaitingInput => "awaiting_input",
         }
     }
 }
@@ -57,6 +72,8 @@ pub(super) enum PostReadyState {
 pub(super) enum PostReadyMode {
     Invite,
     Disabled,
+    // 休眠唤醒：跳过邀请引导，仅等待就绪后解锁状态并投递排队消息。
+    Wake,
 }
 
 impl PostReadyMode {
@@ -68,13 +85,14 @@ impl PostReadyMode {
     }
 
     pub(super) fn should_run(self) -> bool {
-        matches!(self, PostReadyMode::Invite)
+        matches!(self, PostReadyMode::Invite | PostReadyMode::Wake)
     }
 
     pub(super) fn as_str(self) -> &'static str {
         match self {
             PostReadyMode::Invite => "invite",
             PostReadyMode::Disabled => "none",
+            PostReadyMode::Wake => "wake",
         }
     }
 }
@@ -94,10 +112,20 @@ pub(super) enum PostReadyAction {
         pattern: String,
         attempts: u32,
         started_at: Option<u64>,
+        // 脚本指定的超时；缺省时按内置的次数与时长上限。
+        timeout_ms: Option<u64>,
     },
     Introduction {
         prompt_type: String,
     },
+    /// 超时内出现特征字符串则插入 `then_items`，否则插入 `else_items`。
+    Branch {
+        pattern: String,
+        timeout_ms: u64,
+        started_at: Option<u64>,
+        then_items: Vec<PostReadyQueueItem>,
+        else_items: Vec<PostReadyQueueItem>,
+    },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -107,6 +135,7 @@ pub(super) struct OutputRateSample {
     pub bytes_total: u64,
 }
 
+#[derive(Clone, Debug)]
 pub(super) struct PostReadyQueueItem {
     pub(super) action: PostReadyAction,
     pub(super) require_stable: bool,
@@ -174,6 +203,7 @@ impl TerminalSnapshot {
             writer.map(|writer| Box::new(PtyResponseWriter { writer }) as Box<dyn Write + Send>);
         let emulator = create_emulator_with_writer(
             EmulatorConfig {
+                backend: EmulatorBackend::Wezterm,
                 rows,
                 cols,
                 scrollback_limit: SESSION_SCROLLBACK_LINES,
@@ -183,6 +213,21 @@ impl TerminalSnapshot {
         Self { emulator }
     }
 
+    /// 休眠快照：仅以冻结时的可视屏幕重建模拟器，释放回滚缓冲且不再回写 PTY。
+    pub(super) fn frozen(rows: u16, cols: u16, screen: &[u8]) -> Self {
+        let mut emulator = create_emulator_with_writer(
+            EmulatorConfig {
+                backend: EmulatorBackend::Wezterm,
+                rows,
+                cols,
+                scrollback_limit: usize::from(rows),
+            },
+            None,
+        );
+        emulator.apply_output(screen);
+        Self { emulator }
+    }
+
     pub(super) fn apply_output(&mut self, bytes: &[u8]) {
         self.emulator.apply_output(bytes);
     }
@@ -199,9 +244,35 @@ impl TerminalSnapshot {
         self.emulator.snapshot_lines()
     }
 
+    pub(super) fn bracketed_paste_enabled(&self) -> bool {
+        self.emulator.bracketed_paste_enabled()
+    }
+
     pub(super) fn cursor_position(&self) -> (u16, u16) {
         self.emulator.cursor_position()
     }
+
+    /// 光标的（绝对行号，列），用于标记语义块边界。
+    pub(super) fn absolute_cursor(&self) -> (u64, u16) {
+        (
+            self.emulator.absolute_cursor_row(),
+            self.emulator.cursor_position().1,
+        )
+    }
+
+    /// 语义块结束边界：光标停在非行首时当前行仍有内容，计入区间。
+    pub(super) fn block_end_row(&self) -> u64 {
+        let (row, col) = self.absolute_cursor();
+        if col > 0 {
+            row + 1
+        } else {
+            row
+        }
+    }
+
+    pub(super) fn rows_in_range(&self, start_row: u64, end_row: u64) -> Option<RowRangeSnapshot> {
+        self.emulator.rows_in_range(start_row, end_row)
+    }
 }
 
 // 通过响应写入器捕获终端查询响应，避免丢失 cursor 等信息。
@@ -230,6 +301,8 @@ impl Write for PtyResponseWriter {
 pub(crate) struct TerminalSession {
     pub(crate) id: String,
     pub(super) terminal_type: TerminalType,
+    // 非内置终端类型名（如自研 CLI），按 shell 启动，但据此加载配置中的过滤规则。
+    pub(super) custom_type: Option<String>,
     pub(super) status: TerminalSessionStatus,
     pub(crate) output_bytes_total: u64,
     pub(crate) output_seq: u64,
@@ -279,10 +352,15 @@ pub(crate) struct TerminalSession {
     pub(super) snapshot: TerminalSnapshot,
     pub(super) semantic_tx: Option<mpsc::Sender<SemanticEvent>>,
     pub(crate) keep_alive: bool,
+    // 项目工作区根目录：项目数据、路由规则与审计均按此读取；`launch_cwd` 仅用于进程 cwd、
+    // CLI 会话存储查找与沙箱绑定（成员独立工作树时二者不同）。
+    pub(crate) workspace_path: Option<String>,
     pub(crate) launch_cwd: Option<String>,
     pub(crate) launch_command: Option<String>,
     pub(crate) launch_path: Option<String>,
     pub(crate) launch_strict_shell: bool,
+    // 沙箱成员：重启时沿用同一隔离策略。
+    pub(crate) launch_sandboxed: bool,
     pub(crate) owner_window_label: Option<String>,
     pub(crate) output_window_label: Option<String>,
     pub(crate) shell_ready: bool,
@@ -299,6 +377,42 @@ pub(crate) struct TerminalSession {
     // 合并派发时记录所有 message_id，用于去重与确认回写。
     pub(super) dispatch_inflight_message_ids: Vec<String>,
     pub(super) dispatch_recent_message_ids: VecDeque<String>,
+    // asciicast 录制器：开启录制时旁路记录输出/输入/尺寸事件。
+    pub(super) recorder: Option<SessionRecorder>,
+    // 时序调优档位：注册时按终端类型与成员覆盖解析，生命周期内不变。
+    pub(super) tuning: TerminalTuning,
+    // shell 集成标记跟踪：记录 OSC 133/633 命令边界与最近一条完成命令的输出区间。
+    pub(super) shell_turns: ShellTurnTracker,
+    // 当前 chat 块开始时光标所在的绝对行，flush 时与结束行组成消息区间。
+    pub(super) chat_start_row: Option<u64>,
+    // 已写回消息对应的绝对行区间（按写回顺序，超出上限丢弃最早记录）。
+    pub(super) message_rows: VecDeque<MessageRowRange>,
+    // 崩溃重启策略：注册时按成员配置解析，生命周期内不变。
+    pub(super) restart_policy: RestartPolicy,
+    // 守护状态：记录窗口内的自动重启，用于退避与崩溃循环判定。
+    pub(super) supervisor: SupervisorState,
+    // 最近一次派发（含排队）时间，空闲休眠据此计时。
+    pub(super) last_dispatch_at: Option<u64>,
+    // 当前等待审批的提示文本（仅 AwaitingInput 状态下有值）。
+    pub(super) approval_prompt: Option<String>,
+    // 最近一次已作答的提示文本，提示从屏幕尾部消失前不再重复识别。
+    pub(super) approval_answered: Option<String>,
+    // 最近一次采集审批屏幕尾部时的 output_seq，输出未变化时不再重复采集。
+    pub(super) approval_checked_seq: u64,
+    // 审批策略：注册时按成员配置解析，生命周期内不变。
+    pub(super) approval_policy: ApprovalPolicy,
+    // 引导词上下文：注册时从项目数据解析成员、队友与模板，post_ready 引导步骤按当前语言渲染。
+    pub(super) onboarding: Option<OnboardingContext>,
+    // 成员自定义启动后脚本：注册前已校验，post_ready 启动时与内置流程拼接入队。
+    pub(super) post_ready_script: Option<PostReadyScript>,
+}
+
+/// 聊天消息与会话快照绝对行区间 `[start_row, end_row)` 的对应关系。
+#[derive(Clone, Debug)]
+pub(super) struct MessageRowRange {
+    pub(super) message_id: String,
+    pub(super) start_row: u64,
+    pub(super) end_row: u64,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -326,6 +440,13 @@ pub(crate) struct TerminalDispatchEnvelope {
     pub(crate) batched_message_ids: Vec<String>,
 }
 
+#[derive(Clone, Debug)]
+/// 成员会话派发负载：供成员池按空闲/排队长度挑选实例。
+pub(crate) struct MemberDispatchLoad {
+    pub(crate) idle: bool,
+    pub(crate) queued: usize,
+}
+
 #[derive(Clone, Debug)]
 pub(super) struct DispatchQueueItem {
     pub(super) envelope: TerminalDispatchEnvelope,
@@ -342,6 +463,8 @@ pub(crate) struct SessionRegistry {
     pub(super) trigger_bus: Option<Arc<TriggerBus>>,
     // 仅追踪 Working 会话，避免状态回落时全量扫描；仅供 Guardian 规则使用。
     pub(super) working_sessions: Arc<Mutex<HashSet<String>>>,
+    // 会话时钟：所有时序门禁与调度共用，测试可替换为手动时钟。
+    pub(super) clock: Arc<dyn Clock>,
 }
 
 /// 终端会话管理器：Tauri State 持有的全局入口。
@@ -359,6 +482,15 @@ pub(crate) struct TerminalManager {
         Mutex<Option<Arc<dyn crate::ports::settings::TerminalSettingsPort>>>,
 }
 
+impl TerminalSession {
+    /// 成员类型名：自定义/插件类型优先，否则为内置终端类型。
+    pub(super) fn member_type(&self) -> &str {
+        self.custom_type
+            .as_deref()
+            .unwrap_or_else(|| self.terminal_type.as_str())
+    }
+}
+
 impl Default for TerminalManager {
     fn default() -> Self {
         Self {
@@ -369,6 +501,7 @@ impl Default for TerminalManager {
                 pending_status_locks: HashSet::new(),
                 trigger_bus: None,
                 working_sessions: Arc::new(Mutex::new(HashSet::new())),
+                clock: Arc::new(SystemClock),
             })),
             message_pipeline: Mutex::new(default_terminal_message_pipeline()),
             event_port: Mutex::new(default_terminal_event_port()),
@@ -389,6 +522,17 @@ impl TerminalManager {
         }
     }
 
+    /// 注入会话时钟；仅应在创建会话前调用，避免同一会话混用两套时间基准。
+    #[cfg(test)]
+    pub(crate) fn set_clock(&self, clock: Arc<dyn Clock>) {
+        lock_sessions(&self.sessions).clock = clock;
+    }
+
+    /// 获取会话时钟快照，供调度线程在锁外读取时间。
+    pub(crate) fn clock(&self) -> Arc<dyn Clock> {
+        Arc::clone(&lock_sessions(&self.sessions).clock)
+    }
+
     pub(crate) fn set_settings_service(
         &self,
         service: Arc<dyn crate::ports::settings::TerminalSettingsPort>,
@@ -488,6 +632,45 @@ impl TerminalManager {
             .map(|session| session.id.clone())
     }
 
+    /// 读取成员会话所属的项目工作区路径（独立工作树成员也返回工作区根目录）。
+    pub(crate) fn find_member_workspace_path(
+        &self,
+        member_id: &str,
+        workspace_id: Option<&str>,
+    ) -> Option<String> {
+        let guard = lock_sessions(&self.sessions);
+        guard
+            .sessions
+            .values()
+            .find(|session| {
+                session.member_id.as_deref() == Some(member_id)
+                    && session.workspace_id.as_deref() == workspace_id
+            })
+            .and_then(|session| session.workspace_path.clone())
+    }
+
+    /// 读取成员会话的派发负载；仅 Online 且无在途派发视为空闲，Working 不算空闲。
+    pub(crate) fn member_dispatch_load(
+        &self,
+        member_id: &str,
+        workspace_id: Option<&str>,
+    ) -> Option<MemberDispatchLoad> {
+        let guard = lock_sessions(&self.sessions);
+        guard
+            .sessions
+            .values()
+            .find(|session| {
+                session.member_id.as_deref() == Some(member_id)
+                    && session.workspace_id.as_deref() == workspace_id
+            })
+            .map(|session| MemberDispatchLoad {
+                idle: session.status == TerminalSessionStatus::Online
+                    && !session.dispatch_inflight
+                    && session.dispatch_queue.is_empty(),
+                queued: session.dispatch_queue.len() + usize::from(session.dispatch_inflight),
+            })
+    }
+
     /// 读取终端成员 DND 状态，避免派发进入语义阻塞。
     pub(crate) fn is_terminal_dnd(&self, terminal_id: &str) -> bool {
         let guard = lock_sessions(&self.sessions);
@@ -521,3 +704,9 @@ pub(super) fn lock_sessions<'a>(
         }
     }
 }
+
+/// 在锁外读取会话时钟；已持锁的路径直接使用 `guard.clock`。
+pub(super) fn clock_now(sessions: &Arc<Mutex<SessionRegistry>>) -> u64 {
+    let clock = Arc::clone(&lock_sessions(sessions).clock);
+    clock.now_ms()
+}
@@ -0,0 +1,457 @@
+//! 成员会话守护：进程意外退出后按成员重启策略（never / on-failure / always）自动拉起。
+//! 重启间隔按指数退避增长，时间窗口内重启次数达到上限视为崩溃循环并停止守护；
+//! 重启命令复用 `apply_resume_command` 续接远端会话，每次重启都会以系统消息告知成员所在会话。
+
+use std::collections::{HashMap, VecDeque};
+use std::thread;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::message_service::chat_db::{
+    chat_append_system_message, chat_list_member_conversation_ids, ChatDbManager,
+};
+use crate::message_service::project_members::DEFAULT_OWNER_ID;
+use crate::platform::{diagnostics_log_backend_event, DiagnosticsState};
+use crate::terminal_engine::default_members::apply_resume_command;
+
+use super::{lock_sessions, relaunch_terminal_session, RelaunchOverrides, TerminalManager};
+
+const DEFAULT_MAX_RESTARTS: u32 = 5; // 崩溃循环上限：窗口内最多自动重启次数。
+const DEFAULT_WINDOW_MS: u64 = 10 * 60 * 1000; // 崩溃循环统计窗口。
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 1000; // 首次重启前的等待。
+const DEFAULT_MAX_BACKOFF_MS: u64 = 60 * 1000; // 退避上限，避免等待无限增长。
+const MIN_BACKOFF_MS: u64 = 100; // 退避下限，避免配置为 0 时忙等重启。
+
+const NOTICE_KEY_RESTARTED: &str = "chat.messages.system.supervisor.restarted";
+const NOTICE_KEY_RESUMED: &str = "chat.messages.system.supervisor.resumed";
+const NOTICE_KEY_RESTART_FAILED: &str = "chat.messages.system.supervisor.restartFailed";
+const NOTICE_KEY_GAVE_UP: &str = "chat.messages.system.supervisor.gaveUp";
+
+/// 重启模式：`on-failure` 仅在非零退出码或被信号终止时重启。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum RestartMode {
+    #[default]
+    Never,
+    OnFailure,
+    Always,
+}
+
+impl RestartMode {
+    fn from_str(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "never" | "" => Some(RestartMode::Never),
+            "on-failure" | "on_failure" | "onfailure" => Some(RestartMode::OnFailure),
+            "always" => Some(RestartMode::Always),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            RestartMode::Never => "never",
+            RestartMode::OnFailure => "on-failure",
+            RestartMode::Always => "always",
+        }
+    }
+}
+
+/// 成员级重启策略（项目数据 `members[].restartPolicy`），会话注册时解析一次。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct RestartPolicy {
+    pub(crate) mode: RestartMode,
+    pub(crate) max_restarts: u32,
+    pub(crate) window_ms: u64,
+    pub(crate) initial_backoff_ms: u64,
+    pub(crate) max_backoff_ms: u64,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            window_ms: DEFAULT_WINDOW_MS,
+            initial_backoff_ms: DEFAULT_INITIAL_BACKOFF_MS,
+            max_backoff_ms: DEFAULT_MAX_BACKOFF_MS,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RestartPolicyOverrides {
+    mode: Option<String>,
+    max_restarts: Option<u32>,
+    window_ms: Option<u64>,
+    initial_backoff_ms: Option<u64>,
+    max_backoff_ms: Option<u64>,
+}
+
+impl RestartPolicy {
+    /// 解析成员策略；格式错误或模式未知时记录日志并回退为不重启，避免误配导致重启风暴。
+    pub(crate) fn from_value(value: Option<&Value>) -> Self {
+        let Some(value) = value else {
+            return Self::default();
+        };
+        let overrides = match serde_json::from_value::<RestartPolicyOverrides>(value.clone()) {
+            Ok(overrides) => overrides,
+            Err(err) => {
+                log::warn!("terminal restart policy ignored: {err}");
+                return Self::default();
+            }
+        };
+        let mode = match overrides.mode.as_deref().map(RestartMode::from_str) {
+            Some(Some(mode)) => mode,
+            Some(None) => {
+                log::warn!(
+                    "terminal restart policy ignored: unknown mode {}",
+                    overrides.mode.as_deref().unwrap_or("")
+                );
+                return Self::default();
+            }
+            None => RestartMode::Never,
+        };
+        let defaults = Self::default();
+        let initial_backoff_ms = overrides
+            .initial_backoff_ms
+            .unwrap_or(defaults.initial_backoff_ms)
+            .max(MIN_BACKOFF_MS);
+        Self {
+            mode,
+            max_restarts: overrides.max_restarts.unwrap_or(defaults.max_restarts),
+            window_ms: overrides.window_ms.unwrap_or(defaults.window_ms),
+            initial_backoff_ms,
+            max_backoff_ms: overrides
+                .max_backoff_ms
+                .unwrap_or(defaults.max_backoff_ms)
+                .max(initial_backoff_ms),
+        }
+    }
+
+    fn should_restart(&self, failed: bool) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => failed,
+            RestartMode::Always => true,
+        }
+    }
+
+    /// 第 `attempt` 次重启（从 1 开始）前的等待：初始值逐次翻倍，不超过上限。
+    pub(crate) fn backoff_ms(&self, attempt: u32) -> u64 {
+        let shift = attempt.saturating_sub(1).min(32);
+        self.initial_backoff_ms
+            .saturating_mul(1u64 << shift)
+            .min(self.max_backoff_ms)
+    }
+}
+
+/// 守护决策：由退出观察线程在会话锁内计算，锁外执行。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RestartDecision {
+    Skip,
+    Restart { attempt: u32, delay_ms: u64 },
+    GiveUp { restarts: u32 },
+}
+
+/// 会话守护状态：记录窗口内的重启时间点，用于退避与崩溃循环判定。
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SupervisorState {
+    restarts: VecDeque<u64>,
+}
+
+impl SupervisorState {
+    /// 进程意外退出时调用；`failed` 表示非零退出码或被信号终止。
+    pub(crate) fn on_exit(&mut self, policy: &RestartPolicy, failed: bool, now: u64) -> RestartDecision {
+        if !policy.should_restart(failed) {
+            return RestartDecision::Skip;
+        }
+        let window_start = now.saturating_sub(policy.window_ms);
+        while self.restarts.front().is_some_and(|at| *at < window_start) {
+            self.restarts.pop_front();
+        }
+        let recent = self.restarts.len() as u32;
+        if recent >= policy.max_restarts {
+            return RestartDecision::GiveUp { restarts: recent };
+        }
+        self.restarts.push_back(now);
+        let attempt = recent + 1;
+        RestartDecision::Restart {
+            attempt,
+            delay_ms: policy.backoff_ms(attempt),
+        }
+    }
+}
+
+/// 会话退出时的守护上下文：用于重启与系统提示。
+pub(super) struct SupervisorExit {
+    pub(super) terminal_id: String,
+    pub(super) spawn_epoch: u64,
+    pub(super) workspace_id: Option<String>,
+    pub(super) member_id: Option<String>,
+    pub(super) member_name: Option<String>,
+    pub(super) policy: RestartPolicy,
+    pub(super) reason: String,
+    pub(super) decision: RestartDecision,
+}
+
+/// 执行守护决策：重启在退避后于独立线程进行，放弃时通知成员所在会话。
+pub(super) fn apply_restart_decision(app: &AppHandle, exit: SupervisorExit) {
+    match exit.decision {
+        RestartDecision::Skip => {}
+        RestartDecision::GiveUp { restarts } => {
+            log::warn!(
+                "terminal supervisor gave up terminal_id={} restarts={} window_ms={}",
+                exit.terminal_id,
+                restarts,
+                exit.policy.window_ms
+            );
+            let minutes = exit.policy.window_ms.div_ceil(60 * 1000).max(1);
+            post_member_notice(
+                app,
+                &exit,
+                NOTICE_KEY_GAVE_UP,
+                [
+                    ("count", restarts.to_string()),
+                    ("minutes", minutes.to_string()),
+                ],
+            );
+            log_supervisor_event(app, &exit, "gave_up", json!({ "restarts": restarts }));
+        }
+        RestartDecision::Restart { attempt, delay_ms } => {
+            let app = app.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(delay_ms));
+                restart_session(&app, &exit, attempt);
+            });
+        }
+    }
+}
+
+fn restart_session(app: &AppHandle, exit: &SupervisorExit, attempt: u32) {
+    let state = app.state::<TerminalManager>();
+    // 退避期间会话可能已被关闭或由人工重新打开，此时放弃本次重启。
+    let launch = {
+        let guard = lock_sessions(&state.sessions);
+        match guard.sessions.get(&exit.terminal_id) {
+            Some(session) if session.spawn_epoch == exit.spawn_epoch && session.handle.is_none() => Some((
+                session.member_type().to_string(),
+                session.launch_command.clone(),
+                session.remote_session_id.clone(),
+            )),
+            _ => None,
+        }
+    };
+    let Some((member_type, launch_command, remote_session_id)) = launch else {
+        log::info!(
+            "terminal supervisor restart skipped terminal_id={} reason=session_changed",
+            exit.terminal_id
+        );
+        return;
+    };
+    let session_id = remote_session_id.or_else(|| {
+        exit.workspace_id
+            .as_deref()
+            .zip(exit.member_id.as_deref())
+            .and_then(|(workspace_id, member_id)| {
+                state
+                    .session_repository()
+                    .get_terminal_session(workspace_id, member_id)
+                    .ok()
+                    .flatten()
+            })
+    });
+    let command = apply_resume_command(&member_type, launch_command.clone(), session_id.as_deref());
+    let resumed = session_id.is_some() && command != launch_command;
+    // 续接已有会话时本次启动不再重复执行邀请引导；未续接的重启仍按会话的启动后模式执行。
+    let result = relaunch_terminal_session(
+        app.clone(),
+        app.state::<TerminalManager>(),
+        &exit.terminal_id,
+        "supervisor_restart",
+        RelaunchOverrides {
+            command,
+            skip_post_ready: resumed,
+        },
+    );
+    match result {
+        Ok(()) => {
+            log::info!(
+                "terminal supervisor restarted terminal_id={} attempt={} resumed={}",
+                exit.terminal_id,
+                attempt,
+                resumed
+            );
+            let key = if resumed {
+                NOTICE_KEY_RESUMED
+            } else {
+                NOTICE_KEY_RESTARTED
+            };
+            post_member_notice(
+                app,
+                exit,
+                key,
+                [
+                    ("reason", exit.reason.clone()),
+                    ("attempt", attempt.to_string()),
+                    ("max", exit.policy.max_restarts.to_string()),
+                ],
+            );
+            log_supervisor_event(
+                app,
+                exit,
+                "restarted",
+                json!({ "attempt": attempt, "resumed": resumed }),
+            );
+        }
+        Err(err) => {
+            log::warn!(
+                "terminal supervisor restart failed terminal_id={} err={}",
+                exit.terminal_id,
+                err
+            );
+            post_member_notice(
+                app,
+                exit,
+                NOTICE_KEY_RESTART_FAILED,
+                [
+                    ("reason", exit.reason.clone()),
+                    ("attempt", attempt.to_string()),
+                    ("max", exit.policy.max_restarts.to_string()),
+                ],
+            );
+            log_supervisor_event(
+                app,
+                exit,
+                "restart_failed",
+                json!({ "attempt": attempt, "error": err }),
+            );
+            schedule_next_attempt(app, exit);
+        }
+    }
+}
+
+// 重启失败视同一次失败退出：按策略计算下一次尝试，崩溃循环上限同样生效。
+fn schedule_next_attempt(app: &AppHandle, exit: &SupervisorExit) {
+    let state = app.state::<TerminalManager>();
+    let decision = {
+        let mut guard = lock_sessions(&state.sessions);
+        let now = guard.clock.now_ms();
+        match guard.sessions.get_mut(&exit.terminal_id) {
+            Some(session) if session.spawn_epoch == exit.spawn_epoch && session.handle.is_none() => {
+                session.supervisor.on_exit(&session.restart_policy, true, now)
+            }
+            _ => RestartDecision::Skip,
+        }
+    };
+    apply_restart_decision(
+        app,
+        SupervisorExit {
+            terminal_id: exit.terminal_id.clone(),
+            spawn_epoch: exit.spawn_epoch,
+            workspace_id: exit.workspace_id.clone(),
+            member_id: exit.member_id.clone(),
+            member_name: exit.member_name.clone(),
+            policy: exit.policy,
+            reason: exit.reason.clone(),
+            decision,
+        },
+    );
+}
+
+/// 向成员参与的全部会话追加守护相关的系统提示。
+fn post_member_notice<const N: usize>(
+    app: &AppHandle,
+    exit: &SupervisorExit,
+    key: &str,
+    args: [(&str, String); N],
+) {
+    let (Some(workspace_id), Some(member_id)) = (exit.workspace_id.as_deref(), exit.member_id.as_deref()) else {
+        return;
+    };
+    let args = args
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    post_member_system_notice(
+        app,
+        workspace_id,
+        member_id,
+        exit.member_name.as_deref(),
+        key,
+        args,
+    );
+}
+
+/// 向成员参与的全部会话追加系统提示，自动补充 `name` 参数；单个会话失败只记录日志。
+pub(super) fn post_member_system_notice(
+    app: &AppHandle,
+    workspace_id: &str,
+    member_id: &str,
+    member_name: Option<&str>,
+    key: &str,
+    mut args: HashMap<String, String>,
+) {
+    let chat_db = app.state::<ChatDbManager>();
+    let conversation_ids =
+        match chat_list_member_conversation_ids(chat_db.inner(), workspace_id, member_id) {
+            Ok(ids) => ids,
+            Err(err) => {
+                log::warn!(
+                    "terminal member notice skipped member_id={} key={} err={}",
+                    member_id,
+                    key,
+                    err
+                );
+                return;
+            }
+        };
+    args.insert(
+        "name".to_string(),
+        member_name.unwrap_or(member_id).to_string(),
+    );
+    for conversation_id in conversation_ids {
+        if let Err(err) = chat_append_system_message(
+            app,
+            chat_db.inner(),
+            workspace_id,
+            &conversation_id,
+            key,
+            Some(args.clone()),
+            DEFAULT_OWNER_ID,
+        ) {
+            log::warn!(
+                "terminal member notice failed conversation_id={} key={} err={}",
+                conversation_id,
+                key,
+                err
+            );
+        }
+    }
+}
+
+fn log_supervisor_event(app: &AppHandle, exit: &SupervisorExit, outcome: &str, detail: Value) {
+    diagnostics_log_backend_event(
+        &app.state::<DiagnosticsState>(),
+        exit.member_id.clone(),
+        Some(exit.terminal_id.clone()),
+        None,
+        None,
+        exit.workspace_id.clone(),
+        "terminal_supervisor",
+        json!({
+          "terminalId": exit.terminal_id,
+          "memberId": exit.member_id,
+          "workspaceId": exit.workspace_id,
+          "mode": exit.policy.mode.as_str(),
+          "reason": exit.reason,
+          "outcome": outcome,
+          "detail": detail
+        }),
+    );
+}
+
+#[cfg(test)]
+mod policy_tests;
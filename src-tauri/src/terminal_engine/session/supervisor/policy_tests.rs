@@ -0,0 +1,77 @@
+use serde_json::json;
+
+use super::{RestartDecision, RestartMode, RestartPolicy, SupervisorState};
+
+fn on_failure_policy() -> RestartPolicy {
+    RestartPolicy::from_value(Some(&json!({
+        "mode": "on-failure",
+        "maxRestarts": 3,
+        "windowMs": 60_000,
+        "initialBackoffMs": 1000,
+        "maxBackoffMs": 3000
+    })))
+}
+
+#[test]
+fn missing_or_invalid_policy_never_restarts() {
+    assert_eq!(RestartPolicy::from_value(None).mode, RestartMode::Never);
+    let unknown = RestartPolicy::from_value(Some(&json!({ "mode": "sometimes" })));
+    assert_eq!(unknown.mode, RestartMode::Never);
+    let malformed = RestartPolicy::from_value(Some(&json!({ "mode": "always", "maxRestarts": "x" })));
+    assert_eq!(malformed.mode, RestartMode::Never);
+    let mut state = SupervisorState::default();
+    assert_eq!(
+        state.on_exit(&RestartPolicy::default(), true, 0),
+        RestartDecision::Skip
+    );
+}
+
+#[test]
+fn on_failure_ignores_clean_exit() {
+    let policy = on_failure_policy();
+    let mut state = SupervisorState::default();
+    assert_eq!(state.on_exit(&policy, false, 0), RestartDecision::Skip);
+    let always = RestartPolicy::from_value(Some(&json!({ "mode": "always" })));
+    assert!(matches!(
+        state.on_exit(&always, false, 0),
+        RestartDecision::Restart { attempt: 1, .. }
+    ));
+}
+
+#[test]
+fn backoff_doubles_until_cap_then_gives_up() {
+    let policy = on_failure_policy();
+    let mut state = SupervisorState::default();
+    assert_eq!(
+        state.on_exit(&policy, true, 0),
+        RestartDecision::Restart { attempt: 1, delay_ms: 1000 }
+    );
+    assert_eq!(
+        state.on_exit(&policy, true, 5_000),
+        RestartDecision::Restart { attempt: 2, delay_ms: 2000 }
+    );
+    assert_eq!(
+        state.on_exit(&policy, true, 10_000),
+        RestartDecision::Restart { attempt: 3, delay_ms: 3000 }
+    );
+    assert_eq!(
+        state.on_exit(&policy, true, 15_000),
+        RestartDecision::GiveUp { restarts: 3 }
+    );
+}
+
+#[test]
+fn restarts_outside_window_are_forgotten() {
+    let policy = on_failure_policy();
+    let mut state = SupervisorState::default();
+    for now in [0, 1_000, 2_000] {
+        assert!(matches!(
+            state.on_exit(&policy, true, now),
+            RestartDecision::Restart { .. }
+        ));
+    }
+    assert_eq!(
+        state.on_exit(&policy, true, 61_500),
+        RestartDecision::Restart { attempt: 2, delay_ms: 2000 }
+    );
+}
@@ -2,10 +2,7 @@
 // 约束：规则层只产出动作，不直接派发 TriggerEvent，避免形成隐形流程。
 
 use super::super::polling::RuleMask;
-use super::super::{
-    CHAT_IDLE_DEBOUNCE_MS, CHAT_PENDING_FORCE_FLUSH_MS, CHAT_SILENCE_TIMEOUT_MS,
-    POST_READY_STABLE_MS, STATUS_POLL_INTERVAL_MS,
-};
+use super::super::{APPROVAL_PROMPT_SETTLE_MS, STATUS_POLL_INTERVAL_MS};
 use super::{DeferredStage, FactEvent, GuardianEvent, ScheduledTrigger, TriggerEvent};
 
 #[derive(Clone, Debug)]
@@ -67,30 +64,38 @@ fn plan_fact(event: FactEvent) -> TriggerPlan {
         FactEvent::OutputUpdated {
             terminal_id,
             observed_at,
+            tuning,
         } => TriggerPlan {
             mask: RuleMask::POST_READY | RuleMask::SEMANTIC_FLUSH,
             targets: TriggerTargets::SessionIds(vec![terminal_id.clone()]),
             schedules: vec![
                 ScheduledTrigger::deferred(
-                    observed_at.saturating_add(POST_READY_STABLE_MS),
+                    observed_at.saturating_add(tuning.post_ready_stable_ms),
                     terminal_id.clone(),
                     RuleMask::POST_READY,
                     DeferredStage::Stable,
                 ),
                 ScheduledTrigger::deferred(
-                    observed_at.saturating_add(CHAT_SILENCE_TIMEOUT_MS),
+                    observed_at.saturating_add(tuning.chat_silence_timeout_ms),
                     terminal_id.clone(),
                     RuleMask::SEMANTIC_FLUSH,
                     DeferredStage::Silence,
                 ),
                 ScheduledTrigger::deferred(
                     observed_at
-                        .saturating_add(CHAT_SILENCE_TIMEOUT_MS)
-                        .saturating_add(CHAT_IDLE_DEBOUNCE_MS),
+                        .saturating_add(tuning.chat_silence_timeout_ms)
+                        .saturating_add(tuning.chat_idle_debounce_ms),
                     terminal_id.clone(),
                     RuleMask::SEMANTIC_FLUSH,
                     DeferredStage::Debounce,
                 ),
+                // 审批提示只在输出停顿后识别，避免把仍在重绘的界面当成等待确认。
+                ScheduledTrigger::deferred(
+                    observed_at.saturating_add(APPROVAL_PROMPT_SETTLE_MS),
+                    terminal_id.clone(),
+                    RuleMask::APPROVAL_PROMPT,
+                    DeferredStage::PromptSettle,
+                ),
             ],
         },
         FactEvent::ShellReady { terminal_id } => TriggerPlan {
@@ -101,26 +106,27 @@ fn plan_fact(event: FactEvent) -> TriggerPlan {
         FactEvent::ChatPending {
             terminal_id,
             observed_at,
+            tuning,
         } => TriggerPlan {
             mask: RuleMask::SEMANTIC_FLUSH,
             targets: TriggerTargets::SessionIds(vec![terminal_id.clone()]),
             schedules: vec![
                 ScheduledTrigger::deferred(
-                    observed_at.saturating_add(CHAT_SILENCE_TIMEOUT_MS),
+                    observed_at.saturating_add(tuning.chat_silence_timeout_ms),
                     terminal_id.clone(),
                     RuleMask::SEMANTIC_FLUSH,
                     DeferredStage::Silence,
                 ),
                 ScheduledTrigger::deferred(
                     observed_at
-                        .saturating_add(CHAT_SILENCE_TIMEOUT_MS)
-                        .saturating_add(CHAT_IDLE_DEBOUNCE_MS),
+                        .saturating_add(tuning.chat_silence_timeout_ms)
+                        .saturating_add(tuning.chat_idle_debounce_ms),
                     terminal_id.clone(),
                     RuleMask::SEMANTIC_FLUSH,
                     DeferredStage::Debounce,
                 ),
                 ScheduledTrigger::deferred(
-                    observed_at.saturating_add(CHAT_PENDING_FORCE_FLUSH_MS),
+                    observed_at.saturating_add(tuning.chat_pending_force_flush_ms),
                     terminal_id,
                     RuleMask::SEMANTIC_FLUSH,
                     DeferredStage::ChatPendingForce,
@@ -5,6 +5,7 @@ use std::collections::{BinaryHeap, HashMap};
 use std::sync::mpsc;
 
 use super::polling::RuleMask;
+use super::tuning::TerminalTuning;
 
 mod dispatcher;
 
@@ -12,9 +13,10 @@ pub(crate) use dispatcher::{plan_trigger, TriggerTargets};
 
 #[derive(Clone, Debug)]
 pub(crate) enum FactEvent {
-  OutputUpdated { terminal_id: String, observed_at: u64 },
+  // 事件携带会话调优档位，延迟触发按会话阈值排期而非全局常量。
+  OutputUpdated { terminal_id: String, observed_at: u64, tuning: TerminalTuning },
   ShellReady { terminal_id: String },
-  ChatPending { terminal_id: String, observed_at: u64 },
+  ChatPending { terminal_id: String, observed_at: u64, tuning: TerminalTuning },
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -24,6 +26,7 @@ pub(crate) enum DeferredStage {
   Debounce,
   PostReadyTick,
   ChatPendingForce,
+  PromptSettle,
 }
 
 #[derive(Clone, Debug)]
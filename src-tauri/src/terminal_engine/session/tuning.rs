@@ -0,0 +1,153 @@
+//! 会话时序调优：把 Working 回落、chat flush、post_ready 与流控阈值收敛为按终端类型的调优档位。
+//! 来源优先级：编译期默认值 < 全局设置（按终端类型） < 项目成员覆盖；会话注册时解析一次并固定。
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::ports::settings::TerminalSettingsPort;
+
+use super::{
+    CHAT_IDLE_DEBOUNCE_MS, CHAT_PENDING_FORCE_FLUSH_MS, CHAT_SILENCE_TIMEOUT_MS,
+    FLOW_CONTROL_HIGH_WATERMARK, FLOW_CONTROL_LOW_WATERMARK, POST_READY_STABLE_MS,
+    POST_READY_TICK_MS, REDRAW_SUPPRESSION_WINDOW_MS, SHELL_READY_ACTIVITY_MS,
+    SHELL_READY_TIMEOUT_MS, STATUS_IDLE_DEBOUNCE_MS, STATUS_WORKING_SILENCE_TIMEOUT_MS,
+    WORKING_INTENT_WINDOW_MS,
+};
+
+const TUNING_MIN_INTERVAL_MS: u64 = 50; // 时间阈值下限，避免配置为 0 导致每次输出都触发回落/flush。
+const TUNING_MIN_WATERMARK: usize = 1024; // 流控低水位下限，避免频繁暂停/恢复读取。
+
+/// 单个会话生效的时序与流控参数。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TerminalTuning {
+    pub(crate) working_silence_timeout_ms: u64,
+    pub(crate) status_idle_debounce_ms: u64,
+    pub(crate) working_intent_window_ms: u64,
+    pub(crate) chat_silence_timeout_ms: u64,
+    pub(crate) chat_idle_debounce_ms: u64,
+    pub(crate) chat_pending_force_flush_ms: u64,
+    pub(crate) redraw_suppression_window_ms: u64,
+    pub(crate) post_ready_stable_ms: u64,
+    pub(crate) post_ready_tick_ms: u64,
+    pub(crate) shell_ready_timeout_ms: u64,
+    pub(crate) shell_ready_activity_ms: u64,
+    pub(crate) flow_control_high_watermark: usize,
+    pub(crate) flow_control_low_watermark: usize,
+}
+
+impl Default for TerminalTuning {
+    fn default() -> Self {
+        Self {
+            working_silence_timeout_ms: STATUS_WORKING_SILENCE_TIMEOUT_MS,
+            status_idle_debounce_ms: STATUS_IDLE_DEBOUNCE_MS,
+            working_intent_window_ms: WORKING_INTENT_WINDOW_MS,
+            chat_silence_timeout_ms: CHAT_SILENCE_TIMEOUT_MS,
+            chat_idle_debounce_ms: CHAT_IDLE_DEBOUNCE_MS,
+            chat_pending_force_flush_ms: CHAT_PENDING_FORCE_FLUSH_MS,
+            redraw_suppression_window_ms: REDRAW_SUPPRESSION_WINDOW_MS,
+            post_ready_stable_ms: POST_READY_STABLE_MS,
+            post_ready_tick_ms: POST_READY_TICK_MS,
+            shell_ready_timeout_ms: SHELL_READY_TIMEOUT_MS,
+            shell_ready_activity_ms: SHELL_READY_ACTIVITY_MS,
+            flow_control_high_watermark: FLOW_CONTROL_HIGH_WATERMARK,
+            flow_control_low_watermark: FLOW_CONTROL_LOW_WATERMARK,
+        }
+    }
+}
+
+/// 可选覆盖项：全局设置 `terminalTuning.<type>` 与成员 `terminalTuning` 共用同一结构。
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TerminalTuningOverrides {
+    working_silence_timeout_ms: Option<u64>,
+    status_idle_debounce_ms: Option<u64>,
+    working_intent_window_ms: Option<u64>,
+    chat_silence_timeout_ms: Option<u64>,
+    chat_idle_debounce_ms: Option<u64>,
+    chat_pending_force_flush_ms: Option<u64>,
+    redraw_suppression_window_ms: Option<u64>,
+    post_ready_stable_ms: Option<u64>,
+    post_ready_tick_ms: Option<u64>,
+    shell_ready_timeout_ms: Option<u64>,
+    shell_ready_activity_ms: Option<u64>,
+    flow_control_high_watermark: Option<usize>,
+    flow_control_low_watermark: Option<usize>,
+}
+
+impl TerminalTuningOverrides {
+    /// 解析覆盖对象；格式错误时记录日志并忽略，避免一处配置错误阻断终端创建。
+    fn from_value(value: &Value) -> Option<Self> {
+        match serde_json::from_value::<Self>(value.clone()) {
+            Ok(overrides) => Some(overrides),
+            Err(err) => {
+                log::warn!("terminal tuning override ignored: {err}");
+                None
+            }
+        }
+    }
+
+    fn apply(&self, tuning: &mut TerminalTuning) {
+        fn set<T: Copy>(target: &mut T, value: Option<T>) {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+        set(&mut tuning.working_silence_timeout_ms, self.working_silence_timeout_ms);
+        set(&mut tuning.status_idle_debounce_ms, self.status_idle_debounce_ms);
+        set(&mut tuning.working_intent_window_ms, self.working_intent_window_ms);
+        set(&mut tuning.chat_silence_timeout_ms, self.chat_silence_timeout_ms);
+        set(&mut tuning.chat_idle_debounce_ms, self.chat_idle_debounce_ms);
+        set(&mut tuning.chat_pending_force_flush_ms, self.chat_pending_force_flush_ms);
+        set(&mut tuning.redraw_suppression_window_ms, self.redraw_suppression_window_ms);
+        set(&mut tuning.post_ready_stable_ms, self.post_ready_stable_ms);
+        set(&mut tuning.post_ready_tick_ms, self.post_ready_tick_ms);
+        set(&mut tuning.shell_ready_timeout_ms, self.shell_ready_timeout_ms);
+        set(&mut tuning.shell_ready_activity_ms, self.shell_ready_activity_ms);
+        set(&mut tuning.flow_control_high_watermark, self.flow_control_high_watermark);
+        set(&mut tuning.flow_control_low_watermark, self.flow_control_low_watermark);
+    }
+}
+
+impl TerminalTuning {
+    // 归一化：时间阈值设下限，水位保持 low < high，强制 flush 不早于静默门禁。
+    fn normalized(mut self) -> Self {
+        for value in [
+            &mut self.working_silence_timeout_ms,
+            &mut self.chat_silence_timeout_ms,
+            &mut self.post_ready_tick_ms,
+        ] {
+            *value = (*value).max(TUNING_MIN_INTERVAL_MS);
+        }
+        self.chat_pending_force_flush_ms = self
+            .chat_pending_force_flush_ms
+            .max(self.chat_silence_timeout_ms.saturating_add(self.chat_idle_debounce_ms));
+        self.shell_ready_timeout_ms = self.shell_ready_timeout_ms.max(self.shell_ready_activity_ms);
+        self.flow_control_low_watermark = self.flow_control_low_watermark.max(TUNING_MIN_WATERMARK);
+        if self.flow_control_high_watermark <= self.flow_control_low_watermark {
+            self.flow_control_high_watermark = self.flow_control_low_watermark.saturating_mul(2);
+        }
+        self
+    }
+}
+
+/// 解析会话调优档位：默认值 -> 全局设置中该终端类型的覆盖 -> 成员覆盖。
+pub(crate) fn resolve_terminal_tuning(
+    terminal_type: &str,
+    settings_service: Option<&dyn TerminalSettingsPort>,
+    member_overrides: Option<&Value>,
+) -> TerminalTuning {
+    let mut tuning = TerminalTuning::default();
+    let global_overrides = settings_service
+        .and_then(|service| service.get_terminal_tuning(terminal_type))
+        .and_then(|value| TerminalTuningOverrides::from_value(&value));
+    if let Some(overrides) = global_overrides {
+        overrides.apply(&mut tuning);
+    }
+    if let Some(overrides) = member_overrides.and_then(TerminalTuningOverrides::from_value) {
+        overrides.apply(&mut tuning);
+    }
+    tuning.normalized()
+}
+
+#[cfg(test)]
+mod tuning_tests;
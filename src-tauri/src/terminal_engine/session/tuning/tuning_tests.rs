@@ -0,0 +1,105 @@
+use serde_json::{json, Value};
+
+use super::{resolve_terminal_tuning, TerminalTuning, TUNING_MIN_INTERVAL_MS, TUNING_MIN_WATERMARK};
+use crate::ports::settings::TerminalSettingsPort;
+
+/// 全局设置替身：只提供 codex 类型的调优覆盖。
+struct CodexTuningSettings(Value);
+
+impl TerminalSettingsPort for CodexTuningSettings {
+    fn get_language(&self) -> Option<String> {
+        None
+    }
+
+    fn get_chat_stream_enabled(&self) -> Option<bool> {
+        None
+    }
+
+    fn get_terminal_tuning(&self, terminal_type: &str) -> Option<Value> {
+        (terminal_type == "codex").then(|| self.0.clone())
+    }
+
+    fn get_terminal_filter_rules(&self, _terminal_type: &str) -> Option<Value> {
+        None
+    }
+
+    fn get_member_hibernate_after_minutes(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[test]
+fn member_overrides_beat_global_settings_which_beat_defaults() {
+    let settings = CodexTuningSettings(json!({
+        "workingSilenceTimeoutMs": 9000,
+        "chatSilenceTimeoutMs": 7000
+    }));
+    let member = json!({ "chatSilenceTimeoutMs": 3000 });
+    let tuning = resolve_terminal_tuning("codex", Some(&settings), Some(&member));
+    assert_eq!(tuning.working_silence_timeout_ms, 9000);
+    assert_eq!(tuning.chat_silence_timeout_ms, 3000);
+    assert_eq!(
+        tuning.status_idle_debounce_ms,
+        TerminalTuning::default().status_idle_debounce_ms
+    );
+
+    // 全局覆盖按终端类型生效，其他类型仍取默认值。
+    let tuning = resolve_terminal_tuning("claude", Some(&settings), None);
+    assert_eq!(tuning, TerminalTuning::default().normalized());
+}
+
+#[test]
+fn malformed_overrides_are_ignored() {
+    let settings = CodexTuningSettings(json!({ "workingSilenceTimeoutMs": "slow" }));
+    let member = json!({ "chatSilenceTimeoutMs": 3000 });
+    let tuning = resolve_terminal_tuning("codex", Some(&settings), Some(&member));
+    assert_eq!(
+        tuning.working_silence_timeout_ms,
+        TerminalTuning::default().working_silence_timeout_ms
+    );
+    assert_eq!(tuning.chat_silence_timeout_ms, 3000);
+}
+
+#[test]
+fn intervals_are_clamped_to_the_lower_bound() {
+    let member = json!({
+        "workingSilenceTimeoutMs": 0,
+        "chatSilenceTimeoutMs": 1,
+        "postReadyTickMs": 0,
+        "chatIdleDebounceMs": 0,
+        "chatPendingForceFlushMs": 0
+    });
+    let tuning = resolve_terminal_tuning("codex", None, Some(&member));
+    assert_eq!(tuning.working_silence_timeout_ms, TUNING_MIN_INTERVAL_MS);
+    assert_eq!(tuning.chat_silence_timeout_ms, TUNING_MIN_INTERVAL_MS);
+    assert_eq!(tuning.post_ready_tick_ms, TUNING_MIN_INTERVAL_MS);
+    // 强制 flush 不早于静默门禁与空闲防抖之和。
+    assert_eq!(tuning.chat_pending_force_flush_ms, TUNING_MIN_INTERVAL_MS);
+
+    let member = json!({ "shellReadyTimeoutMs": 100, "shellReadyActivityMs": 500 });
+    let tuning = resolve_terminal_tuning("codex", None, Some(&member));
+    assert_eq!(tuning.shell_ready_timeout_ms, 500);
+}
+
+#[test]
+fn huge_flush_gates_saturate_instead_of_overflowing() {
+    let member = json!({
+        "chatSilenceTimeoutMs": u64::MAX,
+        "chatIdleDebounceMs": u64::MAX
+    });
+    let tuning = resolve_terminal_tuning("codex", None, Some(&member));
+    assert_eq!(tuning.chat_pending_force_flush_ms, u64::MAX);
+}
+
+#[test]
+fn watermarks_are_repaired() {
+    let member = json!({ "flowControlLowWatermark": 10, "flowControlHighWatermark": 5 });
+    let tuning = resolve_terminal_tuning("codex", None, Some(&member));
+    assert_eq!(tuning.flow_control_low_watermark, TUNING_MIN_WATERMARK);
+    assert_eq!(tuning.flow_control_high_watermark, TUNING_MIN_WATERMARK * 2);
+
+    let member = json!({ "flowControlLowWatermark": 8192, "flowControlHighWatermark": 4096 });
+    let tuning = resolve_terminal_tuning("codex", None, Some(&member));
+    assert_eq!(tuning.flow_control_low_watermark, 8192);
+    assert_eq!(tuning.flow_control_high_watermark, 16384);
+}
@@ -19,17 +19,25 @@ pub(crate) fn export_commands() -> impl Fn(Invoke) -> bool + Send + Sync + 'stat
     app::workspace_selection_open_window,
     terminal::terminal_create,
     terminal::terminal_list_environments,
+    terminal::terminal_list_member_types,
     terminal::terminal_attach,
     terminal::terminal_write,
+    terminal::terminal_send_keys,
+    terminal::terminal_respond_approval,
     terminal::terminal_ack,
     terminal::terminal_set_active,
     terminal::terminal_emit_status,
     terminal::terminal_set_member_status,
     terminal::terminal_dispatch,
     terminal::terminal_resize,
+    terminal::terminal_recording_start,
+    terminal::terminal_recording_stop,
+    terminal::terminal_recording_list,
+    terminal::terminal_recording_replay,
     terminal::terminal_close,
     terminal::terminal_list_statuses,
     terminal::terminal_snapshot_lines,
+    terminal::terminal_message_rows,
     terminal::terminal_snapshot_text,
     terminal::terminal_dump_snapshot_lines,
     notification::notification_update_state,
@@ -61,6 +69,8 @@ pub(crate) fn export_commands() -> impl Fn(Invoke) -> bool + Send + Sync + 'stat
     message::chat_mark_conversation_read_latest,
     message::chat_send_message,
     message::chat_send_message_and_dispatch,
+    message::chat_list_dead_letters,
+    message::chat_replay_dead_letters,
     message::chat_create_group,
     message::chat_ensure_direct,
     message::chat_set_conversation_settings,
@@ -88,6 +98,10 @@ pub(crate) fn export_commands() -> impl Fn(Invoke) -> bool + Send + Sync + 'stat
     project_data::project_data_write,
     project_members::project_members_invite,
     project_members::project_members_purge_terminal,
+    project_members::project_member_worktrees,
+    project_members::project_member_worktree_diff_get,
+    project_members::project_member_worktree_merge_apply,
+    project_members::project_member_worktree_discard_apply,
     project_skills::project_skills_list,
     project_skills::project_skills_link,
     project_skills::project_skills_unlink,
@@ -4,8 +4,8 @@ use tauri::{AppHandle, State};
 
 use crate::application::chat as chat_app;
 use crate::message_service::chat_db::{
-  ChatClearResult, ChatDbManager, ChatHomeFeedDto, ChatRepairResult, ConversationSummaryDto,
-  MessageAttachment, MessageContent, MessageDto,
+  ChatClearResult, ChatDbManager, ChatHomeFeedDto, ChatInboxDeadLetterDto, ChatRepairResult,
+  ConversationSummaryDto, MessageAttachment, MessageContent, MessageDto,
 };
 use crate::contracts::chat_dispatch::ChatDispatchPayload;
 
@@ -100,7 +100,24 @@ pub(crate) fn chat_send_message_and_dispatch(
   chat_state: State<'_, ChatDbManager>,
   payload: ChatDispatchPayload,
 ) -> Result<MessageDto, String> {
-  chat_app::chat_send_message_and_enqueue(app, chat_state, payload)
+  chat_app::chat_send_message_and_enqueue(app, chat_state, payload, None)
+}
+
+#[tauri::command]
+pub(crate) fn chat_list_dead_letters(
+  state: State<'_, ChatDbManager>,
+  workspace_id: String,
+) -> Result<Vec<ChatInboxDeadLetterDto>, String> {
+  chat_app::chat_list_dead_letters(state, workspace_id)
+}
+
+#[tauri::command]
+pub(crate) fn chat_replay_dead_letters(
+  state: State<'_, ChatDbManager>,
+  workspace_id: String,
+  task_ids: Option<Vec<String>>,
+) -> Result<u32, String> {
+  chat_app::chat_replay_dead_letters(state, workspace_id, task_ids)
 }
 
 #[tauri::command]
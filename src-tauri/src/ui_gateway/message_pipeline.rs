@@ -1,14 +1,21 @@
 //! 消息端口适配：将 Tauri 传输与存储绑定到消息流水线。
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::contracts::terminal_message::TerminalMessagePayload;
-use crate::message_service::chat_db::{chat_append_terminal_message, ChatDbManager};
+use crate::message_service::chat_db::{
+  chat_append_system_message, chat_append_terminal_message, chat_inbox_enqueue,
+  chat_inbox_mark_delivered, chat_inbox_mark_failed, ChatDbManager, ChatInboxPayload,
+  ChatTerminalAppend,
+};
 use crate::message_service::pipeline;
+use crate::orchestration::routing_rules::{route_terminal_final, TerminalFinalRoute};
 use crate::ports::message_service::{
-  TerminalMessageAppendResult, TerminalMessageRepository, TerminalMessageTransport,
+  TerminalInboundMessage, TerminalMessageAppendResult, TerminalMessageRepository,
+  TerminalMessageTransport,
 };
 use crate::ports::terminal_message::TerminalMessagePipeline;
 
@@ -41,26 +48,103 @@ impl UiMessageRepository {
 
 impl TerminalMessageRepository for UiMessageRepository {
   fn append_terminal_message(
+    &self,
+    message: &TerminalInboundMessage,
+    message_id: Option<&str>,
+  ) -> Result<TerminalMessageAppendResult, String> {
+    let state = self.app.state::<ChatDbManager>();
+    let appended = chat_append_terminal_message(
+      &self.app,
+      state.inner(),
+      ChatTerminalAppend {
+        workspace_id: message.workspace_id.as_str(),
+        conversation_id: message.conversation_id.as_str(),
+        member_id: message.member_id.as_str(),
+        content: message.content.clone(),
+        viewer_id: message.viewer_id.as_str(),
+        span_id: message.span_id.as_deref(),
+        message_id,
+      },
+    )?;
+    // 已落库的重试只确认结果，路由在首次落库时已执行。
+    if appended.created {
+      route_terminal_final(
+        &self.app,
+        TerminalFinalRoute {
+          workspace_id: message.workspace_id.as_str(),
+          conversation_id: message.conversation_id.as_str(),
+          member_id: message.member_id.as_str(),
+          message_id: appended.message_id.as_str(),
+          content: message.content.as_str(),
+        },
+      );
+    }
+    Ok(TerminalMessageAppendResult::persisted(appended.message_id))
+  }
+
+  fn enqueue_terminal_inbound(
+    &self,
+    message: &TerminalInboundMessage,
+    lease_ms: u64,
+  ) -> Result<String, String> {
+    let state = self.app.state::<ChatDbManager>();
+    let task = chat_inbox_enqueue(
+      state.inner(),
+      message.workspace_id.as_str(),
+      ChatInboxPayload {
+        conversation_id: message.conversation_id.clone(),
+        member_id: message.member_id.clone(),
+        viewer_id: message.viewer_id.clone(),
+        content: message.content.clone(),
+        span_id: message.span_id.clone(),
+      },
+      lease_ms,
+    )?;
+    Ok(task.task_id)
+  }
+
+  fn mark_terminal_inbound_delivered(&self, workspace_id: &str, task_id: &str) -> Result<(), String> {
+    let state = self.app.state::<ChatDbManager>();
+    chat_inbox_mark_delivered(state.inner(), workspace_id, task_id)
+  }
+
+  fn mark_terminal_inbound_failed(
+    &self,
+    workspace_id: &str,
+    task_id: &str,
+    next_attempt_at: u64,
+    error: &str,
+    mark_dead: bool,
+  ) -> Result<(), String> {
+    let state = self.app.state::<ChatDbManager>();
+    chat_inbox_mark_failed(
+      state.inner(),
+      workspace_id,
+      task_id,
+      next_attempt_at,
+      error,
+      mark_dead,
+    )
+  }
+
+  fn append_system_message(
     &self,
     workspace_id: &str,
     conversation_id: &str,
-    member_id: &str,
-    content: String,
+    key: &str,
+    args: Option<HashMap<String, String>>,
     viewer_id: &str,
-    span_id: Option<&str>,
-  ) -> Result<TerminalMessageAppendResult, String> {
+  ) -> Result<String, String> {
     let state = self.app.state::<ChatDbManager>();
-    let message_id = chat_append_terminal_message(
+    chat_append_system_message(
       &self.app,
       state.inner(),
       workspace_id,
       conversation_id,
-      member_id,
-      content,
+      key,
+      args,
       viewer_id,
-      span_id,
-    )?;
-    Ok(TerminalMessageAppendResult::persisted(message_id))
+    )
   }
 }
 
@@ -31,6 +31,7 @@ pub(crate) use crate::terminal_engine::{
   cleanup_ephemeral_sessions_for_window,
   has_active_sessions,
   shutdown_sessions,
+  spawn_hibernation_sweeper,
   spawn_snapshot_dumper,
   spawn_status_poller,
 };
@@ -1,5 +1,6 @@
 //! 项目成员命令：将成员创建下沉到后端统一处理。
 
+use serde_json::Value;
 use tauri::{AppHandle, Manager};
 
 use crate::application::chat as chat_app;
@@ -8,6 +9,11 @@ use crate::application::project::{
     ProjectInviteMembersResult, ProjectPurgeTerminalMembersRequest,
     ProjectPurgeTerminalMembersResult,
 };
+use crate::application::worktree::{
+    project_member_worktree_diff, project_member_worktree_discard, project_member_worktree_merge,
+    project_member_worktrees_list, MemberWorktreeDiff, MemberWorktreeInfo,
+    MemberWorktreeMergeResult,
+};
 use crate::message_service::chat_db::ChatDbManager;
 use crate::runtime::StorageManager;
 use crate::terminal_engine::session::{terminal_close_by_member_ids, TerminalManager};
@@ -75,3 +81,68 @@ pub(crate) fn project_members_purge_terminal(
     }
     Ok(outcome.result)
 }
+
+#[tauri::command]
+/// 列出成员工作树。
+/// 输入：workspaceId。
+/// 输出：登记了工作树的成员及其分支、脏状态与领先提交数。
+/// 错误：工作区解析或项目数据读取失败。
+pub(crate) fn project_member_worktrees(
+    app: AppHandle,
+    workspace_id: String,
+) -> Result<Vec<MemberWorktreeInfo>, String> {
+    let storage = app.state::<StorageManager>();
+    project_member_worktrees_list(storage.inner(), &workspace_id)
+}
+
+#[tauri::command]
+/// 查看成员工作树相对基线分支的改动。
+/// 输入：workspaceId 与 memberId。
+/// 输出：改动文件列表与统一 diff 文本。
+/// 错误：成员无工作树、目录缺失或 git 执行失败。
+pub(crate) fn project_member_worktree_diff_get(
+    app: AppHandle,
+    workspace_id: String,
+    member_id: String,
+) -> Result<MemberWorktreeDiff, String> {
+    let storage = app.state::<StorageManager>();
+    project_member_worktree_diff(storage.inner(), &workspace_id, &member_id)
+}
+
+#[tauri::command]
+/// 将成员分支合并回基线分支（未提交改动会先自动提交）。
+/// 输入：workspaceId 与 memberId。
+/// 输出：合并提交信息。
+/// 错误：工作区未检出基线分支、存在冲突或 git 执行失败。
+pub(crate) fn project_member_worktree_merge_apply(
+    app: AppHandle,
+    workspace_id: String,
+    member_id: String,
+) -> Result<MemberWorktreeMergeResult, String> {
+    let storage = app.state::<StorageManager>();
+    project_member_worktree_merge(storage.inner(), &workspace_id, &member_id)
+}
+
+#[tauri::command]
+/// 丢弃成员工作树与分支；先关闭成员终端，避免进程仍在被删除的目录中运行。
+/// 输入：workspaceId 与 memberId。
+/// 输出：更新后的成员列表。
+/// 错误：成员无工作树或 git 执行失败。
+pub(crate) fn project_member_worktree_discard_apply(
+    app: AppHandle,
+    workspace_id: String,
+    member_id: String,
+) -> Result<Vec<Value>, String> {
+    let terminal_state = app.state::<TerminalManager>();
+    for warning in terminal_close_by_member_ids(
+        app.clone(),
+        terminal_state,
+        &workspace_id,
+        std::slice::from_ref(&member_id),
+        true,
+    ) {
+        log::warn!("member worktree discard: {warning}");
+    }
+    let storage = app.state::<StorageManager>();
+    project_member_worktree_discard(storage.inner(), &workspace_id, &member_id)
+}
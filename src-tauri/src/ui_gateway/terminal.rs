@@ -8,8 +8,11 @@ use tauri::{AppHandle, State, WebviewWindow};
 use crate::now_millis;
 use crate::platform::resolve_log_dir;
 use crate::runtime::{list_terminal_environments, TerminalEnvironmentOption};
-use crate::terminal_engine::models::{TerminalSnapshotLinesPayload, TerminalSnapshotPayload, TerminalStatusPayload};
-use crate::terminal_engine::session::{self, TerminalDispatchContext};
+use crate::terminal_engine::models::{
+  TerminalMemberTypePayload, TerminalMessageRowsPayload, TerminalRecordingReplayPayload,
+  TerminalSnapshotLinesPayload, TerminalSnapshotPayload, TerminalStatusPayload,
+};
+use crate::terminal_engine::session::{self, TerminalCreateOptions, TerminalDispatchContext};
 use crate::terminal_engine::TerminalManager;
 
 #[tauri::command]
@@ -17,46 +20,9 @@ pub(crate) fn terminal_create(
   app: AppHandle,
   window: WebviewWindow,
   state: State<'_, TerminalManager>,
-  cols: Option<u16>,
-  rows: Option<u16>,
-  cwd: Option<String>,
-  member_id: Option<String>,
-  workspace_id: Option<String>,
-  keep_alive: Option<bool>,
-  terminal_id: Option<String>,
-  terminal_type: Option<String>,
-  terminal_command: Option<String>,
-  terminal_path: Option<String>,
-  strict_shell: Option<bool>,
-  post_ready_mode: Option<String>,
-  member_name: Option<String>,
-  default_command: Option<String>,
-  invite_instance_count: Option<u32>,
-  invite_unlimited_access: Option<bool>,
-  invite_sandboxed: Option<bool>,
+  options: TerminalCreateOptions,
 ) -> Result<String, String> {
-  session::terminal_create(
-    app,
-    window,
-    state,
-    cols,
-    rows,
-    cwd,
-    member_id,
-    workspace_id,
-    keep_alive,
-    terminal_id,
-    terminal_type,
-    terminal_command,
-    terminal_path,
-    strict_shell,
-    post_ready_mode,
-    member_name,
-    default_command,
-    invite_instance_count,
-    invite_unlimited_access,
-    invite_sandboxed,
-  )
+  session::terminal_create(app, window, state, options)
 }
 
 #[tauri::command]
@@ -64,6 +30,11 @@ pub(crate) fn terminal_list_environments() -> Vec<TerminalEnvironmentOption> {
   list_terminal_environments()
 }
 
+#[tauri::command]
+pub(crate) fn terminal_list_member_types() -> Vec<TerminalMemberTypePayload> {
+  session::terminal_list_member_types()
+}
+
 #[tauri::command]
 pub(crate) fn terminal_attach(
   window: WebviewWindow,
@@ -82,6 +53,17 @@ pub(crate) fn terminal_snapshot_lines(
   session::terminal_snapshot_lines(app, state, terminal_id)
 }
 
+#[tauri::command]
+pub(crate) fn terminal_message_rows(
+  state: State<'_, TerminalManager>,
+  terminal_id: String,
+  message_id: Option<String>,
+  start_row: Option<u64>,
+  end_row: Option<u64>,
+) -> Result<TerminalMessageRowsPayload, String> {
+  session::terminal_message_rows(state, terminal_id, message_id, start_row, end_row)
+}
+
 #[tauri::command]
 pub(crate) fn terminal_snapshot_text(
   app: AppHandle,
@@ -144,6 +126,25 @@ pub(crate) fn terminal_write(
   session::terminal_write(app, state, terminal_id, data)
 }
 
+#[tauri::command]
+pub(crate) fn terminal_send_keys(
+  app: AppHandle,
+  state: State<'_, TerminalManager>,
+  terminal_id: String,
+  keys: String,
+) -> Result<(), String> {
+  session::terminal_send_keys(app, state, terminal_id, keys)
+}
+
+#[tauri::command]
+pub(crate) fn terminal_respond_approval(
+  app: AppHandle,
+  terminal_id: String,
+  approve: bool,
+) -> Result<(), String> {
+  session::terminal_respond_approval(app, terminal_id, approve)
+}
+
 #[tauri::command]
 pub(crate) fn terminal_set_active(
   state: State<'_, TerminalManager>,
@@ -209,6 +210,37 @@ pub(crate) fn terminal_resize(
   session::terminal_resize(app, state, terminal_id, cols, rows)
 }
 
+#[tauri::command]
+pub(crate) fn terminal_recording_start(
+  state: State<'_, TerminalManager>,
+  terminal_id: String,
+  workspace_path: Option<String>,
+) -> Result<String, String> {
+  session::terminal_recording_start(state, terminal_id, workspace_path)
+}
+
+#[tauri::command]
+pub(crate) fn terminal_recording_stop(
+  state: State<'_, TerminalManager>,
+  terminal_id: String,
+) -> Result<Option<String>, String> {
+  session::terminal_recording_stop(state, terminal_id)
+}
+
+#[tauri::command]
+pub(crate) fn terminal_recording_list(workspace_path: String) -> Result<Vec<String>, String> {
+  session::terminal_recording_list(workspace_path)
+}
+
+#[tauri::command]
+pub(crate) fn terminal_recording_replay(
+  workspace_path: String,
+  file_name: String,
+  at_ms: Option<u64>,
+) -> Result<TerminalRecordingReplayPayload, String> {
+  session::terminal_recording_replay(workspace_path, file_name, at_ms)
+}
+
 #[tauri::command]
 pub(crate) fn terminal_close(
   app: AppHandle,
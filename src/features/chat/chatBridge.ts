@@ -101,6 +101,17 @@ export type ChatClearResult = {
   clearedTimeline: number;
 };
 
+export type ChatDeadLetter = {
+  taskId: string;
+  conversationId: string;
+  memberId: string;
+  content: string;
+  attempts: number;
+  createdAt: number;
+  updatedAt: number;
+  lastError?: string | null;
+};
+
 /**
  * 获取工作区会话列表与首页信息。
  * 输入：workspaceId、userId、workspaceName 与成员 id 列表。
@@ -270,6 +281,22 @@ export const repairChatMessages = (workspaceId: string) =>
 export const clearAllChatMessages = (workspaceId: string) =>
   invoke<ChatClearResult>('chat_clear_all_messages', { workspaceId });
 
+/**
+ * 列出终端回写落库失败且已超过重试上限的死信。
+ * 输入：工作区 id。
+ * 输出：死信列表（按创建时间升序）。
+ */
+export const listChatDeadLetters = (workspaceId: string) =>
+  invoke<ChatDeadLetter[]>('chat_list_dead_letters', { workspaceId });
+
+/**
+ * 重放死信，重新进入入站队列。
+ * 输入：工作区 id 与可选的任务 id 列表（缺省重放全部）。
+ * 输出：实际重放数量。
+ */
+export const replayChatDeadLetters = (workspaceId: string, taskIds?: string[]) =>
+  invoke<number>('chat_replay_dead_letters', { workspaceId, taskIds: taskIds ?? null });
+
 type ChatMessageListener = (payload: ChatMessageCreatedPayload) => void;
 const chatMessageListeners = new Set<ChatMessageListener>();
 let chatMessageListenerInitialized = false;
@@ -1,5 +1,12 @@
 // 聊天领域模型：定义成员、会话与消息的共享类型契约。
-import type { TerminalConnectionStatus, TerminalType } from '@/shared/types/terminal';
+import type {
+  TerminalApprovalPolicy,
+  TerminalConnectionStatus,
+  TerminalPostReadyScript,
+  TerminalRestartPolicy,
+  TerminalTuningOverrides,
+  TerminalType
+} from '@/shared/types/terminal';
 import type { ConversationType } from '@/shared/types/conversation';
 export type { ConversationType } from '@/shared/types/conversation';
 
@@ -27,6 +34,18 @@ export type Member = {
   manualStatus?: MemberStatus;
   unlimitedAccess?: boolean;
   sandboxed?: boolean;
+  terminalTuning?: TerminalTuningOverrides;
+  restartPolicy?: TerminalRestartPolicy;
+  approvalPolicy?: TerminalApprovalPolicy;
+  postReadyScript?: TerminalPostReadyScript;
+  // 成员独立 git 工作树；存在时终端在该目录启动。
+  worktree?: MemberWorktree;
+};
+
+export type MemberWorktree = {
+  path: string;
+  branch: string;
+  baseBranch: string;
 };
 
 export type Contact = {
@@ -54,7 +73,15 @@ export type FriendEntry = {
   terminalPath?: string;
 };
 
-export type MemberAction = 'send-message' | 'mention' | 'rename' | 'remove' | 'set-status' | 'open-terminal';
+export type MemberAction =
+  | 'send-message'
+  | 'mention'
+  | 'rename'
+  | 'remove'
+  | 'set-status'
+  | 'open-terminal'
+  | 'approve'
+  | 'deny';
 export type MemberActionPayload = {
   action: MemberAction;
   member: Member;
@@ -98,6 +125,12 @@ export type MessageAttachment =
   | {
       type: 'roadmap';
       title: string;
+    }
+  | {
+      type: 'routed';
+      ruleId: string;
+      sourceMemberId: string;
+      sourceMessageId: string;
     };
 
 export type MessageStatus = 'sending' | 'sent' | 'failed';
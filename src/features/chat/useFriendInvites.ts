@@ -10,7 +10,7 @@ import { buildSeededAvatar } from '@/shared/utils/avatar';
 import { hasTerminalConfig } from '@/shared/utils/terminal';
 import { logDiagnosticsEvent } from '@/shared/monitoring/diagnostics/logger';
 import { inviteProjectMembers } from '@/shared/tauri/projectData';
-import type { TerminalType } from '@/shared/types/terminal';
+import type { InviteTerminalType } from '@/shared/types/terminal';
 import { generateUlid } from './chatBridge';
 import { CURRENT_USER_ID } from './data';
 import { useChatStore } from './chatStore';
@@ -23,10 +23,11 @@ export type InviteModel = {
   id: string;
   label: string;
   command: string;
-  terminalType: TerminalType;
+  terminalType: InviteTerminalType;
   instances: number;
   unlimitedAccess: boolean;
   sandboxed: boolean;
+  isolatedWorktree: boolean;
 };
 
 type InviteRoleConfig = {
@@ -168,7 +169,8 @@ export const useFriendInvites = () => {
         terminalType: model.terminalType,
         instanceCount,
         unlimitedAccess: access.unlimitedAccess,
-        sandboxed: access.sandboxed
+        sandboxed: access.sandboxed,
+        isolatedWorktree: model.isolatedWorktree
       });
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
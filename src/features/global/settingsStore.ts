@@ -16,7 +16,15 @@ import {
   type MemberSelectionIndex
 } from '@/shared/utils/memberSelection';
 import { TIME_ZONE_IDS, type TimeZoneId } from '@/shared/constants/timeZones';
-import { isTerminalType, type TerminalType } from '@/shared/types/terminal';
+import {
+  isTerminalType,
+  TERMINAL_TUNING_KEYS,
+  type TerminalFilterRules,
+  type TerminalFilterRulesMap,
+  type TerminalTuningMap,
+  type TerminalTuningOverrides,
+  type TerminalType
+} from '@/shared/types/terminal';
 
 export type AccountStatus = 'online' | 'working' | 'dnd' | 'offline';
 
@@ -86,6 +94,10 @@ export type MemberSettings = {
   terminalPaths: TerminalPathMap;
   defaultTerminalName: string;
   defaultTerminalPath: string;
+  // 沙箱成员是否禁用网络（仅 Linux 生效）。
+  sandboxDenyNetwork: boolean;
+  // 成员终端空闲休眠阈值（分钟），0 表示不休眠。
+  hibernateAfterMinutes: number;
 };
 
 export type SettingsState = {
@@ -96,6 +108,8 @@ export type SettingsState = {
   keybinds: KeybindSettings;
   chat: ChatSettings;
   members: MemberSettings;
+  terminalTuning: TerminalTuningMap;
+  terminalFilterRules: TerminalFilterRulesMap;
 };
 
 // 应用级设置文件，统一由 app data 持久化。
@@ -139,8 +153,12 @@ const DEFAULT_SETTINGS: SettingsState = {
     customTerminals: [],
     terminalPaths: {},
     defaultTerminalName: '',
-    defaultTerminalPath: ''
-  }
+    defaultTerminalPath: '',
+    sandboxDenyNetwork: false,
+    hibernateAfterMinutes: 0
+  },
+  terminalTuning: {},
+  terminalFilterRules: {}
 };
 
 const ALLOWED_STATUSES = new Set<AccountStatus>(['online', 'working', 'dnd', 'offline']);
@@ -257,6 +275,83 @@ const normalizeTerminalPaths = (value: unknown): TerminalPathMap => {
   return next;
 };
 
+// 规范化休眠阈值：非负整数分钟，非法值回退为 0（不休眠）。
+const normalizeHibernateAfterMinutes = (value: unknown): number =>
+  typeof value === 'number' && Number.isFinite(value) && value > 0 ? Math.floor(value) : 0;
+
+// 规范化终端调优覆盖：仅保留已知终端类型下的非负整数字段，其余由后端默认值兜底。
+const normalizeTerminalTuning = (value: unknown): TerminalTuningMap => {
+  if (!value || typeof value !== 'object') {
+    return {};
+  }
+  const next: TerminalTuningMap = {};
+  Object.entries(value as Record<string, unknown>).forEach(([key, raw]) => {
+    if (!isTerminalType(key) || !raw || typeof raw !== 'object') {
+      return;
+    }
+    const overrides: TerminalTuningOverrides = {};
+    TERMINAL_TUNING_KEYS.forEach((field) => {
+      const candidate = (raw as Record<string, unknown>)[field];
+      if (typeof candidate === 'number' && Number.isFinite(candidate) && candidate >= 0) {
+        overrides[field] = Math.floor(candidate);
+      }
+    });
+    if (Object.keys(overrides).length > 0) {
+      next[key] = overrides;
+    }
+  });
+  return next;
+};
+
+const normalizeStringList = (value: unknown): string[] | undefined => {
+  if (!Array.isArray(value)) {
+    return undefined;
+  }
+  const list = value.filter((entry): entry is string => typeof entry === 'string' && entry.length > 0);
+  return list.length > 0 ? list : undefined;
+};
+
+// 规范化声明式过滤规则：只要求 promptPattern 非空，正则合法性由后端编译时校验。
+const normalizeTerminalFilterRules = (value: unknown): TerminalFilterRulesMap => {
+  if (!value || typeof value !== 'object') {
+    return {};
+  }
+  const next: TerminalFilterRulesMap = {};
+  Object.entries(value as Record<string, unknown>).forEach(([key, raw]) => {
+    const terminalType = key.trim().toLowerCase();
+    if (!terminalType || !raw || typeof raw !== 'object') {
+      return;
+    }
+    const payload = raw as Record<string, unknown>;
+    const promptPattern = safeString(payload.promptPattern);
+    if (!promptPattern.trim()) {
+      return;
+    }
+    const rules: TerminalFilterRules = { promptPattern };
+    const bulletMarkers = normalizeStringList(payload.bulletMarkers);
+    const toolMarkers = normalizeStringList(payload.toolMarkers);
+    const stripPatterns = normalizeStringList(payload.stripPatterns);
+    const boundaryPatterns = normalizeStringList(payload.boundaryPatterns);
+    if (bulletMarkers) {
+      rules.bulletMarkers = bulletMarkers;
+    }
+    if (toolMarkers) {
+      rules.toolMarkers = toolMarkers;
+    }
+    if (stripPatterns) {
+      rules.stripPatterns = stripPatterns;
+    }
+    if (boundaryPatterns) {
+      rules.boundaryPatterns = boundaryPatterns;
+    }
+    if (typeof payload.streamBullets === 'number' && Number.isFinite(payload.streamBullets) && payload.streamBullets >= 1) {
+      rules.streamBullets = Math.floor(payload.streamBullets);
+    }
+    next[terminalType] = rules;
+  });
+  return next;
+};
+
 const normalizeCustomTerminals = (value: unknown): CustomTerminal[] => {
   if (!Array.isArray(value)) {
     return [];
@@ -343,8 +438,12 @@ const normalizeSettings = (candidate: SettingsState): SettingsState => {
       customTerminals: normalizeCustomTerminals(candidate.members?.customTerminals),
       terminalPaths: normalizeTerminalPaths(candidate.members?.terminalPaths),
       defaultTerminalName,
-      defaultTerminalPath
-    }
+      defaultTerminalPath,
+      sandboxDenyNetwork: Boolean(candidate.members?.sandboxDenyNetwork),
+      hibernateAfterMinutes: normalizeHibernateAfterMinutes(candidate.members?.hibernateAfterMinutes)
+    },
+    terminalTuning: normalizeTerminalTuning(candidate.terminalTuning),
+    terminalFilterRules: normalizeTerminalFilterRules(candidate.terminalFilterRules)
   };
 };
 
@@ -360,7 +459,9 @@ const buildSettings = (candidate?: Partial<SettingsState>): SettingsState => {
     notifications: { ...DEFAULT_SETTINGS.notifications, ...candidate?.notifications },
     keybinds: { ...DEFAULT_SETTINGS.keybinds, ...candidate?.keybinds },
     chat: { ...DEFAULT_SETTINGS.chat, ...candidate?.chat },
-    members: { ...DEFAULT_SETTINGS.members, ...candidate?.members }
+    members: { ...DEFAULT_SETTINGS.members, ...candidate?.members },
+    terminalTuning: { ...DEFAULT_SETTINGS.terminalTuning, ...candidate?.terminalTuning },
+    terminalFilterRules: { ...DEFAULT_SETTINGS.terminalFilterRules, ...candidate?.terminalFilterRules }
   };
   return normalizeSettings(merged);
 };
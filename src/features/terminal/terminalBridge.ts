@@ -17,6 +17,28 @@ type SnapshotPayload = {
   cursorCol?: number;
 };
 type SnapshotLinesPayload = { terminalId: string; seq: number; lines: string[] };
+export type MessageRowsPayload = {
+  terminalId: string;
+  messageId?: string | null;
+  startRow: number;
+  endRow: number;
+  cursorRow: number;
+  truncated: boolean;
+  lines: string[];
+  text: string;
+  ansi: string;
+};
+export type RecordingReplayPayload = {
+  fileName: string;
+  atMs: number;
+  durationMs: number;
+  appliedEvents: number;
+  rows: number;
+  cols: number;
+  cursorRow: number;
+  cursorCol: number;
+  lines: string[];
+};
 type ExitPayload = { terminalId: string; code?: number | null; signal?: string | null };
 type StatusPayload = { terminalId: string; status: string; memberId?: string; workspaceId?: string };
 type ErrorPayload = { terminalId: string; error: string; fatal?: boolean };
@@ -41,6 +63,7 @@ export type TerminalChatPayload = {
     cursor?: { row: number; col: number };
     startRow?: number;
     endRow?: number;
+    exitCode?: number;
   };
 };
 
@@ -278,7 +301,7 @@ const ensureListeners = async () => {
 
 /**
  * 创建终端会话。
- * 输入：可选 cols/rows/cwd/成员与终端参数。
+ * 输入：可选 cols/rows/cwd/成员与终端参数；workspacePath 为项目根目录（cwd 为成员工作树时需单独传入）。
  * 输出：后端会话 id 字符串。
  * 错误语义：Tauri 调用失败会抛出异常。
  */
@@ -286,6 +309,7 @@ export const createSession = async (options?: {
   cols?: number;
   rows?: number;
   cwd?: string;
+  workspacePath?: string;
   memberId?: string;
   memberName?: string;
   workspaceId?: string;
@@ -315,23 +339,26 @@ export const createSession = async (options?: {
   const memberName = options?.memberName ?? inviteMeta?.memberName;
   const postReadyMode = options?.postReadyMode ?? (inviteMeta ? 'invite' : 'none');
   const terminalId = await invoke<string>('terminal_create', {
-    cols,
-    rows,
-    cwd: options?.cwd,
-    memberId: options?.memberId,
-    workspaceId: options?.workspaceId,
-    keepAlive: options?.keepAlive,
-    terminalId: options?.terminalId,
-    terminalType: options?.terminalType,
-    terminalCommand: options?.terminalCommand,
-    terminalPath: options?.terminalPath,
-    strictShell: options?.strictShell,
-    memberName,
-    defaultCommand: inviteMeta?.defaultCommand,
-    inviteInstanceCount: inviteMeta?.instanceCount,
-    inviteUnlimitedAccess: inviteMeta?.unlimitedAccess,
-    inviteSandboxed: inviteMeta?.sandboxed,
-    postReadyMode
+    options: {
+      cols,
+      rows,
+      cwd: options?.cwd,
+      workspacePath: options?.workspacePath,
+      memberId: options?.memberId,
+      workspaceId: options?.workspaceId,
+      keepAlive: options?.keepAlive,
+      terminalId: options?.terminalId,
+      terminalType: options?.terminalType,
+      terminalCommand: options?.terminalCommand,
+      terminalPath: options?.terminalPath,
+      strictShell: options?.strictShell,
+      memberName,
+      defaultCommand: inviteMeta?.defaultCommand,
+      inviteInstanceCount: inviteMeta?.instanceCount,
+      inviteUnlimitedAccess: inviteMeta?.unlimitedAccess,
+      inviteSandboxed: inviteMeta?.sandboxed,
+      postReadyMode
+    }
   });
   void logDiagnosticsEvent('create-session', {
     terminalId,
@@ -358,6 +385,26 @@ export const writeSession = async (terminalId: string, data: string) => {
   return invoke('terminal_write', { terminalId, data });
 };
 
+/**
+ * 批准或拒绝终端当前的审批提示，后端按成员类型写入对应按键。
+ * 输入：terminalId 与是否批准。
+ * 输出：无。
+ */
+export const respondApproval = async (terminalId: string, approve: boolean) => {
+  await ensureListeners();
+  return invoke('terminal_respond_approval', { terminalId, approve });
+};
+
+/**
+ * 发送按键序列，如 `<C-c>`、`<Esc>`、`paste{...}`。
+ * 输入：terminalId 与按键序列。
+ * 输出：无；序列无法解析时抛出错误。
+ */
+export const sendKeys = async (terminalId: string, keys: string) => {
+  await ensureListeners();
+  return invoke('terminal_send_keys', { terminalId, keys });
+};
+
 /**
  * 确认已消费的输出字节数，用于后端流控。
  * 输入：terminalId 与字节数。
@@ -483,6 +530,19 @@ export const snapshotSessionLines = async (terminalId: string) => {
   return invoke<SnapshotLinesPayload>('terminal_snapshot_lines', { terminalId });
 };
 
+/**
+ * 取回聊天消息对应的终端原文。
+ * 输入：terminalId 与 messageId，或显式的绝对行区间（消息元数据中的 startRow/endRow）。
+ * 输出：区间内的纯文本、ANSI 与当前光标绝对行号。
+ */
+export const fetchMessageRows = async (
+  terminalId: string,
+  target: { messageId: string } | { startRow: number; endRow: number }
+) => {
+  await ensureListeners();
+  return invoke<MessageRowsPayload>('terminal_message_rows', { terminalId, ...target });
+};
+
 /**
  * 只读获取会话快照文本，避免改写输出窗口路由。
  * 输入：terminalId。
@@ -493,6 +553,47 @@ export const snapshotSessionText = async (terminalId: string) => {
   return invoke<SnapshotPayload>('terminal_snapshot_text', { terminalId });
 };
 
+/**
+ * 开启会话 asciicast 录制。
+ * 输入：terminalId 与可选 workspacePath（缺省使用会话启动目录）。
+ * 输出：录制文件路径。
+ */
+export const startSessionRecording = async (terminalId: string, workspacePath?: string) => {
+  return invoke<string>('terminal_recording_start', { terminalId, workspacePath });
+};
+
+/**
+ * 停止会话录制。
+ * 输入：terminalId。
+ * 输出：已关闭的录制文件路径；未在录制时为 null。
+ */
+export const stopSessionRecording = async (terminalId: string) => {
+  return invoke<string | null>('terminal_recording_stop', { terminalId });
+};
+
+/**
+ * 列出工作区内的录制文件名，最新在前。
+ */
+export const listSessionRecordings = async (workspacePath: string) => {
+  return invoke<string[]>('terminal_recording_list', { workspacePath });
+};
+
+/**
+ * 回放录制文件，重建指定时刻（相对录制开始的毫秒数）的屏幕快照。
+ * 输入：workspacePath、fileName 与可选 atMs（缺省为录制结尾）。
+ */
+export const replaySessionRecording = async (
+  workspacePath: string,
+  fileName: string,
+  atMs?: number
+) => {
+  return invoke<RecordingReplayPayload>('terminal_recording_replay', {
+    workspacePath,
+    fileName,
+    atMs
+  });
+};
+
 /**
  * 标记会话为可缓冲输出的追踪对象。
  * 输入：terminalId。
@@ -9,6 +9,13 @@ export const TERMINAL_SNAPSHOT_REQUEST_EVENT = 'terminal-snapshot-request';
 export const TERMINAL_SNAPSHOT_RESPONSE_EVENT = 'terminal-snapshot-response';
 export const TERMINAL_TAB_OPENED_EVENT = 'terminal-tab-opened';
 
+// 终端行区间定位请求：行号为后端会话绝对行，前端按与光标的相对距离换算滚动位置。
+export type TerminalRevealRows = {
+  startRow: number;
+  endRow: number;
+  cursorRow: number;
+};
+
 // 终端标签页打开事件载荷。
 export type TerminalOpenTabPayload = {
   terminalId: string;
@@ -16,6 +23,7 @@ export type TerminalOpenTabPayload = {
   memberId?: string;
   terminalType?: TerminalType;
   keepAlive?: boolean;
+  revealRows?: TerminalRevealRows;
 };
 
 // 终端窗口就绪事件载荷。
@@ -12,8 +12,10 @@ import { openTerminalWindow } from './openTerminalWindow';
 import {
   closeSession,
   createSession,
+  respondApproval,
   dispatchSession,
   emitSessionStatus,
+  fetchMessageRows,
   listSessionStatuses,
   onStatusChange,
   setMemberStatus
@@ -27,6 +29,7 @@ import {
   TERMINAL_WINDOW_READY_EVENT,
   TERMINAL_WINDOW_READY_REQUEST_EVENT,
   type TerminalOpenTabPayload,
+  type TerminalRevealRows,
   type TerminalWindowReadyPayload
 } from './terminalEvents';
 
@@ -76,6 +79,8 @@ const debugLog = createFrontLogger('terminal-member');
 const resolveTerminalStatus = (status: string): TerminalConnectionStatus | null => {
   if (status === 'online') return 'connected';
   if (status === 'working') return 'working';
+  if (status === 'hibernating') return 'hibernating';
+  if (status === 'awaiting_input') return 'awaitingInput';
   if (status === 'offline') return 'disconnected';
   if (status === 'pending' || status === 'connecting' || status === 'connected' || status === 'disconnected') {
     return status;
@@ -303,9 +308,11 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
       lastSnapshotWorkspaceId = workspace.id;
       lastSnapshotAt = Date.now();
       const statusPriority: Record<TerminalConnectionStatus, number> = {
+        awaitingInput: 4,
         working: 3,
         connected: 2,
         connecting: 1,
+        hibernating: 1,
         disconnected: 0,
         pending: -1
       };
@@ -426,7 +433,7 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
   const openMemberTab = async (
     entry: MemberTerminalSession,
     titleOverride?: string,
-    options?: { windowLabel?: string }
+    options?: { windowLabel?: string; revealRows?: TerminalRevealRows }
   ) => {
     const windowLabel = options?.windowLabel ?? (await ensureTerminalWindow());
     if (!windowLabel) {
@@ -438,7 +445,8 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
       title: resolveTitle(titleOverride, entry.title),
       memberId: entry.memberId,
       terminalType,
-      keepAlive: true
+      keepAlive: true,
+      revealRows: options?.revealRows
     };
     void logDiagnosticsEvent('terminal-open-tab', {
       terminalId: entry.terminalId,
@@ -513,7 +521,8 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
     let terminalId = '';
     try {
       terminalId = await createSession({
-        cwd: workspace.path,
+        cwd: member.worktree?.path || workspace.path,
+        workspacePath: workspace.path,
         memberId: member.id,
         memberName: member.name,
         workspaceId: workspace.id,
@@ -690,6 +699,23 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
     await task;
   };
 
+  /**
+   * 在终端中定位聊天消息对应的输出区间。
+   * 输入：成员信息与消息 id（会话需仍在运行，区间记录随会话重启失效）。
+   * 输出：会话条目或 null；区间不可用时抛出后端错误。
+   */
+  const revealMessageInTerminal = async (member: Member, messageId: string) => {
+    const entry = getSession(member.id, currentWorkspace.value?.id);
+    if (!entry) {
+      return null;
+    }
+    const rows = await fetchMessageRows(entry.terminalId, { messageId });
+    await openMemberTab(entry, undefined, {
+      revealRows: { startRow: rows.startRow, endRow: rows.endRow, cursorRow: rows.cursorRow }
+    });
+    return entry;
+  };
+
   /**
    * 打开成员终端并确保自动启动标记为开启。
    * 输入：成员信息与可选窗口标签。
@@ -756,6 +782,19 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
     dispatchChains.delete(buildMemberKey(memberId, workspaceId));
   };
 
+  /**
+   * 批准或拒绝成员终端当前的审批提示。
+   * 输入：成员 id 与是否批准。
+   * 输出：无。
+   */
+  const respondMemberApproval = async (memberId: string, approve: boolean) => {
+    const entry = getSession(memberId, currentWorkspace.value?.id);
+    if (!entry) {
+      return;
+    }
+    await respondApproval(entry.terminalId, approve);
+  };
+
   ensureStatusSync();
 
   return {
@@ -763,7 +802,9 @@ export const useTerminalMemberStore = defineStore('terminal-member', () => {
     ensureMemberSession,
     enqueueTerminalDispatch,
     openMemberTerminal,
+    revealMessageInTerminal,
     stopMemberSession,
+    respondMemberApproval,
     getSession,
     resetWindowReady
   };
@@ -4,6 +4,7 @@ import { acceptHMRUpdate, defineStore } from 'pinia';
 import { i18n } from '@/i18n';
 import { closeSession, createSession } from './terminalBridge';
 import type { TerminalType } from '@/shared/types/terminal';
+import type { TerminalRevealRows } from './terminalEvents';
 
 export type TerminalLayoutMode = 'single' | 'split-vertical' | 'split-horizontal' | 'grid-2x2';
 export type TerminalPaneId =
@@ -49,6 +50,8 @@ export const useTerminalStore = defineStore('terminal', () => {
   const paneAssignments = ref<PaneAssignments>({ primary: null });
   const focusedPaneId = ref<TerminalPaneId>('primary');
   const paneIds = ref<TerminalPaneId[]>(LAYOUT_PANES.single);
+  // 待定位的行区间：面板 attach 完成后消费，未挂载的面板也不会丢失请求。
+  const rowReveals = ref<Record<string, TerminalRevealRows>>({});
   // 本地自增序号用于默认标题，避免重复命名。
   let tabCounter = 1;
 
@@ -391,6 +394,30 @@ export const useTerminalStore = defineStore('terminal', () => {
     }
   };
 
+  /**
+   * 记录终端行区间定位请求。
+   * 输入：terminalId 与行区间。
+   * 输出：无。
+   */
+  const requestRowReveal = (terminalId: string, revealRows: TerminalRevealRows) => {
+    rowReveals.value = { ...rowReveals.value, [terminalId]: revealRows };
+  };
+
+  /**
+   * 取出并清除终端的定位请求。
+   * 输入：terminalId。
+   * 输出：行区间或 null。
+   */
+  const takeRowReveal = (terminalId: string) => {
+    const revealRows = rowReveals.value[terminalId];
+    if (!revealRows) {
+      return null;
+    }
+    const { [terminalId]: _taken, ...rest } = rowReveals.value;
+    rowReveals.value = rest;
+    return revealRows;
+  };
+
   return {
     tabs,
     activeId,
@@ -398,6 +425,7 @@ export const useTerminalStore = defineStore('terminal', () => {
     paneAssignments,
     focusedPaneId,
     paneIds,
+    rowReveals,
     createTab,
     openTab,
     setActive,
@@ -412,7 +440,9 @@ export const useTerminalStore = defineStore('terminal', () => {
     setLayoutMode,
     assignTabToPane,
     unassignTab,
-    syncPaneAssignments
+    syncPaneAssignments,
+    requestRowReveal,
+    takeRowReveal
   };
 });
 
@@ -27,6 +27,34 @@ export type ProjectTerminalRecentTab = {
   closedAt: number;
 };
 
+// 路由规则：终端 final 落库后由后端按规则转发，字段语义见 orchestration/routing_rules.rs。
+export type ProjectRoutingRule = {
+  id: string;
+  enabled?: boolean;
+  from?: string;
+  conversation?: string;
+  contains?: string;
+  prefix?: string;
+  ignoreCase?: boolean;
+  to: string[];
+  toConversation?: string;
+  template?: string;
+};
+
+// 成员池：`@name*` 提及按策略挑选池内实例，未配置时后端默认轮询。
+export type ProjectMemberPool = {
+  name: string;
+  strategy: 'round-robin' | 'least-queued' | 'first-idle';
+};
+
+// 成员引导词：按 roleType 覆盖模板（`default` 兜底）与项目约定，字符串或按语言区分的 zh/en。
+export type ProjectLocalizedText = string | { zh?: string; en?: string };
+
+export type ProjectOnboarding = {
+  templates?: Record<string, ProjectLocalizedText>;
+  conventions?: ProjectLocalizedText;
+};
+
 type ProjectData = {
   projectId: string;
   version: number;
@@ -42,6 +70,9 @@ type ProjectData = {
   skills: {
     current: ProjectSkill[];
   };
+  routingRules?: ProjectRoutingRule[];
+  memberPools?: ProjectMemberPool[];
+  onboarding?: ProjectOnboarding;
 };
 
 // 默认 owner 名称。
@@ -289,7 +320,11 @@ const normalizeProjectData = (data: Partial<ProjectData>, workspaceName: string,
     },
     skills: {
       current: Array.isArray(data.skills?.current) && data.skills?.current.length > 0 ? data.skills.current : defaults.skills.current
-    }
+    },
+    // 路由规则由后端解析，前端仅透传，避免持久化时丢失。
+    routingRules: Array.isArray(data.routingRules) ? data.routingRules : [],
+    memberPools: Array.isArray(data.memberPools) ? data.memberPools : [],
+    ...(data.onboarding && typeof data.onboarding === 'object' ? { onboarding: data.onboarding } : {})
   };
 };
 
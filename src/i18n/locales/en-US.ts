@@ -30,6 +30,7 @@ export default {
     emptyTitle: 'No active terminals',
     emptySubtitle: 'Create a new terminal tab to get started.',
     splitEmpty: 'Drag a tab here or create a new terminal.',
+    revealRowsUnavailable: 'The terminal output for this message has scrolled out of the buffer.',
     unavailableTitle: 'Terminal unavailable',
     unavailableSubtitle: 'Open this view inside the Tauri desktop app to use terminals.',
     errorTitle: 'Terminal failed to start',
@@ -41,6 +42,8 @@ export default {
       connecting: 'Connecting',
       connected: 'Connected',
       working: 'Working',
+      hibernating: 'Hibernating',
+      awaitingInput: 'Awaiting Approval',
       disconnected: 'Disconnected'
     },
     tabMenu: {
@@ -129,6 +132,7 @@ export default {
     messages: {
       dateSeparator: 'October 24, 2023',
       roadmapHint: 'Click to view roadmap',
+      routedHint: 'Forwarded by routing rule {rule}',
       userJoined: '{name} joined the server',
       joinedUser: 'James',
       sampleMessage: {
@@ -141,10 +145,41 @@ export default {
       loadHistory: 'Load earlier messages',
       loadingHistory: 'Loading history...',
       jumpToLatest: 'Jump to latest',
+      locateInTerminal: 'Show in terminal',
+      locateUnavailable: 'Terminal output for this message is no longer available (the terminal may have restarted).',
       typing: '{name} is typing...',
       status: {
         sending: 'Sending...',
         failed: 'Failed to send'
+      },
+      system: {
+        throttle: {
+          final: {
+            member: 'This member is replying too often. Replies are paused for {seconds}s.',
+            conversation: 'Too many replies in this conversation. Replies are paused for {seconds}s.',
+            workspace: 'Too many replies in this workspace. Replies are paused for {seconds}s.'
+          },
+          dispatch: {
+            member: 'This member is receiving messages too often. Delivery is paused for {seconds}s.',
+            conversation: 'Too many messages in this conversation. Delivery is paused for {seconds}s.',
+            workspace: 'Too many messages in this workspace. Delivery is paused for {seconds}s.'
+          },
+          mentionLoop:
+            'Members have @mentioned each other {hops} times without a human reply. Delivery is paused until someone replies.'
+        },
+        supervisor: {
+          restarted: '{name} exited ({reason}) and was restarted automatically (attempt {attempt}/{max}).',
+          resumed:
+            '{name} exited ({reason}) and was restarted automatically, resuming its previous session (attempt {attempt}/{max}).',
+          restartFailed: '{name} exited ({reason}) and automatic restart failed (attempt {attempt}/{max}).',
+          gaveUp:
+            '{name} crashed {count} times within {minutes} min. Automatic restart has stopped; reopen the terminal manually.'
+        },
+        approval: {
+          awaiting: '{name} is waiting for approval: {prompt}',
+          autoApproved: 'Auto-approved for {name} by rule "{rule}": {prompt}',
+          autoDenied: 'Auto-denied for {name} by rule "{rule}": {prompt}'
+        }
       }
     },
     conversation: {
@@ -181,7 +216,9 @@ export default {
     actions: {
       sendMessage: 'Send Message',
       mention: 'Mention',
-      rename: 'Rename'
+      rename: 'Rename',
+      approve: 'Approve',
+      deny: 'Deny'
     },
     manage: {
       title: 'Manage Member',
@@ -255,6 +292,8 @@ export default {
       unlimitedAccess: 'Unlimited Mode',
       unlimitedAccessDesc: 'Bypass usage limits',
       sandboxed: 'Sandboxed environment',
+      isolatedWorktree: 'Isolated worktree',
+      isolatedWorktreeDesc: 'Give each member its own git branch and directory',
       send: 'Send Invitation',
       models: {
         gemini: 'Gemini CLI',
@@ -30,6 +30,7 @@ export default {
     emptyTitle: '没有活动终端',
     emptySubtitle: '新建一个终端标签开始。',
     splitEmpty: '拖动标签到这里，或新建一个终端。',
+    revealRowsUnavailable: '该消息对应的终端输出已滚出缓冲区。',
     unavailableTitle: '终端不可用',
     unavailableSubtitle: '请在 Tauri 桌面应用中打开该页面。',
     errorTitle: '终端启动失败',
@@ -41,6 +42,8 @@ export default {
       connecting: '连接中',
       connected: '已连接',
       working: '工作中',
+      hibernating: '休眠中',
+      awaitingInput: '等待审批',
       disconnected: '已断开'
     },
     tabMenu: {
@@ -129,6 +132,7 @@ export default {
     messages: {
       dateSeparator: '2023年10月24日',
       roadmapHint: '点击查看路线图',
+      routedHint: '由路由规则 {rule} 自动转发',
       userJoined: '{name} 加入了服务器',
       joinedUser: 'James',
       sampleMessage: {
@@ -141,10 +145,38 @@ export default {
       loadHistory: '加载更早消息',
       loadingHistory: '正在加载历史...',
       jumpToLatest: '跳到最新消息',
+      locateInTerminal: '在终端中定位',
+      locateUnavailable: '该消息的终端输出已不可用（终端可能已重启）。',
       typing: '{name} 正在输入...',
       status: {
         sending: '发送中...',
         failed: '发送失败'
+      },
+      system: {
+        throttle: {
+          final: {
+            member: '该成员回复过于频繁，已暂停回写 {seconds} 秒。',
+            conversation: '当前会话回复过多，已暂停回写 {seconds} 秒。',
+            workspace: '当前工作区回复过多，已暂停回写 {seconds} 秒。'
+          },
+          dispatch: {
+            member: '该成员接收消息过于频繁，已暂停派发 {seconds} 秒。',
+            conversation: '当前会话消息过多，已暂停派发 {seconds} 秒。',
+            workspace: '当前工作区消息过多，已暂停派发 {seconds} 秒。'
+          },
+          mentionLoop: '成员之间已连续互相 @ {hops} 次且无人回复，派发已暂停，等待有人发言后恢复。'
+        },
+        supervisor: {
+          restarted: '{name} 已退出（{reason}），已自动重启（第 {attempt}/{max} 次）。',
+          resumed: '{name} 已退出（{reason}），已自动重启并恢复之前的会话（第 {attempt}/{max} 次）。',
+          restartFailed: '{name} 已退出（{reason}），自动重启失败（第 {attempt}/{max} 次）。',
+          gaveUp: '{name} 在 {minutes} 分钟内崩溃 {count} 次，已停止自动重启，请手动重新打开终端。'
+        },
+        approval: {
+          awaiting: '{name} 正在等待审批：{prompt}',
+          autoApproved: '已按规则「{rule}」自动批准 {name} 的请求：{prompt}',
+          autoDenied: '已按规则「{rule}」自动拒绝 {name} 的请求：{prompt}'
+        }
       }
     },
     conversation: {
@@ -181,7 +213,9 @@ export default {
     actions: {
       sendMessage: '发送消息',
       mention: '提及',
-      rename: '更改名称'
+      rename: '更改名称',
+      approve: '批准',
+      deny: '拒绝'
     },
     manage: {
       title: '管理成员',
@@ -255,6 +289,8 @@ export default {
       unlimitedAccess: '无限制模式',
       unlimitedAccessDesc: '绕过使用限制',
       sandboxed: '沙盒环境',
+      isolatedWorktree: '独立工作树',
+      isolatedWorktreeDesc: '每个成员使用独立的 git 分支与目录',
       send: '发送邀请',
       models: {
         gemini: 'Gemini CLI',
@@ -65,6 +65,8 @@ export const BASE_TERMINALS: BaseTerminal[] = [
 // 自定义终端使用统一图标与渐变，避免与内置模型视觉混淆。
 export const CUSTOM_TERMINAL_ICON = 'settings_suggest';
 export const CUSTOM_TERMINAL_GRADIENT = 'from-slate-500 to-slate-300';
+// 插件成员类型沿用自定义渐变，以图标区分来源。
+export const PLUGIN_TERMINAL_ICON = 'extension';
 
 export const resolveBaseTerminalLabel = (terminal: BaseTerminal, translate: (key: string) => string) => {
   if (terminal.label) {
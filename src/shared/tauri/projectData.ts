@@ -1,6 +1,6 @@
 // 项目数据 IPC 封装：统一通过后端业务服务层读写项目数据。
 import { invoke } from '@tauri-apps/api/core';
-import type { TerminalType } from '@/shared/types/terminal';
+import type { InviteTerminalType } from '@/shared/types/terminal';
 
 export type ProjectDataSource = 'workspace' | 'app' | 'none';
 export type ProjectDataStorage = 'workspace' | 'app';
@@ -19,10 +19,11 @@ export type ProjectDataWriteResult = {
 export type ProjectInviteMembersRequest = {
   roleType: 'assistant' | 'member';
   command?: string;
-  terminalType?: TerminalType;
+  terminalType?: InviteTerminalType;
   instanceCount: number;
   unlimitedAccess: boolean;
   sandboxed: boolean;
+  isolatedWorktree?: boolean;
 };
 
 export type ProjectInviteMembersResult<T> = {
@@ -32,6 +33,33 @@ export type ProjectInviteMembersResult<T> = {
   warning?: string | null;
 };
 
+export type MemberWorktreeInfo = {
+  memberId: string;
+  memberName: string;
+  path: string;
+  branch: string;
+  baseBranch: string;
+  exists: boolean;
+  dirty: boolean;
+  commitsAhead: number;
+};
+
+export type MemberWorktreeDiff = {
+  memberId: string;
+  branch: string;
+  baseBranch: string;
+  files: { status: string; path: string }[];
+  diff: string;
+};
+
+export type MemberWorktreeMergeResult = {
+  memberId: string;
+  branch: string;
+  baseBranch: string;
+  committedPending: boolean;
+  mergeCommit: string;
+};
+
 export type ProjectPurgeTerminalMembersScope = 'current';
 
 export type ProjectPurgeTerminalMembersResult = {
@@ -97,3 +125,41 @@ export const purgeProjectTerminalMembers = async (
     workspaceId,
     payload: { scope }
   });
+
+/**
+ * 列出成员工作树。
+ * 输入：workspaceId。
+ * 输出：成员工作树状态列表。
+ */
+export const listMemberWorktrees = async (workspaceId: string): Promise<MemberWorktreeInfo[]> =>
+  invoke<MemberWorktreeInfo[]>('project_member_worktrees', { workspaceId });
+
+/**
+ * 查看成员工作树相对基线分支的改动。
+ * 输入：workspaceId 与 memberId。
+ * 输出：改动文件与 diff 文本。
+ */
+export const getMemberWorktreeDiff = async (
+  workspaceId: string,
+  memberId: string
+): Promise<MemberWorktreeDiff> =>
+  invoke<MemberWorktreeDiff>('project_member_worktree_diff_get', { workspaceId, memberId });
+
+/**
+ * 将成员分支合并回基线分支。
+ * 输入：workspaceId 与 memberId。
+ * 输出：合并结果。
+ */
+export const mergeMemberWorktree = async (
+  workspaceId: string,
+  memberId: string
+): Promise<MemberWorktreeMergeResult> =>
+  invoke<MemberWorktreeMergeResult>('project_member_worktree_merge_apply', { workspaceId, memberId });
+
+/**
+ * 丢弃成员工作树与分支。
+ * 输入：workspaceId 与 memberId。
+ * 输出：更新后的成员列表。
+ */
+export const discardMemberWorktree = async <T>(workspaceId: string, memberId: string): Promise<T[]> =>
+  invoke<T[]>('project_member_worktree_discard_apply', { workspaceId, memberId });
@@ -1,6 +1,6 @@
 // Tauri 终端入口：提供设置页等轻量查询能力。
 import { invoke } from '@tauri-apps/api/core';
-import type { TerminalEnvironmentOption } from '@/shared/types/terminal';
+import type { TerminalEnvironmentOption, TerminalMemberTypeOption } from '@/shared/types/terminal';
 
 /**
  * 获取当前系统可用的终端环境列表。
@@ -8,3 +8,10 @@ import type { TerminalEnvironmentOption } from '@/shared/types/terminal';
  */
 export const listTerminalEnvironments = async (): Promise<TerminalEnvironmentOption[]> =>
   invoke<TerminalEnvironmentOption[]>('terminal_list_environments');
+
+/**
+ * 获取启动时注册的插件成员类型，供邀请面板与内置类型一并展示。
+ * 输出：成员类型数组（id/terminalType/defaultCommand 等）。
+ */
+export const listTerminalMemberTypes = async (): Promise<TerminalMemberTypeOption[]> =>
+  invoke<TerminalMemberTypeOption[]>('terminal_list_member_types');
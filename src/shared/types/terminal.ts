@@ -1,8 +1,92 @@
 // 终端相关基础类型与守卫函数，作为跨模块契约的唯一来源。
 export type TerminalType = 'shell' | 'codex' | 'gemini' | 'claude' | 'opencode' | 'qwen';
-export type TerminalConnectionStatus = 'pending' | 'connecting' | 'connected' | 'working' | 'disconnected';
+export type TerminalConnectionStatus =
+  | 'pending'
+  | 'connecting'
+  | 'connected'
+  | 'working'
+  | 'hibernating'
+  | 'awaitingInput'
+  | 'disconnected';
 export type TerminalPostReadyMode = 'invite' | 'none';
 
+// 终端时序调优覆盖：字段与后端 TerminalTuning 一一对应（毫秒/字节），缺省项使用引擎默认值。
+export const TERMINAL_TUNING_KEYS = [
+  'workingSilenceTimeoutMs',
+  'statusIdleDebounceMs',
+  'workingIntentWindowMs',
+  'chatSilenceTimeoutMs',
+  'chatIdleDebounceMs',
+  'chatPendingForceFlushMs',
+  'redrawSuppressionWindowMs',
+  'postReadyStableMs',
+  'postReadyTickMs',
+  'shellReadyTimeoutMs',
+  'shellReadyActivityMs',
+  'flowControlHighWatermark',
+  'flowControlLowWatermark'
+] as const;
+export type TerminalTuningKey = (typeof TERMINAL_TUNING_KEYS)[number];
+export type TerminalTuningOverrides = Partial<Record<TerminalTuningKey, number>>;
+
+// 全局设置按终端类型配置调优覆盖；成员可在项目数据 `members[].terminalTuning` 中再覆盖。
+export type TerminalTuningMap = Partial<Record<TerminalType, TerminalTuningOverrides>>;
+
+// 成员崩溃重启策略（项目数据 `members[].restartPolicy`）：对应后端 RestartPolicy，缺省为 never。
+export type TerminalRestartMode = 'never' | 'on-failure' | 'always';
+export type TerminalRestartPolicy = {
+  mode: TerminalRestartMode;
+  maxRestarts?: number;
+  windowMs?: number;
+  initialBackoffMs?: number;
+  maxBackoffMs?: number;
+};
+
+// 成员审批策略（项目数据 `members[].approvalPolicy`）：对应后端 ApprovalPolicy。
+// `*` 通配；approve 须整行匹配提取出的命令（链式命令逐条匹配），deny/ask 在提示上下文中任意位置命中即生效。
+// 优先级 deny > ask > approve，未命中时按 default（缺省 ask）。
+export type TerminalApprovalDecision = 'approve' | 'deny' | 'ask';
+export type TerminalApprovalPolicy = {
+  approve?: string[];
+  deny?: string[];
+  ask?: string[];
+  default?: TerminalApprovalDecision;
+};
+
+// 成员自定义启动后脚本（项目数据 `members[].postReadyScript`）：对应后端 PostReadyScript，会话启动前校验。
+// 可直接写步骤数组（默认在内置流程之后执行），或指定 mode 放在内置流程之前/替换内置流程。
+export type TerminalPostReadyScriptStep =
+  | { type: 'input'; input: string; requireStable?: boolean }
+  | { type: 'keys'; keys: string; requireStable?: boolean }
+  | { type: 'delay'; ms: number }
+  | { type: 'extractSessionId'; keyword: string; requireStable?: boolean }
+  | { type: 'waitForPattern'; pattern: string; timeoutMs?: number; requireStable?: boolean }
+  | { type: 'introduction'; promptType: 'onboarding'; requireStable?: boolean }
+  | {
+      type: 'ifPattern';
+      pattern: string;
+      timeoutMs?: number;
+      requireStable?: boolean;
+      then?: TerminalPostReadyScriptStep[];
+      else?: TerminalPostReadyScriptStep[];
+    };
+export type TerminalPostReadyScript =
+  | TerminalPostReadyScriptStep[]
+  | { mode?: 'before' | 'after' | 'replace'; steps: TerminalPostReadyScriptStep[] };
+
+// 声明式过滤规则：字段与后端 DeclarativeRules 一一对应，正则使用 Rust regex 语法。
+export type TerminalFilterRules = {
+  promptPattern: string;
+  bulletMarkers?: string[];
+  toolMarkers?: string[];
+  stripPatterns?: string[];
+  boundaryPatterns?: string[];
+  streamBullets?: number;
+};
+
+// 全局设置按终端类型名配置过滤规则；键可以是内置类型，也可以是自研 CLI 的自定义类型名。
+export type TerminalFilterRulesMap = Record<string, TerminalFilterRules>;
+
 // 终端环境条目：用于设置页展示可用 shell 列表。
 export type TerminalEnvironmentOption = {
   id: string;
@@ -10,6 +94,18 @@ export type TerminalEnvironmentOption = {
   path: string;
 };
 
+// 插件成员类型：后端启动时从用户配置目录加载，terminalType 为注册名（小写）。
+export type TerminalMemberTypeOption = {
+  id: string;
+  terminalType: string;
+  defaultCommand: string;
+  unlimitedAccess: boolean;
+  resumable: boolean;
+};
+
+// 邀请可选的终端类型：内置类型或已注册的插件类型名。
+export type InviteTerminalType = TerminalType | (string & {});
+
 // 终端好友邀请元信息：用于后端接收邀请时的上下文记录。
 export type TerminalFriendInviteMeta = {
   memberName: string;
@@ -42,4 +138,6 @@ export const isTerminalConnectionStatus = (value: unknown): value is TerminalCon
   value === 'connecting' ||
   value === 'connected' ||
   value === 'working' ||
+  value === 'hibernating' ||
+  value === 'awaitingInput' ||
   value === 'disconnected';
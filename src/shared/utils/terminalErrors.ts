@@ -12,7 +12,8 @@ export type TerminalErrorPayload = {
 export const TERMINAL_ERROR_CODES = {
   shellBinaryNotFound: 'terminal.shell.binary_not_found',
   shellLaunchFailed: 'terminal.shell.launch_failed',
-  terminalLaunchFailed: 'terminal.launch_failed'
+  terminalLaunchFailed: 'terminal.launch_failed',
+  sandboxUnavailable: 'terminal.sandbox.unavailable'
 } as const;
 
 export type TerminalErrorCode = (typeof TERMINAL_ERROR_CODES)[keyof typeof TERMINAL_ERROR_CODES];
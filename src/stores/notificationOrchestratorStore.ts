@@ -66,7 +66,7 @@ const resolvePreviewText = (preview: string | undefined, attachment?: { type: st
   if (attachment?.type === 'image') {
     return attachment.mimeType === 'image/gif' ? '[动画表情]' : '[图片]';
   }
-  if (attachment?.type) {
+  if (attachment?.type && attachment.type !== 'routed') {
     return '[文件]';
   }
   return preview ?? '';
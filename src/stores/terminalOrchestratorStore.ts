@@ -64,6 +64,23 @@ export const useTerminalOrchestratorStore = defineStore('terminal-orchestrator',
     }
   };
 
+  /**
+   * 打开成员终端并定位到消息对应的输出区间。
+   * 输入：成员信息与消息 id。
+   * 输出：是否成功定位。
+   */
+  const revealMessageInTerminal = async (member: Member, messageId: string) => {
+    if (!hasTerminalConfig(member.terminalType, member.terminalCommand)) {
+      return false;
+    }
+    try {
+      return Boolean(await terminalMemberStore.revealMessageInTerminal(member, messageId));
+    } catch (error) {
+      console.error('Failed to reveal message in terminal.', error);
+      return false;
+    }
+  };
+
   const resetTerminalWindowReady = (windowLabel: string) => {
     terminalMemberStore.resetWindowReady(windowLabel);
   };
@@ -84,6 +101,19 @@ export const useTerminalOrchestratorStore = defineStore('terminal-orchestrator',
     }
   };
 
+  /**
+   * 批准或拒绝成员终端的审批提示。
+   * 输入：成员 id 与是否批准。
+   * 输出：无。
+   */
+  const respondMemberApproval = async (memberId: string, approve: boolean) => {
+    try {
+      await terminalMemberStore.respondMemberApproval(memberId, approve);
+    } catch (error) {
+      console.error('Failed to respond to terminal approval.', error);
+    }
+  };
+
   /**
    * 串行派发终端指令。
    * 输入：派发请求。
@@ -218,7 +248,9 @@ export const useTerminalOrchestratorStore = defineStore('terminal-orchestrator',
   return {
     ensureMemberSession,
     openMemberTerminal,
+    revealMessageInTerminal,
     stopMemberSession,
+    respondMemberApproval,
     enqueueTerminalDispatch,
     dispatchConversationToTerminals,
     resetTerminalWindowReady,